@@ -0,0 +1,74 @@
+//! Per-wallet encoding of the string fields: fresh strings and `format!` against the buffers a
+//! batch reuses. Run with `cargo bench --bench encode`.
+
+use std::hint::black_box;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use bech32::Hrp;
+use bip39::Mnemonic;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use wallet_generator::generator::{encode_address, evm_address_string, hex_string, public_key_address, WalletEncoder};
+use wallet_generator::{generate_wallets_batch, DerivationConfig, KeyType};
+
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const WALLETS: usize = 1_000;
+
+fn public_keys() -> Vec<PublicKey> {
+    let secp = Secp256k1::new();
+    (1..=WALLETS as u64)
+        .map(|i| {
+            let mut key = [0u8; 32];
+            key[24..].copy_from_slice(&i.to_be_bytes());
+            PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&key).unwrap())
+        })
+        .collect()
+}
+
+fn encode_fields(c: &mut Criterion) {
+    let config = DerivationConfig::new("evmos", KeyType::Ethsecp256k1);
+    let hrp = Hrp::parse(&config.prefix).unwrap();
+    let keys: Vec<([u8; 20], [u8; 33])> = public_keys().iter().map(|key| (public_key_address(key, &config.key_type), key.serialize())).collect();
+
+    let mut group = c.benchmark_group("encode_fields");
+    group.throughput(Throughput::Elements(WALLETS as u64));
+    group.bench_function("fresh_strings", |b| {
+        b.iter(|| {
+            for (index, (address, pubkey)) in keys.iter().enumerate() {
+                black_box(encode_address(hrp, address, config.encoding).unwrap());
+                black_box(format!("0x{}", hex::encode(address)));
+                black_box(hex::encode(pubkey));
+                black_box(config.path(index as u64));
+            }
+        })
+    });
+    group.bench_function("reused_buffers", |b| {
+        let mut encoder = WalletEncoder::new(&config);
+        b.iter(|| {
+            for (index, (address, pubkey)) in keys.iter().enumerate() {
+                black_box(encoder.address(hrp, address).unwrap());
+                black_box(evm_address_string(address));
+                black_box(hex_string(pubkey));
+                black_box(encoder.path(index as u64));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn generate_batch(c: &mut Criterion) {
+    let seed = Mnemonic::parse(MNEMONIC).unwrap().to_seed("");
+    let mut group = c.benchmark_group("generate_batch");
+    group.throughput(Throughput::Elements(WALLETS as u64));
+    for (name, key_type) in [("secp256k1", KeyType::Secp256k1), ("ethsecp256k1", KeyType::Ethsecp256k1)] {
+        let config = DerivationConfig::new("cosmos", key_type);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &config, |b, config| {
+            b.iter(|| generate_wallets_batch(&seed, 0, WALLETS, config, Arc::new(AtomicUsize::new(0))).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, encode_fields, generate_batch);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_header();
+
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+/// Regenerate `include/wallet_factory.h` from the `ffi` module.
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("Failed to read cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_src(format!("{}/src/ffi.rs", crate_dir))
+        .with_config(config)
+        .generate()
+        .expect("Failed to generate C bindings")
+        .write_to_file(format!("{}/include/wallet_factory.h", crate_dir));
+}
+
+/// Compile `proto/wallet_factory.proto` with protox so no system `protoc` is needed.
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto/wallet_factory.proto");
+
+    let descriptors = protox::compile(["proto/wallet_factory.proto"], ["proto"])
+        .expect("Failed to parse proto/wallet_factory.proto");
+    tonic_build::configure()
+        .compile_fds(descriptors)
+        .expect("Failed to generate gRPC code");
+}
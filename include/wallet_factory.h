@@ -0,0 +1,101 @@
+#ifndef WALLET_FACTORY_H
+#define WALLET_FACTORY_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define WF_ADDRESS_LEN 128
+
+#define WF_EVM_ADDRESS_LEN 43
+
+#define WF_PUBKEY_LEN 45
+
+#define WF_PRIVATE_KEY_LEN 65
+
+#define WF_PATH_LEN 64
+
+#define WF_SEED_LEN 64
+
+/**
+ * Values of the `key_type` parameters. They are passed as `uint32_t`, so that an unknown value
+ * from C is rejected with `WF_STATUS_INVALID_KEY_TYPE` rather than read as an enum.
+ */
+typedef enum WfKeyType {
+  WF_KEY_TYPE_SECP256K1 = 0,
+  WF_KEY_TYPE_ETHSECP256K1 = 1,
+} WfKeyType;
+
+typedef enum WfStatus {
+  WF_STATUS_OK = 0,
+  WF_STATUS_NULL_POINTER = 1,
+  WF_STATUS_INVALID_UTF8 = 2,
+  WF_STATUS_BUFFER_TOO_SMALL = 3,
+  WF_STATUS_INVALID_MNEMONIC = 4,
+  WF_STATUS_INVALID_PREFIX = 5,
+  WF_STATUS_DERIVATION = 6,
+  WF_STATUS_INVALID_KEY = 7,
+  WF_STATUS_ENCODING = 8,
+  WF_STATUS_INVALID_KEY_TYPE = 9,
+} WfStatus;
+
+/**
+ * One generated wallet. `evm_address` is an empty string for secp256k1.
+ */
+typedef struct WfWallet {
+  char address[WF_ADDRESS_LEN];
+  char evm_address[WF_EVM_ADDRESS_LEN];
+  char pubkey[WF_PUBKEY_LEN];
+  char private_key[WF_PRIVATE_KEY_LEN];
+  char derivation_path[WF_PATH_LEN];
+} WfWallet;
+
+/**
+ * Convert a BIP39 mnemonic into its 64-byte seed (empty passphrase).
+ *
+ * # Safety
+ * `mnemonic` must be a valid NUL-terminated string and `out_seed` must point
+ * to at least `WF_SEED_LEN` writable bytes.
+ */
+enum WfStatus wf_mnemonic_to_seed(const char *mnemonic, uint8_t *out_seed);
+
+/**
+ * Derive the bech32 (and for ethsecp256k1, EVM) address of a 32-byte private key.
+ *
+ * `out_evm` may be NULL when the EVM address is not wanted.
+ *
+ * # Safety
+ * `private_key` must point to 32 readable bytes, `prefix` must be a valid
+ * NUL-terminated string and each output buffer must have at least the given
+ * number of writable bytes.
+ */
+enum WfStatus wf_derive_address(const uint8_t *private_key,
+                                const char *prefix,
+                                uint32_t key_type,
+                                char *out_address,
+                                size_t out_address_len,
+                                char *out_evm,
+                                size_t out_evm_len);
+
+/**
+ * Generate `count` wallets from `seed` starting at `start_index` into `out`.
+ * `seed` is 16, 32 or 64 bytes, as BIP-32 requires.
+ *
+ * # Safety
+ * `seed` must point to `seed_len` readable bytes, `prefix` must be a valid
+ * NUL-terminated string and `out` must point to `out_len` writable `WfWallet`s.
+ */
+enum WfStatus wf_generate_batch(const uint8_t *seed,
+                                size_t seed_len,
+                                size_t start_index,
+                                size_t count,
+                                const char *prefix,
+                                uint32_t key_type,
+                                struct WfWallet *out,
+                                size_t out_len);
+
+#endif  /* WALLET_FACTORY_H */
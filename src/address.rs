@@ -0,0 +1,143 @@
+//! Key-less address derivation: module accounts, ADR-028 derived addresses, multisigs and contract addresses.
+
+use anyhow::{anyhow, Context, Result};
+use bech32::{Bech32, Hrp};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// ADR-028 `Hash(typ, key) = sha256(sha256(typ) || key)`.
+pub fn hash(typ: &[u8], key: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(typ));
+    hasher.update(key);
+    hasher.finalize().into()
+}
+
+/// Bech32 encoding of raw address bytes.
+pub fn encode(prefix: &str, address: &[u8]) -> Result<String> {
+    let hrp = Hrp::parse(prefix).with_context(|| format!("Invalid bech32 prefix {}", prefix))?;
+    Ok(bech32::encode::<Bech32>(hrp, address)?)
+}
+
+/// Raw bytes of a bech32 address.
+pub fn decode(address: &str) -> Result<Vec<u8>> {
+    Ok(bech32::decode(address).with_context(|| format!("Invalid bech32 address {}", address))?.1)
+}
+
+/// Bech32 prefix of an address.
+pub fn prefix(address: &str) -> Result<String> {
+    let (hrp, _) = bech32::decode(address).with_context(|| format!("Invalid bech32 address {}", address))?;
+    Ok(hrp.to_string())
+}
+
+fn length_prefixed(data: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(data.len() as u64).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+/// ADR-028 `Derive(address, key)`: a sub-account address owned by `address`.
+pub fn derive(address: &[u8], key: &[u8]) -> [u8; 32] {
+    hash(address, key)
+}
+
+/// Module account address.
+///
+/// Without derivation keys this is the classic `sha256(name)[:20]`; with keys it is the ADR-028
+/// module-derived address `Hash("module", name || 0 || keys[0])`, further derived by each remaining key.
+pub fn module(name: &str, derivation_keys: &[&[u8]]) -> Vec<u8> {
+    let Some((first, rest)) = derivation_keys.split_first() else {
+        return Sha256::digest(name.as_bytes())[..20].to_vec();
+    };
+    let mut module_key = name.as_bytes().to_vec();
+    module_key.push(0);
+    module_key.extend_from_slice(first);
+    let address = hash(b"module", &module_key);
+    rest.iter().fold(address, |address, key| derive(&address, key)).to_vec()
+}
+
+/// Amino prefix of `tendermint/PubKeyMultisigThreshold`.
+const AMINO_MULTISIG_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+/// Amino prefix of `tendermint/PubKeySecp256k1`.
+const AMINO_SECP256K1_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// Amino binary encoding of a legacy threshold multisig over compressed secp256k1 keys, in the given order.
+pub fn legacy_multisig_bytes(threshold: u32, pubkeys: &[[u8; 33]]) -> Result<Vec<u8>> {
+    if threshold == 0 || threshold as usize > pubkeys.len() {
+        return Err(anyhow!("Threshold must be between 1 and {}, got {}", pubkeys.len(), threshold));
+    }
+
+    let mut bytes = AMINO_MULTISIG_PREFIX.to_vec();
+    bytes.push(0x08);
+    prost::encoding::encode_varint(u64::from(threshold), &mut bytes);
+    for pubkey in pubkeys {
+        bytes.push(0x12);
+        bytes.push((AMINO_SECP256K1_PREFIX.len() + 1 + pubkey.len()) as u8);
+        bytes.extend_from_slice(&AMINO_SECP256K1_PREFIX);
+        bytes.push(pubkey.len() as u8);
+        bytes.extend_from_slice(pubkey);
+    }
+    Ok(bytes)
+}
+
+/// Address of a legacy amino multisig: `sha256(amino bytes)[:20]`.
+pub fn legacy_multisig(threshold: u32, pubkeys: &[[u8; 33]]) -> Result<[u8; 20]> {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&Sha256::digest(legacy_multisig_bytes(threshold, pubkeys)?)[..20]);
+    Ok(address)
+}
+
+/// Predictable CosmWasm contract address (`MsgInstantiateContract2`).
+///
+/// `init_msg` is only part of the address when the contract was instantiated with `fix_msg`.
+pub fn instantiate2(checksum: &[u8], creator: &[u8], salt: &[u8], init_msg: Option<&[u8]>) -> Result<[u8; 32]> {
+    if checksum.len() != 32 {
+        return Err(anyhow!("Code checksum must be 32 bytes, got {}", checksum.len()));
+    }
+    if salt.is_empty() || salt.len() > 64 {
+        return Err(anyhow!("Salt must be 1 to 64 bytes, got {}", salt.len()));
+    }
+
+    let mut key = Vec::with_capacity(32 + creator.len() + salt.len() + 32);
+    length_prefixed(checksum, &mut key);
+    length_prefixed(creator, &mut key);
+    length_prefixed(salt, &mut key);
+    length_prefixed(init_msg.unwrap_or_default(), &mut key);
+    let mut address = [0u8; 32];
+    address.copy_from_slice(&module("wasm", &[&key]));
+    Ok(address)
+}
+
+/// EVM contract address deployed by `sender` at `nonce` (`CREATE`).
+pub fn create(sender: &[u8; 20], nonce: u64) -> [u8; 20] {
+    // rlp([sender, nonce]); the payload is always shorter than 56 bytes
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce_bytes.iter().take_while(|b| **b == 0).count()..];
+    let mut payload = vec![0x80 + 20];
+    payload.extend_from_slice(sender);
+    match nonce_bytes {
+        [] => payload.push(0x80),
+        [b] if *b < 0x80 => payload.push(*b),
+        bytes => {
+            payload.push(0x80 + bytes.len() as u8);
+            payload.extend_from_slice(bytes);
+        }
+    }
+
+    let mut rlp = vec![0xc0 + payload.len() as u8];
+    rlp.extend_from_slice(&payload);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&Keccak256::digest(&rlp)[12..]);
+    address
+}
+
+/// EVM contract address deployed by `sender` with `CREATE2`.
+pub fn create2(sender: &[u8; 20], salt: &[u8; 32], init_code_hash: &[u8; 32]) -> [u8; 20] {
+    let mut hasher = Keccak256::new();
+    hasher.update([0xff]);
+    hasher.update(sender);
+    hasher.update(salt);
+    hasher.update(init_code_hash);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hasher.finalize()[12..]);
+    address
+}
@@ -0,0 +1,118 @@
+//! How Cosmos wallets get their addresses from their public keys, behind a trait so library users
+//! can add chains with another hash or text encoding without forking the generator.
+//!
+//! The built-in encoders cover the two key types: [`Secp256k1Addresses`] (SHA256/RIPEMD160) and
+//! [`EthSecp256k1Addresses`] (Keccak256, with the `0x` EVM address). An encoder registered with
+//! [`register`] is used by the batch generators and `rederive` for configs whose
+//! `address_encoder` names it; key derivation and the other fields of the record are unchanged.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use secp256k1::PublicKey;
+//! use sha2::{Digest, Sha256};
+//! use wallet_generator::address_encoder::{self, Addresses, AddressEncoder};
+//! use wallet_generator::generator::WalletEncoder;
+//! use wallet_generator::{DerivationConfig, GeneratorError, KeyType};
+//!
+//! /// Bech32 of the first 20 bytes of SHA-256(pubkey)
+//! struct Sha256Addresses;
+//!
+//! impl AddressEncoder for Sha256Addresses {
+//!     fn encode(&self, public_key: &PublicKey, fields: &mut WalletEncoder) -> Result<Addresses, GeneratorError> {
+//!         let hrp = fields.hrp()?;
+//!         let hash = Sha256::digest(public_key.serialize());
+//!         Ok(Addresses::new(fields.address(hrp, &hash[..20])?))
+//!     }
+//! }
+//!
+//! address_encoder::register("sha256", Arc::new(Sha256Addresses));
+//! let config = DerivationConfig { address_encoder: Some("sha256".to_string()), ..DerivationConfig::new("mychain", KeyType::Secp256k1) };
+//! ```
+
+use secp256k1::PublicKey;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::error::GeneratorError;
+use crate::generator::{evm_address_string, public_key_address, DerivationConfig, WalletEncoder};
+use crate::types::KeyType;
+
+/// The address fields of a wallet record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Addresses {
+    /// `address`
+    pub address: String,
+    /// `evmAddress`, for keys with an EVM account
+    pub evm_address: Option<String>,
+    /// `secp256k1Address`, the key's address under a second scheme
+    pub secp256k1_address: Option<String>,
+}
+
+impl Addresses {
+    /// Just the `address` field.
+    pub fn new(address: String) -> Self {
+        Self { address, evm_address: None, secp256k1_address: None }
+    }
+}
+
+/// Turns a secp256k1 public key into the addresses of a wallet record.
+///
+/// Encoders are shared by all worker threads. `fields` is the calling batch's encoder, holding
+/// the config, its bech32 prefix and a buffer [`WalletEncoder::address`] reuses.
+pub trait AddressEncoder: Send + Sync {
+    fn encode(&self, public_key: &PublicKey, fields: &mut WalletEncoder) -> Result<Addresses, GeneratorError>;
+}
+
+/// Cosmos SDK secp256k1 accounts: bech32 of RIPEMD160(SHA256(compressed key)).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Secp256k1Addresses;
+
+impl AddressEncoder for Secp256k1Addresses {
+    fn encode(&self, public_key: &PublicKey, fields: &mut WalletEncoder) -> Result<Addresses, GeneratorError> {
+        let hrp = fields.hrp()?;
+        Ok(Addresses::new(fields.address(hrp, &public_key_address(public_key, &KeyType::Secp256k1))?))
+    }
+}
+
+/// Ethermint ethsecp256k1 accounts: the last 20 bytes of Keccak256(uncompressed key), in bech32
+/// and as the `0x` EVM address, and the secp256k1 address too when the config asks for both.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EthSecp256k1Addresses;
+
+impl AddressEncoder for EthSecp256k1Addresses {
+    fn encode(&self, public_key: &PublicKey, fields: &mut WalletEncoder) -> Result<Addresses, GeneratorError> {
+        let hrp = fields.hrp()?;
+        let hash = public_key_address(public_key, &KeyType::Ethsecp256k1);
+        let secp256k1_address = match fields.config().records_secp256k1_address() {
+            true => Some(fields.address(hrp, &public_key_address(public_key, &KeyType::Secp256k1))?),
+            false => None,
+        };
+        Ok(Addresses { address: fields.address(hrp, &hash)?, evm_address: Some(evm_address_string(&hash)), secp256k1_address })
+    }
+}
+
+type Registry = RwLock<HashMap<String, Arc<dyn AddressEncoder>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Make `encoder` available to configs naming it, returning the encoder it replaces, if any.
+pub fn register(name: &str, encoder: Arc<dyn AddressEncoder>) -> Option<Arc<dyn AddressEncoder>> {
+    registry().write().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(name.to_string(), encoder)
+}
+
+/// The encoder registered as `name`.
+pub fn registered(name: &str) -> Option<Arc<dyn AddressEncoder>> {
+    registry().read().unwrap_or_else(|poisoned| poisoned.into_inner()).get(name).cloned()
+}
+
+/// The encoder `config` names, or the built-in one of its key type.
+pub fn for_config(config: &DerivationConfig) -> Result<Arc<dyn AddressEncoder>, GeneratorError> {
+    match (&config.address_encoder, &config.key_type) {
+        (Some(name), _) => registered(name).ok_or_else(|| GeneratorError::Unsupported(format!("No address encoder is registered as '{}'", name))),
+        (None, KeyType::Secp256k1) => Ok(Arc::new(Secp256k1Addresses)),
+        (None, KeyType::Ethsecp256k1) => Ok(Arc::new(EthSecp256k1Addresses)),
+    }
+}
@@ -0,0 +1,69 @@
+//! Mnemonics and seeds read from age-encrypted files, decrypted in memory so the root secret is
+//! never written to disk in plaintext.
+//!
+//! Files may be binary or ASCII-armored, and encrypted to a passphrase or to the recipients of an
+//! identity file. Decrypted bytes are zeroed when dropped.
+
+use age::armor::ArmoredReader;
+use age::secrecy::SecretString;
+use age::{Decryptor, Identity, IdentityFile};
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+/// Length of a BIP-39 seed.
+pub const SEED_LEN: usize = 64;
+
+/// The root secret held by a decrypted file.
+pub enum RootSecret {
+    /// A BIP-39 phrase, not yet validated
+    Mnemonic(Zeroizing<String>),
+    /// A BIP-39 seed, written as 128 hex digits
+    Seed(Zeroizing<[u8; SEED_LEN]>),
+}
+
+impl RootSecret {
+    /// Read a decrypted file: a hex seed if it is exactly 128 hex digits, otherwise a mnemonic
+    /// with its words separated by single spaces.
+    pub fn parse(plaintext: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(plaintext).map_err(|_| anyhow!("The decrypted file is not text"))?.trim();
+        let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+        if text.len() == 2 * SEED_LEN && hex::decode_to_slice(text, &mut seed[..]).is_ok() {
+            return Ok(RootSecret::Seed(seed));
+        }
+        Ok(RootSecret::Mnemonic(Zeroizing::new(text.split_whitespace().collect::<Vec<_>>().join(" "))))
+    }
+}
+
+/// Decrypt the age file at `path` with the identities in `identity_file`, or, for a file
+/// encrypted to a passphrase, with the one `passphrase` supplies.
+pub fn decrypt<P: AsRef<Path>>(
+    path: P,
+    identity_file: Option<&Path>,
+    passphrase: impl FnOnce() -> Result<String>,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let decryptor = Decryptor::new(ArmoredReader::new(BufReader::new(file)))
+        .with_context(|| format!("{} is not an age-encrypted file", path.display()))?;
+
+    let identities: Vec<Box<dyn Identity>> = match (decryptor.is_scrypt(), identity_file) {
+        (true, _) => vec![Box::new(age::scrypt::Identity::new(SecretString::from(passphrase()?)))],
+        (false, Some(identity_file)) => IdentityFile::from_file(identity_file.to_string_lossy().into_owned())
+            .with_context(|| format!("Failed to read identity file {}", identity_file.display()))?
+            .into_identities()
+            .with_context(|| format!("Unusable identity file {}", identity_file.display()))?,
+        (false, None) => {
+            return Err(anyhow!("{} is encrypted to recipients, not a passphrase; pass --identity", path.display()));
+        }
+    };
+
+    let mut reader = decryptor
+        .decrypt(identities.iter().map(|identity| identity.as_ref()))
+        .with_context(|| format!("Failed to decrypt {}", path.display()))?;
+    let mut plaintext = Zeroizing::new(Vec::new());
+    reader.read_to_end(&mut plaintext).with_context(|| format!("Failed to decrypt {}", path.display()))?;
+    Ok(plaintext)
+}
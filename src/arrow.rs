@@ -0,0 +1,180 @@
+//! Wallets as Arrow record batches, in memory or as an Arrow IPC file (Feather v2), for loading
+//! into Polars, DataFusion or pandas without parsing text.
+//!
+//! Columns are named and encoded like the JSON fields, with nullable columns for the fields JSON
+//! omits.
+
+use anyhow::Result;
+use arrow_array::builder::{ArrayBuilder, StringBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use rayon::prelude::*;
+use std::io::Write;
+use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, LazyLock};
+
+use crate::generator::{generate_wallets_batch, DerivationConfig};
+use crate::wallet::Wallet;
+
+/// Rows per record batch written to IPC files.
+pub const BATCH_ROWS: usize = 64 * 1024;
+
+static SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    let text = |name: &str, nullable: bool| Field::new(name, DataType::Utf8, nullable);
+    Arc::new(Schema::new(vec![
+        text("address", false),
+        text("evmAddress", true),
+        text("pubkey", false),
+        text("privateKey", false),
+        text("derivationPath", false),
+        Field::new("index", DataType::UInt64, false),
+        text("pubkeyHex", true),
+        text("pubkeyType", true),
+        text("pubkeyUncompressed", true),
+        text("secp256k1Address", true),
+        text("wif", true),
+    ]))
+});
+
+/// Schema of wallet record batches.
+pub fn schema() -> SchemaRef {
+    SCHEMA.clone()
+}
+
+/// Column builders for a batch of wallets.
+struct Columns {
+    address: StringBuilder,
+    evm_address: StringBuilder,
+    pubkey: StringBuilder,
+    private_key: StringBuilder,
+    derivation_path: StringBuilder,
+    index: UInt64Builder,
+    pubkey_hex: StringBuilder,
+    pubkey_type: StringBuilder,
+    pubkey_uncompressed: StringBuilder,
+    secp256k1_address: StringBuilder,
+    wif: StringBuilder,
+}
+
+impl Columns {
+    fn new() -> Self {
+        Self {
+            address: StringBuilder::new(),
+            evm_address: StringBuilder::new(),
+            pubkey: StringBuilder::new(),
+            private_key: StringBuilder::new(),
+            derivation_path: StringBuilder::new(),
+            index: UInt64Builder::new(),
+            pubkey_hex: StringBuilder::new(),
+            pubkey_type: StringBuilder::new(),
+            pubkey_uncompressed: StringBuilder::new(),
+            secp256k1_address: StringBuilder::new(),
+            wif: StringBuilder::new(),
+        }
+    }
+
+    fn append(&mut self, wallet: &Wallet) {
+        self.address.append_value(&wallet.address);
+        self.evm_address.append_option(wallet.evm_address.as_deref());
+        self.pubkey.append_value(&wallet.pubkey);
+        self.private_key.append_value(&wallet.private_key);
+        self.derivation_path.append_value(&wallet.derivation_path);
+        self.index.append_value(wallet.index);
+        self.pubkey_hex.append_option(wallet.pubkey_hex.as_deref());
+        self.pubkey_type.append_option(wallet.pubkey_type.as_deref());
+        self.pubkey_uncompressed.append_option(wallet.pubkey_uncompressed.as_deref());
+        self.secp256k1_address.append_option(wallet.secp256k1_address.as_deref());
+        self.wif.append_option(wallet.wif.as_deref());
+    }
+
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// The rows appended so far as a batch, leaving the builders empty.
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.address.finish()),
+            Arc::new(self.evm_address.finish()),
+            Arc::new(self.pubkey.finish()),
+            Arc::new(self.private_key.finish()),
+            Arc::new(self.derivation_path.finish()),
+            Arc::new(self.index.finish()),
+            Arc::new(self.pubkey_hex.finish()),
+            Arc::new(self.pubkey_type.finish()),
+            Arc::new(self.pubkey_uncompressed.finish()),
+            Arc::new(self.secp256k1_address.finish()),
+            Arc::new(self.wif.finish()),
+        ];
+        Ok(RecordBatch::try_new(schema(), columns)?)
+    }
+}
+
+/// One record batch of `wallets`.
+pub fn record_batch(wallets: &[Wallet]) -> Result<RecordBatch> {
+    let mut columns = Columns::new();
+    wallets.iter().for_each(|wallet| columns.append(wallet));
+    columns.finish()
+}
+
+/// Generate `count` wallets from `start_index` as record batches of up to `batch_rows` rows, in
+/// index order, deriving the batches in parallel.
+pub fn generate_record_batches(
+    seed: &[u8],
+    start_index: usize,
+    count: usize,
+    config: &DerivationConfig,
+    batch_rows: usize,
+) -> Result<Vec<RecordBatch>> {
+    let batch_rows = batch_rows.max(1);
+    (0..count.div_ceil(batch_rows))
+        .into_par_iter()
+        .map(|batch| {
+            let start = start_index + batch * batch_rows;
+            let rows = batch_rows.min(start_index + count - start);
+            let wallets = generate_wallets_batch(seed, start, rows, config, Arc::new(AtomicUsize::new(0)))?;
+            record_batch(&wallets)
+        })
+        .collect()
+}
+
+/// Writes wallets one at a time to an Arrow IPC file, in batches of [`BATCH_ROWS`].
+/// [`ArrowIpcWriter::finish`] writes the last batch and the footer and returns the inner writer.
+pub struct ArrowIpcWriter<W: Write> {
+    writer: FileWriter<W>,
+    columns: Columns,
+    records: usize,
+}
+
+impl<W: Write> ArrowIpcWriter<W> {
+    pub fn new(writer: W) -> Result<Self> {
+        Ok(Self { writer: FileWriter::try_new(writer, &schema())?, columns: Columns::new(), records: 0 })
+    }
+
+    pub fn write(&mut self, wallet: &Wallet) -> Result<()> {
+        self.columns.append(wallet);
+        self.records += 1;
+        if self.columns.len() == BATCH_ROWS {
+            self.writer.write(&self.columns.finish()?)?;
+        }
+        Ok(())
+    }
+
+    /// Wallets written so far.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// The inner writer, which holds whole batches only.
+    pub fn get_ref(&self) -> &W {
+        self.writer.get_ref()
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        if self.columns.len() > 0 {
+            self.writer.write(&self.columns.finish()?)?;
+        }
+        Ok(self.writer.into_inner()?)
+    }
+}
@@ -0,0 +1,218 @@
+//! Compact binary wallet files: a JSON header holding the run's derivation settings, then
+//! fixed-size records of raw bytes. Text fields are rebuilt from the bytes and the settings when a
+//! file is read, so a secp256k1 wallet takes 93 bytes instead of about 550 as JSON.
+//!
+//! Layout: the magic `WFBIN`, a version byte, the header length as a little-endian u32, the JSON
+//! header, then records of index (little-endian u64), address hash, private key and public key,
+//! each as long as the header states.
+
+use anyhow::{anyhow, Context, Result};
+use secp256k1::PublicKey;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+
+use crate::bitcoin::{self, Network};
+use crate::generator::{encode_address, parse_hrp, public_key_address, DerivationConfig};
+use crate::sign::wallet_private_key;
+use crate::solana;
+use crate::types::{BitcoinAddress, ChainFamily, KeyType};
+use crate::wallet::Wallet;
+
+pub const MAGIC: &[u8; 5] = b"WFBIN";
+pub const VERSION: u8 = 1;
+
+/// Settings and record layout of a binary file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    /// Settings the wallets were derived with; addresses and key encodings are rebuilt from them
+    pub config: DerivationConfig,
+    /// Bytes of the address hash; 0 where the address is the public key itself (Solana)
+    pub address_len: usize,
+    pub private_key_len: usize,
+    /// 33 for compressed secp256k1 keys, 32 for ed25519 keys
+    pub public_key_len: usize,
+}
+
+impl Header {
+    pub fn new(config: &DerivationConfig) -> Self {
+        let (address_len, public_key_len) = match config.family {
+            ChainFamily::Solana => (0, 32),
+            ChainFamily::Cosmos | ChainFamily::Bitcoin(_) => (20, 33),
+        };
+        Self { config: config.clone(), address_len, private_key_len: 32, public_key_len }
+    }
+
+    /// Bytes of each record.
+    pub fn record_len(&self) -> usize {
+        8 + self.address_len + self.private_key_len + self.public_key_len
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let header = serde_json::to_vec(self)?;
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+        writer.write_all(&header)?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut magic = [0u8; 5];
+        reader.read_exact(&mut magic).context("Not a binary wallet file")?;
+        if &magic != MAGIC {
+            return Err(anyhow!("Not a binary wallet file"));
+        }
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != VERSION {
+            return Err(anyhow!("Binary wallet file version {} is not supported (expected {})", version[0], VERSION));
+        }
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let mut header = vec![0u8; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut header)?;
+        let header: Header = serde_json::from_slice(&header).context("Invalid binary wallet file header")?;
+
+        let expected = Header::new(&header.config);
+        if (header.address_len, header.private_key_len, header.public_key_len)
+            != (expected.address_len, expected.private_key_len, expected.public_key_len)
+        {
+            return Err(anyhow!("Binary wallet file header has field sizes that do not match its chain"));
+        }
+        Ok(header)
+    }
+
+    /// Append the record of `wallet`, derived with this header's settings, to `out`.
+    pub fn encode(&self, wallet: &Wallet, out: &mut Vec<u8>) -> Result<()> {
+        let public_key = wallet
+            .pubkey_hex
+            .as_deref()
+            .map(hex::decode)
+            .transpose()?
+            .filter(|key| key.len() == self.public_key_len)
+            .ok_or_else(|| anyhow!("Wallet {} has no {}-byte pubkeyHex", wallet.address, self.public_key_len))?;
+
+        out.extend_from_slice(&wallet.index.to_le_bytes());
+        match self.config.family {
+            ChainFamily::Solana => {}
+            ChainFamily::Bitcoin(_) => out.extend_from_slice(&bitcoin::hash160(&public_key)),
+            ChainFamily::Cosmos => {
+                let key = PublicKey::from_slice(&public_key)?;
+                out.extend_from_slice(&public_key_address(&key, &self.config.key_type));
+            }
+        }
+        out.extend_from_slice(&wallet_private_key(wallet)?);
+        out.extend_from_slice(&public_key);
+        Ok(())
+    }
+
+    /// Rebuild the wallet of a record, with the same fields generation would have written.
+    pub fn decode(&self, record: &[u8]) -> Result<Wallet> {
+        let config = &self.config;
+        let (index, rest) = record.split_at(8);
+        let (address_hash, rest) = rest.split_at(self.address_len);
+        let (private_key, public_key) = rest.split_at(self.private_key_len);
+        let index = u64::from_le_bytes(index.try_into()?);
+        let private_key: [u8; 32] = private_key.try_into()?;
+
+        let mut wallet = Wallet {
+            address: String::new(),
+            evm_address: None,
+            pubkey: String::new(),
+            private_key: config.encode_private_key(&private_key)?,
+            derivation_path: config.path(index),
+            index,
+            pubkey_hex: Some(hex::encode(public_key)),
+            pubkey_type: None,
+            pubkey_uncompressed: None,
+            secp256k1_address: None,
+            wif: None,
+        };
+        if config.family == ChainFamily::Solana {
+            let public_key: [u8; 32] = public_key.try_into()?;
+            wallet.address = solana::address(&public_key);
+            wallet.pubkey = config.encode_ed25519_public_key(&public_key);
+            return Ok(wallet);
+        }
+
+        (wallet.pubkey, wallet.pubkey_uncompressed) = config.encode_public_key(&PublicKey::from_slice(public_key)?);
+        let address_hash: &[u8; 20] = address_hash.try_into()?;
+        match config.family {
+            ChainFamily::Bitcoin(address_type) => {
+                let network = Network::from_hrp(&config.prefix)?;
+                wallet.address = match address_type {
+                    BitcoinAddress::P2pkh => bitcoin::p2pkh(address_hash, network),
+                    BitcoinAddress::P2wpkh => bitcoin::p2wpkh(address_hash, network)?,
+                };
+                wallet.wif = Some(bitcoin::wif(&private_key, network));
+            }
+            _ => {
+                wallet.address = encode_address(parse_hrp(&config.prefix)?, address_hash, config.encoding)?;
+                if matches!(config.key_type, KeyType::Ethsecp256k1) {
+                    wallet.evm_address = Some(format!("0x{}", hex::encode(address_hash)));
+                }
+                wallet.secp256k1_address = config.secp256k1_address(&PublicKey::from_slice(public_key)?)?;
+                wallet.pubkey_type = Some(config.key_type.pubkey_type_url().to_string());
+            }
+        }
+        Ok(wallet)
+    }
+}
+
+/// Whether the file at `path` starts with the binary magic.
+pub fn is_binary<P: AsRef<Path>>(path: P) -> Result<bool> {
+    let path = path.as_ref();
+    let mut file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut magic = [0u8; 5];
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(&magic == MAGIC),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Streams the wallets of a binary file.
+pub struct BinaryReader {
+    reader: BufReader<File>,
+    header: Header,
+    record: Vec<u8>,
+    records: u64,
+}
+
+impl BinaryReader {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut reader = BufReader::with_capacity(8 * 1024 * 1024, file);
+        let header = Header::read(&mut reader).with_context(|| format!("Failed to read {}", path.display()))?;
+        let record = vec![0u8; header.record_len()];
+        Ok(Self { reader, header, record, records: 0 })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+}
+
+impl Iterator for BinaryReader {
+    type Item = Result<Wallet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A clean end falls between records; anything else is a truncated file
+        let mut filled = 0;
+        while filled < self.record.len() {
+            match self.reader.read(&mut self.record[filled..]) {
+                Ok(0) if filled == 0 => return None,
+                Ok(0) => return Some(Err(anyhow!("Record {} is truncated", self.records))),
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+        self.records += 1;
+        Some(self.header.decode(&self.record))
+    }
+}
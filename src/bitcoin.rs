@@ -0,0 +1,96 @@
+//! Bitcoin addresses and WIF private keys.
+//!
+//! P2PKH and P2WPKH both commit to HASH160 of the compressed public key, the same
+//! SHA256 + RIPEMD160 digest Cosmos secp256k1 addresses use; only the encoding differs.
+
+use bech32::Hrp;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::error::GeneratorError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl Network {
+    /// The network whose SegWit prefix is `hrp` (`bc`, `tb` or `bcrt`).
+    pub fn from_hrp(hrp: &str) -> Result<Self, GeneratorError> {
+        match hrp.to_ascii_lowercase().as_str() {
+            "bc" => Ok(Network::Mainnet),
+            "tb" => Ok(Network::Testnet),
+            "bcrt" => Ok(Network::Regtest),
+            _ => Err(GeneratorError::BitcoinNetwork(hrp.to_string())),
+        }
+    }
+
+    pub fn hrp(self) -> &'static str {
+        match self {
+            Network::Mainnet => "bc",
+            Network::Testnet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+
+    /// SLIP-44 coin type: 0 on mainnet, 1 on every test network.
+    pub fn coin_type(self) -> u32 {
+        match self {
+            Network::Mainnet => 0,
+            Network::Testnet | Network::Regtest => 1,
+        }
+    }
+
+    fn p2pkh_version(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x00,
+            Network::Testnet | Network::Regtest => 0x6f,
+        }
+    }
+
+    pub fn wif_version(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x80,
+            Network::Testnet | Network::Regtest => 0xef,
+        }
+    }
+}
+
+/// RIPEMD160(SHA256(data)).
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Base58check P2PKH address of a public key hash.
+pub fn p2pkh(pubkey_hash: &[u8; 20], network: Network) -> String {
+    let mut payload = Vec::with_capacity(21);
+    payload.push(network.p2pkh_version());
+    payload.extend_from_slice(pubkey_hash);
+    bs58::encode(payload).with_check().into_string()
+}
+
+/// Bech32 witness version 0 P2WPKH address of a public key hash.
+pub fn p2wpkh(pubkey_hash: &[u8; 20], network: Network) -> Result<String, GeneratorError> {
+    let hrp = Hrp::parse(network.hrp()).expect("network prefixes are valid");
+    Ok(bech32::segwit::encode_v0(hrp, pubkey_hash)?)
+}
+
+/// Wallet Import Format of a private key whose public key is used compressed.
+pub fn wif(private_key: &[u8; 32], network: Network) -> String {
+    let mut payload = Vec::with_capacity(34);
+    payload.push(network.wif_version());
+    payload.extend_from_slice(private_key);
+    payload.push(0x01);
+    bs58::encode(payload).with_check().into_string()
+}
+
+/// The private key of a compressed-key WIF string, on any network.
+pub fn parse_wif(wif: &str) -> Option<[u8; 32]> {
+    let payload = bs58::decode(wif).with_check(None).into_vec().ok()?;
+    match payload.as_slice() {
+        [0x80 | 0xef, key @ .., 0x01] => key.try_into().ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,131 @@
+//! Submission of signed transactions, keeping each signer's transactions in sequence order.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use prost::Message;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::rpc::{BroadcastMode, BroadcastResult, RpcClient};
+use crate::sign::load_txs;
+use crate::tx::proto;
+
+/// A signed transaction ready for submission.
+#[derive(Debug, Clone)]
+pub struct SignedTx {
+    /// Protobuf `TxRaw` bytes
+    pub raw: Vec<u8>,
+    /// Public key of the first signer; transactions are ordered per key
+    pub signer: Vec<u8>,
+    pub sequence: u64,
+}
+
+impl SignedTx {
+    pub fn from_raw(raw: Vec<u8>) -> Result<Self> {
+        let tx = proto::TxRaw::decode(raw.as_slice()).context("Invalid TxRaw")?;
+        let auth_info = proto::AuthInfo::decode(tx.auth_info_bytes.as_slice()).context("Invalid AuthInfo")?;
+        let signer_info = auth_info.signer_infos.first().ok_or_else(|| anyhow!("Transaction has no signer info"))?;
+        Ok(Self {
+            signer: signer_info.public_key.as_ref().map(|any| any.value.clone()).unwrap_or_default(),
+            sequence: signer_info.sequence,
+            raw,
+        })
+    }
+}
+
+/// Load signed transactions from a `.b64` file (one base64 `TxRaw` per line) or anything `load_txs` accepts.
+pub fn load_signed_txs<P: AsRef<Path>>(path: P) -> Result<Vec<SignedTx>> {
+    let path = path.as_ref();
+    if path.extension().is_some_and(|ext| ext == "b64") {
+        let contents = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        return contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                let raw = general_purpose::STANDARD.decode(line.trim()).with_context(|| format!("Invalid base64 on line {}", i + 1))?;
+                SignedTx::from_raw(raw)
+            })
+            .collect();
+    }
+
+    load_txs(path)?
+        .iter()
+        .map(|tx| {
+            if tx.signatures.is_empty() {
+                return Err(anyhow!("Transaction is not signed"));
+            }
+            SignedTx::from_raw(tx.to_raw_bytes()?)
+        })
+        .collect()
+}
+
+/// Result of submitting one transaction.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BroadcastOutcome {
+    /// Position in the input
+    pub index: usize,
+    #[serde(flatten)]
+    pub result: Option<BroadcastResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BroadcastOutcome {
+    pub fn is_accepted(&self) -> bool {
+        self.result.as_ref().is_some_and(|r| r.code == 0)
+    }
+}
+
+/// Broadcast every transaction on a pool of `concurrency` threads.
+///
+/// Transactions of one signer are sent one at a time in sequence order; after a rejection the
+/// signer's remaining transactions are skipped, since their sequences can no longer be valid.
+/// Results keep the input order.
+pub fn broadcast_all(
+    client: &RpcClient,
+    txs: &[SignedTx],
+    mode: BroadcastMode,
+    concurrency: usize,
+    progress: &AtomicUsize,
+) -> Result<Vec<BroadcastOutcome>> {
+    let mut by_signer: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (index, tx) in txs.iter().enumerate() {
+        by_signer.entry(&tx.signer).or_default().push(index);
+    }
+    let mut queues: Vec<Vec<usize>> = by_signer.into_values().collect();
+    for queue in queues.iter_mut() {
+        queue.sort_by_key(|&index| txs[index].sequence);
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency.max(1)).build()?;
+    let mut outcomes: Vec<BroadcastOutcome> = pool.install(|| {
+        queues
+            .into_par_iter()
+            .flat_map_iter(|queue| {
+                let mut failed = false;
+                queue
+                    .into_iter()
+                    .map(|index| {
+                        let outcome = if failed {
+                            BroadcastOutcome { index, result: None, error: Some("Skipped after an earlier failure from the same signer".to_string()) }
+                        } else {
+                            match client.broadcast_tx(&txs[index].raw, mode) {
+                                Ok(result) => BroadcastOutcome { index, result: Some(result), error: None },
+                                Err(e) => BroadcastOutcome { index, result: None, error: Some(e.to_string()) },
+                            }
+                        };
+                        failed |= !outcome.is_accepted();
+                        progress.fetch_add(1, Ordering::Relaxed);
+                        outcome
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    });
+    outcomes.sort_by_key(|outcome| outcome.index);
+    Ok(outcomes)
+}
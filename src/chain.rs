@@ -0,0 +1,75 @@
+//! Built-in chain presets: address prefix, key type, coin type and address encoding per chain.
+
+use anyhow::{anyhow, Result};
+
+use crate::types::{AddressEncoding, BitcoinAddress, ChainFamily, DerivationScheme, KeyType, PrivateKeyFormat, PubkeyFormat};
+use crate::generator::DerivationConfig;
+use crate::solana;
+
+#[derive(Debug, Clone)]
+pub struct ChainPreset {
+    pub name: &'static str,
+    pub prefix: &'static str,
+    pub key_type: KeyType,
+    pub coin_type: u32,
+    pub encoding: AddressEncoding,
+    pub family: ChainFamily,
+}
+
+impl ChainPreset {
+    const fn new(name: &'static str, prefix: &'static str, key_type: KeyType, coin_type: u32) -> Self {
+        Self { name, prefix, key_type, coin_type, encoding: AddressEncoding::Bech32, family: ChainFamily::Cosmos }
+    }
+
+    /// A Bitcoin network, with native SegWit addresses unless `--bitcoin` says otherwise.
+    const fn bitcoin(name: &'static str, prefix: &'static str, coin_type: u32) -> Self {
+        let family = ChainFamily::Bitcoin(BitcoinAddress::P2wpkh);
+        Self { family, ..Self::new(name, prefix, KeyType::Secp256k1, coin_type) }
+    }
+
+    pub fn config(&self) -> DerivationConfig {
+        DerivationConfig {
+            prefix: self.prefix.to_string(),
+            key_type: self.key_type.clone(),
+            coin_type: self.coin_type,
+            encoding: self.encoding,
+            scheme: DerivationScheme::Standard,
+            family: self.family,
+            private_key_format: PrivateKeyFormat::Hex,
+            pubkey_format: PubkeyFormat::Base64,
+            include_uncompressed: false,
+            dual_address: false,
+            path_range: None,
+            address_encoder: None,
+        }
+    }
+}
+
+pub const PRESETS: &[ChainPreset] = &[
+    ChainPreset::new("cosmoshub", "cosmos", KeyType::Secp256k1, 118),
+    ChainPreset::new("osmosis", "osmo", KeyType::Secp256k1, 118),
+    ChainPreset::new("juno", "juno", KeyType::Secp256k1, 118),
+    ChainPreset::new("stargaze", "stars", KeyType::Secp256k1, 118),
+    ChainPreset::new("akash", "akash", KeyType::Secp256k1, 118),
+    ChainPreset::new("celestia", "celestia", KeyType::Secp256k1, 118),
+    ChainPreset::new("neutron", "neutron", KeyType::Secp256k1, 118),
+    ChainPreset::new("sei", "sei", KeyType::Secp256k1, 118),
+    ChainPreset::new("terra", "terra", KeyType::Secp256k1, 330),
+    ChainPreset::new("kava", "kava", KeyType::Secp256k1, 459),
+    ChainPreset::new("secret", "secret", KeyType::Secp256k1, 529),
+    ChainPreset::new("evmos", "evmos", KeyType::Ethsecp256k1, 60),
+    ChainPreset::new("injective", "inj", KeyType::Ethsecp256k1, 60),
+    ChainPreset::new("cronos", "crc", KeyType::Ethsecp256k1, 60),
+    ChainPreset::bitcoin("bitcoin", "bc", 0),
+    ChainPreset::bitcoin("bitcoin-testnet", "tb", 1),
+    ChainPreset::bitcoin("bitcoin-regtest", "bcrt", 1),
+    ChainPreset { family: ChainFamily::Solana, ..ChainPreset::new("solana", "", KeyType::Secp256k1, solana::COIN_TYPE) },
+];
+
+/// Look up a preset by name (case-insensitive).
+pub fn preset(name: &str) -> Result<&'static ChainPreset> {
+    PRESETS.iter().find(|preset| preset.name.eq_ignore_ascii_case(name)).ok_or_else(|| {
+        let names: Vec<&str> = PRESETS.iter().map(|preset| preset.name).collect();
+        anyhow!("Unknown chain preset {}; available: {}", name, names.join(", "))
+    })
+}
@@ -1,28 +1,98 @@
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
-#[derive(ValueEnum, Debug, Clone)]
-pub enum KeyType {
-    /// Standard secp256k1 (SHA256 + RIPEMD160)
-    Secp256k1,
-    /// Ethereum-compatible secp256k1 (Keccak256)
-    Ethsecp256k1,
+pub use crate::types::{AddressEncoding, BitcoinAddress, DerivationScheme, KeyType, OutputFormat, PrivateKeyFormat, PubkeyFormat};
+pub use crate::vanity::Position;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per event
+    Json,
 }
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Args {
-    /// Number of wallets to generate
-    #[arg(short, long)]
-    pub count: usize,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Number of wallets to generate (default with --path: every path of the spec)
+    #[arg(short, long, required_unless_present_any = ["path", "duration", "generate_man"])]
+    pub count: Option<usize>,
+
+    /// Generate as many wallets as possible within this time (e.g. 90s, 10m, 1h30m) instead of a
+    /// fixed --count, then report the final index reached
+    #[arg(long, value_name = "DURATION", conflicts_with_all = ["count", "dry_run", "check_duplicates", "mmap"])]
+    pub duration: Option<String>,
 
     /// Mnemonic phrase (uses default test mnemonic if not provided)
     #[arg(short, long)]
     pub mnemonic: Option<String>,
 
-    /// Output file path
+    /// age-encrypted file holding the mnemonic or a hex seed, decrypted in memory
+    #[cfg(feature = "age")]
+    #[arg(long, value_name = "PATH", conflicts_with = "mnemonic")]
+    pub mnemonic_file: Option<PathBuf>,
+
+    /// age identity file for a --mnemonic-file encrypted to recipients (passphrase files prompt instead)
+    #[cfg(feature = "age")]
+    #[arg(long, value_name = "PATH", requires = "mnemonic_file")]
+    pub identity: Option<PathBuf>,
+
+    /// Output file path, - to stream records to stdout, s3://bucket/key or gs://bucket/key
+    /// (a key ending in / is a prefix) to upload them, or a postgres:// URL to load them into a table
     #[arg(short, long, default_value = "data/wallets/wallets_info.json")]
     pub output: String,
 
+    /// Record format (default: json, or ndjson when writing to stdout)
+    #[arg(long, value_enum)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Preallocate the CSV output file and write it through a memory map, each worker into its own region
+    #[arg(long)]
+    pub mmap: bool,
+
+    /// Add to an existing JSON or NDJSON output file, continuing from one past its highest index
+    #[arg(long, conflicts_with_all = ["mmap", "check_duplicates"])]
+    pub append: bool,
+
+    /// Roll to a new numbered file every N wallets, listing the files in <output stem>.manifest.json
+    #[arg(long, value_name = "N", conflicts_with_all = ["mmap", "append"])]
+    pub rotate_every: Option<u64>,
+
+    /// Roll to a new numbered file before one would pass this size (e.g. 1GB, 512MiB)
+    #[arg(long, value_name = "SIZE", conflicts_with_all = ["mmap", "append"])]
+    pub rotate_size: Option<String>,
+
+    /// Write <output stem>.manifest.json with the output's size, SHA-256, record count and generation settings (rotated output always has one)
+    #[arg(long)]
+    pub manifest: bool,
+
+    /// Multipart upload part size for s3:// and gs:// outputs; also the memory the upload buffers
+    #[arg(long, value_name = "MiB", default_value_t = 64)]
+    pub part_size: usize,
+
+    /// Table for postgres:// outputs, created if missing (table or schema.table)
+    #[cfg(feature = "postgres")]
+    #[arg(long, default_value = "wallets")]
+    pub table: String,
+
+    /// Rows per COPY and transaction for postgres:// outputs
+    #[cfg(feature = "postgres")]
+    #[arg(long, default_value_t = 100_000)]
+    pub copy_batch: usize,
+
     /// Bech32 prefix for addresses
     #[arg(short, long, default_value = "cosmos")]
     pub prefix: String,
@@ -34,4 +104,1277 @@ pub struct Args {
     /// Number of parallel threads (0 = auto-detect)
     #[arg(short, long, default_value_t = 0)]
     pub threads: usize,
-}
\ No newline at end of file
+
+    /// Wallets per work item handed to the threads (default: about 8 items per thread in each segment)
+    #[arg(long, value_name = "N")]
+    pub chunk_size: Option<usize>,
+
+    /// Generate at most this many wallets per second across all threads, leaving CPU to other services
+    #[arg(long, value_name = "WALLETS_PER_SEC")]
+    pub rate_limit: Option<u64>,
+
+    /// Run at this lower priority: niceness 0-19, or a below-normal priority class on Windows
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+    pub nice: Option<i32>,
+
+    /// BIP-44 coin type (default: 118 for secp256k1, 60 for ethsecp256k1)
+    #[arg(long)]
+    pub coin_type: Option<u32>,
+
+    /// Which path level the wallet index increments
+    #[arg(long, value_enum, default_value_t = DerivationScheme::Standard)]
+    pub scheme: DerivationScheme,
+
+    /// Derivation path with ranges, generating every combination in order, last level fastest
+    /// (e.g. m/44'/118'/0-4'/0/0-999; * is every index)
+    #[arg(long, value_name = "SPEC", conflicts_with_all = ["scheme", "coin_type", "mmap"])]
+    pub path: Option<String>,
+
+    /// Chain preset setting prefix, key type, coin type and encoding (e.g. osmosis, evmos)
+    #[arg(long, conflicts_with_all = ["prefix", "key_type"])]
+    pub chain: Option<String>,
+
+    /// Address checksum (default: bech32, or the chain preset's encoding)
+    #[arg(long, value_enum)]
+    pub encoding: Option<AddressEncoding>,
+
+    /// Generate Bitcoin addresses of this type with WIF keys (default network: --chain bitcoin)
+    #[arg(long, value_enum, conflicts_with_all = ["prefix", "key_type", "encoding"])]
+    pub bitcoin: Option<BitcoinAddress>,
+
+    /// Encoding of the pubkey field
+    #[arg(long, value_enum, default_value_t = PubkeyFormat::Base64)]
+    pub pubkey_format: PubkeyFormat,
+
+    /// Also write the uncompressed public key as pubkeyUncompressed (hex), next to pubkey
+    #[arg(long)]
+    pub include_uncompressed: bool,
+
+    /// For ethsecp256k1 keys, also write the SHA256/RIPEMD160 bech32 address as secp256k1Address
+    #[arg(long)]
+    pub dual_address: bool,
+
+    /// Encoding of the privateKey field (wif: mainnet, or the network of a Bitcoin chain)
+    #[arg(long, value_enum, default_value_t = PrivateKeyFormat::Hex)]
+    pub privkey_format: PrivateKeyFormat,
+
+    /// Encrypt each privateKey (and wif) field to a passphrase: Argon2id key, XChaCha20-Poly1305 per record
+    #[arg(long)]
+    pub encrypt_keys: bool,
+
+    /// Passphrase for --encrypt-keys (prompted for when unset)
+    #[arg(long, env = "WALLET_KEY_PASSPHRASE", hide_env_values = true, requires = "encrypt_keys")]
+    pub key_passphrase: Option<String>,
+
+    /// Write only wallets whose address matches this regular expression
+    #[arg(long, value_name = "REGEX")]
+    pub filter_address: Option<String>,
+
+    /// Write only wallets whose EVM address (0x..., ethsecp256k1) matches this regular expression
+    #[arg(long, value_name = "REGEX")]
+    pub filter_evm: Option<String>,
+
+    /// Write only wallets whose address is listed in this file (one bech32, 0x or other address per line)
+    #[arg(long, value_name = "PATH")]
+    pub match_file: Option<String>,
+
+    /// Fail without writing output if any address was generated more than once
+    #[arg(long)]
+    pub check_duplicates: bool,
+
+    /// Memory budget in MiB: size the segments generated ahead of the writer to fit it
+    #[arg(long, value_name = "MB", conflicts_with = "check_duplicates")]
+    pub max_memory: Option<usize>,
+
+    /// Validate the mnemonic, prefix, derivation path and output, then estimate runtime, output size
+    /// and memory from a short calibration burst, without generating the full set or writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Full-screen dashboard with per-thread throughput, memory, ETA and write progress (p pauses, q aborts)
+    #[cfg(feature = "tui")]
+    #[arg(long, conflicts_with = "quiet")]
+    pub tui: bool,
+
+    /// Serve Prometheus metrics (progress, rates, queue depth, output size) at http://ADDR/metrics during the run
+    #[arg(long, value_name = "ADDR")]
+    pub metrics_addr: Option<std::net::SocketAddr>,
+
+    /// Write a JSON summary of the run (counts, timings, rates, output hash, parameters) to this path
+    #[arg(long, value_name = "PATH")]
+    pub report_json: Option<String>,
+
+    /// Append a JSON line per run (parameters, mnemonic fingerprint, index range, output files and digests) to this log
+    #[arg(long, value_name = "PATH")]
+    pub audit_log: Option<String>,
+
+    /// Log nothing but errors, and skip the summary
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Log verbosity (default: info, or error with --quiet; RUST_LOG filters apply when unset)
+    #[arg(long, global = true, value_enum)]
+    pub log_level: Option<LogLevel>,
+
+    /// Log line format; logs go to stderr
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// Octal umask of wallet output files, which hold private keys; 077 keeps them owner-only (0600)
+    #[arg(long, global = true, value_name = "OCTAL", default_value = "077")]
+    pub umask: String,
+
+    /// Write wallet outputs into directories other users can read or write
+    #[arg(long, global = true)]
+    pub insecure_output: bool,
+
+    /// Option defaults for this and every subcommand (default: ./wallet-factory.toml, if present)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Write man pages for the CLI and each subcommand to this directory, then exit
+    #[arg(long, value_name = "DIR", exclusive = true)]
+    pub generate_man: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Query bank balances for every wallet in a file
+    ScanBalances(ScanBalancesArgs),
+
+    /// Record the on-chain account number and sequence of every wallet in a file
+    Annotate(AnnotateArgs),
+
+    /// Build transactions that send coins to every wallet in a file
+    Fund(FundArgs),
+
+    /// Sign transactions with the keys in a wallet file
+    Sign(SignArgs),
+
+    /// Estimate the gas and fee of transactions against a node before signing or funding
+    Simulate(SimulateArgs),
+
+    /// Compute module, derived and contract addresses
+    #[command(subcommand)]
+    Address(AddressCommand),
+
+    /// Find the derivation index of a known address under a mnemonic
+    FindIndex(FindIndexArgs),
+
+    /// Discover the used accounts of a mnemonic from live chain state
+    Recover(RecoverArgs),
+
+    /// Compute addresses from public keys, without private keys
+    FromPubkey(FromPubkeyArgs),
+
+    /// Print the extended public key (xpub) of an account for watch-only derivation
+    Xpub(XpubArgs),
+
+    /// Derive one wallet, or answer JSON-lines derivation requests on stdin as a co-process
+    Derive(DeriveArgs),
+
+    /// Inspect mnemonic phrases
+    #[command(subcommand)]
+    Mnemonic(MnemonicCommand),
+
+    /// Judge vanity address patterns
+    #[command(subcommand)]
+    Vanity(VanityCommand),
+
+    /// Check derivation against published Cosmos Hub, Osmosis and Evmos wallets
+    Selftest,
+
+    /// Print a completion script for a shell
+    Completions(CompletionsArgs),
+
+    /// Look up records in a wallet file by address, EVM address or derivation index
+    Query(QueryArgs),
+
+    /// Compare two wallet files by address
+    Diff(DiffArgs),
+
+    /// Combine wallet files, removing duplicates
+    Merge(MergeArgs),
+
+    /// Report addresses that appear more than once across wallet files
+    Dupes(DupesArgs),
+
+    /// Convert a binary wallet file to JSON, NDJSON or CSV
+    Convert(ConvertArgs),
+
+    /// Restore the plaintext keys of a wallet file written with --encrypt-keys
+    Decrypt(DecryptArgs),
+
+    /// Write a Keplr/Leap import bundle for every wallet in a file, one JSON file each
+    ExportExtension(ExportExtensionArgs),
+
+    /// Recompute the addresses of a wallet file for another chain from its private keys
+    Rederive(RederiveArgs),
+
+    /// Re-check the files listed in a manifest against their sizes, SHA-256 digests and record counts
+    VerifyManifest(VerifyManifestArgs),
+
+    /// Compute a legacy amino multisig address from public keys
+    Multisig(MultisigArgs),
+
+    /// Build airdrop data over the wallets in a file
+    #[command(subcommand)]
+    Airdrop(AirdropCommand),
+
+    /// Edit a genesis file
+    #[command(subcommand)]
+    Genesis(GenesisCommand),
+
+    /// Submit signed transactions through a node's CometBFT RPC endpoint
+    Broadcast(BroadcastArgs),
+
+    /// Sign arbitrary data with a wallet key (ADR-36)
+    SignMessage(SignMessageArgs),
+
+    /// Verify an ADR-36 signature over arbitrary data
+    VerifyMessage(VerifyMessageArgs),
+
+    /// Run an HTTP API for derivation and batch generation
+    #[cfg(feature = "server")]
+    Serve(ServeArgs),
+
+    /// Run a gRPC service with streaming batch generation
+    #[cfg(feature = "grpc")]
+    Grpc(GrpcArgs),
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FundedFilter {
+    /// Keep wallets holding a non-zero balance
+    Funded,
+    /// Keep wallets with no balance
+    Unfunded,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ScanBalancesArgs {
+    /// Wallet file (JSON array or NDJSON)
+    pub input: String,
+
+    /// REST (LCD) endpoint of a node, e.g. http://localhost:1317
+    #[arg(short, long)]
+    pub node: String,
+
+    /// Output file for annotated records
+    #[arg(short, long, default_value = "data/wallets/wallets_balances.json")]
+    pub output: String,
+
+    /// Maximum concurrent requests
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: usize,
+
+    /// Retries per request on transport errors, 429 and 5xx responses
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Request timeout in seconds
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+
+    /// Only write funded or unfunded wallets
+    #[arg(long, value_enum)]
+    pub only: Option<FundedFilter>,
+
+    /// Only count balances of this denom as funded
+    #[arg(long)]
+    pub denom: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountFilter {
+    /// Accounts that exist on chain
+    Existing,
+    /// Addresses with no account yet
+    New,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AnnotateArgs {
+    /// Wallet file (JSON array or NDJSON)
+    pub input: String,
+
+    /// REST (LCD) endpoint of a node, e.g. http://localhost:1317
+    #[arg(short, long)]
+    pub node: String,
+
+    /// Output file for annotated records
+    #[arg(short, long, default_value = "data/wallets/wallets_accounts.json")]
+    pub output: String,
+
+    /// Maximum concurrent requests
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: usize,
+
+    /// Retries per request on transport errors, 429 and 5xx responses
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Request timeout in seconds
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+
+    /// Only write wallets whose account exists, or does not exist yet
+    #[arg(long, value_enum)]
+    pub only: Option<AccountFilter>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FundModeArg {
+    /// One MsgMultiSend per transaction
+    Multisend,
+    /// Batched MsgSend messages
+    Send,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FundArgs {
+    /// Wallet file whose addresses receive funds
+    pub input: String,
+
+    /// Coins sent to each wallet, e.g. 1000000uatom (repeatable)
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub amount: Vec<crate::coin::Coin>,
+
+    /// Funder address (derived from --funder-key when signing)
+    #[arg(long)]
+    pub from: Option<String>,
+
+    /// Message layout
+    #[arg(long, value_enum, default_value_t = FundModeArg::Multisend)]
+    pub mode: FundModeArg,
+
+    /// Maximum encoded transaction size in bytes
+    #[arg(long, default_value_t = 500_000)]
+    pub max_tx_bytes: usize,
+
+    /// Maximum recipients per transaction
+    #[arg(long, default_value_t = 500)]
+    pub max_recipients: usize,
+
+    /// Gas limit per transaction before recipients are added
+    #[arg(long, default_value_t = 100_000)]
+    pub gas_base: u64,
+
+    /// Additional gas per recipient
+    #[arg(long, default_value_t = 25_000)]
+    pub gas_per_recipient: u64,
+
+    /// Gas price used to compute fees, e.g. 0.025uatom
+    #[arg(long, conflicts_with = "fee")]
+    pub gas_price: Option<crate::coin::GasPrice>,
+
+    /// Fixed fee per transaction, e.g. 5000uatom
+    #[arg(long, value_delimiter = ',')]
+    pub fee: Vec<crate::coin::Coin>,
+
+    /// Transaction memo
+    #[arg(long, default_value = "")]
+    pub memo: String,
+
+    /// Directory for the transaction files
+    #[arg(short, long, default_value = "data/txs")]
+    pub output_dir: String,
+
+    /// Hex private key of the funder; transactions are signed when set
+    #[arg(long, env = "FUNDER_PRIVATE_KEY", hide_env_values = true)]
+    #[cfg_attr(feature = "pkcs11", arg(conflicts_with = "pkcs11_module"))]
+    pub funder_key: Option<String>,
+
+    /// Key type of the funder key, on disk or in the HSM
+    #[arg(long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub funder_key_type: KeyType,
+
+    #[cfg(feature = "pkcs11")]
+    #[command(flatten)]
+    pub pkcs11: Pkcs11Args,
+
+    /// Chain ID (required when signing)
+    #[arg(long)]
+    pub chain_id: Option<String>,
+
+    /// Funder account number (fetched from --node when omitted)
+    #[arg(long)]
+    pub account_number: Option<u64>,
+
+    /// Funder sequence of the first transaction (fetched from --node when omitted)
+    #[arg(long)]
+    pub sequence: Option<u64>,
+
+    /// REST (LCD) endpoint used to look up the funder account
+    #[arg(short, long)]
+    pub node: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(clap::ArgGroup::new("source").required(true).args(["txs", "send_spec"])))]
+pub struct SignArgs {
+    /// Wallet file holding the signing keys
+    pub wallets: String,
+
+    /// Unsigned transactions: a directory of JSON files, a JSON array or a single transaction
+    #[arg(long)]
+    pub txs: Option<String>,
+
+    /// JSON send spec; builds and signs one MsgSend from every wallet
+    #[arg(long)]
+    pub send_spec: Option<String>,
+
+    /// Chain ID
+    #[arg(long)]
+    pub chain_id: String,
+
+    /// REST (LCD) endpoint used to look up account numbers and sequences
+    #[arg(short, long)]
+    pub node: Option<String>,
+
+    /// Account number for every signer (single-signer runs without --node)
+    #[arg(long, requires = "sequence", conflicts_with = "node")]
+    pub account_number: Option<u64>,
+
+    /// Starting sequence for every signer (single-signer runs without --node)
+    #[arg(long, requires = "account_number", conflicts_with = "node")]
+    pub sequence: Option<u64>,
+
+    /// Key type override (inferred per wallet from the record by default)
+    #[arg(short = 'k', long, value_enum)]
+    pub key_type: Option<KeyType>,
+
+    /// Maximum concurrent account lookups
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: usize,
+
+    /// Directory for the signed transactions
+    #[arg(short, long, default_value = "data/txs/signed")]
+    pub output_dir: String,
+
+    #[cfg(feature = "pkcs11")]
+    #[command(flatten)]
+    pub pkcs11: Pkcs11Args,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignModeArg {
+    /// SIGN_MODE_DIRECT, as `sign` and `fund` sign
+    Direct,
+    /// SIGN_MODE_LEGACY_AMINO_JSON, as Ledger devices sign
+    AminoJson,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SimulateArgs {
+    /// Transactions: a directory of JSON files, a JSON array or a single transaction, signed or not
+    pub txs: String,
+
+    /// REST (LCD) endpoint of a node, e.g. http://localhost:1317
+    #[arg(short, long)]
+    pub node: String,
+
+    /// Wallet file supplying the public keys of signers of unsigned transactions
+    #[arg(long)]
+    pub wallets: Option<String>,
+
+    /// Sign mode the unsigned transactions will be signed with
+    #[arg(long, value_enum, default_value_t = SignModeArg::Direct)]
+    pub sign_mode: SignModeArg,
+
+    /// Factor applied to the simulated gas to get the gas limit
+    #[arg(long, default_value_t = 1.3)]
+    pub gas_adjustment: f64,
+
+    /// Gas price used to compute fees, e.g. 0.025uatom
+    #[arg(long)]
+    pub gas_price: Option<crate::coin::GasPrice>,
+
+    /// Maximum concurrent requests
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: usize,
+
+    /// Retries per request on transport errors, 429 and 5xx responses
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Request timeout in seconds
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+
+    /// Output file for per-transaction estimates
+    #[arg(short, long, default_value = "data/txs/simulation.json")]
+    pub output: String,
+}
+
+/// A signing key held in an HSM or token, used through its PKCS#11 module.
+#[cfg(feature = "pkcs11")]
+#[derive(clap::Args, Debug, Clone)]
+pub struct Pkcs11Args {
+    /// PKCS#11 module of the HSM holding a signing key, e.g. /usr/lib/softhsm/libsofthsm2.so
+    #[arg(long, value_name = "PATH", requires = "pkcs11_key_label")]
+    pub pkcs11_module: Option<PathBuf>,
+
+    /// Label of the secp256k1 key pair on the token
+    #[arg(long, requires = "pkcs11_module")]
+    pub pkcs11_key_label: Option<String>,
+
+    /// Slot holding the token (the first slot with a token by default)
+    #[arg(long, requires = "pkcs11_module")]
+    pub pkcs11_slot: Option<u64>,
+
+    /// User PIN of the token
+    #[arg(long, env = "PKCS11_PIN", hide_env_values = true)]
+    pub pkcs11_pin: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageScheme {
+    /// Cosmos ADR-36 amino sign doc
+    Adr36,
+    /// Ethereum `personal_sign` (ethsecp256k1 keys only)
+    Eip191,
+    /// EIP-712 typed data; the data is an `eth_signTypedData_v4` JSON document (ethsecp256k1 keys only)
+    Eip712,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AddressCommand {
+    /// Module account addresses, optionally ADR-028 derived with keys
+    Module(ModuleAddressArgs),
+
+    /// ADR-028 derived address of a sub-account owned by an address
+    Derive(DeriveAddressArgs),
+
+    /// Predict CosmWasm instantiate2 contract addresses for every wallet as creator
+    Instantiate2(Instantiate2Args),
+
+    /// Predict EVM CREATE contract addresses of every ethsecp256k1 wallet at the given nonces
+    Create(CreateArgs),
+
+    /// Predict EVM CREATE2 contract addresses with every wallet as deployer
+    Create2(Create2Args),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ModuleAddressArgs {
+    /// Module names, e.g. distribution bonded_tokens_pool
+    #[arg(required = true)]
+    pub names: Vec<String>,
+
+    /// Hex derivation keys, applied in order
+    #[arg(long)]
+    pub key: Vec<String>,
+
+    /// Bech32 prefix for the addresses
+    #[arg(short, long, default_value = "cosmos")]
+    pub prefix: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DeriveAddressArgs {
+    /// Bech32 address of the owning account
+    pub address: String,
+
+    /// Hex derivation keys, applied in order
+    #[arg(long, required = true)]
+    pub key: Vec<String>,
+
+    /// Bech32 prefix for the result (defaults to the owner's prefix)
+    #[arg(short, long)]
+    pub prefix: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Instantiate2Args {
+    /// Wallet file whose addresses are the creators
+    pub wallets: String,
+
+    /// Hex SHA256 checksum of the contract code
+    #[arg(long)]
+    pub checksum: String,
+
+    /// Hex salts; every wallet gets one address per salt
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub salt: Vec<String>,
+
+    /// Instantiate message, for contracts instantiated with fix_msg
+    #[arg(long)]
+    pub init_msg: Option<String>,
+
+    /// Output file for the predicted addresses
+    #[arg(short, long, default_value = "data/contracts/instantiate2.json")]
+    pub output: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CreateArgs {
+    /// Wallet file of ethsecp256k1 wallets deploying the contracts
+    pub wallets: String,
+
+    /// Deployer account nonces
+    #[arg(long, value_delimiter = ',', default_value = "0")]
+    pub nonce: Vec<u64>,
+
+    /// Output file for the predicted addresses
+    #[arg(short, long, default_value = "data/contracts/create.json")]
+    pub output: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Create2Args {
+    /// Wallet file of ethsecp256k1 wallets (or factory contracts) deploying the contracts
+    pub wallets: String,
+
+    /// Hex keccak256 hash of the init code
+    #[arg(long)]
+    pub init_code_hash: String,
+
+    /// Hex salts, left-padded to 32 bytes; every wallet gets one address per salt
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub salt: Vec<String>,
+
+    /// Output file for the predicted addresses
+    #[arg(short, long, default_value = "data/contracts/create2.json")]
+    pub output: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FindIndexArgs {
+    /// Address to look for: bech32, or 0x hex for ethsecp256k1 keys
+    #[arg(short, long)]
+    pub address: String,
+
+    /// Mnemonic phrase the address was derived from
+    #[arg(short, long, env = "WALLET_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: String,
+
+    /// Highest derivation index to try
+    #[arg(long, default_value_t = 1_000_000)]
+    pub max_index: u64,
+
+    /// Derivation path template; {index} is replaced by each candidate index
+    /// [default: m/44'/<coin type of the key type>'/0'/0/{index}]
+    #[arg(long)]
+    pub path: Option<String>,
+
+    /// Key type the address was derived with (implied ethsecp256k1 for 0x addresses)
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Number of parallel threads (0 = auto-detect)
+    #[arg(short, long, default_value_t = 0)]
+    pub threads: usize,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RecoverArgs {
+    /// Mnemonic phrase to recover
+    #[arg(short, long, env = "WALLET_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: String,
+
+    /// REST (LCD) endpoint of a node, e.g. http://localhost:1317
+    #[arg(short, long)]
+    pub node: String,
+
+    /// Bech32 prefix of the chain
+    #[arg(short, long, default_value = "cosmos")]
+    pub prefix: String,
+
+    /// Key type of the chain's accounts
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Derivation path templates using {account} and/or {index}
+    #[arg(long = "path", value_delimiter = ',', default_values_t = [
+        "m/44'/118'/{account}'/0/{index}".to_string(),
+        "m/44'/60'/{account}'/0/{index}".to_string(),
+    ])]
+    pub paths: Vec<String>,
+
+    /// Consecutive unused indices before an account is considered exhausted
+    #[arg(long, default_value_t = 20)]
+    pub gap_limit: u64,
+
+    /// Maximum accounts scanned per path template
+    #[arg(long, default_value_t = 100)]
+    pub max_accounts: u64,
+
+    /// Output file for used accounts
+    #[arg(short, long, default_value = "data/wallets/recovered.json")]
+    pub output: String,
+
+    /// Maximum concurrent requests
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: usize,
+
+    /// Retries per request on transport errors, 429 and 5xx responses
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Request timeout in seconds
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FromPubkeyArgs {
+    /// File with one base64 or hex secp256k1 public key per line; reads stdin if omitted or "-"
+    #[arg(conflicts_with = "xpub")]
+    pub input: Option<String>,
+
+    /// Derive public keys 0/<index> from an account-level extended public key instead
+    #[arg(long)]
+    pub xpub: Option<String>,
+
+    /// Number of addresses to derive from --xpub
+    #[arg(short, long, default_value_t = 20, requires = "xpub")]
+    pub count: u64,
+
+    /// First address index to derive from --xpub
+    #[arg(long, default_value_t = 0, requires = "xpub")]
+    pub start_index: u64,
+
+    /// Bech32 prefix for addresses
+    #[arg(short, long, default_value = "cosmos")]
+    pub prefix: String,
+
+    /// Address hashing scheme
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Address checksum
+    #[arg(long, value_enum, default_value_t = AddressEncoding::Bech32)]
+    pub encoding: AddressEncoding,
+
+    /// Write a JSON array instead of printing NDJSON to stdout
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct XpubArgs {
+    /// Mnemonic phrase of the account
+    #[arg(short, long, env = "WALLET_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: String,
+
+    /// Key type, which selects the default coin type
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Account number
+    #[arg(short, long, default_value_t = 0)]
+    pub account: u32,
+
+    /// Derivation path of the extended key [default: m/44'/<coin type of the key type>'/<account>']
+    #[arg(long, conflicts_with = "account")]
+    pub path: Option<String>,
+
+    /// Also print the extended private key (xprv)
+    #[arg(long)]
+    pub private: bool,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(clap::ArgGroup::new("request").required(true).args(["path", "index", "stdin"])))]
+pub struct DeriveArgs {
+    /// Mnemonic phrase, read once and held in memory for the session
+    #[arg(short, long, env = "WALLET_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: String,
+
+    /// Bech32 prefix of requests that do not name one
+    #[arg(short, long, default_value = "cosmos")]
+    pub prefix: String,
+
+    /// Key type of requests that do not name one
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Derivation path of the wallet
+    #[arg(long)]
+    pub path: Option<String>,
+
+    /// Address index on the standard path of the key type's coin type
+    #[arg(long)]
+    pub index: Option<u64>,
+
+    /// Read requests as JSON lines from stdin and answer each with a JSON line on stdout until EOF
+    #[arg(long)]
+    pub stdin: bool,
+
+    /// Include privateKey in the answer (stdin requests ask with "include_private_key")
+    #[arg(long, conflicts_with = "stdin")]
+    pub include_private_key: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MnemonicCommand {
+    /// Validate a mnemonic and suggest corrections for mistyped or misplaced words
+    Check(MnemonicCheckArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MnemonicCheckArgs {
+    /// Mnemonic phrase to check; read from stdin if omitted
+    #[arg(short, long, env = "WALLET_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VanityCommand {
+    /// Estimate the attempts and time a pattern takes at this machine's measured throughput
+    Estimate(VanityEstimateArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct VanityEstimateArgs {
+    /// Characters to look for after the prefix's 1 separator (bech32) or after 0x (--hex)
+    pub pattern: String,
+
+    /// Where the pattern must appear
+    #[arg(long, value_enum, default_value_t = Position::Prefix)]
+    pub position: Position,
+
+    /// Match the 0x EVM address of ethsecp256k1 keys instead of the bech32 address
+    #[arg(long)]
+    pub hex: bool,
+
+    /// Hex letters must match the EIP-55 checksum case of the pattern
+    #[arg(long, requires = "hex")]
+    pub case_sensitive: bool,
+
+    /// Key type to measure throughput with (ethsecp256k1 with --hex)
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Addresses per second to assume instead of measuring
+    #[arg(long)]
+    pub rate: Option<f64>,
+
+    /// Number of parallel threads to measure with (0 = auto-detect)
+    #[arg(short, long, default_value_t = 0)]
+    pub threads: usize,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DiffArgs {
+    /// First wallet file
+    pub left: String,
+
+    /// Second wallet file
+    pub right: String,
+
+    /// Write the full report as JSON
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MergeArgs {
+    /// Wallet files to combine
+    #[arg(required = true)]
+    pub inputs: Vec<String>,
+
+    /// Output file path
+    #[arg(short, long, default_value = "data/wallets/merged.json")]
+    pub output: String,
+
+    /// Record format: json, ndjson or csv
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub output_format: OutputFormat,
+
+    /// Stream inputs that are each in index order, as generated, instead of loading them into memory
+    #[arg(long)]
+    pub sorted: bool,
+
+    /// Fail if any index between the first and last merged is missing
+    #[arg(long)]
+    pub contiguous: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DupesArgs {
+    /// Wallet files to check together
+    #[arg(required = true)]
+    pub inputs: Vec<String>,
+
+    /// Write the full report as JSON
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ConvertArgs {
+    /// Binary wallet file (--output-format bin)
+    pub input: String,
+
+    /// Output file path, or - for stdout
+    #[arg(short, long, default_value = "-")]
+    pub output: String,
+
+    /// Record format (default: json, or ndjson when writing to stdout)
+    #[arg(long, value_enum)]
+    pub output_format: Option<OutputFormat>,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(clap::ArgGroup::new("reference").required(true).args(["mnemonic", "mnemonic_ref"])))]
+pub struct ExportExtensionArgs {
+    /// Wallet file (JSON, NDJSON or binary) of Cosmos wallets
+    pub input: String,
+
+    /// Directory the bundles are written to, as <address>.json
+    #[arg(short, long, default_value = "data/wallets/extension")]
+    pub output_dir: String,
+
+    /// Mnemonic of the wallets, referenced by its master key fingerprint and checked against the file
+    #[arg(short, long, env = "WALLET_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Label referencing the mnemonic instead, for when it is not at hand
+    #[arg(long)]
+    pub mnemonic_ref: Option<String>,
+
+    /// Account names are this prefix and the derivation index
+    #[arg(long, default_value = "wallet")]
+    pub name_prefix: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DecryptArgs {
+    /// JSON or NDJSON wallet file with encrypted keys
+    pub input: String,
+
+    /// Output file path, or - for stdout
+    #[arg(short, long, default_value = "-")]
+    pub output: String,
+
+    /// Record format (default: json, or ndjson when writing to stdout)
+    #[arg(long, value_enum)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Passphrase the keys were encrypted with (prompted for when unset)
+    #[arg(long, env = "WALLET_KEY_PASSPHRASE", hide_env_values = true)]
+    pub key_passphrase: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to complete for
+    #[arg(value_enum)]
+    pub shell: clap_complete::Shell,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct VerifyManifestArgs {
+    /// Manifest written by generation (<output stem>.manifest.json); the files are looked up beside it
+    pub manifest: PathBuf,
+
+    /// Expected SHA-256 of the manifest itself, as printed by generation and recorded in --report-json
+    #[arg(long, value_name = "HEX")]
+    pub sha256: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RederiveArgs {
+    /// Wallet file with private keys (JSON, NDJSON or binary)
+    pub input: String,
+
+    /// Output file path, or - for stdout
+    #[arg(short, long, default_value = "-")]
+    pub output: String,
+
+    /// Record format (default: json, or ndjson when writing to stdout)
+    #[arg(long, value_enum)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Bech32 prefix for the new addresses
+    #[arg(short, long, default_value = "cosmos")]
+    pub prefix: String,
+
+    /// Address hashing scheme of the new addresses
+    #[arg(short = 'k', long, value_enum, default_value_t = KeyType::Secp256k1)]
+    pub key_type: KeyType,
+
+    /// Chain preset setting prefix, key type and encoding (e.g. osmosis, evmos)
+    #[arg(long, conflicts_with_all = ["prefix", "key_type"])]
+    pub chain: Option<String>,
+
+    /// Address checksum (default: bech32, or the chain preset's encoding)
+    #[arg(long, value_enum)]
+    pub encoding: Option<AddressEncoding>,
+
+    /// Bitcoin addresses of this type with WIF keys (default network: --chain bitcoin)
+    #[arg(long, value_enum, conflicts_with_all = ["prefix", "key_type", "encoding"])]
+    pub bitcoin: Option<BitcoinAddress>,
+
+    /// Encoding of the pubkey field
+    #[arg(long, value_enum, default_value_t = PubkeyFormat::Base64)]
+    pub pubkey_format: PubkeyFormat,
+
+    /// Also write the uncompressed public key as pubkeyUncompressed (hex), next to pubkey
+    #[arg(long)]
+    pub include_uncompressed: bool,
+
+    /// For ethsecp256k1 keys, also write the SHA256/RIPEMD160 bech32 address as secp256k1Address
+    #[arg(long)]
+    pub dual_address: bool,
+
+    /// Encoding of the privateKey field (wif: mainnet, or the network of a Bitcoin chain)
+    #[arg(long, value_enum, default_value_t = PrivateKeyFormat::Hex)]
+    pub privkey_format: PrivateKeyFormat,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(clap::ArgGroup::new("key").required(true).args(["address", "index", "evm"])))]
+pub struct QueryArgs {
+    /// Wallet file (JSON array or NDJSON)
+    pub input: String,
+
+    /// Bech32 address
+    #[arg(long)]
+    pub address: Option<String>,
+
+    /// Derivation index (last path component)
+    #[arg(long)]
+    pub index: Option<u64>,
+
+    /// EVM address
+    #[arg(long)]
+    pub evm: Option<String>,
+
+    /// Build or refresh the sidecar index (<file>.idx) before querying; done automatically for files over 256 MiB
+    #[arg(long, conflicts_with = "no_index")]
+    pub build_index: bool,
+
+    /// Scan the file instead of using or building an index
+    #[arg(long)]
+    pub no_index: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct MultisigArgs {
+    /// Base64 compressed secp256k1 public keys, as in the wallet "pubkey" field
+    #[arg(long = "pubkey", value_delimiter = ',', required = true)]
+    pub pubkeys: Vec<String>,
+
+    /// Signatures required
+    #[arg(long)]
+    pub threshold: u32,
+
+    /// Bech32 prefix for the address
+    #[arg(short, long, default_value = "cosmos")]
+    pub prefix: String,
+
+    /// Keep the keys in the given order instead of sorting them by address like `keys add --multisig`
+    #[arg(long)]
+    pub no_sort: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AirdropCommand {
+    /// Build a Merkle tree over (address, amount) pairs and emit the root and per-address proofs
+    Merkle(AirdropMerkleArgs),
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleFormatArg {
+    /// cw20-merkle-airdrop: sha256(address + amount) over bech32 addresses
+    Cosmwasm,
+    /// merkle-distributor: keccak256(abi.encodePacked(index, account, amount)) over EVM addresses
+    Evm,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct AirdropMerkleArgs {
+    /// Wallet file; records may carry an "amount" field overriding --amount
+    pub input: String,
+
+    /// Amount for every address without its own "amount"
+    #[arg(short, long)]
+    pub amount: Option<u128>,
+
+    /// Leaf and proof format of the target contract
+    #[arg(short, long, value_enum, default_value_t = MerkleFormatArg::Cosmwasm)]
+    pub format: MerkleFormatArg,
+
+    /// Output file for the root and claims
+    #[arg(short, long, default_value = "data/airdrop/merkle.json")]
+    pub output: String,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GenesisCommand {
+    /// Add a funded auth account for every wallet in a file
+    AddAccounts(GenesisAddAccountsArgs),
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenesisAccountTypeArg {
+    /// cosmos.auth.v1beta1.BaseAccount
+    Base,
+    /// ethermint.types.v1.EthAccount
+    Eth,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct GenesisAddAccountsArgs {
+    /// Wallet file whose addresses become genesis accounts
+    pub wallets: String,
+
+    /// Genesis file to update
+    #[arg(short, long)]
+    pub genesis: String,
+
+    /// Initial balance of every account, e.g. 1000000uatom,5stake
+    #[arg(short, long, value_delimiter = ',', required = true)]
+    pub amount: Vec<crate::coin::Coin>,
+
+    /// Account type to insert
+    #[arg(long, value_enum, default_value_t = GenesisAccountTypeArg::Base)]
+    pub account_type: GenesisAccountTypeArg,
+
+    /// Write the result here instead of updating the genesis file in place
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastModeArg {
+    /// Return without waiting for CheckTx
+    Async,
+    /// Wait for CheckTx
+    Sync,
+    /// Wait for block inclusion
+    Commit,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BroadcastArgs {
+    /// Signed transactions: a directory of JSON files, a JSON array, a single transaction or a .b64 file
+    pub input: String,
+
+    /// CometBFT RPC endpoint of a node
+    #[arg(short, long, default_value = "http://localhost:26657")]
+    pub node: String,
+
+    /// Broadcast mode
+    #[arg(long, value_enum, default_value_t = BroadcastModeArg::Sync)]
+    pub mode: BroadcastModeArg,
+
+    /// Maximum signers submitting at once; each signer's transactions are sent in sequence order
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: usize,
+
+    /// Retries per request on transport errors, 429 and 5xx responses
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Request timeout in seconds
+    #[arg(long, default_value_t = 30)]
+    pub timeout: u64,
+
+    /// Output file for per-transaction results
+    #[arg(short, long, default_value = "data/txs/broadcast_results.json")]
+    pub output: String,
+}
+
+/// The data being signed or verified.
+#[derive(clap::Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct MessageData {
+    /// Data as a UTF-8 string
+    #[arg(long)]
+    pub data: Option<String>,
+
+    /// Read the data from a file
+    #[arg(long)]
+    pub data_file: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SignMessageArgs {
+    /// Wallet file holding the signing key
+    pub wallets: String,
+
+    /// Address of the wallet to sign with (bech32, or 0x for eip191/eip712)
+    #[arg(long)]
+    pub signer: String,
+
+    #[command(flatten)]
+    pub message: MessageData,
+
+    /// Signature scheme
+    #[arg(long, value_enum, default_value_t = MessageScheme::Adr36)]
+    pub scheme: MessageScheme,
+
+    /// Key type override (inferred from the wallet record by default)
+    #[arg(short = 'k', long, value_enum)]
+    pub key_type: Option<KeyType>,
+
+    /// Write the signature JSON to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct VerifyMessageArgs {
+    /// Address that claims to have signed the data (bech32, or 0x for eip191/eip712)
+    #[arg(long)]
+    pub signer: String,
+
+    #[command(flatten)]
+    pub message: MessageData,
+
+    /// Signature JSON file written by sign-message; for eip191/eip712 also a 0x hex signature
+    #[arg(long)]
+    pub signature: String,
+
+    /// Signature scheme
+    #[arg(long, value_enum, default_value_t = MessageScheme::Adr36)]
+    pub scheme: MessageScheme,
+}
+
+#[cfg(feature = "server")]
+#[derive(clap::Args, Debug)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    pub listen: String,
+
+    /// Bearer token required on every request
+    #[arg(long, env = "WALLET_FACTORY_TOKEN", hide_env_values = true)]
+    pub auth_token: Option<String>,
+
+    /// Maximum wallets per generation job
+    #[arg(long, default_value_t = 1_000_000)]
+    pub max_job_size: usize,
+
+    /// Maximum generation jobs running at once
+    #[arg(long, default_value_t = 4)]
+    pub max_jobs: usize,
+
+    /// Maximum generation jobs queued or running; further requests are refused with 429
+    #[arg(long, default_value_t = 64)]
+    pub max_pending_jobs: usize,
+
+    /// Seconds a finished job's results are kept before being discarded
+    #[arg(long, default_value_t = 3600)]
+    pub job_ttl: u64,
+}
+
+#[cfg(feature = "grpc")]
+#[derive(clap::Args, Debug)]
+pub struct GrpcArgs {
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:50051")]
+    pub listen: String,
+
+    /// Bearer token required in the `authorization` metadata of every call
+    #[arg(long, env = "WALLET_FACTORY_TOKEN", hide_env_values = true)]
+    pub auth_token: Option<String>,
+
+    /// Maximum wallets per GenerateWallets stream
+    #[arg(long, default_value_t = 10_000_000)]
+    pub max_stream_size: u64,
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A Cosmos SDK coin, with the amount kept as a decimal string as on the wire.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: String,
+}
+
+impl Coin {
+    pub fn new(amount: u128, denom: &str) -> Self {
+        Self { denom: denom.to_string(), amount: amount.to_string() }
+    }
+
+    /// The amount as an integer, treating malformed amounts as zero.
+    pub fn amount_u128(&self) -> u128 {
+        self.amount.parse().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+impl FromStr for Coin {
+    type Err = String;
+
+    /// Parse the SDK's `<amount><denom>` notation, e.g. `1000uatom`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (amount, denom) = s.split_at(split);
+        if amount.is_empty() || denom.is_empty() {
+            return Err(format!("Invalid coin '{}': expected <amount><denom>, e.g. 1000uatom", s));
+        }
+        let amount: u128 = amount.parse().map_err(|e| format!("Invalid coin amount '{}': {}", amount, e))?;
+        Ok(Coin::new(amount, denom))
+    }
+}
+
+/// A decimal gas price such as `0.025uatom`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasPrice {
+    pub amount: f64,
+    pub denom: String,
+}
+
+impl GasPrice {
+    /// Fee for `gas` units, rounded up.
+    pub fn fee(&self, gas: u64) -> Coin {
+        Coin::new((self.amount * gas as f64).ceil() as u128, &self.denom)
+    }
+}
+
+impl FromStr for GasPrice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(s.len());
+        let (amount, denom) = s.split_at(split);
+        if amount.is_empty() || denom.is_empty() {
+            return Err(format!("Invalid gas price '{}': expected <amount><denom>, e.g. 0.025uatom", s));
+        }
+        let amount: f64 = amount.parse().map_err(|e| format!("Invalid gas price amount '{}': {}", amount, e))?;
+        Ok(GasPrice { amount, denom: denom.to_string() })
+    }
+}
@@ -0,0 +1,167 @@
+//! `wallet-factory.toml`: defaults for command-line options, plus custom chain presets.
+//!
+//! Top-level keys set options of wallet generation; a table named after a subcommand
+//! (`[scan-balances]`, `[address.module]`) sets that subcommand's options. Keys are long flag
+//! names, or argument names for positionals. Values become clap defaults, so flags and environment
+//! variables given for a run still take precedence.
+//!
+//! `[chains.<name>]` tables define presets for `--chain` next to the built-in ones.
+
+use anyhow::{anyhow, Context, Result};
+use clap::builder::Resettable;
+use clap::Command;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::chain;
+use crate::types::{AddressEncoding, ChainFamily, DerivationScheme, KeyType, PrivateKeyFormat, PubkeyFormat};
+use crate::generator::DerivationConfig;
+
+/// Looked up in the working directory when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "wallet-factory.toml";
+
+/// Table holding custom chain presets rather than subcommand options.
+const CHAINS_TABLE: &str = "chains";
+
+/// A chain preset defined in the config file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ChainConfig {
+    pub prefix: String,
+    pub key_type: KeyType,
+    /// Defaults to the key type's coin type
+    pub coin_type: Option<u32>,
+    #[serde(default)]
+    pub encoding: Option<AddressEncoding>,
+}
+
+impl ChainConfig {
+    pub fn config(&self) -> DerivationConfig {
+        DerivationConfig {
+            prefix: self.prefix.clone(),
+            key_type: self.key_type.clone(),
+            coin_type: self.coin_type.unwrap_or_else(|| self.key_type.coin_type()),
+            encoding: self.encoding.unwrap_or(AddressEncoding::Bech32),
+            scheme: DerivationScheme::Standard,
+            family: ChainFamily::Cosmos,
+            private_key_format: PrivateKeyFormat::Hex,
+            pubkey_format: PubkeyFormat::Base64,
+            include_uncompressed: false,
+            dual_address: false,
+            path_range: None,
+            address_encoder: None,
+        }
+    }
+}
+
+/// A parsed config file. The default value is an empty config, used when no file exists.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFile {
+    pub path: Option<PathBuf>,
+    options: toml::Table,
+    pub chains: BTreeMap<String, ChainConfig>,
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut options: toml::Table = text.parse().with_context(|| format!("Invalid config file {}", path.display()))?;
+        let chains = match options.remove(CHAINS_TABLE) {
+            Some(chains) => chains.try_into().with_context(|| format!("Invalid [{}] in {}", CHAINS_TABLE, path.display()))?,
+            None => BTreeMap::new(),
+        };
+        Ok(Self { path: Some(path.to_path_buf()), options, chains })
+    }
+
+    /// Load `explicit`, or `./wallet-factory.toml` if it exists, or an empty config.
+    pub fn find(explicit: Option<&Path>) -> Result<Self> {
+        match explicit {
+            Some(path) => Self::load(path),
+            None if Path::new(DEFAULT_CONFIG_FILE).is_file() => Self::load(Path::new(DEFAULT_CONFIG_FILE)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Install the file's options as defaults of `command` and its subcommands.
+    ///
+    /// Fails on keys that name no option, so typos do not go unnoticed.
+    pub fn apply(&self, command: Command) -> Result<Command> {
+        let mut errors = Vec::new();
+        let command = apply_table(command, &self.options, "", &mut errors);
+        if errors.is_empty() {
+            return Ok(command);
+        }
+        let source = self.path.as_deref().map_or_else(|| "config".to_string(), |path| path.display().to_string());
+        Err(anyhow!("{}: {}", source, errors.join("; ")))
+    }
+
+    /// Resolve `--chain`: presets from the file first, then the built-in ones.
+    pub fn chain(&self, name: &str) -> Result<DerivationConfig> {
+        match self.chains.iter().find(|(custom, _)| custom.eq_ignore_ascii_case(name)) {
+            Some((_, custom)) => Ok(custom.config()),
+            None => Ok(chain::preset(name)?.config()),
+        }
+    }
+}
+
+fn apply_table(mut command: Command, table: &toml::Table, scope: &str, errors: &mut Vec<String>) -> Command {
+    for (key, value) in table {
+        let name = format!("{}{}", scope, key);
+        if let toml::Value::Table(options) = value {
+            if command.find_subcommand(key).is_some() {
+                let scope = format!("{}.", name);
+                command = command.mut_subcommand(key, |subcommand| apply_table(subcommand, options, &scope, errors));
+            } else {
+                errors.push(format!("[{}] is not a subcommand", name));
+            }
+            continue;
+        }
+
+        let id = key.replace('-', "_");
+        let Some(arg) = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(key.as_str()) || (arg.is_positional() && arg.get_id() == id.as_str()))
+        else {
+            errors.push(format!("{} is not an option", name));
+            continue;
+        };
+        let id = arg.get_id().clone();
+        match option_values(value) {
+            // A configured value satisfies a required option, including one required unless others are given
+            Some(values) => command = command.mut_arg(id, |arg| arg.default_values(values).required(false).required_unless_present(Resettable::Reset)),
+            None => errors.push(format!("{} must be a string, number, boolean or array of those", name)),
+        }
+    }
+    command
+}
+
+fn option_values(value: &toml::Value) -> Option<Vec<String>> {
+    match value {
+        toml::Value::String(s) => Some(vec![s.clone()]),
+        toml::Value::Integer(n) => Some(vec![n.to_string()]),
+        toml::Value::Float(n) => Some(vec![n.to_string()]),
+        toml::Value::Boolean(b) => Some(vec![b.to_string()]),
+        toml::Value::Array(items) => items.iter().map(|item| option_values(item).filter(|v| v.len() == 1).map(|mut v| v.remove(0))).collect(),
+        toml::Value::Datetime(_) | toml::Value::Table(_) => None,
+    }
+}
+
+/// `--config` from raw arguments; the file must be read before they are parsed.
+pub fn config_arg<I: IntoIterator<Item = OsString>>(args: I) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
@@ -0,0 +1,105 @@
+//! Derivation as a long-lived co-process: requests arrive as JSON lines and each gets one JSON
+//! line back, derived from a seed held in memory for the whole session.
+//!
+//! A request is `{"id": ..., "path": ..., "index": ..., "prefix": ..., "key_type": ...,
+//! "coin_type": ..., "include_private_key": ...}`, every field optional. `path` takes precedence
+//! over `index`; missing prefix and key type fall back to the session defaults. The answer is the
+//! wallet record, without `privateKey` unless asked for, or `{"error": ...}`; either echoes `id`
+//! so callers can pipeline requests. A bad request does not end the session.
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+use crate::derivation::KeyDeriver;
+use crate::generator::{derivation_path, derive_wallet_with};
+use crate::types::KeyType;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct DeriveRequest {
+    /// Echoed in the answer
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub path: Option<String>,
+    pub index: Option<u64>,
+    pub prefix: Option<String>,
+    pub key_type: Option<String>,
+    /// Coin type of `index` requests (default: the key type's)
+    pub coin_type: Option<u32>,
+    #[serde(default)]
+    pub include_private_key: bool,
+}
+
+/// Prefix and key type of requests that do not name their own.
+#[derive(Debug, Clone)]
+pub struct Defaults {
+    pub prefix: String,
+    pub key_type: KeyType,
+}
+
+/// Derives requests from one seed, reusing the path levels neighbouring requests share.
+pub struct Coprocess<'a> {
+    deriver: KeyDeriver<'a>,
+    defaults: Defaults,
+}
+
+impl<'a> Coprocess<'a> {
+    pub fn new(seed: &'a [u8], defaults: Defaults) -> Self {
+        Self { deriver: KeyDeriver::new(seed), defaults }
+    }
+
+    /// The answer line to one request.
+    pub fn answer(&mut self, request: &DeriveRequest) -> Value {
+        let mut answer = match self.derive(request) {
+            Ok(record) => record,
+            Err(e) => json!({ "error": e.to_string() }),
+        };
+        if let Some(id) = &request.id {
+            answer["id"] = id.clone();
+        }
+        answer
+    }
+
+    fn derive(&mut self, request: &DeriveRequest) -> Result<Value> {
+        let key_type = match &request.key_type {
+            Some(key_type) => key_type.parse().map_err(|_| anyhow!("Unknown key type: {}", key_type))?,
+            None => self.defaults.key_type.clone(),
+        };
+        let path = match (&request.path, request.index) {
+            (Some(path), _) => path.clone(),
+            (None, Some(index)) => derivation_path(request.coin_type.unwrap_or_else(|| key_type.coin_type()), index),
+            (None, None) => return Err(anyhow!("A request needs a path or an index")),
+        };
+        let prefix = request.prefix.as_deref().unwrap_or(&self.defaults.prefix);
+
+        let wallet = derive_wallet_with(&mut self.deriver, &path, prefix, &key_type)?;
+        let mut record = serde_json::to_value(&wallet)?;
+        if !request.include_private_key {
+            record.as_object_mut().expect("wallets serialize as objects").remove("privateKey");
+        }
+        Ok(record)
+    }
+
+    /// Answer every request line of `input` on `output`, flushing after each answer so a caller
+    /// waiting on it is not held up by buffering. Blank lines are skipped. Returns the requests
+    /// answered once `input` ends.
+    pub fn serve<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<usize> {
+        let mut answered = 0;
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let answer = match serde_json::from_str::<DeriveRequest>(&line) {
+                Ok(request) => self.answer(&request),
+                Err(e) => json!({ "error": format!("Invalid request: {}", e) }),
+            };
+            serde_json::to_writer(&mut output, &answer)?;
+            output.write_all(b"\n")?;
+            output.flush()?;
+            answered += 1;
+        }
+        Ok(answered)
+    }
+}
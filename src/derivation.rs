@@ -0,0 +1,166 @@
+//! BIP-32 key derivation on top of the `bip32` crate, and SLIP-0010 derivation of ed25519 keys.
+//!
+//! Batches derive thousands of neighbouring paths (`m/44'/118'/0'/0/*`), so
+//! [`KeyDeriver`] keeps every level of the previous path and only derives the levels
+//! that changed; a standard wallet then costs one HMAC-SHA512 and a scalar addition.
+
+use bip32::secp256k1::{PublicKey as Point, SecretKey};
+use bip32::{ChainCode, ChildNumber, DerivationPath, Prefix, PrivateKey, PublicKey, XPrv, XPub};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+use crate::error::GeneratorError;
+
+/// Derive the raw secp256k1 private key at `path` (e.g. `m/44'/118'/0'/0/0`).
+pub fn derive_private_key(seed: &[u8], path: &str) -> Result<[u8; 32], GeneratorError> {
+    KeyDeriver::new(seed).private_key(path)
+}
+
+/// Derive the extended private key at `path`.
+pub fn derive_xprv(seed: &[u8], path: &str) -> Result<XPrv, GeneratorError> {
+    XPrv::derive_from_path(seed, &parse_path(path)?).map_err(|e| derivation_error(path, e))
+}
+
+/// Serialized `xpub` and `xprv` at `path`.
+pub fn extended_keys(seed: &[u8], path: &str) -> Result<(String, String), GeneratorError> {
+    let xprv = derive_xprv(seed, path)?;
+    Ok((xprv.public_key().to_string(Prefix::XPUB), xprv.to_string(Prefix::XPRV).to_string()))
+}
+
+/// BIP-32 fingerprint of the master key of `seed`, which names the seed without revealing it.
+pub fn master_fingerprint(seed: &[u8]) -> Result<[u8; 4], GeneratorError> {
+    let master = XPrv::new(seed).map_err(|e| derivation_error("m", e))?;
+    Ok(master.public_key().fingerprint())
+}
+
+/// Parse a serialized extended public key (`xpub...`).
+pub fn parse_xpub(xpub: &str) -> Result<XPub, GeneratorError> {
+    xpub.parse().map_err(|e| derivation_error("m", e))
+}
+
+/// Compressed public key of the non-hardened child `path` (e.g. `0/5`) below `xpub`.
+pub fn derive_public_key(xpub: &XPub, path: &str) -> Result<[u8; 33], GeneratorError> {
+    let mut key = xpub.clone();
+    for component in path.split('/').filter(|component| !component.is_empty()) {
+        let child: ChildNumber = component.parse().map_err(|e| derivation_error(path, e))?;
+        key = key.derive_child(child).map_err(|e| derivation_error(path, e))?;
+    }
+    Ok(key.to_bytes())
+}
+
+/// Derives private keys from one seed, reusing the levels shared with the previous path.
+pub struct KeyDeriver<'a> {
+    seed: &'a [u8],
+    /// Keys along the previous path, root first
+    levels: Vec<Level>,
+}
+
+struct Level {
+    child: Option<ChildNumber>,
+    key: SecretKey,
+    chain_code: ChainCode,
+    /// Computed on first use; only non-hardened children need it
+    public_key: Option<Point>,
+}
+
+impl<'a> KeyDeriver<'a> {
+    pub fn new(seed: &'a [u8]) -> Self {
+        Self { seed, levels: Vec::new() }
+    }
+
+    /// Derive the raw private key at `path`.
+    pub fn private_key(&mut self, path: &str) -> Result<[u8; 32], GeneratorError> {
+        let path_error = |e| derivation_error(path, e);
+        let children: Vec<ChildNumber> = parse_path(path)?.iter().collect();
+
+        if self.levels.is_empty() {
+            let root = XPrv::new(self.seed).map_err(path_error)?;
+            let chain_code = root.attrs().chain_code;
+            let key = SecretKey::from(root.private_key().as_nonzero_scalar());
+            self.levels.push(Level { child: None, key, chain_code, public_key: None });
+        }
+        let shared = self.levels[1..]
+            .iter()
+            .zip(&children)
+            .take_while(|(level, child)| level.child == Some(**child))
+            .count();
+        self.levels.truncate(shared + 1);
+
+        for &child in &children[shared..] {
+            let parent = self.levels.last_mut().expect("root level");
+            let (tweak, chain_code) = if child.is_hardened() {
+                parent.key.derive_tweak(&parent.chain_code, child)
+            } else {
+                let public_key = parent.public_key.get_or_insert_with(|| parent.key.public_key());
+                public_key.derive_tweak(&parent.chain_code, child)
+            }
+            .map_err(path_error)?;
+            let key = parent.key.derive_child(tweak).map_err(path_error)?;
+            self.levels.push(Level { child: Some(child), key, chain_code, public_key: None });
+        }
+
+        Ok(PrivateKey::to_bytes(&self.levels.last().expect("root level").key))
+    }
+}
+
+/// Derives SLIP-0010 ed25519 private keys from one seed, reusing the levels shared with the
+/// previous path. SLIP-0010 defines only hardened children for ed25519.
+pub struct Ed25519Deriver<'a> {
+    seed: &'a [u8],
+    /// Key and chain code along the previous path, root first
+    levels: Vec<(Option<ChildNumber>, [u8; 32], [u8; 32])>,
+}
+
+impl<'a> Ed25519Deriver<'a> {
+    pub fn new(seed: &'a [u8]) -> Self {
+        Self { seed, levels: Vec::new() }
+    }
+
+    /// Derive the 32-byte ed25519 secret key at `path` (e.g. `m/44'/501'/0'/0'`).
+    pub fn private_key(&mut self, path: &str) -> Result<[u8; 32], GeneratorError> {
+        let children: Vec<ChildNumber> = parse_path(path)?.iter().collect();
+        if children.iter().any(|child| !child.is_hardened()) {
+            return Err(GeneratorError::Derivation {
+                path: path.to_string(),
+                reason: "ed25519 keys only have hardened children".to_string(),
+            });
+        }
+
+        if self.levels.is_empty() {
+            let (key, chain_code) = hmac_sha512(b"ed25519 seed", &[self.seed]);
+            self.levels.push((None, key, chain_code));
+        }
+        let shared = self.levels[1..]
+            .iter()
+            .zip(&children)
+            .take_while(|((level, _, _), child)| *level == Some(**child))
+            .count();
+        self.levels.truncate(shared + 1);
+
+        for &child in &children[shared..] {
+            let (_, key, chain_code) = self.levels.last().expect("root level");
+            let (key, chain_code) = hmac_sha512(chain_code, &[&[0], key, &child.to_bytes()]);
+            self.levels.push((Some(child), key, chain_code));
+        }
+        Ok(self.levels.last().expect("root level").1)
+    }
+}
+
+/// HMAC-SHA512 of `data` split into its left (key) and right (chain code) halves.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    for part in data {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+    let (left, right) = output.split_at(32);
+    (left.try_into().expect("32 bytes"), right.try_into().expect("32 bytes"))
+}
+
+fn parse_path(path: &str) -> Result<DerivationPath, GeneratorError> {
+    path.parse().map_err(|e| derivation_error(path, e))
+}
+
+fn derivation_error(path: &str, error: bip32::Error) -> GeneratorError {
+    GeneratorError::Derivation { path: path.to_string(), reason: error.to_string() }
+}
@@ -0,0 +1,226 @@
+//! EIP-712 typed-data hashing and signing (`eth_signTypedData_v4`).
+//!
+//! Evmos/Injective-style chains accept Cosmos transactions wrapped as typed data; this module
+//! signs whatever typed data document the chain's tooling produces.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::message::{eth_recover, eth_sign_digest, PersonalSignature};
+
+/// One member of a struct type.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TypedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+}
+
+/// A typed data document as accepted by `eth_signTypedData_v4`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Element type of an array type, e.g. `Coin` for `Coin[]` or `uint256[2]`.
+fn array_element(type_name: &str) -> Option<&str> {
+    type_name.strip_suffix(']').and_then(|t| t.rfind('[').map(|i| &t[..i]))
+}
+
+/// Struct name a field type refers to, with any array suffixes removed.
+fn base_type(type_name: &str) -> &str {
+    type_name.find('[').map_or(type_name, |i| &type_name[..i])
+}
+
+/// Parse a decimal or 0x hex integer into a 256-bit big-endian word.
+fn parse_word(value: &Value) -> Result<[u8; 32]> {
+    let text = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        other => return Err(anyhow!("Expected an integer, got {}", other)),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+
+    let mut word = [0u8; 32];
+    if let Some(hex_digits) = digits.strip_prefix("0x") {
+        let bytes = hex::decode(format!("{:0>1$}", hex_digits, hex_digits.len() + hex_digits.len() % 2))
+            .with_context(|| format!("Invalid hex integer {}", text))?;
+        if bytes.len() > 32 {
+            return Err(anyhow!("Integer {} exceeds 256 bits", text));
+        }
+        word[32 - bytes.len()..].copy_from_slice(&bytes);
+    } else {
+        if digits.is_empty() {
+            return Err(anyhow!("Invalid integer {}", text));
+        }
+        for c in digits.chars() {
+            let mut carry = c.to_digit(10).ok_or_else(|| anyhow!("Invalid integer {}", text))?;
+            for byte in word.iter_mut().rev() {
+                let v = *byte as u32 * 10 + carry;
+                *byte = v as u8;
+                carry = v >> 8;
+            }
+            if carry != 0 {
+                return Err(anyhow!("Integer {} exceeds 256 bits", text));
+            }
+        }
+    }
+
+    if negative {
+        // Two's complement
+        for byte in word.iter_mut() {
+            *byte = !*byte;
+        }
+        for byte in word.iter_mut().rev() {
+            let (v, overflow) = byte.overflowing_add(1);
+            *byte = v;
+            if !overflow {
+                break;
+            }
+        }
+    }
+    Ok(word)
+}
+
+fn parse_hex(value: &Value) -> Result<Vec<u8>> {
+    let s = value.as_str().ok_or_else(|| anyhow!("Expected a hex string, got {}", value))?;
+    hex::decode(s.trim_start_matches("0x")).with_context(|| format!("Invalid hex value {}", s))
+}
+
+impl TypedData {
+    /// Struct types `primary` depends on, including itself.
+    fn dependencies<'a>(&'a self, primary: &'a str, found: &mut BTreeSet<&'a str>) {
+        if found.contains(primary) {
+            return;
+        }
+        let Some(fields) = self.types.get(primary) else {
+            return;
+        };
+        found.insert(primary);
+        for field in fields {
+            self.dependencies(base_type(&field.type_name), found);
+        }
+    }
+
+    /// `Name(type name,...)` for `primary` followed by its dependencies in alphabetical order.
+    pub fn encode_type(&self, primary: &str) -> Result<String> {
+        if !self.types.contains_key(primary) {
+            return Err(anyhow!("Unknown type {}", primary));
+        }
+        let mut deps = BTreeSet::new();
+        self.dependencies(primary, &mut deps);
+        deps.remove(primary);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(primary).chain(deps) {
+            let fields: Vec<String> = self.types[name].iter().map(|f| format!("{} {}", f.type_name, f.name)).collect();
+            encoded.push_str(&format!("{}({})", name, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    pub fn type_hash(&self, primary: &str) -> Result<[u8; 32]> {
+        Ok(keccak(self.encode_type(primary)?.as_bytes()))
+    }
+
+    /// 32-byte encoding of a single value of type `type_name`.
+    fn encode_value(&self, type_name: &str, value: &Value) -> Result<[u8; 32]> {
+        if let Some(element) = array_element(type_name) {
+            let items = value.as_array().ok_or_else(|| anyhow!("Expected an array for {}", type_name))?;
+            let mut encoded = Vec::with_capacity(items.len() * 32);
+            for item in items {
+                encoded.extend_from_slice(&self.encode_value(element, item)?);
+            }
+            return Ok(keccak(&encoded));
+        }
+        if self.types.contains_key(type_name) {
+            return self.hash_struct(type_name, value);
+        }
+
+        match type_name {
+            "string" => {
+                let s = value.as_str().ok_or_else(|| anyhow!("Expected a string, got {}", value))?;
+                Ok(keccak(s.as_bytes()))
+            }
+            "bytes" => Ok(keccak(&parse_hex(value)?)),
+            "bool" => {
+                let b = value.as_bool().ok_or_else(|| anyhow!("Expected a bool, got {}", value))?;
+                let mut word = [0u8; 32];
+                word[31] = b as u8;
+                Ok(word)
+            }
+            "address" => {
+                let bytes = parse_hex(value)?;
+                if bytes.len() != 20 {
+                    return Err(anyhow!("Address {} is not 20 bytes", value));
+                }
+                let mut word = [0u8; 32];
+                word[12..].copy_from_slice(&bytes);
+                Ok(word)
+            }
+            t if t.starts_with("bytes") => {
+                let bytes = parse_hex(value)?;
+                if bytes.len() > 32 {
+                    return Err(anyhow!("{} value is longer than 32 bytes", t));
+                }
+                let mut word = [0u8; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(word)
+            }
+            t if t.starts_with("uint") || t.starts_with("int") => parse_word(value),
+            other => Err(anyhow!("Unsupported EIP-712 type {}", other)),
+        }
+    }
+
+    /// `keccak256(typeHash || encodeData(value))` for a struct of type `primary`.
+    pub fn hash_struct(&self, primary: &str, value: &Value) -> Result<[u8; 32]> {
+        let mut encoded = self.type_hash(primary)?.to_vec();
+        for field in &self.types[primary] {
+            let member = value.get(&field.name).ok_or_else(|| anyhow!("{} is missing field {}", primary, field.name))?;
+            encoded.extend_from_slice(
+                &self
+                    .encode_value(&field.type_name, member)
+                    .with_context(|| format!("Invalid {}.{}", primary, field.name))?,
+            );
+        }
+        Ok(keccak(&encoded))
+    }
+
+    pub fn domain_separator(&self) -> Result<[u8; 32]> {
+        self.hash_struct("EIP712Domain", &self.domain)
+    }
+
+    /// The digest that is signed: `keccak256(0x1901 || domainSeparator || hashStruct(message))`.
+    pub fn signing_hash(&self) -> Result<[u8; 32]> {
+        let mut encoded = vec![0x19, 0x01];
+        encoded.extend_from_slice(&self.domain_separator()?);
+        if self.primary_type != "EIP712Domain" {
+            encoded.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Ok(keccak(&encoded))
+    }
+}
+
+/// Sign typed data with an ethsecp256k1 key.
+pub fn sign_typed_data(private_key: &[u8; 32], typed_data: &TypedData) -> Result<PersonalSignature> {
+    eth_sign_digest(private_key, typed_data.signing_hash()?)
+}
+
+/// Recover the 20-byte address that signed typed data.
+pub fn recover_typed_data(typed_data: &TypedData, signature: &str) -> Result<[u8; 20]> {
+    eth_recover(typed_data.signing_hash()?, signature)
+}
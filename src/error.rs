@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+/// Errors raised while deriving keys and encoding addresses.
+#[derive(Error, Debug)]
+pub enum GeneratorError {
+    #[error("invalid bech32 prefix '{prefix}': {source}")]
+    InvalidPrefix {
+        prefix: String,
+        #[source]
+        source: bech32::primitives::hrp::Error,
+    },
+
+    #[error("invalid derivation path '{spec}': {reason}")]
+    InvalidPath { spec: String, reason: String },
+
+    #[error("key derivation failed at {path}: {reason}")]
+    Derivation { path: String, reason: String },
+
+    #[error("invalid secret key: {0}")]
+    InvalidKey(#[from] secp256k1::Error),
+
+    #[error("bech32 encoding failed: {0}")]
+    Encoding(#[from] bech32::EncodeError),
+
+    #[error("segwit encoding failed: {0}")]
+    SegwitEncoding(#[from] bech32::segwit::EncodeError),
+
+    #[error("'{0}' is not a Bitcoin network prefix; use bc, tb or bcrt")]
+    BitcoinNetwork(String),
+
+    #[error("{0}")]
+    Unsupported(String),
+
+    #[error("index range {start} + {count} overflows")]
+    IndexOverflow { start: usize, count: usize },
+
+    #[cfg(feature = "async")]
+    #[error("generation task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
@@ -0,0 +1,79 @@
+//! Import bundles for the Keplr and Leap browser extensions: one JSON document per wallet with the
+//! fields their `getKey` account objects carry, so bulk import tooling can load test accounts.
+//!
+//! Bundles hold no keys and no mnemonic. `mnemonicRef` names the mnemonic the account comes from,
+//! by the BIP-32 fingerprint of its master key or a label, and `bip44HDPath` is the account,
+//! change and address index the extensions ask for when importing that mnemonic.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::sign::{wallet_key_type, wallet_public_key};
+use crate::tx::PubKeyJson;
+use crate::types::KeyType;
+use crate::wallet::Wallet;
+
+/// The BIP-44 levels after the coin type, as the extensions' `bip44HDPath`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Bip44HdPath {
+    pub account: u32,
+    pub change: u32,
+    pub address_index: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionBundle {
+    pub name: String,
+    /// `secp256k1`, or `ethsecp256k1` for EVM chains
+    pub algo: String,
+    pub bech32_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ethereum_hex_address: Option<String>,
+    /// SDK proto-JSON form, `{"@type": ..., "key": ...}`
+    pub pub_key: PubKeyJson,
+    pub coin_type: u32,
+    #[serde(rename = "bip44HDPath")]
+    pub bip44_hd_path: Bip44HdPath,
+    /// Fingerprint of the mnemonic's master key, or a label for it
+    pub mnemonic_ref: String,
+}
+
+/// Coin type and remaining levels of a `m/44'/coin'/account'/change/index` path.
+pub fn parse_bip44_path(path: &str) -> Option<(u32, Bip44HdPath)> {
+    let levels: Vec<&str> = path.strip_prefix("m/")?.split('/').collect();
+    let [purpose, coin, account, change, index] = levels[..] else {
+        return None;
+    };
+    let hardened = |level: &str| level.strip_suffix('\'')?.parse::<u32>().ok();
+    if hardened(purpose)? != 44 {
+        return None;
+    }
+    let hd_path = Bip44HdPath { account: hardened(account)?, change: change.parse().ok()?, address_index: index.parse().ok()? };
+    Some((hardened(coin)?, hd_path))
+}
+
+/// The bundle of one Cosmos wallet record, named `name`.
+pub fn bundle(wallet: &Wallet, name: String, mnemonic_ref: &str) -> Result<ExtensionBundle> {
+    if wallet.wif.is_some() || wallet.pubkey_hex.as_deref().is_some_and(|key| key.len() == 64) {
+        return Err(anyhow!("{} is a Bitcoin or Solana wallet; Keplr and Leap import Cosmos accounts", wallet.address));
+    }
+    let (coin_type, bip44_hd_path) = parse_bip44_path(&wallet.derivation_path)
+        .ok_or_else(|| anyhow!("{} has derivation path {}, which is not of the form m/44'/coin'/account'/change/index", wallet.address, wallet.derivation_path))?;
+    let pub_key = wallet_public_key(wallet).ok_or_else(|| anyhow!("{} has neither a readable private key nor pubkeyHex", wallet.address))?;
+    let algo = match wallet_key_type(wallet) {
+        KeyType::Secp256k1 => "secp256k1",
+        KeyType::Ethsecp256k1 => "ethsecp256k1",
+    };
+    Ok(ExtensionBundle {
+        name,
+        algo: algo.to_string(),
+        bech32_address: wallet.address.clone(),
+        ethereum_hex_address: wallet.evm_address.clone(),
+        pub_key,
+        coin_type,
+        bip44_hd_path,
+        mnemonic_ref: mnemonic_ref.to_string(),
+    })
+}
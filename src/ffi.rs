@@ -0,0 +1,205 @@
+//! C ABI for linking the generator from Go, C and C++.
+//!
+//! All output goes into caller-owned memory: strings are written NUL-terminated
+//! into fixed-size buffers and batches fill a caller-allocated `WfWallet` array.
+//! Every function returns a `WfStatus`; nothing allocated here needs freeing.
+
+use bip39::Mnemonic;
+use std::ffi::{c_char, CStr};
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use crate::types::KeyType;
+use crate::error::GeneratorError;
+use crate::generator::{generate_addresses, generate_wallets_batch, DerivationConfig};
+use crate::wallet::Wallet;
+
+pub const WF_ADDRESS_LEN: usize = 128;
+pub const WF_EVM_ADDRESS_LEN: usize = 43;
+pub const WF_PUBKEY_LEN: usize = 45;
+pub const WF_PRIVATE_KEY_LEN: usize = 65;
+pub const WF_PATH_LEN: usize = 64;
+pub const WF_SEED_LEN: usize = 64;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WfStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    BufferTooSmall = 3,
+    InvalidMnemonic = 4,
+    InvalidPrefix = 5,
+    Derivation = 6,
+    InvalidKey = 7,
+    Encoding = 8,
+    InvalidKeyType = 9,
+}
+
+/// Values of the `key_type` parameters. They are passed as `uint32_t`, so that an unknown value
+/// from C is rejected with `WF_STATUS_INVALID_KEY_TYPE` rather than read as an enum.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WfKeyType {
+    Secp256k1 = 0,
+    Ethsecp256k1 = 1,
+}
+
+/// One generated wallet. `evm_address` is an empty string for secp256k1.
+#[repr(C)]
+pub struct WfWallet {
+    pub address: [c_char; WF_ADDRESS_LEN],
+    pub evm_address: [c_char; WF_EVM_ADDRESS_LEN],
+    pub pubkey: [c_char; WF_PUBKEY_LEN],
+    pub private_key: [c_char; WF_PRIVATE_KEY_LEN],
+    pub derivation_path: [c_char; WF_PATH_LEN],
+}
+
+fn read_key_type(key_type: u32) -> Result<KeyType, WfStatus> {
+    match key_type {
+        k if k == WfKeyType::Secp256k1 as u32 => Ok(KeyType::Secp256k1),
+        k if k == WfKeyType::Ethsecp256k1 as u32 => Ok(KeyType::Ethsecp256k1),
+        _ => Err(WfStatus::InvalidKeyType),
+    }
+}
+
+impl From<GeneratorError> for WfStatus {
+    fn from(e: GeneratorError) -> Self {
+        match e {
+            GeneratorError::InvalidPrefix { .. } | GeneratorError::BitcoinNetwork(_) => WfStatus::InvalidPrefix,
+            GeneratorError::InvalidPath { .. } | GeneratorError::Derivation { .. } | GeneratorError::IndexOverflow { .. } => WfStatus::Derivation,
+            GeneratorError::InvalidKey(_) => WfStatus::InvalidKey,
+            GeneratorError::Encoding(_) | GeneratorError::SegwitEncoding(_) | GeneratorError::Unsupported(_) => WfStatus::Encoding,
+            #[cfg(feature = "async")]
+            GeneratorError::Task(_) => WfStatus::Derivation,
+        }
+    }
+}
+
+unsafe fn read_str<'a>(ptr: *const c_char) -> Result<&'a str, WfStatus> {
+    if ptr.is_null() {
+        return Err(WfStatus::NullPointer);
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|_| WfStatus::InvalidUtf8)
+}
+
+/// Copy `src` into `dst` with a trailing NUL, failing if it does not fit.
+unsafe fn write_str(src: &str, dst: *mut c_char, len: usize) -> Result<(), WfStatus> {
+    if dst.is_null() {
+        return Err(WfStatus::NullPointer);
+    }
+    if src.len() + 1 > len {
+        return Err(WfStatus::BufferTooSmall);
+    }
+    std::ptr::copy_nonoverlapping(src.as_ptr() as *const c_char, dst, src.len());
+    *dst.add(src.len()) = 0;
+    Ok(())
+}
+
+fn fill_wallet(out: &mut WfWallet, wallet: &Wallet) -> Result<(), WfStatus> {
+    unsafe {
+        write_str(&wallet.address, out.address.as_mut_ptr(), WF_ADDRESS_LEN)?;
+        write_str(wallet.evm_address.as_deref().unwrap_or(""), out.evm_address.as_mut_ptr(), WF_EVM_ADDRESS_LEN)?;
+        write_str(&wallet.pubkey, out.pubkey.as_mut_ptr(), WF_PUBKEY_LEN)?;
+        write_str(&wallet.private_key, out.private_key.as_mut_ptr(), WF_PRIVATE_KEY_LEN)?;
+        write_str(&wallet.derivation_path, out.derivation_path.as_mut_ptr(), WF_PATH_LEN)?;
+    }
+    Ok(())
+}
+
+fn status(result: Result<(), WfStatus>) -> WfStatus {
+    result.err().unwrap_or(WfStatus::Ok)
+}
+
+/// Convert a BIP39 mnemonic into its 64-byte seed (empty passphrase).
+///
+/// # Safety
+/// `mnemonic` must be a valid NUL-terminated string and `out_seed` must point
+/// to at least `WF_SEED_LEN` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wf_mnemonic_to_seed(mnemonic: *const c_char, out_seed: *mut u8) -> WfStatus {
+    status((|| {
+        let phrase = read_str(mnemonic)?;
+        if out_seed.is_null() {
+            return Err(WfStatus::NullPointer);
+        }
+        let mnemonic = Mnemonic::parse(phrase).map_err(|_| WfStatus::InvalidMnemonic)?;
+        let seed = mnemonic.to_seed("");
+        std::ptr::copy_nonoverlapping(seed.as_ptr(), out_seed, WF_SEED_LEN);
+        Ok(())
+    })())
+}
+
+/// Derive the bech32 (and for ethsecp256k1, EVM) address of a 32-byte private key.
+///
+/// `out_evm` may be NULL when the EVM address is not wanted.
+///
+/// # Safety
+/// `private_key` must point to 32 readable bytes, `prefix` must be a valid
+/// NUL-terminated string and each output buffer must have at least the given
+/// number of writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wf_derive_address(
+    private_key: *const u8,
+    prefix: *const c_char,
+    key_type: u32,
+    out_address: *mut c_char,
+    out_address_len: usize,
+    out_evm: *mut c_char,
+    out_evm_len: usize,
+) -> WfStatus {
+    status((|| {
+        if private_key.is_null() {
+            return Err(WfStatus::NullPointer);
+        }
+        let prefix = read_str(prefix)?;
+        let key_type = read_key_type(key_type)?;
+        let mut key = [0u8; 32];
+        std::ptr::copy_nonoverlapping(private_key, key.as_mut_ptr(), 32);
+
+        let (address, evm_address, _, _) = generate_addresses(&key, prefix, &key_type)?;
+        write_str(&address, out_address, out_address_len)?;
+        if !out_evm.is_null() {
+            write_str(evm_address.as_deref().unwrap_or(""), out_evm, out_evm_len)?;
+        }
+        Ok(())
+    })())
+}
+
+/// Generate `count` wallets from `seed` starting at `start_index` into `out`.
+/// `seed` is 16, 32 or 64 bytes, as BIP-32 requires.
+///
+/// # Safety
+/// `seed` must point to `seed_len` readable bytes, `prefix` must be a valid
+/// NUL-terminated string and `out` must point to `out_len` writable `WfWallet`s.
+#[no_mangle]
+pub unsafe extern "C" fn wf_generate_batch(
+    seed: *const u8,
+    seed_len: usize,
+    start_index: usize,
+    count: usize,
+    prefix: *const c_char,
+    key_type: u32,
+    out: *mut WfWallet,
+    out_len: usize,
+) -> WfStatus {
+    status((|| {
+        if seed.is_null() || out.is_null() {
+            return Err(WfStatus::NullPointer);
+        }
+        if out_len < count {
+            return Err(WfStatus::BufferTooSmall);
+        }
+        let prefix = read_str(prefix)?;
+        let key_type = read_key_type(key_type)?;
+        let seed = std::slice::from_raw_parts(seed, seed_len);
+        let out = std::slice::from_raw_parts_mut(out, count);
+
+        let config = DerivationConfig::new(prefix, key_type);
+        let wallets = generate_wallets_batch(seed, start_index, count, &config, Arc::new(AtomicUsize::new(0)))?;
+        for (slot, wallet) in out.iter_mut().zip(&wallets) {
+            fill_wallet(slot, wallet)?;
+        }
+        Ok(())
+    })())
+}
@@ -0,0 +1,47 @@
+//! Regular-expression filters that keep only some generated wallets, applied by the workers as
+//! they derive so unmatched wallets are never held or written.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::targets::TargetSet;
+use crate::wallet::Wallet;
+
+/// Conditions a wallet must meet to be kept; every one given must hold.
+pub struct WalletFilter {
+    address: Option<Regex>,
+    evm_address: Option<Regex>,
+    targets: Option<TargetSet>,
+}
+
+impl WalletFilter {
+    /// Filter on the `address` field and/or the `evmAddress` field, and/or membership of a set of
+    /// target addresses, or `None` if none is given.
+    pub fn new(address: Option<&str>, evm_address: Option<&str>, targets: Option<TargetSet>) -> Result<Option<Self>> {
+        let compile = |pattern: Option<&str>, field: &str| {
+            pattern
+                .map(|pattern| Regex::new(pattern).with_context(|| format!("Invalid {} filter {:?}", field, pattern)))
+                .transpose()
+        };
+        let filter = Self { address: compile(address, "address")?, evm_address: compile(evm_address, "EVM address")?, targets };
+        Ok((filter.address.is_some() || filter.evm_address.is_some() || filter.targets.is_some()).then_some(filter))
+    }
+
+    /// Whether the filter reads the EVM address, which only ethsecp256k1 wallets have.
+    pub fn needs_evm_address(&self) -> bool {
+        self.evm_address.is_some()
+    }
+
+    pub fn matches(&self, wallet: &Wallet) -> bool {
+        self.address.as_ref().is_none_or(|pattern| pattern.is_match(&wallet.address))
+            && self.evm_address.as_ref().is_none_or(|pattern| {
+                wallet.evm_address.as_deref().is_some_and(|evm_address| pattern.is_match(evm_address))
+            })
+            && self.targets.as_ref().is_none_or(|targets| targets.matches(wallet))
+    }
+
+    /// Whether the filter looks for target addresses, whose matches are worth reporting one by one.
+    pub fn has_targets(&self) -> bool {
+        self.targets.is_some()
+    }
+}
@@ -0,0 +1,118 @@
+//! Funding transactions that distribute coins from one account to many wallets.
+
+use prost::encoding::encoded_len_varint;
+use prost::Message;
+use std::collections::BTreeMap;
+
+use crate::coin::{Coin, GasPrice};
+use crate::tx::{proto, AddressCoins, Fee, Msg, Tx};
+
+/// Bytes kept free for signer info, fee and signature when sizing a transaction.
+const AUTH_INFO_RESERVE: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FundMode {
+    /// One `MsgMultiSend` per transaction
+    MultiSend,
+    /// One `MsgSend` per recipient, batched into transactions
+    Send,
+}
+
+#[derive(Debug, Clone)]
+pub struct FundPlan {
+    pub from: String,
+    /// Coins sent to every recipient
+    pub amount: Vec<Coin>,
+    pub mode: FundMode,
+    pub max_tx_bytes: usize,
+    pub max_recipients: usize,
+    pub gas_base: u64,
+    pub gas_per_recipient: u64,
+    /// Fee derived from the gas limit; takes precedence over `fee`
+    pub gas_price: Option<GasPrice>,
+    /// Fixed fee per transaction
+    pub fee: Vec<Coin>,
+    pub memo: String,
+}
+
+/// Length of a length-delimited field holding `len` bytes.
+fn field_len(len: usize) -> usize {
+    1 + encoded_len_varint(len as u64) + len
+}
+
+impl FundPlan {
+    /// Encoded bytes one recipient adds to the transaction body.
+    fn recipient_len(&self, address: &str) -> usize {
+        let coins: Vec<proto::Coin> = self.amount.iter().map(proto::Coin::from).collect();
+        match self.mode {
+            FundMode::MultiSend => field_len(proto::AddressCoins { address: address.to_string(), coins }.encoded_len()),
+            FundMode::Send => {
+                let msg = proto::MsgSend { from_address: self.from.clone(), to_address: address.to_string(), amount: coins };
+                let any = proto::Any { type_url: crate::tx::MSG_SEND_TYPE_URL.to_string(), value: msg.encode_to_vec() };
+                field_len(any.encoded_len())
+            }
+        }
+    }
+
+    fn fee_for(&self, recipients: usize) -> Fee {
+        let gas_limit = self.gas_base + self.gas_per_recipient * recipients as u64;
+        let amount = match &self.gas_price {
+            Some(price) => vec![price.fee(gas_limit)],
+            None => self.fee.clone(),
+        };
+        Fee { amount, gas_limit, payer: String::new(), granter: String::new() }
+    }
+
+    /// Sum of `amount` sent to `recipients` addresses, per denom.
+    fn total_for(&self, recipients: usize) -> Vec<Coin> {
+        let mut totals: BTreeMap<&str, u128> = BTreeMap::new();
+        for coin in &self.amount {
+            *totals.entry(&coin.denom).or_default() += coin.amount_u128() * recipients as u128;
+        }
+        totals.into_iter().map(|(denom, amount)| Coin::new(amount, denom)).collect()
+    }
+
+    fn build_tx(&self, recipients: &[&str]) -> Tx {
+        let messages = match self.mode {
+            FundMode::MultiSend => vec![Msg::MultiSend {
+                inputs: vec![AddressCoins { address: self.from.clone(), coins: self.total_for(recipients.len()) }],
+                outputs: recipients
+                    .iter()
+                    .map(|address| AddressCoins { address: address.to_string(), coins: self.amount.clone() })
+                    .collect(),
+            }],
+            FundMode::Send => recipients
+                .iter()
+                .map(|address| Msg::Send {
+                    from_address: self.from.clone(),
+                    to_address: address.to_string(),
+                    amount: self.amount.clone(),
+                })
+                .collect(),
+        };
+        Tx::new(messages, self.fee_for(recipients.len()), self.memo.clone())
+    }
+
+    /// Split `recipients` into unsigned transactions that respect the size and count limits.
+    pub fn build_txs(&self, recipients: &[&str]) -> Vec<Tx> {
+        let budget = self.max_tx_bytes.saturating_sub(AUTH_INFO_RESERVE + field_len(self.memo.len()));
+        let mut txs = Vec::new();
+        let mut chunk: Vec<&str> = Vec::new();
+        let mut chunk_len = 0;
+
+        for &address in recipients {
+            let len = self.recipient_len(address);
+            if !chunk.is_empty() && (chunk_len + len > budget || chunk.len() >= self.max_recipients) {
+                txs.push(self.build_tx(&chunk));
+                chunk.clear();
+                chunk_len = 0;
+            }
+            chunk.push(address);
+            chunk_len += len;
+        }
+        if !chunk.is_empty() {
+            txs.push(self.build_tx(&chunk));
+        }
+        txs
+    }
+}
@@ -1,17 +1,29 @@
-use anyhow::Result;
 use base64::{Engine as _, engine::general_purpose};
-use bech32::{Bech32, Hrp};
+use bech32::{Bech32, Bech32m, Hrp};
 use hex;
 use secp256k1::{Secp256k1, SecretKey, PublicKey};
 use sha2::{Sha256, Digest};
 use sha3::Keccak256;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use ripemd::Ripemd160;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tiny_hderive::bip32::ExtendedPrivKey;
+use serde::{Deserialize, Serialize};
+use tracing::{debug_span, trace_span};
 
 use crate::wallet::Wallet;
-use crate::cli::KeyType;
+use crate::address_encoder;
+use crate::bitcoin::{self, Network};
+use crate::solana;
+use crate::types::{AddressEncoding, BitcoinAddress, ChainFamily, DerivationScheme, KeyType, PrivateKeyFormat, PubkeyFormat};
+use crate::path_range::PathRange;
+use crate::derivation::{Ed25519Deriver, KeyDeriver};
+use crate::error::GeneratorError;
+
+/// Wallets a batch derives between updates of its progress counter, which then counts every
+/// wallet exactly once.
+const PROGRESS_STEP: usize = 1000;
 
 // Pre-compute and cache the secp256k1 context
 thread_local! {
@@ -19,7 +31,7 @@ thread_local! {
 }
 
 #[inline(always)]
-pub fn generate_addresses(private_key: &[u8; 32], prefix: &str, key_type: &KeyType) -> Result<(String, Option<String>, String, String)> {
+pub fn generate_addresses(private_key: &[u8; 32], prefix: &str, key_type: &KeyType) -> Result<(String, Option<String>, String, String), GeneratorError> {
     SECP.with(|secp| {
         // Create secret key from bytes
         let secret_key = SecretKey::from_slice(private_key)?;
@@ -27,159 +39,591 @@ pub fn generate_addresses(private_key: &[u8; 32], prefix: &str, key_type: &KeyTy
         // Generate public key
         let public_key = PublicKey::from_secret_key(secp, &secret_key);
 
-        // Convert private key to hex once (used by both paths)
-        let private_key_hex = hex::encode(private_key);
+        let (address, evm_address, pubkey_base64) = encode_public_key(&public_key, prefix, key_type, AddressEncoding::Bech32)?;
+        Ok((address, evm_address, pubkey_base64, hex::encode(private_key)))
+    })
+}
 
-        match key_type {
-            KeyType::Secp256k1 => {
-                // Standard secp256k1 path
-                let pubkey_compressed = public_key.serialize();
-                let pubkey_base64 = general_purpose::STANDARD.encode(&pubkey_compressed);
+/// Bech32 address, EVM address (ethsecp256k1 only) and base64 compressed form of a public key.
+pub fn encode_public_key(
+    public_key: &PublicKey,
+    prefix: &str,
+    key_type: &KeyType,
+    encoding: AddressEncoding,
+) -> Result<(String, Option<String>, String), GeneratorError> {
+    let pubkey_compressed = public_key.serialize();
+    let pubkey_base64 = general_purpose::STANDARD.encode(pubkey_compressed);
+    let hrp = parse_hrp(prefix)?;
 
-                // SHA256 -> RIPEMD160
-                let sha256_hash = Sha256::digest(&pubkey_compressed);
-                let ripemd_hash = Ripemd160::digest(&sha256_hash);
+    match key_type {
+        KeyType::Secp256k1 => {
+            // SHA256 -> RIPEMD160
+            let sha256_hash = Sha256::digest(pubkey_compressed);
+            let ripemd_hash = Ripemd160::digest(sha256_hash);
+            let cosmos_addr = encode_address(hrp, &ripemd_hash[..], encoding)?;
 
-                let hrp = Hrp::parse(prefix)?;
-                let cosmos_addr = bech32::encode::<Bech32>(hrp, &ripemd_hash[..])?;
+            Ok((cosmos_addr, None, pubkey_base64))
+        },
+        KeyType::Ethsecp256k1 => {
+            // Keccak256 of uncompressed pubkey
+            let pubkey_uncompressed = public_key.serialize_uncompressed();
+            let keccak_hash = Keccak256::digest(&pubkey_uncompressed[1..]); // Skip 0x04
+            let address_bytes = &keccak_hash[12..];
 
-                Ok((cosmos_addr, None, pubkey_base64, private_key_hex))
-            },
-            KeyType::Ethsecp256k1 => {
-                // Ethereum-compatible path
-                let pubkey_compressed = public_key.serialize();
-                let pubkey_base64 = general_purpose::STANDARD.encode(&pubkey_compressed);
+            let cosmos_addr = encode_address(hrp, address_bytes, encoding)?;
+            let evm_addr = format!("0x{}", hex::encode(address_bytes));
 
-                // Keccak256 of uncompressed pubkey
-                let pubkey_uncompressed = public_key.serialize_uncompressed();
-                let keccak_hash = Keccak256::digest(&pubkey_uncompressed[1..]); // Skip 0x04
-                let address_bytes = &keccak_hash[12..];
+            Ok((cosmos_addr, Some(evm_addr), pubkey_base64))
+        }
+    }
+}
 
-                let hrp = Hrp::parse(prefix)?;
-                let cosmos_addr = bech32::encode::<Bech32>(hrp, address_bytes)?;
-                let evm_addr = format!("0x{}", hex::encode(address_bytes));
+/// Encode address bytes with the Bech32 or Bech32m checksum.
+#[inline]
+pub fn encode_address(hrp: Hrp, data: &[u8], encoding: AddressEncoding) -> Result<String, bech32::EncodeError> {
+    match encoding {
+        AddressEncoding::Bech32 => bech32::encode::<Bech32>(hrp, data),
+        AddressEncoding::Bech32m => bech32::encode::<Bech32m>(hrp, data),
+    }
+}
 
-                Ok((cosmos_addr, Some(evm_addr), pubkey_base64, private_key_hex))
-            }
+/// Lowercase hex of `bytes`, written on the stack first so that only the result is allocated, at
+/// its final size. Longer inputs than an uncompressed key fall back to `hex::encode`.
+#[inline]
+pub fn hex_string(bytes: &[u8]) -> String {
+    let mut buf = [0u8; 130];
+    let Some(out) = buf.get_mut(..bytes.len() * 2) else {
+        return hex::encode(bytes);
+    };
+    hex::encode_to_slice(bytes, out).expect("the buffer holds two digits per byte");
+    std::str::from_utf8(out).expect("hex digits are ASCII").to_owned()
+}
+
+/// `0x` and the lowercase hex of a 20-byte EVM address, allocated once.
+#[inline]
+pub fn evm_address_string(address: &[u8]) -> String {
+    let mut buf = [0u8; 42];
+    buf[..2].copy_from_slice(b"0x");
+    hex::encode_to_slice(address, &mut buf[2..]).expect("EVM addresses are 20 bytes");
+    std::str::from_utf8(&buf).expect("hex digits are ASCII").to_owned()
+}
+
+/// Encodes the string fields of a batch's wallets through buffers the batch reuses.
+///
+/// A batch runs on one worker thread, so each worker encodes its wallets through one scratch
+/// string: bech32 addresses are written into it and copied out at their final size, and paths
+/// are assembled around the index from a template split once, instead of each field growing a
+/// fresh string or going through `format!`.
+pub struct WalletEncoder<'a> {
+    config: &'a DerivationConfig,
+    /// The config's prefix, if it is a valid bech32 prefix
+    hrp: Option<Hrp>,
+    /// The path before and after the index, unless a path range numbers the paths
+    path: Option<(String, String)>,
+    scratch: String,
+}
+
+impl<'a> WalletEncoder<'a> {
+    pub fn new(config: &'a DerivationConfig) -> Self {
+        let path = match config.path_range {
+            Some(_) => None,
+            None => config
+                .path_template()
+                .split_once(INDEX_PLACEHOLDER)
+                .map(|(before, after)| (before.to_string(), after.to_string())),
+        };
+        Self { config, hrp: Hrp::parse(&config.prefix).ok(), path, scratch: String::with_capacity(128) }
+    }
+
+    pub fn config(&self) -> &'a DerivationConfig {
+        self.config
+    }
+
+    /// The config's prefix as a bech32 prefix.
+    pub fn hrp(&self) -> Result<Hrp, GeneratorError> {
+        match self.hrp {
+            Some(hrp) => Ok(hrp),
+            None => parse_hrp(&self.config.prefix),
         }
+    }
+
+    /// Derivation path of the wallet at `index`, as [`DerivationConfig::path`].
+    #[inline]
+    pub fn path(&self, index: u64) -> String {
+        use std::fmt::Write as _;
+        let Some((before, after)) = &self.path else {
+            return self.config.path(index);
+        };
+        let digits = index.checked_ilog10().map_or(1, |log| log as usize + 1);
+        let mut path = String::with_capacity(before.len() + digits + after.len());
+        path.push_str(before);
+        write!(path, "{}", index).expect("writing to a String cannot fail");
+        path.push_str(after);
+        path
+    }
+
+    /// Bech32 or Bech32m address of `data`, as [`encode_address`] with the config's encoding.
+    #[inline]
+    pub fn address(&mut self, hrp: Hrp, data: &[u8]) -> Result<String, bech32::EncodeError> {
+        self.scratch.clear();
+        match self.config.encoding {
+            AddressEncoding::Bech32 => bech32::encode_lower_to_fmt::<Bech32, _>(&mut self.scratch, hrp, data)?,
+            AddressEncoding::Bech32m => bech32::encode_lower_to_fmt::<Bech32m, _>(&mut self.scratch, hrp, data)?,
+        }
+        Ok(self.scratch.as_str().to_owned())
+    }
+
+}
+
+/// 20-byte account address of a public key for the given key type.
+pub fn public_key_address(public_key: &PublicKey, key_type: &KeyType) -> [u8; 20] {
+    let mut address = [0u8; 20];
+    match key_type {
+        KeyType::Secp256k1 => address.copy_from_slice(&Ripemd160::digest(Sha256::digest(public_key.serialize()))),
+        KeyType::Ethsecp256k1 => address.copy_from_slice(&Keccak256::digest(&public_key.serialize_uncompressed()[1..])[12..]),
+    }
+    address
+}
+
+/// Standard BIP-44 path of an address index in the first account.
+pub fn derivation_path(coin_type: u32, index: u64) -> String {
+    format!("m/44'/{}'/0'/0/{}", coin_type, index)
+}
+
+/// The final, unhardened index of a derivation path such as `m/44'/118'/0'/0/17`.
+pub fn derivation_index(path: &str) -> Option<u64> {
+    path.rsplit('/').next()?.parse().ok()
+}
+
+/// Derive the wallet at an explicit derivation path.
+pub fn derive_wallet(seed: &[u8], path: &str, prefix: &str, key_type: &KeyType) -> Result<Wallet, GeneratorError> {
+    derive_wallet_with(&mut KeyDeriver::new(seed), path, prefix, key_type)
+}
+
+/// Derive the wallet at an explicit derivation path with `deriver`, which reuses the levels the
+/// path shares with the one derived before it.
+pub fn derive_wallet_with(deriver: &mut KeyDeriver, path: &str, prefix: &str, key_type: &KeyType) -> Result<Wallet, GeneratorError> {
+    let private_key = deriver.private_key(path)?;
+    let secret_key = SecretKey::from_slice(&private_key)?;
+    let public_key = SECP.with(|secp| PublicKey::from_secret_key(secp, &secret_key));
+    let (address, evm_address, pubkey) = encode_public_key(&public_key, prefix, key_type, AddressEncoding::Bech32)?;
+
+    Ok(Wallet {
+        address,
+        evm_address,
+        pubkey,
+        private_key: hex::encode(private_key),
+        derivation_path: path.to_string(),
+        index: derivation_index(path).unwrap_or(0),
+        pubkey_hex: Some(hex::encode(public_key.serialize())),
+        pubkey_type: Some(key_type.pubkey_type_url().to_string()),
+        pubkey_uncompressed: None,
+        secp256k1_address: None,
+        wif: None,
     })
 }
 
+/// Placeholder for the derivation index in path templates such as `m/44'/118'/0'/0/{index}`.
+pub const INDEX_PLACEHOLDER: &str = "{index}";
+
+/// Search indices `0..=max_index` of a path template for the key whose account address is `target`.
+///
+/// Indices are checked in parallel on the global rayon pool; the search stops at the first match.
+#[cfg(feature = "parallel")]
+pub fn find_index(
+    seed: &[u8],
+    template: &str,
+    target: &[u8; 20],
+    key_type: &KeyType,
+    max_index: u64,
+    progress: &AtomicUsize,
+) -> Result<Option<u64>, GeneratorError> {
+    const CHUNK: u64 = 1024;
+    if !template.contains(INDEX_PLACEHOLDER) {
+        return Err(GeneratorError::Derivation {
+            path: template.to_string(),
+            reason: format!("path template has no {} placeholder", INDEX_PLACEHOLDER),
+        });
+    }
+
+    let chunks = max_index / CHUNK + 1;
+    (0..chunks)
+        .into_par_iter()
+        .find_map_any(|chunk| {
+            let start = chunk * CHUNK;
+            let end = (start + CHUNK - 1).min(max_index);
+            let mut deriver = KeyDeriver::new(seed);
+            let found = SECP.with(|secp| {
+                for index in start..=end {
+                    let path = template.replace(INDEX_PLACEHOLDER, &index.to_string());
+                    let secret_key = SecretKey::from_slice(&deriver.private_key(&path)?)?;
+                    if public_key_address(&PublicKey::from_secret_key(secp, &secret_key), key_type) == *target {
+                        return Ok(Some(index));
+                    }
+                }
+                Ok(None)
+            });
+            progress.fetch_add((end - start + 1) as usize, Ordering::Relaxed);
+            found.transpose()
+        })
+        .transpose()
+}
+
+/// How the batch generators derive and encode wallets.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationConfig {
+    /// Bech32 human-readable prefix
+    pub prefix: String,
+    pub key_type: KeyType,
+    /// BIP-44 coin type
+    pub coin_type: u32,
+    pub encoding: AddressEncoding,
+    pub scheme: DerivationScheme,
+    pub family: ChainFamily,
+    /// Encoding of the `privateKey` field
+    pub private_key_format: PrivateKeyFormat,
+    /// Encoding of the `pubkey` field; `SdkJson` is base64 here and wrapped when written
+    pub pubkey_format: PubkeyFormat,
+    /// Also record the uncompressed key as `pubkeyUncompressed` (secp256k1 only)
+    pub include_uncompressed: bool,
+    /// Also record the SHA256/RIPEMD160 address of ethsecp256k1 keys as `secp256k1Address`
+    #[serde(default)]
+    pub dual_address: bool,
+    /// Derive the combinations of this spec in turn instead of following the scheme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_range: Option<PathRange>,
+    /// Name of a registered [`AddressEncoder`](crate::address_encoder::AddressEncoder) to address
+    /// Cosmos wallets with instead of the key type's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_encoder: Option<String>,
+}
+
+impl DerivationConfig {
+    /// Bech32 addresses along the standard path at the key type's conventional coin type.
+    pub fn new(prefix: &str, key_type: KeyType) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            coin_type: key_type.coin_type(),
+            key_type,
+            encoding: AddressEncoding::Bech32,
+            scheme: DerivationScheme::Standard,
+            family: ChainFamily::Cosmos,
+            private_key_format: PrivateKeyFormat::Hex,
+            pubkey_format: PubkeyFormat::Base64,
+            include_uncompressed: false,
+            dual_address: false,
+            path_range: None,
+            address_encoder: None,
+        }
+    }
+
+    /// Bitcoin addresses of `network` along the address type's BIP-44 or BIP-84 path.
+    pub fn bitcoin(network: Network, address: BitcoinAddress) -> Self {
+        Self {
+            prefix: network.hrp().to_string(),
+            coin_type: network.coin_type(),
+            family: ChainFamily::Bitcoin(address),
+            ..Self::new(network.hrp(), KeyType::Secp256k1)
+        }
+    }
+
+    /// Solana addresses along `m/44'/501'/{index}'/0'`.
+    pub fn solana() -> Self {
+        Self { coin_type: solana::COIN_TYPE, family: ChainFamily::Solana, ..Self::new("", KeyType::Secp256k1) }
+    }
+
+    /// The `privateKey` field for a raw private key.
+    ///
+    /// WIF keys are for the Bitcoin network of a Bitcoin run, and mainnet otherwise, as Cosmos
+    /// tooling that imports WIF expects; ed25519 (Solana) keys have no WIF form.
+    pub fn encode_private_key(&self, private_key: &[u8; 32]) -> Result<String, GeneratorError> {
+        match (self.private_key_format, self.family) {
+            (PrivateKeyFormat::Hex, _) => Ok(hex_string(private_key)),
+            (PrivateKeyFormat::Base64, _) => Ok(general_purpose::STANDARD.encode(private_key)),
+            (PrivateKeyFormat::Wif, ChainFamily::Solana) => {
+                Err(GeneratorError::Unsupported("WIF private keys are only defined for secp256k1 keys".to_string()))
+            }
+            (PrivateKeyFormat::Wif, ChainFamily::Bitcoin(_)) => Ok(bitcoin::wif(private_key, Network::from_hrp(&self.prefix)?)),
+            (PrivateKeyFormat::Wif, ChainFamily::Cosmos) => Ok(bitcoin::wif(private_key, Network::Mainnet)),
+        }
+    }
+
+    /// The `pubkey` field, and the `pubkeyUncompressed` field if requested, for a secp256k1 key.
+    pub fn encode_public_key(&self, public_key: &PublicKey) -> (String, Option<String>) {
+        let pubkey = match self.pubkey_format {
+            PubkeyFormat::Base64 | PubkeyFormat::SdkJson => general_purpose::STANDARD.encode(public_key.serialize()),
+            PubkeyFormat::Hex => hex_string(&public_key.serialize()),
+            PubkeyFormat::UncompressedHex => hex_string(&public_key.serialize_uncompressed()),
+        };
+        let uncompressed = self.include_uncompressed.then(|| hex_string(&public_key.serialize_uncompressed()));
+        (pubkey, uncompressed)
+    }
+
+    /// The `secp256k1Address` field: the bech32 address the key would have as a plain secp256k1
+    /// account, next to the Keccak-derived `address` of an ethsecp256k1 Cosmos wallet, if requested.
+    pub fn secp256k1_address(&self, public_key: &PublicKey) -> Result<Option<String>, GeneratorError> {
+        if !self.records_secp256k1_address() {
+            return Ok(None);
+        }
+        let hash = public_key_address(public_key, &KeyType::Secp256k1);
+        Ok(Some(encode_address(parse_hrp(&self.prefix)?, &hash, self.encoding)?))
+    }
+
+    pub(crate) fn records_secp256k1_address(&self) -> bool {
+        self.dual_address && self.family == ChainFamily::Cosmos && matches!(self.key_type, KeyType::Ethsecp256k1)
+    }
+
+    /// The `pubkey` field for an ed25519 (Solana) key, which has no uncompressed form.
+    pub fn encode_ed25519_public_key(&self, public_key: &[u8; 32]) -> String {
+        match self.pubkey_format {
+            PubkeyFormat::Hex => hex_string(public_key),
+            _ => general_purpose::STANDARD.encode(public_key),
+        }
+    }
+
+    /// Derivation path of the wallet at `index`: the combination numbered `index` of a path range.
+    pub fn path(&self, index: u64) -> String {
+        match &self.path_range {
+            Some(range) => range.path(index),
+            None => self.format_path(index),
+        }
+    }
+
+    /// The derivation path with `{index}` in place of the wallet index, or the path range, for display.
+    pub fn path_template(&self) -> String {
+        match &self.path_range {
+            Some(range) => range.to_string(),
+            None => self.format_path(INDEX_PLACEHOLDER),
+        }
+    }
+
+    /// Solana wallets, whose ed25519 keys have only hardened children, increment the account as
+    /// Phantom does; `ledger-live` drops the trailing change level, as Ledger Live does for Solana.
+    fn format_path(&self, index: impl std::fmt::Display) -> String {
+        let purpose = match self.family {
+            ChainFamily::Bitcoin(address) => address.purpose(),
+            ChainFamily::Cosmos | ChainFamily::Solana => 44,
+        };
+        match (self.family, self.scheme) {
+            (ChainFamily::Solana, DerivationScheme::Standard) => format!("m/44'/{}'/{}'/0'", self.coin_type, index),
+            (ChainFamily::Solana, DerivationScheme::LedgerLive) => format!("m/44'/{}'/{}'", self.coin_type, index),
+            (_, DerivationScheme::Standard) => format!("m/{}'/{}'/0'/0/{}", purpose, self.coin_type, index),
+            (_, DerivationScheme::LedgerLive) => format!("m/{}'/{}'/{}'/0/0", purpose, self.coin_type, index),
+        }
+    }
+}
+
+/// Generate `count` wallets from `start_index`, following the config's derivation scheme.
 pub fn generate_wallets_batch(
     seed: &[u8],
     start_index: usize,
     count: usize,
-    prefix: &str,
-    key_type: &KeyType,
+    config: &DerivationConfig,
     progress: Arc<AtomicUsize>,
-) -> Vec<Wallet> {
-    match key_type {
-        KeyType::Secp256k1 => generate_secp256k1_batch(seed, start_index, count, prefix, progress),
-        KeyType::Ethsecp256k1 => generate_ethsecp256k1_batch(seed, start_index, count, prefix, progress),
+) -> Result<Vec<Wallet>, GeneratorError> {
+    let _span = debug_span!("derive_batch", start_index, count, key_type = ?config.key_type).entered();
+    match (config.family, &config.key_type) {
+        (ChainFamily::Bitcoin(address), _) => generate_bitcoin_batch(seed, start_index, count, config, address, progress),
+        (ChainFamily::Solana, _) => generate_solana_batch(seed, start_index, count, config, progress),
+        (ChainFamily::Cosmos, _) => generate_cosmos_batch(seed, start_index, count, config, progress),
     }
 }
 
+/// Generate `count` wallets on the global rayon pool, `chunk_size` wallets per task.
+///
+/// Wallets are returned in derivation index order.
+#[cfg(feature = "parallel")]
+pub fn generate_wallets_parallel(
+    seed: &[u8],
+    start_index: usize,
+    count: usize,
+    config: &DerivationConfig,
+    chunk_size: usize,
+    progress: Arc<AtomicUsize>,
+) -> Result<Vec<Wallet>, GeneratorError> {
+    let chunk_size = chunk_size.max(1);
+    let chunks = count.div_ceil(chunk_size);
+
+    let batches = (0..chunks)
+        .into_par_iter()
+        .map(|chunk| {
+            let offset = chunk * chunk_size;
+            let batch = chunk_size.min(count - offset);
+            generate_wallets_batch(seed, start_index + offset, batch, config, progress.clone())
+        })
+        .collect::<Result<Vec<Vec<Wallet>>, GeneratorError>>()?;
+
+    Ok(batches.into_iter().flatten().collect())
+}
+
+/// Cosmos wallets, addressed by the config's [`AddressEncoder`](crate::address_encoder::AddressEncoder).
 #[inline]
-fn generate_secp256k1_batch(
+fn generate_cosmos_batch(
     seed: &[u8],
     start_index: usize,
     count: usize,
-    prefix: &str,
+    config: &DerivationConfig,
     progress: Arc<AtomicUsize>,
-) -> Vec<Wallet> {
+) -> Result<Vec<Wallet>, GeneratorError> {
     let mut wallets = Vec::with_capacity(count);
-    let hrp = Hrp::parse(prefix).expect("Invalid prefix");
+    let address_encoder = address_encoder::for_config(config)?;
+    let mut deriver = KeyDeriver::new(seed);
+    let mut encoder = WalletEncoder::new(config);
+    let pubkey_type = config.key_type.pubkey_type_url();
 
     SECP.with(|secp| {
         for i in 0..count {
             let index = start_index + i;
-            let path = format!("m/44'/118'/0'/0/{}", index);
-
-            if let Ok(derived_key) = ExtendedPrivKey::derive(seed, path.as_str()) {
-                let private_key = derived_key.secret();
-                let secret_key = SecretKey::from_slice(&private_key).expect("Invalid key");
-                let public_key = PublicKey::from_secret_key(secp, &secret_key);
-
-                // Standard secp256k1
-                let pubkey_compressed = public_key.serialize();
-                let pubkey_base64 = general_purpose::STANDARD.encode(&pubkey_compressed);
-                let private_key_hex = hex::encode(&private_key);
-
-                // SHA256 -> RIPEMD160
-                let sha256_hash = Sha256::digest(&pubkey_compressed);
-                let ripemd_hash = Ripemd160::digest(&sha256_hash);
-
-                if let Ok(cosmos_addr) = bech32::encode::<Bech32>(hrp.clone(), &ripemd_hash[..]) {
-                    wallets.push(Wallet {
-                        address: cosmos_addr,
-                        evm_address: None,
-                        pubkey: pubkey_base64,
-                        private_key: private_key_hex,
-                        derivation_path: path,
-                    });
-                }
-            }
+            let path = encoder.path(index as u64);
+
+            let (private_key, public_key) = trace_span!("derive", index).in_scope(|| -> Result<_, GeneratorError> {
+                let private_key = deriver.private_key(&path)?;
+                let secret_key = SecretKey::from_slice(&private_key)?;
+                Ok((private_key, PublicKey::from_secret_key(secp, &secret_key)))
+            })?;
+
+            let (pubkey, pubkey_uncompressed) = config.encode_public_key(&public_key);
+            let private_key_encoded = config.encode_private_key(&private_key)?;
 
-            if i % 1000 == 0 {
-                progress.fetch_add(1000, Ordering::Relaxed);
+            // SHA256/RIPEMD160 for secp256k1, Keccak256 for ethsecp256k1, unless a custom encoder says otherwise
+            let addresses = trace_span!("hash").in_scope(|| address_encoder.encode(&public_key, &mut encoder))?;
+            wallets.push(Wallet {
+                address: addresses.address,
+                evm_address: addresses.evm_address,
+                pubkey,
+                private_key: private_key_encoded,
+                derivation_path: path,
+                index: index as u64,
+                pubkey_hex: Some(hex_string(&public_key.serialize())),
+                pubkey_type: Some(pubkey_type.to_string()),
+                pubkey_uncompressed,
+                secp256k1_address: addresses.secp256k1_address,
+                wif: None,
+            });
+
+            if (i + 1) % PROGRESS_STEP == 0 {
+                progress.fetch_add(PROGRESS_STEP, Ordering::Relaxed);
             }
         }
-    });
+        Ok::<(), GeneratorError>(())
+    })?;
 
-    progress.fetch_add(count % 1000, Ordering::Relaxed);
-    wallets
+    progress.fetch_add(count % PROGRESS_STEP, Ordering::Relaxed);
+    Ok(wallets)
 }
 
 #[inline]
-fn generate_ethsecp256k1_batch(
+fn generate_bitcoin_batch(
     seed: &[u8],
     start_index: usize,
     count: usize,
-    prefix: &str,
+    config: &DerivationConfig,
+    address_type: BitcoinAddress,
     progress: Arc<AtomicUsize>,
-) -> Vec<Wallet> {
+) -> Result<Vec<Wallet>, GeneratorError> {
     let mut wallets = Vec::with_capacity(count);
-    let hrp = Hrp::parse(prefix).expect("Invalid prefix");
+    let network = Network::from_hrp(&config.prefix)?;
+    let mut deriver = KeyDeriver::new(seed);
+    let encoder = WalletEncoder::new(config);
 
     SECP.with(|secp| {
         for i in 0..count {
             let index = start_index + i;
-            let path = format!("m/44'/118'/0'/0/{}", index);
-
-            if let Ok(derived_key) = ExtendedPrivKey::derive(seed, path.as_str()) {
-                let private_key = derived_key.secret();
-                let secret_key = SecretKey::from_slice(&private_key).expect("Invalid key");
-                let public_key = PublicKey::from_secret_key(secp, &secret_key);
-
-                // ethsecp256k1
-                let pubkey_compressed = public_key.serialize();
-                let pubkey_base64 = general_purpose::STANDARD.encode(&pubkey_compressed);
-                let private_key_hex = hex::encode(&private_key);
-
-                // Keccak256
-                let pubkey_uncompressed = public_key.serialize_uncompressed();
-                let keccak_hash = Keccak256::digest(&pubkey_uncompressed[1..]);
-                let address_bytes = &keccak_hash[12..];
-
-                if let Ok(cosmos_addr) = bech32::encode::<Bech32>(hrp.clone(), address_bytes) {
-                    let evm_addr = format!("0x{}", hex::encode(address_bytes));
-
-                    wallets.push(Wallet {
-                        address: cosmos_addr,
-                        evm_address: Some(evm_addr),
-                        pubkey: pubkey_base64,
-                        private_key: private_key_hex,
-                        derivation_path: path,
-                    });
-                }
-            }
+            let path = encoder.path(index as u64);
 
-            if i % 1000 == 0 {
-                progress.fetch_add(1000, Ordering::Relaxed);
+            let (private_key, public_key) = trace_span!("derive", index).in_scope(|| -> Result<_, GeneratorError> {
+                let private_key = deriver.private_key(&path)?;
+                let secret_key = SecretKey::from_slice(&private_key)?;
+                Ok((private_key, PublicKey::from_secret_key(secp, &secret_key)))
+            })?;
+
+            // HASH160 of the compressed key, as for Cosmos secp256k1
+            let pubkey_compressed = public_key.serialize();
+            let pubkey_hash = trace_span!("hash").in_scope(|| bitcoin::hash160(&pubkey_compressed));
+            let address = match address_type {
+                BitcoinAddress::P2pkh => bitcoin::p2pkh(&pubkey_hash, network),
+                BitcoinAddress::P2wpkh => bitcoin::p2wpkh(&pubkey_hash, network)?,
+            };
+
+            let (pubkey, pubkey_uncompressed) = config.encode_public_key(&public_key);
+            wallets.push(Wallet {
+                address,
+                evm_address: None,
+                pubkey,
+                private_key: config.encode_private_key(&private_key)?,
+                derivation_path: path,
+                index: index as u64,
+                pubkey_hex: Some(hex_string(&pubkey_compressed)),
+                pubkey_type: None,
+                pubkey_uncompressed,
+                secp256k1_address: None,
+                wif: Some(bitcoin::wif(&private_key, network)),
+            });
+
+            if (i + 1) % PROGRESS_STEP == 0 {
+                progress.fetch_add(PROGRESS_STEP, Ordering::Relaxed);
             }
         }
-    });
+        Ok::<(), GeneratorError>(())
+    })?;
+
+    progress.fetch_add(count % PROGRESS_STEP, Ordering::Relaxed);
+    Ok(wallets)
+}
+
+#[inline]
+fn generate_solana_batch(
+    seed: &[u8],
+    start_index: usize,
+    count: usize,
+    config: &DerivationConfig,
+    progress: Arc<AtomicUsize>,
+) -> Result<Vec<Wallet>, GeneratorError> {
+    if config.pubkey_format == PubkeyFormat::UncompressedHex || config.include_uncompressed {
+        return Err(GeneratorError::Unsupported("ed25519 public keys have no uncompressed form".to_string()));
+    }
+    let mut wallets = Vec::with_capacity(count);
+    let mut deriver = Ed25519Deriver::new(seed);
+    let encoder = WalletEncoder::new(config);
+
+    for i in 0..count {
+        let index = start_index + i;
+        let path = encoder.path(index as u64);
+
+        let (private_key, public_key) = trace_span!("derive", index).in_scope(|| -> Result<_, GeneratorError> {
+            let private_key = deriver.private_key(&path)?;
+            Ok((private_key, solana::public_key(&private_key)))
+        })?;
+
+        // The address is the public key itself
+        wallets.push(Wallet {
+            address: solana::address(&public_key),
+            evm_address: None,
+            pubkey: config.encode_ed25519_public_key(&public_key),
+            private_key: config.encode_private_key(&private_key)?,
+            derivation_path: path,
+            index: index as u64,
+            pubkey_hex: Some(hex_string(&public_key)),
+            pubkey_type: None,
+            pubkey_uncompressed: None,
+            secp256k1_address: None,
+            wif: None,
+        });
+
+        if (i + 1) % PROGRESS_STEP == 0 {
+            progress.fetch_add(PROGRESS_STEP, Ordering::Relaxed);
+        }
+    }
+
+    progress.fetch_add(count % PROGRESS_STEP, Ordering::Relaxed);
+    Ok(wallets)
+}
 
-    progress.fetch_add(count % 1000, Ordering::Relaxed);
-    wallets
-}
\ No newline at end of file
+#[inline]
+pub(crate) fn parse_hrp(prefix: &str) -> Result<Hrp, GeneratorError> {
+    Hrp::parse(prefix).map_err(|source| GeneratorError::InvalidPrefix {
+        prefix: prefix.to_string(),
+        source,
+    })
+}
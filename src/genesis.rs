@@ -0,0 +1,110 @@
+//! Injection of generated wallets into a `genesis.json` as funded auth accounts.
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::coin::Coin;
+
+/// Code hash of an account without contract code, as used by ethermint `EthAccount`s.
+const EMPTY_CODE_HASH: &str = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenesisAccountType {
+    /// `/cosmos.auth.v1beta1.BaseAccount`
+    Base,
+    /// `/ethermint.types.v1.EthAccount` wrapping a base account
+    Eth,
+}
+
+fn base_account(address: &str, account_number: u64) -> Value {
+    json!({
+        "address": address,
+        "pub_key": null,
+        "account_number": account_number.to_string(),
+        "sequence": "0",
+    })
+}
+
+fn genesis_account(address: &str, account_number: u64, account_type: GenesisAccountType) -> Value {
+    match account_type {
+        GenesisAccountType::Base => {
+            let mut account = base_account(address, account_number);
+            account["@type"] = json!("/cosmos.auth.v1beta1.BaseAccount");
+            account
+        }
+        GenesisAccountType::Eth => json!({
+            "@type": "/ethermint.types.v1.EthAccount",
+            "base_account": base_account(address, account_number),
+            "code_hash": EMPTY_CODE_HASH,
+        }),
+    }
+}
+
+/// Address and account number of an existing genesis account, looking through wrappers.
+fn account_fields(account: &Value) -> Option<(&str, u64)> {
+    let object = account.as_object()?;
+    if let Some(address) = object.get("address").and_then(Value::as_str) {
+        let number = object.get("account_number").and_then(Value::as_str).and_then(|n| n.parse().ok()).unwrap_or(0);
+        return Some((address, number));
+    }
+    object.values().find_map(account_fields)
+}
+
+fn array_mut<'a>(genesis: &'a mut Value, module: &str, field: &str) -> Result<&'a mut Vec<Value>> {
+    let value = genesis
+        .pointer_mut(&format!("/app_state/{}", module))
+        .ok_or_else(|| anyhow!("Genesis has no app_state.{}", module))?;
+    if value[field].is_null() {
+        value[field] = json!([]);
+    }
+    value[field].as_array_mut().ok_or_else(|| anyhow!("app_state.{}.{} is not an array", module, field))
+}
+
+/// Add an auth account holding `coins` for every address and raise the bank supply to match.
+///
+/// Account numbers continue after the highest existing one. Fails if any address already has an
+/// account, leaving `genesis` untouched.
+pub fn add_accounts(genesis: &mut Value, addresses: &[&str], coins: &[Coin], account_type: GenesisAccountType) -> Result<()> {
+    let mut existing = HashSet::new();
+    let mut next_number = 0;
+    for account in array_mut(genesis, "auth", "accounts")?.iter() {
+        if let Some((address, number)) = account_fields(account) {
+            existing.insert(address.to_string());
+            next_number = next_number.max(number + 1);
+        }
+    }
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = addresses.iter().find(|a| existing.contains(**a) || !seen.insert(**a)) {
+        return Err(anyhow!("Account {} already exists in genesis", duplicate));
+    }
+
+    let mut totals: BTreeMap<String, u128> = BTreeMap::new();
+    for coin in array_mut(genesis, "bank", "supply")?.iter() {
+        let coin: Coin = serde_json::from_value(coin.clone())?;
+        *totals.entry(coin.denom.clone()).or_default() += coin.amount_u128();
+    }
+    for coin in coins {
+        let added = coin
+            .amount_u128()
+            .checked_mul(addresses.len() as u128)
+            .ok_or_else(|| anyhow!("Supply of {} overflows", coin.denom))?;
+        let total = totals.entry(coin.denom.clone()).or_default();
+        *total = total.checked_add(added).ok_or_else(|| anyhow!("Supply of {} overflows", coin.denom))?;
+    }
+
+    array_mut(genesis, "auth", "accounts")?.extend(
+        addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| genesis_account(address, next_number + i as u64, account_type)),
+    );
+
+    let mut sorted_coins = coins.to_vec();
+    sorted_coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+    array_mut(genesis, "bank", "balances")?
+        .extend(addresses.iter().map(|address| json!({ "address": address, "coins": sorted_coins })));
+
+    *array_mut(genesis, "bank", "supply")? = totals.iter().map(|(denom, amount)| json!(Coin::new(*amount, denom))).collect();
+    Ok(())
+}
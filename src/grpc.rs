@@ -0,0 +1,152 @@
+//! tonic gRPC service defined in `proto/wallet_factory.proto`.
+//!
+//! `GenerateWallets` is server-streaming: records are sent as soon as each
+//! chunk is derived, so clients can start consuming before the run finishes.
+//! Like the HTTP API, mnemonics are used to derive the seed and then dropped.
+
+// tonic's handler signatures fix the error type to `Status`
+#![allow(clippy::result_large_err)]
+
+use bip39::Mnemonic;
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use crate::types::KeyType;
+use crate::generator::{derivation_path, derive_wallet};
+use crate::stream::{wallet_stream, DEFAULT_CHUNK_SIZE};
+use crate::wallet::Wallet;
+
+pub mod proto {
+    tonic::include_proto!("wallet_factory.v1");
+}
+
+use proto::wallet_factory_server::{WalletFactory, WalletFactoryServer};
+use proto::{DeriveRequest, GenerateRequest, WalletRecord};
+
+#[derive(Debug, Clone)]
+pub struct GrpcConfig {
+    pub listen: SocketAddr,
+    pub auth_token: Option<String>,
+    pub max_stream_size: u64,
+}
+
+pub struct WalletFactoryService {
+    max_stream_size: u64,
+}
+
+impl From<proto::KeyType> for KeyType {
+    fn from(key_type: proto::KeyType) -> Self {
+        match key_type {
+            proto::KeyType::Secp256k1 => KeyType::Secp256k1,
+            proto::KeyType::Ethsecp256k1 => KeyType::Ethsecp256k1,
+        }
+    }
+}
+
+impl From<Wallet> for WalletRecord {
+    fn from(wallet: Wallet) -> Self {
+        WalletRecord {
+            address: wallet.address,
+            evm_address: wallet.evm_address.unwrap_or_default(),
+            pubkey: wallet.pubkey,
+            private_key: wallet.private_key,
+            derivation_path: wallet.derivation_path,
+        }
+    }
+}
+
+fn parse_key_type(value: i32) -> Result<KeyType, Status> {
+    proto::KeyType::try_from(value)
+        .map(KeyType::from)
+        .map_err(|_| Status::invalid_argument(format!("Unknown key type: {}", value)))
+}
+
+fn parse_seed(mnemonic: &str) -> Result<[u8; 64], Status> {
+    let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| Status::invalid_argument(format!("Invalid mnemonic: {}", e)))?;
+    Ok(mnemonic.to_seed(""))
+}
+
+fn prefix_or_default(prefix: String) -> String {
+    if prefix.is_empty() {
+        "cosmos".to_string()
+    } else {
+        prefix
+    }
+}
+
+type WalletStream = Pin<Box<dyn Stream<Item = Result<WalletRecord, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl WalletFactory for WalletFactoryService {
+    async fn derive_wallet(&self, request: Request<DeriveRequest>) -> Result<Response<WalletRecord>, Status> {
+        let req = request.into_inner();
+        let key_type = parse_key_type(req.key_type)?;
+        let seed = parse_seed(&req.mnemonic)?;
+        let path = if req.path.is_empty() {
+            derivation_path(key_type.coin_type(), req.index)
+        } else {
+            req.path
+        };
+
+        let wallet = derive_wallet(&seed, &path, &prefix_or_default(req.prefix), &key_type)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let mut record = WalletRecord::from(wallet);
+        if !req.include_private_key {
+            record.private_key.clear();
+        }
+        Ok(Response::new(record))
+    }
+
+    type GenerateWalletsStream = WalletStream;
+
+    async fn generate_wallets(&self, request: Request<GenerateRequest>) -> Result<Response<Self::GenerateWalletsStream>, Status> {
+        let req = request.into_inner();
+        if req.count == 0 || req.count > self.max_stream_size {
+            return Err(Status::invalid_argument(format!("count must be between 1 and {}", self.max_stream_size)));
+        }
+        let key_type = parse_key_type(req.key_type)?;
+        let seed = parse_seed(&req.mnemonic)?;
+
+        let stream = wallet_stream(
+            seed.to_vec(),
+            req.start_index as usize,
+            req.count as usize,
+            prefix_or_default(req.prefix),
+            key_type,
+            DEFAULT_CHUNK_SIZE,
+        )
+        .map_err(|e| Status::invalid_argument(e.to_string()))?
+        .map(|result| result.map(WalletRecord::from).map_err(|e| Status::internal(e.to_string())));
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Serve the gRPC API until the process is stopped.
+pub async fn serve(config: GrpcConfig) -> Result<(), tonic::transport::Error> {
+    let service = WalletFactoryService { max_stream_size: config.max_stream_size };
+    let expected = config.auth_token.map(|token| Sha256::digest(token.as_bytes()));
+
+    let server = WalletFactoryServer::with_interceptor(service, move |request: Request<()>| {
+        if let Some(expected) = &expected {
+            let provided = request
+                .metadata()
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .unwrap_or("");
+            if Sha256::digest(provided.as_bytes()) != *expected {
+                return Err(Status::unauthenticated("Missing or invalid bearer token"));
+            }
+        }
+        Ok(request)
+    });
+
+    tonic::transport::Server::builder()
+        .add_service(server)
+        .serve(config.listen)
+        .await
+}
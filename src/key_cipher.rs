@@ -0,0 +1,124 @@
+//! Private keys encrypted to a passphrase inside otherwise plaintext wallet records, so addresses,
+//! public keys and paths can be shared while the keys cannot be read without the passphrase.
+//!
+//! The passphrase is stretched once per run with Argon2id under a random salt, and each key is
+//! sealed with XChaCha20-Poly1305 under its own random nonce, authenticated together with the
+//! record's address so a key cannot be moved to another record. An encrypted field reads
+//! `enc1:` followed by the base64 of salt, nonce and ciphertext, so every record decrypts alone.
+
+use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use crate::wallet::Wallet;
+
+/// Marks an encrypted field and the scheme it was encrypted with.
+pub const ENCRYPTED_PREFIX: &str = "enc1:";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Whether a `privateKey` or `wif` value is encrypted.
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Argon2id with its default cost (19 MiB, two passes), which `enc1` fixes.
+fn derive_cipher(passphrase: &str, salt: &[u8; SALT_LEN]) -> Result<XChaCha20Poly1305> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive the key encryption key: {}", e))?;
+    Ok(XChaCha20Poly1305::new(&key.into()))
+}
+
+/// Encrypts the keys of generated wallets under one passphrase.
+pub struct KeyCipher {
+    salt: [u8; SALT_LEN],
+    cipher: XChaCha20Poly1305,
+}
+
+impl KeyCipher {
+    /// Stretch `passphrase` under a fresh salt; this takes a noticeable fraction of a second.
+    pub fn new(passphrase: &str) -> Result<Self> {
+        if passphrase.is_empty() {
+            return Err(anyhow!("The key passphrase is empty"));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Ok(Self { salt, cipher: derive_cipher(passphrase, &salt)? })
+    }
+
+    /// Encrypt one field of the record for `address`.
+    pub fn encrypt(&self, plaintext: &str, address: &str) -> String {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext.as_bytes(), aad: address.as_bytes() })
+            .expect("a private key fits one AEAD message");
+        let mut sealed = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&self.salt);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        format!("{}{}", ENCRYPTED_PREFIX, general_purpose::STANDARD.encode(sealed))
+    }
+
+    /// Replace the private key of `wallet`, and its WIF key if it has one, with their encryptions.
+    pub fn encrypt_wallet(&self, wallet: &mut Wallet) {
+        wallet.private_key = self.encrypt(&wallet.private_key, &wallet.address);
+        if let Some(wif) = &wallet.wif {
+            wallet.wif = Some(self.encrypt(wif, &wallet.address));
+        }
+    }
+}
+
+/// Decrypts keys encrypted under one passphrase, stretching it once for each salt met.
+pub struct KeyDecryptor {
+    passphrase: String,
+    ciphers: HashMap<[u8; SALT_LEN], XChaCha20Poly1305>,
+}
+
+impl KeyDecryptor {
+    pub fn new(passphrase: &str) -> Self {
+        Self { passphrase: passphrase.to_string(), ciphers: HashMap::new() }
+    }
+
+    /// Decrypt one field of the record for `address`.
+    pub fn decrypt(&mut self, value: &str, address: &str) -> Result<String> {
+        let encoded = value.strip_prefix(ENCRYPTED_PREFIX).ok_or_else(|| anyhow!("The key of {} is not encrypted", address))?;
+        let sealed = general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| anyhow!("The encrypted key of {} is not valid base64", address))?;
+        if sealed.len() < SALT_LEN + NONCE_LEN {
+            return Err(anyhow!("The encrypted key of {} is truncated", address));
+        }
+        let (salt, rest) = sealed.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let salt: [u8; SALT_LEN] = salt.try_into()?;
+
+        let cipher = match self.ciphers.entry(salt) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(derive_cipher(&self.passphrase, &salt)?),
+        };
+        let plaintext = cipher
+            .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad: address.as_bytes() })
+            .map_err(|_| anyhow!("Failed to decrypt the key of {}: wrong passphrase, or the record was altered", address))?;
+        String::from_utf8(plaintext).map_err(|_| anyhow!("The decrypted key of {} is not text", address))
+    }
+
+    /// Restore the plaintext keys of `wallet`; fields that are not encrypted are left alone.
+    pub fn decrypt_wallet(&mut self, wallet: &mut Wallet) -> Result<()> {
+        if is_encrypted(&wallet.private_key) {
+            wallet.private_key = self.decrypt(&wallet.private_key, &wallet.address)?;
+        }
+        if let Some(wif) = wallet.wif.as_deref().filter(|wif| is_encrypted(wif)) {
+            wallet.wif = Some(self.decrypt(wif, &wallet.address)?);
+        }
+        Ok(())
+    }
+}
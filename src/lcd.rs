@@ -0,0 +1,175 @@
+//! Minimal blocking client for the Cosmos SDK REST (LCD) API.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use std::collections::HashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::coin::Coin;
+
+/// Base delay before the first retry; doubled on each attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+pub struct LcdClient {
+    base_url: String,
+    agent: ureq::Agent,
+    retries: u32,
+}
+
+#[derive(Deserialize)]
+struct Pagination {
+    next_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BalancesResponse {
+    balances: Vec<Coin>,
+    pagination: Option<Pagination>,
+}
+
+#[derive(Deserialize)]
+struct GasInfo {
+    gas_used: String,
+}
+
+#[derive(Deserialize)]
+struct SimulateResponse {
+    gas_info: GasInfo,
+}
+
+/// Account number and sequence of an on-chain account.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountInfo {
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+/// Find the base account fields in any account wrapper (vesting, module, EthAccount, ...).
+fn find_account_info(value: &Value) -> Option<AccountInfo> {
+    let object = value.as_object()?;
+    if let Some(number) = object.get("account_number") {
+        let parse = |v: Option<&Value>| v.and_then(Value::as_str).and_then(|s| s.parse().ok());
+        return Some(AccountInfo {
+            account_number: parse(Some(number))?,
+            sequence: parse(object.get("sequence")).unwrap_or(0),
+        });
+    }
+    object.values().find_map(find_account_info)
+}
+
+impl LcdClient {
+    pub fn new(base_url: &str, timeout: Duration, retries: u32) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+            retries,
+        }
+    }
+
+    /// GET a JSON document, retrying transport errors, 429 and 5xx responses.
+    ///
+    /// Returns `Ok(None)` on 404 so callers can treat missing resources as empty.
+    pub fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<Option<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        let request = || query.iter().fold(self.agent.get(&url), |request, (key, value)| request.query(key, value));
+        self.send(request, None)
+    }
+
+    /// POST a JSON body and read a JSON document back, retrying like [`get`](Self::get).
+    pub fn post<T: DeserializeOwned>(&self, path: &str, body: &Value) -> Result<Option<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        self.send(|| self.agent.post(&url), Some(body))
+    }
+
+    fn send<T: DeserializeOwned>(&self, request: impl Fn() -> ureq::Request, body: Option<&Value>) -> Result<Option<T>> {
+        let mut attempt = 0;
+
+        loop {
+            let request = request();
+            let (method, url) = (request.method().to_string(), request.url().to_string());
+            let response = match body {
+                Some(body) => request.send_json(body),
+                None => request.call(),
+            };
+            let retryable = match response {
+                Ok(response) => return Ok(Some(response.into_json()?)),
+                Err(ureq::Error::Status(404, _)) => return Ok(None),
+                Err(ureq::Error::Status(code, response)) if code != 429 && code < 500 => {
+                    let body = response.into_string().unwrap_or_default();
+                    return Err(anyhow!("{} {} returned {}: {}", method, url, code, body));
+                }
+                Err(e) => e,
+            };
+
+            if attempt >= self.retries {
+                return Err(anyhow!("{} {} failed after {} attempts: {}", method, url, attempt + 1, retryable));
+            }
+            std::thread::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// All bank balances of an address, following pagination.
+    pub fn balances(&self, address: &str) -> Result<Vec<Coin>> {
+        let path = format!("/cosmos/bank/v1beta1/balances/{}", address);
+        let mut balances = Vec::new();
+        let mut next_key: Option<String> = None;
+
+        loop {
+            let query: Vec<(&str, &str)> = next_key.iter().map(|key| ("pagination.key", key.as_str())).collect();
+            let Some(page) = self.get::<BalancesResponse>(&path, &query)? else {
+                break;
+            };
+            balances.extend(page.balances);
+
+            next_key = page.pagination.and_then(|p| p.next_key).filter(|key| !key.is_empty());
+            if next_key.is_none() {
+                break;
+            }
+        }
+        Ok(balances)
+    }
+
+    /// Account number and sequence, or `None` if the account does not exist yet.
+    pub fn account(&self, address: &str) -> Result<Option<AccountInfo>> {
+        let path = format!("/cosmos/auth/v1beta1/accounts/{}", address);
+        match self.get::<Value>(&path, &[])? {
+            Some(response) => find_account_info(&response["account"])
+                .map(Some)
+                .ok_or_else(|| anyhow!("Unrecognized account response for {}", address)),
+            None => Ok(None),
+        }
+    }
+
+    /// Gas used by a transaction in simulation, from its protobuf `TxRaw` bytes.
+    pub fn simulate(&self, tx_bytes: &[u8]) -> Result<u64> {
+        let body = serde_json::json!({ "tx_bytes": general_purpose::STANDARD.encode(tx_bytes) });
+        let response: SimulateResponse = self
+            .post("/cosmos/tx/v1beta1/simulate", &body)?
+            .ok_or_else(|| anyhow!("{} does not serve /cosmos/tx/v1beta1/simulate", self.base_url))?;
+        response.gas_info.gas_used.parse().map_err(|_| anyhow!("Invalid gas_used '{}' in simulation response", response.gas_info.gas_used))
+    }
+
+    /// Look up many accounts on a pool of `concurrency` threads.
+    ///
+    /// Fails if any lookup fails or any account does not exist yet.
+    #[cfg(feature = "parallel")]
+    pub fn accounts(&self, addresses: &[&str], concurrency: usize) -> Result<HashMap<String, AccountInfo>> {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency.max(1)).build()?;
+        pool.install(|| {
+            addresses
+                .par_iter()
+                .map(|address| {
+                    let info = self.account(address)?.ok_or_else(|| anyhow!("Account {} does not exist on chain", address))?;
+                    Ok((address.to_string(), info))
+                })
+                .collect()
+        })
+    }
+}
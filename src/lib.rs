@@ -1,7 +1,88 @@
 pub mod wallet;
+pub mod address;
+pub mod address_encoder;
+pub mod binary;
+pub mod bitcoin;
+pub mod error;
+pub mod derivation;
 pub mod generator;
+pub mod types;
+pub mod chain;
+pub mod coin;
+pub mod coprocess;
+pub mod eip712;
+pub mod extension;
+pub mod fund;
+pub mod genesis;
+pub mod key_cipher;
+pub mod lcd;
+pub mod merge;
+pub mod merkle;
+pub mod metrics;
+pub mod message;
+pub mod mnemonic;
+pub mod object_store;
+pub mod path_range;
+pub mod progress;
+pub mod query;
+pub mod rederive;
+pub mod report;
+pub mod rotate;
+pub mod rpc;
+pub mod secure_output;
+pub mod selftest;
+pub mod sign;
+pub mod signer;
+pub mod solana;
+pub mod targets;
+pub mod throttle;
+pub mod tx;
+pub mod vanity;
+pub mod wallet_file;
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod filter;
+#[cfg(feature = "age")]
+pub mod age_file;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
+#[cfg(feature = "parallel")]
+pub mod broadcast;
+#[cfg(feature = "parallel")]
+pub mod mmap_output;
+#[cfg(feature = "parallel")]
+pub mod recover;
+#[cfg(feature = "parallel")]
+pub mod scan;
+#[cfg(feature = "parallel")]
+pub mod simulate;
+#[cfg(feature = "async")]
+pub mod stream;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "postgres")]
+pub mod pg;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 
 pub use wallet::Wallet;
-pub use generator::{generate_wallets_batch, generate_addresses};
-pub use cli::{Args, KeyType};
\ No newline at end of file
+pub use error::GeneratorError;
+pub use generator::{derive_wallet, generate_wallets_batch, generate_addresses, DerivationConfig};
+#[cfg(feature = "parallel")]
+pub use generator::generate_wallets_parallel;
+pub use types::KeyType;
+#[cfg(feature = "cli")]
+pub use cli::Args;
\ No newline at end of file
@@ -1,55 +1,2241 @@
 use anyhow::Result;
+use base64::{engine::general_purpose, Engine as _};
 use bip39::Mnemonic;
-use clap::Parser;
-use indicatif::{ProgressBar, ProgressStyle};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use rayon::prelude::*;
-use serde_json;
-use std::fs::{self, File};
-use std::io::{BufWriter, Write};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{IsTerminal, Write};
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, info_span, warn};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
 
-use wallet_generator::{Args, KeyType, Wallet, generate_wallets_batch};
+#[cfg(feature = "age")]
+use wallet_generator::age_file::{self, RootSecret};
+#[cfg(feature = "arrow")]
+use wallet_generator::arrow::ArrowIpcWriter;
+#[cfg(feature = "pkcs11")]
+use wallet_generator::cli::Pkcs11Args;
+#[cfg(feature = "pkcs11")]
+use wallet_generator::pkcs11::{Pkcs11Config, Pkcs11Signer};
+use wallet_generator::binary::{self, BinaryReader};
+use wallet_generator::broadcast::{self as broadcaster, load_signed_txs};
+use wallet_generator::address;
+use wallet_generator::cli::{
+    AccountFilter, AddressCommand, AirdropCommand, AirdropMerkleArgs, AnnotateArgs, BroadcastArgs, BroadcastModeArg, Command,
+    CompletionsArgs, ConvertArgs, Create2Args, CreateArgs, DecryptArgs, DeriveAddressArgs, DeriveArgs, DiffArgs, DupesArgs, ExportExtensionArgs, FindIndexArgs, FromPubkeyArgs,
+    FundArgs, FundModeArg, FundedFilter, GenesisAccountTypeArg, GenesisAddAccountsArgs,
+    GenesisCommand, Instantiate2Args, LogFormat, LogLevel, MergeArgs, MerkleFormatArg, MessageData,
+    MessageScheme, MnemonicCheckArgs, MnemonicCommand, ModuleAddressArgs, MultisigArgs,
+    OutputFormat, PrivateKeyFormat, PubkeyFormat, QueryArgs, RecoverArgs, RederiveArgs, ScanBalancesArgs, SignArgs, SignMessageArgs, SignModeArg, SimulateArgs,
+    VanityCommand, VanityEstimateArgs, VerifyManifestArgs, VerifyMessageArgs, XpubArgs,
+};
+use wallet_generator::coprocess::{Coprocess, Defaults, DeriveRequest};
+use wallet_generator::eip712::{self, TypedData};
+use wallet_generator::extension;
+use wallet_generator::filter::WalletFilter;
+use wallet_generator::targets::TargetSet;
+use wallet_generator::throttle;
+use wallet_generator::fund::{FundMode, FundPlan};
+use wallet_generator::generator::INDEX_PLACEHOLDER;
+use wallet_generator::types::{BitcoinAddress, ChainFamily};
+use wallet_generator::genesis::{self, GenesisAccountType};
+use wallet_generator::tx::{write_tx_file, Msg, SignMode, SignerData};
+use wallet_generator::key_cipher::{self, KeyCipher, KeyDecryptor};
+use wallet_generator::lcd::{AccountInfo, LcdClient};
+use wallet_generator::merge::{self, Duplicate, DuplicateChecker, SortedMerge};
+use wallet_generator::merkle::{self, AirdropEntry, LeafFormat};
+use wallet_generator::metrics;
+use wallet_generator::message::{self, PersonalSignature, StdSignature};
+use wallet_generator::mnemonic::{self, Correction, Problem};
+use wallet_generator::mmap_output::{CsvLayout, MappedCsvWriter};
+use wallet_generator::object_store::{MultipartUpload, ObjectStore, ObjectUrl, MIN_PART_SIZE};
+use wallet_generator::path_range::PathRange;
+use wallet_generator::query::{self, RecordKey, WalletIndex};
+use wallet_generator::rederive;
+use wallet_generator::recover::{self as recovery, RecoverConfig};
+use wallet_generator::rpc::{BroadcastMode, RpcClient};
+use wallet_generator::secure_output::{self, OutputPermissions};
+use wallet_generator::sign::{load_txs, wallet_key_type, wallet_private_key, SendSpec, WalletSigner};
+use wallet_generator::signer::{Signer, SoftwareSigner};
+use wallet_generator::config::{config_arg, ConfigFile};
+use wallet_generator::progress::{self, RunState};
+use wallet_generator::report::{self, AuditArtifact, AuditEntry, DigestWriter, RunParameters, RunReport};
+use wallet_generator::rotate::{self, Manifest, ManifestPart, Rotation};
+use wallet_generator::scan;
+use wallet_generator::simulate::{self as simulation, SimulateOptions};
+use wallet_generator::vanity::{Alphabet, Pattern, Position};
+use wallet_generator::wallet_file::{
+    self as wallet_file, append_output, check_output, create_output, read_wallets, ExistingOutput, write_json_array, OutputSink, RecordReader, WalletReader, WalletWriter, STDOUT, WRITE_BUFFER,
+};
+use wallet_generator::{Args, DerivationConfig, GeneratorError, KeyType, Wallet, generate_wallets_batch, generate_wallets_parallel};
+
+/// Where summary lines go: nowhere with `--quiet`, and to stderr when the records themselves
+/// stream to stdout.
+#[derive(Clone, Copy)]
+struct Console {
+    quiet: bool,
+    to_stdout: bool,
+}
+
+impl Console {
+    fn say(self, line: std::fmt::Arguments) {
+        if self.quiet {
+            return;
+        }
+        if self.to_stdout {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// `println!` for summary lines, sent where `$console` directs.
+macro_rules! say {
+    ($console:expr, $($arg:tt)*) => {
+        $console.say(format_args!($($arg)*))
+    };
+}
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let file_config = ConfigFile::find(config_arg(std::env::args_os()).as_deref())?;
+    let matches = file_config.apply(Args::command())?.get_matches();
+    let mut args = Args::from_arg_matches(&matches)?;
+
+    // A configured chain gives way to a prefix or key type set for this run
+    let explicit = |id| matches!(matches.value_source(id), Some(ValueSource::CommandLine | ValueSource::EnvVariable));
+    if matches.value_source("chain") == Some(ValueSource::DefaultValue) && (explicit("prefix") || explicit("key_type")) {
+        args.chain = None;
+    }
+
+    #[cfg(feature = "tui")]
+    let quiet_logs = args.quiet || args.tui;
+    #[cfg(not(feature = "tui"))]
+    let quiet_logs = args.quiet;
+    init_logging(args.log_level, args.log_format, quiet_logs)?;
+
+    if let Some(dir) = &args.generate_man {
+        return generate_man(dir);
+    }
+
+    let permissions = OutputPermissions { umask: secure_output::parse_umask(&args.umask)?, insecure: args.insecure_output };
+    match args.command.take() {
+        Some(command) => run_command(command, args.quiet, &file_config, &permissions),
+        None => generate(args, &file_config, &permissions),
+    }
+}
+
+fn completions(completions_args: CompletionsArgs) -> Result<()> {
+    // Rendered first: the generator panics on write errors, such as a closed pipe
+    let mut script = Vec::new();
+    clap_complete::generate(completions_args.shell, &mut Args::command(), "wallet-generator", &mut script);
+    std::io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+/// Write a man page for the CLI and one for each subcommand, e.g. `wallet-generator-convert.1`.
+fn generate_man(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).map_err(|e| anyhow::anyhow!("Failed to create {}: {}", dir.display(), e))?;
+    clap_mangen::generate_to(Args::command(), dir).map_err(|e| anyhow::anyhow!("Failed to write man pages to {}: {}", dir.display(), e))?;
+    let pages = fs::read_dir(dir)?.filter_map(|entry| entry.ok()).filter(|entry| entry.path().extension().is_some_and(|ext| ext == "1")).count();
+    println!("Wrote {} man pages to {}", pages, dir.display());
+    Ok(())
+}
+
+/// Send tracing events to stderr. An explicit --log-level wins over --quiet, which wins over RUST_LOG.
+fn init_logging(level: Option<LogLevel>, format: LogFormat, quiet: bool) -> Result<()> {
+    let filter = match level {
+        Some(level) => EnvFilter::new(match level {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }),
+        None if quiet => EnvFilter::new("error"),
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    };
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_target(false)
+        .with_span_events(FmtSpan::CLOSE);
+    match format {
+        LogFormat::Text => subscriber.try_init(),
+        LogFormat::Json => subscriber.json().try_init(),
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to set up logging: {}", e))
+}
+
+fn run_command(command: Command, quiet: bool, file_config: &ConfigFile, permissions: &OutputPermissions) -> Result<()> {
+    match command {
+        Command::ScanBalances(scan_args) => scan_balances(scan_args, quiet, permissions),
+        Command::Annotate(annotate_args) => annotate(annotate_args, quiet, permissions),
+        Command::Fund(fund_args) => fund(fund_args),
+        Command::Sign(sign_args) => sign(sign_args),
+        Command::Simulate(simulate_args) => simulate(simulate_args, quiet),
+        Command::Address(AddressCommand::Module(module_args)) => module_address(module_args),
+        Command::Address(AddressCommand::Derive(derive_args)) => derive_address(derive_args),
+        Command::Address(AddressCommand::Instantiate2(instantiate2_args)) => predict_instantiate2(instantiate2_args),
+        Command::Address(AddressCommand::Create(create_args)) => predict_create(create_args),
+        Command::Address(AddressCommand::Create2(create2_args)) => predict_create2(create2_args),
+        Command::FindIndex(find_args) => find_index(find_args, quiet),
+        Command::Recover(recover_args) => recover(recover_args, quiet, permissions),
+        Command::FromPubkey(pubkey_args) => from_pubkey(pubkey_args),
+        Command::Xpub(xpub_args) => xpub(xpub_args),
+        Command::Derive(derive_args) => derive(derive_args),
+        Command::Mnemonic(MnemonicCommand::Check(check_args)) => check_mnemonic(check_args),
+        Command::Vanity(VanityCommand::Estimate(estimate_args)) => vanity_estimate(estimate_args),
+        Command::Selftest => selftest(),
+        Command::Completions(completions_args) => completions(completions_args),
+        Command::Query(query_args) => query_wallets(query_args),
+        Command::Diff(diff_args) => diff(diff_args),
+        Command::Merge(merge_args) => merge(merge_args, permissions),
+        Command::Dupes(dupes_args) => dupes(dupes_args),
+        Command::Convert(convert_args) => convert(convert_args, permissions),
+        Command::Decrypt(decrypt_args) => decrypt(decrypt_args, permissions),
+        Command::ExportExtension(export_args) => export_extension(export_args),
+        Command::Rederive(rederive_args) => rederive(rederive_args, file_config, permissions),
+        Command::VerifyManifest(verify_args) => verify_manifest(verify_args),
+        Command::Multisig(multisig_args) => multisig(multisig_args),
+        Command::Airdrop(AirdropCommand::Merkle(merkle_args)) => airdrop_merkle(merkle_args),
+        Command::Genesis(GenesisCommand::AddAccounts(genesis_args)) => genesis_add_accounts(genesis_args),
+        Command::Broadcast(broadcast_args) => broadcast(broadcast_args, quiet),
+        Command::SignMessage(message_args) => sign_message(message_args),
+        Command::VerifyMessage(message_args) => verify_message(message_args),
+        #[cfg(feature = "server")]
+        Command::Serve(serve_args) => serve(serve_args),
+        #[cfg(feature = "grpc")]
+        Command::Grpc(grpc_args) => serve_grpc(grpc_args),
+    }
+}
+
+/// Run `task` on a scoped thread while drawing the counter it advances: a bar of `total` items, or a
+/// spinner when the total is unknown. Returns the task's result and the final count.
+fn with_progress<T: Send>(total: Option<u64>, quiet: bool, task: impl FnOnce(&AtomicUsize) -> T + Send) -> Result<(T, usize)> {
+    let pb = match total {
+        Some(total) => ProgressBar::new(total).with_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) | {per_sec}")?
+                .progress_chars("#>-"),
+        ),
+        None => ProgressBar::new_spinner().with_style(ProgressStyle::default_spinner().template("{spinner:.green} [{elapsed_precise}] {pos} checked")?),
+    };
+    if quiet {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+
+    let progress = AtomicUsize::new(0);
+    let result = std::thread::scope(|scope| {
+        let handle = scope.spawn(|| task(&progress));
+        while !handle.is_finished() {
+            pb.set_position(progress.load(Ordering::Relaxed) as u64);
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        handle.join().unwrap()
+    });
+    let done = progress.load(Ordering::Relaxed);
+    pb.set_position(done as u64);
+    pb.finish();
+    Ok((result, done))
+}
+
+fn scan_balances(scan_args: ScanBalancesArgs, quiet: bool, permissions: &OutputPermissions) -> Result<()> {
+    // The records keep their private keys
+    permissions.check_directory(Path::new(&scan_args.output))?;
+    let wallets = read_wallets(&scan_args.input)?;
+    let total = wallets.len();
+    let client = LcdClient::new(&scan_args.node, Duration::from_secs(scan_args.timeout), scan_args.retries);
+
+    info!(wallets = total, node = %scan_args.node, concurrency = scan_args.concurrency, "Scanning balances");
+    let (scanned, _) =
+        with_progress(Some(total as u64), quiet, |progress| scan::scan_balances(&client, wallets, scan_args.concurrency, progress))?;
+    let scanned = scanned?;
+
+    let denom = scan_args.denom.as_deref();
+    let funded = scanned.iter().filter(|w| w.is_funded(denom)).count();
+    let failed = scanned.iter().filter(|w| w.error.is_some()).count();
+
+    let records = scanned.iter().filter(|w| match scan_args.only {
+        Some(FundedFilter::Funded) => w.is_funded(denom),
+        Some(FundedFilter::Unfunded) => w.error.is_none() && !w.is_funded(denom),
+        None => true,
+    });
+    write_json_array(&scan_args.output, records, Some(permissions))?;
+
+    println!("\nScan Summary:");
+    println!("────────────────────");
+    println!("Wallets scanned: {}", total);
+    println!("Funded: {}", funded);
+    println!("Unfunded: {}", total - funded - failed);
+    println!("Failed queries: {}", failed);
+    println!("Output: {}", scan_args.output);
+
+    if failed > 0 {
+        warn!(failed, "Queries failed after retries; their records carry an \"error\" field");
+    }
+    Ok(())
+}
+
+/// Record the on-chain account number and sequence of every wallet in a file.
+fn annotate(annotate_args: AnnotateArgs, quiet: bool, permissions: &OutputPermissions) -> Result<()> {
+    permissions.check_directory(Path::new(&annotate_args.output))?;
+    let wallets = read_wallets(&annotate_args.input)?;
+    let total = wallets.len();
+    let client = LcdClient::new(&annotate_args.node, Duration::from_secs(annotate_args.timeout), annotate_args.retries);
+
+    info!(wallets = total, node = %annotate_args.node, concurrency = annotate_args.concurrency, "Looking up accounts");
+    let (annotated, _) =
+        with_progress(Some(total as u64), quiet, |progress| scan::annotate_accounts(&client, wallets, annotate_args.concurrency, progress))?;
+    let annotated = annotated?;
+
+    let existing = annotated.iter().filter(|w| w.account_exists == Some(true)).count();
+    let failed = annotated.iter().filter(|w| w.error.is_some()).count();
+
+    let records = annotated.iter().filter(|w| match annotate_args.only {
+        Some(AccountFilter::Existing) => w.account_exists == Some(true),
+        Some(AccountFilter::New) => w.account_exists == Some(false),
+        None => true,
+    });
+    write_json_array(&annotate_args.output, records, Some(permissions))?;
+
+    println!("\nAnnotate Summary:");
+    println!("────────────────────");
+    println!("Wallets queried: {}", total);
+    println!("Existing accounts: {}", existing);
+    println!("New addresses: {}", total - existing - failed);
+    println!("Failed queries: {}", failed);
+    println!("Output: {}", annotate_args.output);
+
+    if failed > 0 {
+        warn!(failed, "Queries failed after retries; their records carry an \"error\" field");
+    }
+    Ok(())
+}
+
+/// Parse a 32-byte hex private key, with or without a `0x` prefix.
+fn parse_private_key(hex_key: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(hex_key.trim().trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid private key hex: {}", e))?;
+    bytes.try_into().map_err(|_| anyhow::anyhow!("Private key must be 32 bytes"))
+}
+
+fn fund(fund_args: FundArgs) -> Result<()> {
+    let wallets = read_wallets(&fund_args.input)?;
+    let first = wallets.first().ok_or_else(|| anyhow::anyhow!("{} contains no wallets", fund_args.input))?;
+    let funder: Option<Box<dyn Signer>> = match fund_args.funder_key.as_deref() {
+        Some(key) => Some(Box::new(SoftwareSigner::new(&parse_private_key(key)?, fund_args.funder_key_type.clone())?)),
+        None => None,
+    };
+    #[cfg(feature = "pkcs11")]
+    let funder = match funder {
+        Some(funder) => Some(funder),
+        None => pkcs11_signer(&fund_args.pkcs11, fund_args.funder_key_type.clone())?,
+    };
+
+    let from = match (&funder, &fund_args.from) {
+        (Some(key), from) => {
+            let (hrp, _) = bech32::decode(&first.address)?;
+            let derived = key.address(hrp.as_str())?;
+            if from.as_ref().is_some_and(|from| *from != derived) {
+                return Err(anyhow::anyhow!("--from does not match the address of the funder key ({})", derived));
+            }
+            derived
+        }
+        (None, Some(from)) => from.clone(),
+        (None, None) => return Err(anyhow::anyhow!("Either --from or a funder key is required")),
+    };
+
+    let plan = FundPlan {
+        from: from.clone(),
+        amount: fund_args.amount,
+        mode: match fund_args.mode {
+            FundModeArg::Multisend => FundMode::MultiSend,
+            FundModeArg::Send => FundMode::Send,
+        },
+        max_tx_bytes: fund_args.max_tx_bytes,
+        max_recipients: fund_args.max_recipients.max(1),
+        gas_base: fund_args.gas_base,
+        gas_per_recipient: fund_args.gas_per_recipient,
+        gas_price: fund_args.gas_price,
+        fee: fund_args.fee,
+        memo: fund_args.memo,
+    };
+
+    let recipients: Vec<&str> = wallets.iter().map(|w| w.address.as_str()).collect();
+    let mut txs = plan.build_txs(&recipients);
+
+    if let Some(key) = &funder {
+        let chain_id = fund_args.chain_id.ok_or_else(|| anyhow::anyhow!("--chain-id is required when signing"))?;
+        let (account_number, sequence) = match (fund_args.account_number, fund_args.sequence) {
+            (Some(account_number), Some(sequence)) => (account_number, sequence),
+            (account_number, sequence) => {
+                let node = fund_args.node.as_deref().ok_or_else(|| {
+                    anyhow::anyhow!("--account-number and --sequence, or --node to look them up, are required when signing")
+                })?;
+                let info = LcdClient::new(node, Duration::from_secs(10), 3)
+                    .account(&from)?
+                    .ok_or_else(|| anyhow::anyhow!("Funder account {} does not exist on chain", from))?;
+                (account_number.unwrap_or(info.account_number), sequence.unwrap_or(info.sequence))
+            }
+        };
+
+        for (i, tx) in txs.iter_mut().enumerate() {
+            let signer = SignerData { chain_id: chain_id.clone(), account_number, sequence: sequence + i as u64 };
+            tx.sign_with(key.as_ref(), &signer)?;
+        }
+    }
+
+    for (i, tx) in txs.iter().enumerate() {
+        let path = Path::new(&fund_args.output_dir).join(format!("fund_{:05}.json", i));
+        write_tx_file(&path, tx)?;
+    }
+
+    println!("\nFunding Summary:");
+    println!("────────────────────");
+    println!("Funder: {}", from);
+    println!("Recipients: {}", recipients.len());
+    println!("Transactions: {}", txs.len());
+    println!("Signed: {}", if funder.is_some() { "yes" } else { "no" });
+    println!("Output: {}", fund_args.output_dir);
+    Ok(())
+}
+
+/// The HSM key selected by the `--pkcs11-*` options, if any.
+#[cfg(feature = "pkcs11")]
+fn pkcs11_signer(pkcs11_args: &Pkcs11Args, key_type: KeyType) -> Result<Option<Box<dyn Signer>>> {
+    let (Some(module), Some(label)) = (&pkcs11_args.pkcs11_module, &pkcs11_args.pkcs11_key_label) else {
+        return Ok(None);
+    };
+    let config = Pkcs11Config { module, slot: pkcs11_args.pkcs11_slot, label, pin: pkcs11_args.pkcs11_pin.as_deref() };
+    Ok(Some(Box::new(Pkcs11Signer::open(&config, key_type)?)))
+}
+
+fn sign(sign_args: SignArgs) -> Result<()> {
+    let wallets = read_wallets(&sign_args.wallets)?;
+
+    let mut txs = match (&sign_args.txs, &sign_args.send_spec) {
+        (Some(path), _) => load_txs(path)?,
+        (None, Some(spec_path)) => {
+            let spec: SendSpec = serde_json::from_str(&fs::read_to_string(spec_path)?)
+                .map_err(|e| anyhow::anyhow!("Invalid send spec {}: {}", spec_path, e))?;
+            wallets.iter().map(|w| spec.build_tx(&w.address)).collect()
+        }
+        (None, None) => unreachable!("clap requires --txs or --send-spec"),
+    };
+
+    let mut signers: Vec<String> = txs
+        .iter()
+        .map(|tx| {
+            tx.body.messages.first().and_then(Msg::signer).map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("Transaction has no messages"))
+        })
+        .collect::<Result<_>>()?;
+    signers.sort_unstable();
+    signers.dedup();
+
+    let accounts: HashMap<String, AccountInfo> = match (&sign_args.node, sign_args.account_number, sign_args.sequence) {
+        (Some(node), _, _) => {
+            info!(accounts = signers.len(), node = %node, "Looking up accounts");
+            let addresses: Vec<&str> = signers.iter().map(String::as_str).collect();
+            LcdClient::new(node, Duration::from_secs(10), 3).accounts(&addresses, sign_args.concurrency)?
+        }
+        (None, Some(account_number), Some(sequence)) => signers
+            .iter()
+            .map(|s| (s.clone(), AccountInfo { account_number, sequence }))
+            .collect(),
+        _ => return Err(anyhow::anyhow!("Either --node or --account-number and --sequence are required")),
+    };
+
+    let mut signer = WalletSigner::new(&wallets, accounts, sign_args.chain_id, sign_args.key_type.clone());
+    #[cfg(feature = "pkcs11")]
+    if let Some(first) = signers.first() {
+        if let Some(key) = pkcs11_signer(&sign_args.pkcs11, sign_args.key_type.clone().unwrap_or(KeyType::Secp256k1))? {
+            let (hrp, _) = bech32::decode(first)?;
+            let address = key.address(hrp.as_str())?;
+            info!(address = %address, "Signing with the PKCS#11 key");
+            signer.add_signer(address, key);
+        }
+    }
+    for tx in txs.iter_mut() {
+        signer.sign(tx)?;
+    }
+
+    let output_dir = Path::new(&sign_args.output_dir);
+    let mut raw = String::new();
+    for (i, tx) in txs.iter().enumerate() {
+        write_tx_file(output_dir.join(format!("signed_{:05}.json", i)), tx)?;
+        raw.push_str(&general_purpose::STANDARD.encode(tx.to_raw_bytes()?));
+        raw.push('\n');
+    }
+    fs::write(output_dir.join("signed_txs.b64"), raw)?;
+
+    println!("\nSigning Summary:");
+    println!("────────────────────");
+    println!("Transactions signed: {}", txs.len());
+    println!("Signers: {}", signers.len());
+    println!("Output: {}", sign_args.output_dir);
+    Ok(())
+}
+
+fn simulate(simulate_args: SimulateArgs, quiet: bool) -> Result<()> {
+    let txs = load_txs(&simulate_args.txs)?;
+    let wallets = simulate_args.wallets.as_deref().map(read_wallets).transpose()?.unwrap_or_default();
+    let total = txs.len();
+    let client = LcdClient::new(&simulate_args.node, Duration::from_secs(simulate_args.timeout), simulate_args.retries);
+    if simulate_args.gas_adjustment < 1.0 {
+        return Err(anyhow::anyhow!("--gas-adjustment below 1 sets gas limits under the gas used"));
+    }
+    let options = SimulateOptions {
+        sign_mode: match simulate_args.sign_mode {
+            SignModeArg::Direct => SignMode::Direct,
+            SignModeArg::AminoJson => SignMode::LegacyAminoJson,
+        },
+        gas_adjustment: simulate_args.gas_adjustment,
+        gas_price: simulate_args.gas_price,
+    };
+
+    info!(transactions = total, node = %simulate_args.node, concurrency = simulate_args.concurrency, "Simulating");
+    let (simulations, _) = with_progress(Some(total as u64), quiet, |progress| {
+        simulation::simulate_all(&client, &txs, &wallets, &options, simulate_args.concurrency, progress)
+    })?;
+    let simulations = simulations?;
+
+    let failed = simulations.iter().filter(|s| s.error.is_some()).count();
+    let gas_limit: u64 = simulations.iter().filter_map(|s| s.gas_limit).sum();
+    let mut fees: BTreeMap<&str, u128> = BTreeMap::new();
+    for coin in simulations.iter().filter_map(|s| s.fee.as_ref()).flatten() {
+        *fees.entry(&coin.denom).or_default() += coin.amount_u128();
+    }
+    write_json_array(&simulate_args.output, &simulations, None)?;
+
+    println!("\nSimulation Summary:");
+    println!("────────────────────");
+    println!("Transactions: {}", total);
+    println!("Simulated: {}", total - failed);
+    println!("Failed: {}", failed);
+    println!("Total gas limit: {}", gas_limit);
+    if !fees.is_empty() {
+        let fees: Vec<String> = fees.into_iter().map(|(denom, amount)| format!("{}{}", amount, denom)).collect();
+        println!("Total fees: {}", fees.join(", "));
+    }
+    println!("Output: {}", simulate_args.output);
+
+    if failed > 0 {
+        warn!(failed, "Simulations failed; their records carry an \"error\" field");
+    }
+    Ok(())
+}
+
+fn parse_hex_arg(name: &str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|e| anyhow::anyhow!("Invalid hex for {} '{}': {}", name, value, e))
+}
+
+fn module_address(module_args: ModuleAddressArgs) -> Result<()> {
+    let keys = module_args.key.iter().map(|key| parse_hex_arg("--key", key)).collect::<Result<Vec<_>>>()?;
+    let keys: Vec<&[u8]> = keys.iter().map(Vec::as_slice).collect();
+
+    for name in &module_args.names {
+        println!("{}: {}", name, address::encode(&module_args.prefix, &address::module(name, &keys))?);
+    }
+    Ok(())
+}
+
+fn derive_address(derive_args: DeriveAddressArgs) -> Result<()> {
+    let owner = address::decode(&derive_args.address)?;
+    let prefix = match derive_args.prefix {
+        Some(prefix) => prefix,
+        None => address::prefix(&derive_args.address)?,
+    };
+
+    let derived = derive_args.key.iter().try_fold(owner, |address, key| {
+        Ok::<_, anyhow::Error>(address::derive(&address, &parse_hex_arg("--key", key)?).to_vec())
+    })?;
+    println!("{}", address::encode(&prefix, &derived)?);
+    Ok(())
+}
+
+fn predict_instantiate2(instantiate2_args: Instantiate2Args) -> Result<()> {
+    let wallets = read_wallets(&instantiate2_args.wallets)?;
+    let checksum = parse_hex_arg("--checksum", &instantiate2_args.checksum)?;
+    let salts = instantiate2_args
+        .salt
+        .iter()
+        .map(|salt| parse_hex_arg("--salt", salt))
+        .collect::<Result<Vec<_>>>()?;
+    let init_msg = instantiate2_args.init_msg.as_deref().map(str::as_bytes);
+
+    let mut records = Vec::with_capacity(wallets.len() * salts.len());
+    for wallet in &wallets {
+        let creator = address::decode(&wallet.address)?;
+        let prefix = address::prefix(&wallet.address)?;
+        for salt in &salts {
+            let contract = address::instantiate2(&checksum, &creator, salt, init_msg)?;
+            records.push(serde_json::json!({
+                "creator": wallet.address,
+                "salt": hex::encode(salt),
+                "address": address::encode(&prefix, &contract)?,
+            }));
+        }
+    }
+    write_json_array(&instantiate2_args.output, &records, None)?;
+
+    println!("\nInstantiate2 Summary:");
+    println!("────────────────────");
+    println!("Creators: {}", wallets.len());
+    println!("Salts: {}", salts.len());
+    println!("Addresses: {}", records.len());
+    println!("Output: {}", instantiate2_args.output);
+    Ok(())
+}
+
+/// 20-byte EVM address of an ethsecp256k1 wallet.
+fn evm_address(wallet: &Wallet) -> Result<[u8; 20]> {
+    let evm_address = wallet
+        .evm_address
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("{} has no EVM address; use ethsecp256k1 wallets", wallet.address))?;
+    parse_hex_arg("evmAddress", evm_address)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid EVM address {}", evm_address))
+}
+
+/// Parse a hex value of at most 32 bytes, left-padding it to 32.
+fn parse_word_arg(name: &str, value: &str) -> Result<[u8; 32]> {
+    let digits = value.trim_start_matches("0x");
+    let bytes = parse_hex_arg(name, &format!("{:0>1$}", digits, digits.len() + digits.len() % 2))?;
+    if bytes.len() > 32 {
+        return Err(anyhow::anyhow!("{} '{}' is longer than 32 bytes", name, value));
+    }
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(word)
+}
+
+fn predict_create(create_args: CreateArgs) -> Result<()> {
+    let wallets = read_wallets(&create_args.wallets)?;
+
+    let mut records = Vec::with_capacity(wallets.len() * create_args.nonce.len());
+    for wallet in &wallets {
+        let deployer = evm_address(wallet)?;
+        for &nonce in &create_args.nonce {
+            records.push(serde_json::json!({
+                "deployer": format!("0x{}", hex::encode(deployer)),
+                "nonce": nonce,
+                "address": format!("0x{}", hex::encode(address::create(&deployer, nonce))),
+            }));
+        }
+    }
+    write_json_array(&create_args.output, &records, None)?;
+
+    println!("\nCREATE Summary:");
+    println!("────────────────────");
+    println!("Deployers: {}", wallets.len());
+    println!("Addresses: {}", records.len());
+    println!("Output: {}", create_args.output);
+    Ok(())
+}
+
+fn predict_create2(create2_args: Create2Args) -> Result<()> {
+    let wallets = read_wallets(&create2_args.wallets)?;
+    let init_code_hash: [u8; 32] = parse_hex_arg("--init-code-hash", &create2_args.init_code_hash)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("--init-code-hash must be 32 bytes"))?;
+    let salts = create2_args
+        .salt
+        .iter()
+        .map(|salt| parse_word_arg("--salt", salt))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut records = Vec::with_capacity(wallets.len() * salts.len());
+    for wallet in &wallets {
+        let deployer = evm_address(wallet)?;
+        for salt in &salts {
+            records.push(serde_json::json!({
+                "deployer": format!("0x{}", hex::encode(deployer)),
+                "salt": format!("0x{}", hex::encode(salt)),
+                "address": format!("0x{}", hex::encode(address::create2(&deployer, salt, &init_code_hash))),
+            }));
+        }
+    }
+    write_json_array(&create2_args.output, &records, None)?;
+
+    println!("\nCREATE2 Summary:");
+    println!("────────────────────");
+    println!("Deployers: {}", wallets.len());
+    println!("Salts: {}", salts.len());
+    println!("Addresses: {}", records.len());
+    println!("Output: {}", create2_args.output);
+    Ok(())
+}
+
+fn find_index(find_args: FindIndexArgs, quiet: bool) -> Result<()> {
+    let (target, key_type) = if find_args.address.starts_with("0x") {
+        let bytes = parse_hex_arg("EVM address", &find_args.address)?;
+        (bytes, KeyType::Ethsecp256k1)
+    } else {
+        (address::decode(&find_args.address)?, find_args.key_type)
+    };
+    let target: [u8; 20] = target
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow::anyhow!("Expected a 20-byte account address, got {} bytes", bytes.len()))?;
+
+    let path = find_args.path.unwrap_or_else(|| format!("m/44'/{}'/0'/0/{}", key_type.coin_type(), INDEX_PLACEHOLDER));
+
+    let mnemonic = parse_mnemonic(&find_args.mnemonic)?;
+    let seed = mnemonic.to_seed("");
+    let num_threads = if find_args.threads > 0 { find_args.threads } else { num_cpus::get() };
+    rayon::ThreadPoolBuilder::new().num_threads(num_threads).build_global()?;
+
+    info!(max_index = find_args.max_index, path = %path, key_type = ?key_type, threads = num_threads, "Searching indices");
+    let start_time = Instant::now();
+    let (found, _) = with_progress(Some(find_args.max_index + 1), quiet, |progress| {
+        wallet_generator::generator::find_index(&seed, &path, &target, &key_type, find_args.max_index, progress)
+    })?;
+    let found = found?;
+
+    let Some(index) = found else {
+        return Err(anyhow::anyhow!("{} not found in indices 0..={} of {}", find_args.address, find_args.max_index, path));
+    };
+    println!("\nFind Index Summary:");
+    println!("────────────────────");
+    println!("Address: {}", find_args.address);
+    println!("Index: {}", index);
+    println!("Derivation path: {}", path.replace(INDEX_PLACEHOLDER, &index.to_string()));
+    println!("Search time: {:.2}s", start_time.elapsed().as_secs_f64());
+    Ok(())
+}
+
+fn recover(recover_args: RecoverArgs, quiet: bool, permissions: &OutputPermissions) -> Result<()> {
+    permissions.check_directory(Path::new(&recover_args.output))?;
+    let mnemonic = parse_mnemonic(&recover_args.mnemonic)?;
+    let seed = mnemonic.to_seed("");
+    let client = LcdClient::new(&recover_args.node, Duration::from_secs(recover_args.timeout), recover_args.retries);
+    let config = RecoverConfig {
+        templates: &recover_args.paths,
+        prefix: &recover_args.prefix,
+        key_type: &recover_args.key_type,
+        gap_limit: recover_args.gap_limit,
+        max_accounts: recover_args.max_accounts,
+        concurrency: recover_args.concurrency,
+    };
+
+    info!(node = %recover_args.node, gap_limit = recover_args.gap_limit, paths = ?recover_args.paths, "Recovering accounts");
+    let (recovered, checked) = with_progress(None, quiet, |progress| recovery::recover(&client, &seed, &config, progress))?;
+    let recovered = recovered?;
+
+    write_json_array(&recover_args.output, &recovered, Some(permissions))?;
+
+    println!("\nRecovery Summary:");
+    println!("────────────────────");
+    println!("Addresses checked: {}", checked);
+    println!("Used accounts: {}", recovered.len());
+    for account in &recovered {
+        let balances: Vec<String> = account.balances.iter().map(ToString::to_string).collect();
+        println!("  {} {} {}", account.wallet.derivation_path, account.wallet.address, balances.join(","));
+    }
+    println!("Output: {}", recover_args.output);
+    Ok(())
+}
+
+/// Parse a secp256k1 public key given as base64 or hex, compressed or uncompressed.
+fn parse_public_key(value: &str) -> Result<secp256k1::PublicKey> {
+    let value = value.trim();
+    let hex_digits = value.trim_start_matches("0x");
+    let bytes = if matches!(hex_digits.len(), 66 | 130) && hex_digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        hex::decode(hex_digits)?
+    } else {
+        general_purpose::STANDARD.decode(value).map_err(|e| anyhow::anyhow!("Public key {} is neither hex nor base64: {}", value, e))?
+    };
+    secp256k1::PublicKey::from_slice(&bytes).map_err(|e| anyhow::anyhow!("Invalid public key {}: {}", value, e))
+}
+
+fn from_pubkey(pubkey_args: FromPubkeyArgs) -> Result<()> {
+    let encode = |public_key: &secp256k1::PublicKey| -> Result<serde_json::Value> {
+        let (address, evm_address, pubkey) =
+            wallet_generator::generator::encode_public_key(public_key, &pubkey_args.prefix, &pubkey_args.key_type, pubkey_args.encoding)?;
+        let mut record = serde_json::json!({ "address": address, "pubkey": pubkey });
+        if let Some(evm_address) = evm_address {
+            record["evmAddress"] = serde_json::json!(evm_address);
+        }
+        Ok(record)
+    };
+
+    let records = if let Some(xpub) = &pubkey_args.xpub {
+        let xpub = wallet_generator::derivation::parse_xpub(xpub).map_err(|e| anyhow::anyhow!("Invalid --xpub: {}", e))?;
+        let end = pubkey_args
+            .start_index
+            .checked_add(pubkey_args.count)
+            .filter(|end| *end <= 1 << 31)
+            .ok_or_else(|| anyhow::anyhow!("--start-index + --count must stay below 2^31 (non-hardened indices)"))?;
+        (pubkey_args.start_index..end)
+            .map(|index| {
+                let path = format!("0/{}", index);
+                let public_key = secp256k1::PublicKey::from_slice(&wallet_generator::derivation::derive_public_key(&xpub, &path)?)?;
+                let mut record = encode(&public_key)?;
+                record["derivationPath"] = serde_json::json!(path);
+                record["index"] = serde_json::json!(index);
+                Ok(record)
+            })
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        let contents = match pubkey_args.input.as_deref() {
+            None | Some("-") => std::io::read_to_string(std::io::stdin())?,
+            Some(path) => fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?,
+        };
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| encode(&parse_public_key(line)?))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    match &pubkey_args.output {
+        Some(output) => {
+            write_json_array(output, &records, None)?;
+            info!(addresses = records.len(), output = %output, "Wrote addresses");
+        }
+        None => {
+            for record in &records {
+                println!("{}", record);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn xpub(xpub_args: XpubArgs) -> Result<()> {
+    let mnemonic = parse_mnemonic(&xpub_args.mnemonic)?;
+    let seed = mnemonic.to_seed("");
+    let path = xpub_args
+        .path
+        .unwrap_or_else(|| format!("m/44'/{}'/{}'", xpub_args.key_type.coin_type(), xpub_args.account));
+
+    let (xpub, xprv) = wallet_generator::derivation::extended_keys(&seed, &path)?;
+    info!(path = %path, "Derived extended key");
+    println!("{}", xpub);
+    if xpub_args.private {
+        println!("{}", xprv);
+    }
+    Ok(())
+}
+
+fn derive(derive_args: DeriveArgs) -> Result<()> {
+    let seed = parse_mnemonic(&derive_args.mnemonic)?.to_seed("");
+    let defaults = Defaults { prefix: derive_args.prefix, key_type: derive_args.key_type };
+    let mut coprocess = Coprocess::new(&seed, defaults);
+
+    if derive_args.stdin {
+        let answered = coprocess.serve(std::io::stdin().lock(), std::io::stdout().lock())?;
+        info!(requests = answered, "Derivation requests answered");
+        return Ok(());
+    }
+    let request = DeriveRequest {
+        path: derive_args.path,
+        index: derive_args.index,
+        include_private_key: derive_args.include_private_key,
+        ..Default::default()
+    };
+    let answer = coprocess.answer(&request);
+    if let Some(error) = answer.get("error").and_then(|error| error.as_str()) {
+        return Err(anyhow::anyhow!("{}", error));
+    }
+    println!("{}", serde_json::to_string_pretty(&answer)?);
+    Ok(())
+}
+
+fn check_mnemonic(check_args: MnemonicCheckArgs) -> Result<()> {
+    let phrase = match check_args.mnemonic {
+        Some(phrase) => phrase,
+        None => std::io::read_to_string(std::io::stdin())?,
+    };
+    let problems = mnemonic::check(&phrase);
+
+    println!("\nMnemonic Check:");
+    println!("────────────────────");
+    println!("Words: {}", phrase.split_whitespace().count());
+    for problem in &problems {
+        match problem {
+            Problem::WordCount(count) => {
+                println!("Word count {} is not one of {:?}; a word may be missing or repeated", count, mnemonic::WORD_COUNTS)
+            }
+            Problem::UnknownWord { position, word, suggestions } if suggestions.is_empty() => {
+                println!("Word {} \"{}\" is not in the BIP-39 wordlist, and no entry is close", position, word)
+            }
+            Problem::UnknownWord { position, word, suggestions } => {
+                println!("Word {} \"{}\" is not in the BIP-39 wordlist; did you mean: {}", position, word, suggestions.join(", "))
+            }
+            Problem::Checksum { corrections } if corrections.is_empty() => {
+                println!("Checksum does not match, and no single typo or swap of neighbouring words fixes it")
+            }
+            Problem::Checksum { corrections } => {
+                println!("Checksum does not match; single changes that fix it:");
+                let words: Vec<&str> = phrase.split_whitespace().collect();
+                for correction in corrections {
+                    match correction {
+                        Correction::Replace { position, word } => {
+                            println!("  word {}: {} -> {}", position, words[position - 1], word)
+                        }
+                        Correction::Swap { position } => println!("  swap words {} and {}", position, position + 1),
+                    }
+                }
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(anyhow::anyhow!("Invalid mnemonic"));
+    }
+    println!("Valid");
+    Ok(())
+}
+
+fn vanity_estimate(estimate_args: VanityEstimateArgs) -> Result<()> {
+    let alphabet = if estimate_args.hex { Alphabet::Hex } else { Alphabet::Bech32 };
+    let pattern = Pattern::new(&estimate_args.pattern, alphabet, estimate_args.position, estimate_args.case_sensitive)?;
+    let key_type = if estimate_args.hex { KeyType::Ethsecp256k1 } else { estimate_args.key_type };
+
+    // Derive full wallets for a moment, as a search would; any seed derives at the same speed
+    let (rate, measured) = match estimate_args.rate {
+        Some(rate) if rate > 0.0 => (rate, None),
+        Some(_) => return Err(anyhow::anyhow!("--rate must be above 0")),
+        None => {
+            let num_threads = if estimate_args.threads > 0 { estimate_args.threads } else { num_cpus::get() };
+            rayon::ThreadPoolBuilder::new().num_threads(num_threads).build_global()?;
+            let config = DerivationConfig::new("cosmos", key_type.clone());
+            let seed = [0u8; 64];
+            let (mut derived, mut elapsed) = (0, Duration::ZERO);
+            while elapsed < CALIBRATION_TIME {
+                let burst = num_threads * PAUSE_CHUNK;
+                let burst_start = Instant::now();
+                generate_wallets_parallel(&seed, derived, burst, &config, PAUSE_CHUNK, Arc::new(AtomicUsize::new(0)))?;
+                elapsed += burst_start.elapsed();
+                derived += burst;
+            }
+            (derived as f64 / elapsed.as_secs_f64(), Some((elapsed, num_threads)))
+        }
+    };
+
+    let text = pattern.text();
+    let shown = match (alphabet, pattern.position()) {
+        (Alphabet::Bech32, Position::Prefix) => format!("<prefix>1{}...", text),
+        (Alphabet::Bech32, Position::Suffix) => format!("<prefix>1...{}", text),
+        (Alphabet::Bech32, Position::Contains) => format!("<prefix>1...{}...", text),
+        (Alphabet::Hex, Position::Prefix) => format!("0x{}...", text),
+        (Alphabet::Hex, Position::Suffix) => format!("0x...{}", text),
+        (Alphabet::Hex, Position::Contains) => format!("0x...{}...", text),
+    };
+    println!("\nVanity Estimate:");
+    println!("────────────────────");
+    println!("Pattern: {}", shown);
+    println!("Odds per address: 1 in {}", format_count(pattern.expected_attempts()));
+    match measured {
+        Some((elapsed, threads)) => println!(
+            "Throughput: {} addresses/sec ({:?}, measured over {:.2}s on {} threads)",
+            format_count(rate),
+            key_type,
+            elapsed.as_secs_f64(),
+            threads
+        ),
+        None => println!("Throughput: {} addresses/sec (given)", format_count(rate)),
+    }
+    println!("Expected: {} attempts, {}", format_count(pattern.expected_attempts()), format_eta(pattern.expected_attempts() / rate));
+    for confidence in [0.5, 0.9, 0.99] {
+        let attempts = pattern.attempts_for(confidence);
+        println!("{:.0}% chance: {} attempts, {}", confidence * 100.0, format_count(attempts), format_eta(attempts / rate));
+    }
+    Ok(())
+}
+
+fn selftest() -> Result<()> {
+    let outcomes = wallet_generator::selftest::run();
+
+    println!("\nSelf-test:");
+    println!("────────────────────");
+    for outcome in &outcomes {
+        let vector = outcome.vector;
+        let status = if outcome.passed() { "ok" } else { "FAILED" };
+        println!("{} {} {}: {}", status, vector.chain, vector.derivation_path, vector.address);
+        if let Some(error) = &outcome.error {
+            println!("  {}", error);
+        }
+        for mismatch in &outcome.mismatches {
+            println!("  {}: expected {}, got {}", mismatch.field, mismatch.expected, mismatch.actual);
+        }
+    }
+
+    let failed = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} of {} test vectors failed; do not trust generated wallets", failed, outcomes.len()));
+    }
+    println!("All {} test vectors match", outcomes.len());
+    Ok(())
+}
+
+fn query_wallets(query_args: QueryArgs) -> Result<()> {
+    let path = Path::new(&query_args.input);
+    let key = match (query_args.address, query_args.index, query_args.evm) {
+        (Some(address), _, _) => RecordKey::Address(address),
+        (None, Some(index), _) => RecordKey::Index(index),
+        (None, None, Some(evm)) => RecordKey::EvmAddress(evm),
+        (None, None, None) => unreachable!("clap requires --address, --index or --evm"),
+    };
+
+    if !query_args.no_index {
+        let large = fs::metadata(path)?.len() >= query::AUTO_INDEX_SIZE;
+        if query_args.build_index || (large && WalletIndex::open(path)?.is_none()) {
+            let _span = info_span!("build_index", index = %query::index_path(path).display()).entered();
+            let records = query::build_index(path)?;
+            info!(records, "Indexed records");
+        }
+    }
+
+    let found = query::find_records(path, &key, !query_args.no_index)?;
+    if found.is_empty() {
+        return Err(anyhow::anyhow!("No record matches {:?}", key));
+    }
+    for wallet in found {
+        println!("{}", serde_json::to_string_pretty(&wallet)?);
+    }
+    Ok(())
+}
+
+fn diff(diff_args: DiffArgs) -> Result<()> {
+    let report = merge::diff(&diff_args.left, &diff_args.right)?;
+    if let Some(output) = &diff_args.output {
+        if let Some(parent) = Path::new(output).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(output, serde_json::to_vec_pretty(&report)?)?;
+    }
+
+    println!("\nDiff Summary:");
+    println!("────────────────────");
+    println!("Matching: {}", report.matching);
+    println!("Only in {}: {}", diff_args.left, report.only_left.len());
+    println!("Only in {}: {}", diff_args.right, report.only_right.len());
+    println!("Mismatched keys: {}", report.mismatched.len());
+    for mismatch in report.mismatched.iter().take(10) {
+        println!("  {} ({})", mismatch.address, mismatch.fields.join(", "));
+    }
+    if let Some(output) = &diff_args.output {
+        println!("Report: {}", output);
+    }
+
+    if !report.is_empty() {
+        return Err(anyhow::anyhow!("Wallet files differ"));
+    }
+    Ok(())
+}
+
+fn verify_manifest(verify_args: VerifyManifestArgs) -> Result<()> {
+    let path = &verify_args.manifest;
+    let contents = fs::read(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let manifest_sha256 = hex::encode(Sha256::digest(&contents));
+    let manifest_intact = verify_args.sha256.as_ref().is_none_or(|expected| expected.eq_ignore_ascii_case(&manifest_sha256));
+    let manifest = Manifest::read(path)?;
+
+    // Files are hashed in parallel; multi-GB shards dominate the time
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let checks = manifest
+        .files
+        .par_iter()
+        .map(|part| part.check(dir, manifest.format))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut failed = 0;
+    for (part, check) in manifest.files.iter().zip(&checks) {
+        let differences = match check {
+            Some(found) => part.differences(found),
+            None => vec!["missing".to_string()],
+        };
+        if differences.is_empty() {
+            println!("{}: OK", part.path);
+        } else {
+            println!("{}: FAILED ({})", part.path, differences.join(", "));
+            failed += 1;
+        }
+    }
+    let listed: u64 = manifest.files.iter().map(|part| part.records).sum();
+
+    println!("\nManifest Verification Summary:");
+    println!("────────────────────");
+    println!("Manifest: {}", path.display());
+    println!("Manifest SHA-256: {}{}", manifest_sha256, if manifest_intact { "" } else { " (does not match --sha256)" });
+    if let Some(version) = &manifest.version {
+        println!("Generator version: {}", version);
+    }
+    println!("Files: {}", manifest.files.len());
+    println!("Files failed: {}", failed);
+    println!("Wallets: {}", group_digits(manifest.wallets as usize));
+
+    if !manifest_intact {
+        return Err(anyhow::anyhow!("The manifest does not match the expected SHA-256"));
+    }
+    if listed != manifest.wallets {
+        return Err(anyhow::anyhow!("The manifest lists {} wallets across its files but {} in total", listed, manifest.wallets));
+    }
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} of {} files failed verification", failed, manifest.files.len()));
+    }
+    Ok(())
+}
+
+fn merge(merge_args: MergeArgs, permissions: &OutputPermissions) -> Result<()> {
+    if !matches!(merge_args.output_format, OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Csv) {
+        return Err(anyhow::anyhow!("merge writes JSON, NDJSON or CSV; use convert for other formats"));
+    }
+
+    let mut writer = WalletWriter::new(create_output(&merge_args.output, WRITE_BUFFER, permissions)?, merge_args.output_format, None)?;
+    let (duplicates, coverage) = if merge_args.sorted {
+        let mut merged = SortedMerge::open(&merge_args.inputs)?;
+        for wallet in merged.by_ref() {
+            writer.write(&wallet?)?;
+        }
+        (merged.duplicates(), merged.coverage())
+    } else {
+        let merged = merge::merge(&merge_args.inputs)?;
+        for wallet in &merged.wallets {
+            writer.write(wallet)?;
+        }
+        (merged.duplicates, merged.coverage)
+    };
+    let records = writer.records();
+    writer.finish()?.finish()?;
+
+    println!("\nMerge Summary:");
+    println!("────────────────────");
+    println!("Input files: {}", merge_args.inputs.len());
+    println!("Wallets: {}", records);
+    println!("Duplicates removed: {}", duplicates);
+    if let Some(coverage) = coverage {
+        println!("Indices: {}-{}", coverage.first, coverage.last);
+        println!("Missing indices: {}", coverage.missing);
+    }
+    println!("Output: {} ({})", merge_args.output, merge_args.output_format.extension());
+
+    if let Some(coverage) = coverage.filter(|coverage| !coverage.is_contiguous()) {
+        if merge_args.contiguous {
+            return Err(anyhow::anyhow!(
+                "{} indices between {} and {} are missing from the inputs",
+                coverage.missing,
+                coverage.first,
+                coverage.last
+            ));
+        }
+        warn!(missing = coverage.missing, "Merged indices have gaps, so record N is not wallet N");
+    }
+    Ok(())
+}
+
+fn convert(convert_args: ConvertArgs, permissions: &OutputPermissions) -> Result<()> {
+    let to_stdout = convert_args.output == STDOUT;
+    let output_format = convert_args.output_format.unwrap_or(if to_stdout { OutputFormat::Ndjson } else { OutputFormat::Json });
+    if output_format == OutputFormat::Bin {
+        return Err(anyhow::anyhow!("convert writes text formats; binary files come from generation with --output-format bin"));
+    }
+
+    let reader = BinaryReader::open(&convert_args.input)?;
+    let config = reader.header().config.clone();
+    let mut writer = RecordWriter::new(create_output(&convert_args.output, WRITE_BUFFER, permissions)?, output_format, None, &config)?;
+    for wallet in reader {
+        writer.write(&wallet?)?;
+    }
+    let records = writer.records();
+    writer.finish()?.finish()?;
+
+    // Records streamed to stdout push the summary to stderr
+    let console = Console { quiet: false, to_stdout };
+    say!(console, "\nConvert Summary:");
+    say!(console, "────────────────────");
+    say!(console, "Wallets: {}", records);
+    say!(console, "Derivation path: {}", config.path_template());
+    say!(console, "Output: {} ({})", convert_args.output, output_format.extension());
+    Ok(())
+}
+
+fn decrypt(decrypt_args: DecryptArgs, permissions: &OutputPermissions) -> Result<()> {
+    let to_stdout = decrypt_args.output == STDOUT;
+    let output_format = decrypt_args.output_format.unwrap_or(if to_stdout { OutputFormat::Ndjson } else { OutputFormat::Json });
+    if !matches!(output_format, OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Csv) {
+        return Err(anyhow::anyhow!("decrypt writes JSON, NDJSON or CSV"));
+    }
+
+    let passphrase = key_passphrase(decrypt_args.key_passphrase, false)?;
+    let mut decryptor = KeyDecryptor::new(&passphrase);
+    let mut writer = WalletWriter::new(create_output(&decrypt_args.output, WRITE_BUFFER, permissions)?, output_format, None)?;
+    for wallet in WalletReader::open(&decrypt_args.input)? {
+        let mut wallet = wallet?;
+        decryptor.decrypt_wallet(&mut wallet)?;
+        writer.write(&wallet)?;
+    }
+    let records = writer.records();
+    writer.finish()?.finish()?;
+
+    // Records streamed to stdout push the summary to stderr
+    let console = Console { quiet: false, to_stdout };
+    say!(console, "\nDecrypt Summary:");
+    say!(console, "────────────────────");
+    say!(console, "Wallets: {}", records);
+    say!(console, "Output: {} ({})", decrypt_args.output, output_format.extension());
+    Ok(())
+}
+
+fn export_extension(export_args: ExportExtensionArgs) -> Result<()> {
+    let seed = export_args.mnemonic.as_deref().map(|phrase| parse_mnemonic(phrase).map(|mnemonic| mnemonic.to_seed(""))).transpose()?;
+    let mnemonic_ref = match (&seed, export_args.mnemonic_ref) {
+        (Some(seed), _) => hex::encode(wallet_generator::derivation::master_fingerprint(seed)?),
+        (None, Some(label)) => label,
+        (None, None) => unreachable!("clap requires --mnemonic or --mnemonic-ref"),
+    };
+
+    let output_dir = Path::new(&export_args.output_dir);
+    fs::create_dir_all(output_dir).map_err(|e| anyhow::anyhow!("Failed to create {}: {}", output_dir.display(), e))?;
+    let mut written = 0;
+    for (record, wallet) in wallet_file::stream_wallets(&export_args.input)?.enumerate() {
+        let wallet = wallet?;
+        // Bundles without keys cannot show that the reference is right; the mnemonic can
+        if let Some(seed) = seed.as_ref().filter(|_| !key_cipher::is_encrypted(&wallet.private_key)) {
+            let derived = wallet_generator::derivation::derive_private_key(seed, &wallet.derivation_path)?;
+            if wallet_private_key(&wallet)? != derived {
+                return Err(anyhow::anyhow!("Record {}: {} was not derived from the given mnemonic", record + 1, wallet.address));
+            }
+        }
+        let name = format!("{}-{}", export_args.name_prefix, wallet.index);
+        let bundle = extension::bundle(&wallet, name, &mnemonic_ref).map_err(|e| anyhow::anyhow!("Record {}: {}", record + 1, e))?;
+        let path = output_dir.join(format!("{}.json", wallet.address));
+        fs::write(&path, serde_json::to_string_pretty(&bundle)? + "\n").map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+        written += 1;
+    }
+
+    println!("\nExport Summary:");
+    println!("────────────────────");
+    println!("Bundles: {}", written);
+    println!("Mnemonic reference: {}", mnemonic_ref);
+    println!("Output: {}", output_dir.display());
+    Ok(())
+}
+
+fn rederive(rederive_args: RederiveArgs, file_config: &ConfigFile, permissions: &OutputPermissions) -> Result<()> {
+    let to_stdout = rederive_args.output == STDOUT;
+    let output_format = rederive_args.output_format.unwrap_or(if to_stdout { OutputFormat::Ndjson } else { OutputFormat::Json });
+    if output_format == OutputFormat::Bin {
+        return Err(anyhow::anyhow!("Binary files rebuild paths from the index, which re-derived wallets keep from another chain; use a text format"));
+    }
+
+    let mut config = match (&rederive_args.chain, rederive_args.bitcoin) {
+        (Some(name), _) => file_config.chain(name)?,
+        (None, Some(_)) => file_config.chain("bitcoin")?,
+        (None, None) => DerivationConfig::new(&rederive_args.prefix, rederive_args.key_type.clone()),
+    };
+    if let Some(address) = rederive_args.bitcoin {
+        if !matches!(config.family, ChainFamily::Bitcoin(_)) {
+            return Err(anyhow::anyhow!("--bitcoin needs a Bitcoin --chain: bitcoin, bitcoin-testnet or bitcoin-regtest"));
+        }
+        config.family = ChainFamily::Bitcoin(address);
+    }
+    if config.family == ChainFamily::Solana {
+        return Err(anyhow::anyhow!("rederive carries secp256k1 keys to other chains; Solana keys come from the mnemonic by SLIP-0010"));
+    }
+    if config.family != ChainFamily::Cosmos && (rederive_args.encoding.is_some() || rederive_args.pubkey_format == PubkeyFormat::SdkJson) {
+        return Err(anyhow::anyhow!("--encoding and --pubkey-format sdk-json do not apply to Bitcoin or Solana wallets"));
+    }
+    if let Some(encoding) = rederive_args.encoding {
+        config.encoding = encoding;
+    }
+    config.private_key_format = rederive_args.privkey_format;
+    config.pubkey_format = rederive_args.pubkey_format;
+    config.include_uncompressed = rederive_args.include_uncompressed;
+    config.dual_address = rederive_args.dual_address;
+    if config.dual_address && !matches!(config.key_type, KeyType::Ethsecp256k1) {
+        return Err(anyhow::anyhow!("--dual-address needs ethsecp256k1 keys, whose address is not the SHA256/RIPEMD160 one"));
+    }
+
+    let wallets: Box<dyn Iterator<Item = Result<Wallet>>> = match binary::is_binary(&rederive_args.input)? {
+        true => Box::new(BinaryReader::open(&rederive_args.input)?),
+        false => Box::new(WalletReader::open(&rederive_args.input)?),
+    };
+    let sdk_pubkey_type = (config.pubkey_format == PubkeyFormat::SdkJson).then(|| config.key_type.pubkey_type_url());
+    let mut writer = RecordWriter::new(create_output(&rederive_args.output, WRITE_BUFFER, permissions)?, output_format, sdk_pubkey_type, &config)?;
+    for (record, wallet) in wallets.enumerate() {
+        let wallet = wallet?;
+        let rederived = rederive::rederive(&wallet, &config).map_err(|e| anyhow::anyhow!("Record {}: {}", record + 1, e))?;
+        writer.write(&rederived)?;
+    }
+    let records = writer.records();
+    writer.finish()?.finish()?;
+
+    // Records streamed to stdout push the summary to stderr
+    let console = Console { quiet: false, to_stdout };
+    say!(console, "\nRederive Summary:");
+    say!(console, "────────────────────");
+    say!(console, "Wallets: {}", records);
+    match config.family {
+        ChainFamily::Bitcoin(address) => say!(console, "Addresses: {:?} on {}", address, config.prefix),
+        _ => say!(console, "Addresses: {} ({:?}, {:?})", config.prefix, config.key_type, config.encoding),
+    }
+    say!(console, "Output: {} ({})", rederive_args.output, output_format.extension());
+    Ok(())
+}
+
+/// Print duplicate addresses and where they came from, then fail.
+fn report_duplicates(duplicates: &[Duplicate]) -> Result<()> {
+    eprintln!("\nDuplicate addresses: {}", duplicates.len());
+    for duplicate in duplicates.iter().take(20) {
+        eprintln!("  {}", duplicate.address);
+        for occurrence in &duplicate.occurrences {
+            eprintln!("    {} record {} ({})", occurrence.source, occurrence.record, occurrence.derivation_path);
+        }
+    }
+    if duplicates.len() > 20 {
+        eprintln!("  ... and {} more", duplicates.len() - 20);
+    }
+    Err(anyhow::anyhow!(
+        "Found {} duplicate addresses; this indicates a derivation bug or overlapping index ranges",
+        duplicates.len()
+    ))
+}
+
+fn dupes(dupes_args: DupesArgs) -> Result<()> {
+    let mut checker = DuplicateChecker::new();
+    for input in &dupes_args.inputs {
+        checker.add_file(input)?;
+    }
+    let unique = checker.unique();
+    let duplicates = checker.finish();
+    if let Some(output) = &dupes_args.output {
+        if let Some(parent) = Path::new(output).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(output, serde_json::to_vec_pretty(&duplicates)?)?;
+    }
+
+    println!("\nDuplicate Check Summary:");
+    println!("────────────────────");
+    println!("Input files: {}", dupes_args.inputs.len());
+    println!("Unique addresses: {}", unique);
+    println!("Duplicated addresses: {}", duplicates.len());
+    if let Some(output) = &dupes_args.output {
+        println!("Report: {}", output);
+    }
+
+    if !duplicates.is_empty() {
+        return report_duplicates(&duplicates);
+    }
+    Ok(())
+}
+
+fn multisig(multisig_args: MultisigArgs) -> Result<()> {
+    let mut pubkeys = multisig_args
+        .pubkeys
+        .iter()
+        .map(|pubkey| {
+            let bytes = general_purpose::STANDARD.decode(pubkey).map_err(|e| anyhow::anyhow!("Invalid base64 pubkey {}: {}", pubkey, e))?;
+            let key = secp256k1::PublicKey::from_slice(&bytes).map_err(|e| anyhow::anyhow!("Invalid pubkey {}: {}", pubkey, e))?;
+            Ok(key.serialize())
+        })
+        .collect::<Result<Vec<[u8; 33]>>>()?;
+    if !multisig_args.no_sort {
+        pubkeys.sort_by_cached_key(|pubkey| {
+            let key = secp256k1::PublicKey::from_slice(pubkey).expect("validated above");
+            wallet_generator::generator::public_key_address(&key, &KeyType::Secp256k1)
+        });
+    }
+
+    let multisig_address = address::legacy_multisig(multisig_args.threshold, &pubkeys)?;
+    let public_keys: Vec<serde_json::Value> = pubkeys
+        .iter()
+        .map(|pubkey| serde_json::json!({ "@type": KeyType::Secp256k1.pubkey_type_url(), "key": general_purpose::STANDARD.encode(pubkey) }))
+        .collect();
+    let pubkey_json = serde_json::json!({
+        "@type": "/cosmos.crypto.multisig.LegacyAminoPubKey",
+        "threshold": multisig_args.threshold,
+        "public_keys": public_keys,
+    });
+
+    println!("Address: {}", address::encode(&multisig_args.prefix, &multisig_address)?);
+    println!("Threshold: {} of {}", multisig_args.threshold, pubkeys.len());
+    println!("Public key: {}", pubkey_json);
+    Ok(())
+}
+
+fn airdrop_merkle(merkle_args: AirdropMerkleArgs) -> Result<()> {
+    let entries = RecordReader::<AirdropEntry>::open(&merkle_args.input)?.collect::<Result<Vec<_>>>()?;
+    let format = match merkle_args.format {
+        MerkleFormatArg::Cosmwasm => LeafFormat::CosmWasm,
+        MerkleFormatArg::Evm => LeafFormat::Evm,
+    };
+    let airdrop = merkle::build_airdrop(&entries, format, merkle_args.amount)?;
+
+    if let Some(parent) = Path::new(&merkle_args.output).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&merkle_args.output, serde_json::to_vec_pretty(&airdrop)?)?;
+
+    println!("\nAirdrop Summary:");
+    println!("────────────────────");
+    println!("Recipients: {}", airdrop.claims.len());
+    println!("Total amount: {}", airdrop.total_amount);
+    println!("Merkle root: {}", airdrop.root);
+    println!("Output: {}", merkle_args.output);
+    Ok(())
+}
+
+fn genesis_add_accounts(genesis_args: GenesisAddAccountsArgs) -> Result<()> {
+    let wallets = read_wallets(&genesis_args.wallets)?;
+    let addresses: Vec<&str> = wallets.iter().map(|w| w.address.as_str()).collect();
+
+    let mut genesis: serde_json::Value = serde_json::from_str(&fs::read_to_string(&genesis_args.genesis)?)
+        .map_err(|e| anyhow::anyhow!("Invalid genesis file {}: {}", genesis_args.genesis, e))?;
+    let account_type = match genesis_args.account_type {
+        GenesisAccountTypeArg::Base => GenesisAccountType::Base,
+        GenesisAccountTypeArg::Eth => GenesisAccountType::Eth,
+    };
+    genesis::add_accounts(&mut genesis, &addresses, &genesis_args.amount, account_type)?;
+
+    let output = genesis_args.output.as_deref().unwrap_or(&genesis_args.genesis);
+    fs::write(output, serde_json::to_vec_pretty(&genesis)?)?;
+
+    let amount: Vec<String> = genesis_args.amount.iter().map(ToString::to_string).collect();
+    println!("\nGenesis Summary:");
+    println!("────────────────────");
+    println!("Accounts added: {}", addresses.len());
+    println!("Balance per account: {}", amount.join(","));
+    println!("Output: {}", output);
+    Ok(())
+}
+
+fn broadcast(broadcast_args: BroadcastArgs, quiet: bool) -> Result<()> {
+    let txs = load_signed_txs(&broadcast_args.input)?;
+    let total = txs.len();
+    let client = RpcClient::new(&broadcast_args.node, Duration::from_secs(broadcast_args.timeout), broadcast_args.retries);
+    let mode = match broadcast_args.mode {
+        BroadcastModeArg::Async => BroadcastMode::Async,
+        BroadcastModeArg::Sync => BroadcastMode::Sync,
+        BroadcastModeArg::Commit => BroadcastMode::Commit,
+    };
+
+    info!(transactions = total, node = %broadcast_args.node, concurrency = broadcast_args.concurrency, "Broadcasting");
+    let start = Instant::now();
+    let (outcomes, _) =
+        with_progress(Some(total as u64), quiet, |progress| broadcaster::broadcast_all(&client, &txs, mode, broadcast_args.concurrency, progress))?;
+    let outcomes = outcomes?;
+    let duration = start.elapsed();
+
+    let accepted = outcomes.iter().filter(|o| o.is_accepted()).count();
+    let rejected = outcomes.iter().filter(|o| o.result.is_some() && !o.is_accepted()).count();
+    write_json_array(&broadcast_args.output, &outcomes, None)?;
+
+    println!("\nBroadcast Summary:");
+    println!("────────────────────");
+    println!("Transactions: {}", total);
+    println!("Accepted: {}", accepted);
+    println!("Rejected: {}", rejected);
+    println!("Failed or skipped: {}", total - accepted - rejected);
+    println!("Time taken: {:.2?}", duration);
+    println!("Rate: {:.0} tx/sec", total as f64 / duration.as_secs_f64());
+    println!("Output: {}", broadcast_args.output);
+    Ok(())
+}
+
+fn read_message_data(message: &MessageData) -> Result<Vec<u8>> {
+    match (&message.data, &message.data_file) {
+        (Some(data), _) => Ok(data.as_bytes().to_vec()),
+        (None, Some(path)) => fs::read(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e)),
+        (None, None) => unreachable!("clap requires --data or --data-file"),
+    }
+}
+
+fn parse_typed_data(data: &[u8]) -> Result<TypedData> {
+    serde_json::from_slice(data).map_err(|e| anyhow::anyhow!("Invalid EIP-712 typed data: {}", e))
+}
+
+fn sign_message(message_args: SignMessageArgs) -> Result<()> {
+    let wallets = read_wallets(&message_args.wallets)?;
+    let signer = message_args.signer.as_str();
+    let wallet = wallets
+        .iter()
+        .find(|w| w.address == signer || w.evm_address.as_deref().is_some_and(|evm| evm.eq_ignore_ascii_case(signer)))
+        .ok_or_else(|| anyhow::anyhow!("No wallet for signer {}", signer))?;
+    let key_type = message_args.key_type.unwrap_or_else(|| wallet_key_type(wallet));
+    let private_key = wallet_private_key(wallet)?;
+
+    let data = read_message_data(&message_args.message)?;
+    let json = match message_args.scheme {
+        MessageScheme::Adr36 => {
+            serde_json::to_string_pretty(&message::sign_arbitrary(&private_key, &key_type, &wallet.address, &data)?)?
+        }
+        MessageScheme::Eip191 | MessageScheme::Eip712 => {
+            if !matches!(key_type, KeyType::Ethsecp256k1) {
+                return Err(anyhow::anyhow!("Ethereum signatures require an ethsecp256k1 wallet"));
+            }
+            let signature = match message_args.scheme {
+                MessageScheme::Eip712 => eip712::sign_typed_data(&private_key, &parse_typed_data(&data)?)?,
+                _ => message::personal_sign(&private_key, &data)?,
+            };
+            serde_json::to_string_pretty(&signature)?
+        }
+    };
+
+    match &message_args.output {
+        Some(path) => {
+            fs::write(path, json)?;
+            info!(output = %path, "Signature written");
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+fn verify_message(message_args: VerifyMessageArgs) -> Result<()> {
+    let data = read_message_data(&message_args.message)?;
+    let read_signature = || {
+        fs::read_to_string(&message_args.signature).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", message_args.signature, e))
+    };
+
+    let valid = match message_args.scheme {
+        MessageScheme::Adr36 => {
+            let signature: StdSignature = serde_json::from_str(&read_signature()?)
+                .map_err(|e| anyhow::anyhow!("Invalid signature file {}: {}", message_args.signature, e))?;
+            message::verify_arbitrary(&message_args.signer, &data, &signature)?
+        }
+        MessageScheme::Eip191 | MessageScheme::Eip712 => {
+            let signature = if message_args.signature.starts_with("0x") {
+                message_args.signature.clone()
+            } else {
+                let signature: PersonalSignature = serde_json::from_str(&read_signature()?)
+                    .map_err(|e| anyhow::anyhow!("Invalid signature file {}: {}", message_args.signature, e))?;
+                signature.signature
+            };
+            let recovered = match message_args.scheme {
+                MessageScheme::Eip712 => eip712::recover_typed_data(&parse_typed_data(&data)?, &signature)?,
+                _ => message::recover_personal_sign(&data, &signature)?,
+            };
+            recovered == message::signer_address_bytes(&message_args.signer)?.as_slice()
+        }
+    };
 
-    // Validate count
-    const MAX_WALLETS: usize = 1_000_000_000;
-    if args.count > MAX_WALLETS {
-        return Err(anyhow::anyhow!("Too many wallets requested. Maximum is {} billion", MAX_WALLETS / 1_000_000_000));
+    if valid {
+        println!("Valid signature from {}", message_args.signer);
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Invalid signature for {}", message_args.signer))
+    }
+}
+
+#[cfg(feature = "server")]
+fn serve(serve_args: wallet_generator::cli::ServeArgs) -> Result<()> {
+    use wallet_generator::server::{self, ServerConfig};
+
+    let listen: std::net::SocketAddr = serve_args.listen.parse()
+        .map_err(|e| anyhow::anyhow!("Invalid listen address {}: {}", serve_args.listen, e))?;
+    if serve_args.auth_token.is_none() && !listen.ip().is_loopback() {
+        warn!(%listen, "Listening without --auth-token; anyone who can reach it can derive keys");
+    }
+
+    let config = ServerConfig {
+        listen,
+        auth_token: serve_args.auth_token,
+        max_job_size: serve_args.max_job_size,
+        max_jobs: serve_args.max_jobs,
+        max_pending_jobs: serve_args.max_pending_jobs,
+        job_ttl: std::time::Duration::from_secs(serve_args.job_ttl),
+    };
+
+    info!("Serving wallet API on http://{}", listen);
+    tokio::runtime::Runtime::new()?.block_on(server::serve(config))?;
+    Ok(())
+}
+
+#[cfg(feature = "grpc")]
+fn serve_grpc(grpc_args: wallet_generator::cli::GrpcArgs) -> Result<()> {
+    use wallet_generator::grpc::{self, GrpcConfig};
+
+    let listen: std::net::SocketAddr = grpc_args.listen.parse()
+        .map_err(|e| anyhow::anyhow!("Invalid listen address {}: {}", grpc_args.listen, e))?;
+    if grpc_args.auth_token.is_none() && !listen.ip().is_loopback() {
+        warn!(%listen, "Listening without --auth-token; anyone who can reach it can derive keys");
+    }
+
+    let config = GrpcConfig {
+        listen,
+        auth_token: grpc_args.auth_token,
+        max_stream_size: grpc_args.max_stream_size,
+    };
+
+    info!("Serving gRPC wallet API on {}", listen);
+    tokio::runtime::Runtime::new()?.block_on(grpc::serve(config))?;
+    Ok(())
+}
+
+/// Wallets a worker generates between pause and abort checks, and the largest default work item.
+const PAUSE_CHUNK: usize = 1_000;
+
+/// Smallest default work item: each one starts by deriving its whole path from the seed.
+const MIN_CHUNK: usize = 100;
+
+/// Work items per thread in a segment by default, so threads that finish early take over the rest.
+const CHUNKS_PER_THREAD: usize = 8;
+
+/// Work items started per second at most under --rate-limit, by default.
+const RATE_LIMITED_ITEMS_PER_SEC: usize = 10;
+
+/// Most wallets generated as one segment; the writer starts on a segment while the next is generated.
+const SEGMENT_SIZE: usize = 100_000;
+
+/// Generated segments queued for the writer.
+const PIPELINE_DEPTH: usize = 2;
+
+/// Segments held at once: the queued ones, the one being generated and the one being written.
+const PIPELINE_SLOTS: usize = PIPELINE_DEPTH + 2;
+
+/// How long `--dry-run` generates wallets to measure throughput.
+const CALIBRATION_TIME: Duration = Duration::from_secs(1);
+
+/// Size and hex SHA-256 of output written as a byte stream.
+type OutputDigest = (u64, String);
+
+/// Generation and write times, the output digest, duplicates found and wallets generated.
+type RunOutcome = (Duration, Duration, Option<OutputDigest>, Vec<Duplicate>, usize);
+
+/// Where a generation run writes its wallets.
+enum Destination {
+    Stream(RecordWriter<DigestWriter<Box<dyn OutputSink>>>),
+    Mapped(MappedCsvWriter),
+    Rotating(Box<RotatingOutput>),
+    #[cfg(feature = "postgres")]
+    Database(wallet_generator::pg::PostgresOutput),
+}
+
+impl Destination {
+    /// Write a segment of consecutive batches, counting the records as written.
+    fn write(&mut self, batches: &[Vec<Wallet>], state: &RunState) -> Result<()> {
+        let mut wallets = batches.iter().flatten().inspect(|_| state.record_written());
+        match self {
+            Destination::Stream(writer) => wallets.try_for_each(|wallet| writer.write(wallet)),
+            Destination::Mapped(writer) => {
+                writer.write(batches)?;
+                state.records_written(batches.iter().map(Vec::len).sum());
+                Ok(())
+            }
+            Destination::Rotating(output) => wallets.try_for_each(|wallet| output.write(wallet)),
+            #[cfg(feature = "postgres")]
+            Destination::Database(database) => database.write(wallets).map(drop),
+        }
+    }
+
+    /// Bytes written so far, across all files of a rotated output; `None` for databases.
+    fn bytes(&self) -> Option<u64> {
+        match self {
+            Destination::Stream(writer) => Some(writer.get_ref().bytes()),
+            Destination::Mapped(writer) => Some(writer.bytes()),
+            Destination::Rotating(output) => Some(output.bytes()),
+            #[cfg(feature = "postgres")]
+            Destination::Database(_) => None,
+        }
+    }
+
+    /// Close the output, returning the digest of a byte stream, or of the manifest and the total
+    /// size of rotated files.
+    fn finish(self) -> Result<Option<OutputDigest>> {
+        match self {
+            Destination::Stream(writer) => {
+                let output = writer.finish()?;
+                let digest = (output.bytes(), output.sha256());
+                output.into_inner().finish()?;
+                Ok(Some(digest))
+            }
+            Destination::Mapped(writer) => Ok(Some(writer.finish()?)),
+            Destination::Rotating(output) => Ok(Some(output.finish()?)),
+            #[cfg(feature = "postgres")]
+            Destination::Database(_) => Ok(None),
+        }
+    }
+}
+
+/// Writes generated records as a byte stream in any [`OutputFormat`].
+enum RecordWriter<W: std::io::Write> {
+    Wallets(WalletWriter<W>),
+    #[cfg(feature = "arrow")]
+    Arrow(Box<ArrowIpcWriter<W>>),
+}
+
+impl<W: std::io::Write> RecordWriter<W> {
+    /// Binary output records the derivation settings in its header.
+    fn new(writer: W, format: OutputFormat, sdk_pubkey_type: Option<&'static str>, config: &DerivationConfig) -> Result<Self> {
+        Ok(match format {
+            OutputFormat::Bin => RecordWriter::Wallets(WalletWriter::binary(writer, config)?),
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow => RecordWriter::Arrow(Box::new(ArrowIpcWriter::new(writer)?)),
+            _ => RecordWriter::Wallets(WalletWriter::new(writer, format, sdk_pubkey_type)?),
+        })
+    }
+
+    fn write(&mut self, wallet: &Wallet) -> Result<()> {
+        match self {
+            RecordWriter::Wallets(writer) => writer.write(wallet),
+            #[cfg(feature = "arrow")]
+            RecordWriter::Arrow(writer) => writer.write(wallet),
+        }
+    }
+
+    fn records(&self) -> usize {
+        match self {
+            RecordWriter::Wallets(writer) => writer.records(),
+            #[cfg(feature = "arrow")]
+            RecordWriter::Arrow(writer) => writer.records(),
+        }
+    }
+
+    fn get_ref(&self) -> &W {
+        match self {
+            RecordWriter::Wallets(writer) => writer.get_ref(),
+            #[cfg(feature = "arrow")]
+            RecordWriter::Arrow(writer) => writer.get_ref(),
+        }
+    }
+
+    fn finish(self) -> Result<W> {
+        match self {
+            RecordWriter::Wallets(writer) => writer.finish(),
+            #[cfg(feature = "arrow")]
+            RecordWriter::Arrow(writer) => writer.finish(),
+        }
+    }
+}
+
+type FileWriter = RecordWriter<DigestWriter<Box<dyn OutputSink>>>;
+
+/// Writes records to numbered files, each a complete document, rolling to the next once one is
+/// full, and lists them in a manifest.
+struct RotatingOutput {
+    path: String,
+    rotation: Rotation,
+    format: OutputFormat,
+    sdk_pubkey_type: Option<&'static str>,
+    config: DerivationConfig,
+    buffer_size: usize,
+    permissions: OutputPermissions,
+    /// The open file and the index of its first wallet
+    current: Option<(FileWriter, u64)>,
+    /// Widest record so far, to tell whether the next one fits under a size limit
+    largest: u64,
+    manifest: Manifest,
+}
+
+impl RotatingOutput {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        path: &str,
+        rotation: Rotation,
+        format: OutputFormat,
+        sdk_pubkey_type: Option<&'static str>,
+        config: &DerivationConfig,
+        parameters: &RunParameters,
+        buffer_size: usize,
+        permissions: OutputPermissions,
+    ) -> Self {
+        let mut manifest = Manifest::new(format);
+        manifest.version = Some(env!("CARGO_PKG_VERSION").to_string());
+        manifest.parameters = Some(parameters.clone());
+        Self {
+            path: path.to_string(),
+            rotation,
+            format,
+            sdk_pubkey_type,
+            config: config.clone(),
+            buffer_size,
+            permissions,
+            current: None,
+            largest: 0,
+            manifest,
+        }
+    }
+
+    fn write(&mut self, wallet: &Wallet) -> Result<()> {
+        if let Some((writer, _)) = &self.current {
+            if self.rotation.is_full(writer.records() as u64, writer.get_ref().bytes(), self.largest) {
+                self.close()?;
+            }
+        }
+        let (writer, _) = match &mut self.current {
+            Some(current) => current,
+            None => self.current.insert((self.open()?, wallet.index)),
+        };
+        let before = writer.get_ref().bytes();
+        writer.write(wallet)?;
+        self.largest = self.largest.max(writer.get_ref().bytes() - before);
+        Ok(())
+    }
+
+    /// Bytes in the closed files and the open one.
+    fn bytes(&self) -> u64 {
+        self.manifest.bytes() + self.current.as_ref().map_or(0, |(writer, _)| writer.get_ref().bytes())
+    }
+
+    /// Create the next numbered file.
+    fn open(&self) -> Result<FileWriter> {
+        let path = rotate::part_path(&self.path, self.manifest.files.len() + 1);
+        let sink = create_output(&path.to_string_lossy(), self.buffer_size, &self.permissions)?;
+        RecordWriter::new(DigestWriter::new(sink), self.format, self.sdk_pubkey_type, &self.config)
+    }
+
+    /// Close the open file, if any, and add it to the manifest.
+    fn close(&mut self) -> Result<()> {
+        let Some((writer, first_index)) = self.current.take() else {
+            return Ok(());
+        };
+        let records = writer.records() as u64;
+        let output = writer.finish()?;
+        let (bytes, sha256) = (output.bytes(), output.sha256());
+        output.into_inner().finish()?;
+        let path = rotate::part_path(&self.path, self.manifest.files.len() + 1);
+        let path = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        self.manifest.push(ManifestPart { path, records, first_index, bytes, sha256 });
+        Ok(())
+    }
+
+    /// Close the last file and write the manifest, returning the total size of the files and the
+    /// manifest's SHA-256. An empty run still leaves one (empty) file.
+    fn finish(mut self) -> Result<OutputDigest> {
+        if self.manifest.files.is_empty() && self.current.is_none() {
+            self.current = Some((self.open()?, 0));
+        }
+        self.close()?;
+        let sha256 = self.manifest.write(rotate::manifest_path(&self.path))?;
+        Ok((self.manifest.bytes(), sha256))
+    }
+}
+
+/// Wallets per work item of a segment of `count` wallets; under a rate limit, small enough that
+/// workers wait briefly and often rather than long and rarely.
+fn default_chunk_size(count: usize, num_threads: usize, rate_limit: Option<u64>) -> usize {
+    let chunk_size = (count / (num_threads * CHUNKS_PER_THREAD)).clamp(MIN_CHUNK, PAUSE_CHUNK);
+    match rate_limit {
+        Some(rate) => chunk_size.min((rate as usize).div_ceil(RATE_LIMITED_ITEMS_PER_SEC)),
+        None => chunk_size,
+    }
+}
+
+/// Generate `count` wallets from `start` across the rayon pool as work items of `chunk_size`
+/// wallets, returned in index order. Idle threads steal the items left, so no core waits on a
+/// slow one near the end of a segment, and a paused or aborted run stops after the items under
+/// way. With a filter, workers keep only the wallets it matches, and with a cipher they encrypt
+/// the keys of those they keep. Under a rate limit, each item waits for its share of the budget.
+/// Also returns how many wallets from `start` were generated: fewer than `count` once a time
+/// budget runs out, as items are no longer started.
+#[allow(clippy::too_many_arguments)]
+fn generate_segment(
+    seed: &[u8],
+    start: usize,
+    count: usize,
+    chunk_size: usize,
+    config: &DerivationConfig,
+    filter: Option<&WalletFilter>,
+    cipher: Option<&KeyCipher>,
+    state: &RunState,
+) -> Result<(Vec<Vec<Wallet>>, usize), GeneratorError> {
+    let chunks = (0..count.div_ceil(chunk_size))
+        .into_par_iter()
+        .map(|chunk| {
+            let offset = chunk * chunk_size;
+            let wallets = chunk_size.min(count - offset);
+            if !state.proceed() || !state.throttle(wallets) || !state.within_deadline() {
+                return Ok(None);
+            }
+            let worker = rayon::current_thread_index().unwrap_or(0);
+            let mut wallets = generate_wallets_batch(seed, start + offset, wallets, config, state.thread_counter(worker))?;
+            if let Some(filter) = filter {
+                wallets.retain(|wallet| filter.matches(wallet));
+                state.records_matched(wallets.len());
+            }
+            if let Some(cipher) = cipher {
+                wallets.iter_mut().for_each(|wallet| cipher.encrypt_wallet(wallet));
+            }
+            Ok(Some(wallets))
+        })
+        .collect::<Result<Vec<_>, GeneratorError>>()?;
+
+    // Chunks skipped once the run stopped leave only those before the first of them contiguous
+    let batches: Vec<Vec<Wallet>> = chunks.into_iter().map_while(|wallets| wallets).collect();
+    let generated = count.min(batches.len() * chunk_size);
+    Ok((batches, generated))
+}
+
+/// Memory the system can still hand out, where the platform exposes it.
+fn available_memory() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemAvailable:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// `1234567` as `1,234,567`.
+fn group_digits(value: usize) -> String {
+    value
+        .to_string()
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A count that may be astronomically large: grouped digits, or scientific notation.
+fn format_count(value: f64) -> String {
+    match value < 1e15 {
+        true => group_digits(value.round() as usize),
+        false => format!("{:.2e}", value),
+    }
+}
+
+/// Time `secs` from now: `HH:MM:SS` under a day, then days, then years.
+fn format_eta(secs: f64) -> String {
+    const DAY: f64 = 86_400.0;
+    const YEAR: f64 = 365.25 * DAY;
+    match secs {
+        secs if secs < DAY => format_duration(Duration::from_secs_f64(secs)),
+        secs if secs < YEAR => format!("{:.1} days", secs / DAY),
+        secs if secs < 1e6 * YEAR => format!("{:.1} years", secs / YEAR),
+        secs => format!("{:.2e} years", secs / YEAR),
+    }
+}
+
+/// `HH:MM:SS`, hours unbounded.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Parse a mnemonic, saying which check failed without echoing any of its words.
+fn parse_mnemonic(phrase: &str) -> Result<Mnemonic> {
+    Mnemonic::parse(phrase).map_err(|e| {
+        let problems: Vec<String> = mnemonic::check(phrase)
+            .iter()
+            .map(|problem| match problem {
+                Problem::WordCount(count) => format!("it has {} words", count),
+                Problem::UnknownWord { position, .. } => format!("word {} is not in the BIP-39 wordlist", position),
+                Problem::Checksum { .. } => "its checksum does not match".to_string(),
+            })
+            .collect();
+        match problems.is_empty() {
+            true => anyhow::anyhow!("Invalid mnemonic: {}", e),
+            false => anyhow::anyhow!(
+                "Invalid mnemonic: {}; `wallet-generator mnemonic check` suggests corrections",
+                problems.join(", ")
+            ),
+        }
+    })
+}
+
+/// Seed of the mnemonic or hex seed in an age-encrypted file, prompting for the passphrase of a
+/// file encrypted to one.
+#[cfg(feature = "age")]
+fn mnemonic_file_seed(path: &Path, identity: Option<&Path>) -> Result<[u8; 64]> {
+    let plaintext = age_file::decrypt(path, identity, || {
+        rpassword::prompt_password(format!("Passphrase for {}: ", path.display())).map_err(|e| anyhow::anyhow!("Failed to read the passphrase: {}", e))
+    })?;
+    match RootSecret::parse(&plaintext)? {
+        RootSecret::Seed(seed) => Ok(*seed),
+        RootSecret::Mnemonic(phrase) => Ok(parse_mnemonic(&phrase)?.to_seed("")),
+    }
+}
+
+/// The passphrase for encrypted keys: the one given, or one read from the terminal, typed twice
+/// when it is about to encrypt.
+fn key_passphrase(given: Option<String>, confirm: bool) -> Result<String> {
+    if let Some(passphrase) = given {
+        return Ok(passphrase);
+    }
+    let read = |prompt: &str| rpassword::prompt_password(prompt).map_err(|e| anyhow::anyhow!("Failed to read the passphrase: {}", e));
+    let passphrase = read("Key passphrase: ")?;
+    if confirm && read("Repeat the key passphrase: ")? != passphrase {
+        return Err(anyhow::anyhow!("The passphrases do not match"));
+    }
+    Ok(passphrase)
+}
+
+/// Whether `--output` names a PostgreSQL database rather than a file.
+fn is_postgres_url(output: &str) -> bool {
+    output.starts_with("postgres://") || output.starts_with("postgresql://")
+}
+
+fn generate(args: Args, file_config: &ConfigFile, permissions: &OutputPermissions) -> Result<()> {
+    let path_range = args.path.as_deref().map(str::parse::<PathRange>).transpose()?;
+
+    // Records streamed to stdout push the summary to stderr
+    let to_stdout = args.output == STDOUT;
+    let output_format = args.output_format.unwrap_or(if to_stdout { OutputFormat::Ndjson } else { OutputFormat::Json });
+
+    // Appending continues the file's own format, from one past its highest index
+    let existing = match args.append {
+        true if to_stdout || is_postgres_url(&args.output) || ObjectUrl::parse(&args.output)?.is_some() => {
+            return Err(anyhow::anyhow!("--append needs a local JSON or NDJSON output file"));
+        }
+        true => ExistingOutput::inspect(&args.output)?,
+        false => None,
+    };
+    let output_format = match &existing {
+        Some(existing) if args.output_format.is_some_and(|format| format != existing.format) => {
+            return Err(anyhow::anyhow!("{} holds {} records; --output-format must match to append", args.output, existing.format.extension()));
+        }
+        Some(existing) => existing.format,
+        None if args.append && !matches!(output_format, OutputFormat::Json | OutputFormat::Ndjson) => {
+            return Err(anyhow::anyhow!("--append needs a JSON or NDJSON output format"));
+        }
+        None => output_format,
+    };
+    let start_index = existing.as_ref().map_or(0, ExistingOutput::next_index) as usize;
+
+    // A time budget generates up to the wallet limit, or the paths left, and stops when it runs out
+    let time_budget = args.duration.as_deref().map(progress::parse_duration).transpose()?;
+
+    // A path range numbers its paths like wallet indices; --count defaults to the ones left
+    let count = match (args.count, &path_range) {
+        (count, Some(range)) => {
+            let paths = range.count().map_or(usize::MAX, |len| usize::try_from(len).unwrap_or(usize::MAX));
+            let left = paths.saturating_sub(start_index);
+            match count {
+                Some(count) if count > left => {
+                    return Err(anyhow::anyhow!("{} holds {} paths, {} of them left to generate; --count {} is too many", range, group_digits(paths), group_digits(left), count));
+                }
+                Some(count) => count,
+                None => left,
+            }
+        }
+        (Some(count), None) => count,
+        (None, None) if time_budget.is_some() => MAX_WALLETS,
+        (None, None) => return Err(anyhow::anyhow!("--count is required")),
+    };
+
+    // Rotated output goes to numbered files beside --output, listed in a manifest
+    let rotation = Rotation { records: args.rotate_every, bytes: args.rotate_size.as_deref().map(rotate::parse_size).transpose()? };
+    let rotating = rotation != Rotation::default();
+    if args.chunk_size == Some(0) {
+        return Err(anyhow::anyhow!("--chunk-size must be at least 1"));
+    }
+    if args.rate_limit == Some(0) {
+        return Err(anyhow::anyhow!("--rate-limit must be at least 1 wallet per second"));
+    }
+    if args.nice.is_some_and(|nice| !(0..=19).contains(&nice)) {
+        return Err(anyhow::anyhow!("--nice takes a niceness from 0 to 19"));
+    }
+    if args.rotate_every == Some(0) {
+        return Err(anyhow::anyhow!("--rotate-every must be at least 1"));
+    }
+    if rotating && (to_stdout || is_postgres_url(&args.output) || ObjectUrl::parse(&args.output)?.is_some()) {
+        return Err(anyhow::anyhow!("--rotate-every and --rotate-size write numbered local files"));
+    }
+    if args.manifest && (to_stdout || is_postgres_url(&args.output) || ObjectUrl::parse(&args.output)?.is_some()) {
+        return Err(anyhow::anyhow!("--manifest describes local output files"));
+    }
+    #[cfg(feature = "arrow")]
+    if output_format == OutputFormat::Arrow && rotation.bytes.is_some() {
+        return Err(anyhow::anyhow!("--rotate-size cannot follow Arrow output, which is written in whole batches; use --rotate-every"));
+    }
+
+    if output_format == OutputFormat::Csv && args.pubkey_format == PubkeyFormat::SdkJson {
+        return Err(anyhow::anyhow!("--pubkey-format sdk-json needs a JSON output format; CSV has a pubkeyType column"));
+    }
+    if output_format == OutputFormat::Bin && args.pubkey_format == PubkeyFormat::SdkJson {
+        return Err(anyhow::anyhow!("--pubkey-format sdk-json needs a JSON output format; binary records hold raw keys"));
+    }
+    #[cfg(feature = "arrow")]
+    if output_format == OutputFormat::Arrow && args.pubkey_format == PubkeyFormat::SdkJson {
+        return Err(anyhow::anyhow!("--pubkey-format sdk-json needs a JSON output format; the Arrow pubkey column is a string"));
     }
+    if output_format == OutputFormat::Csv && args.include_uncompressed {
+        return Err(anyhow::anyhow!("--include-uncompressed needs a JSON output format; CSV keeps its columns"));
+    }
+    if output_format == OutputFormat::Csv && args.dual_address {
+        return Err(anyhow::anyhow!("--dual-address needs a JSON output format; CSV keeps its columns"));
+    }
+    if output_format == OutputFormat::Bin && args.encrypt_keys {
+        return Err(anyhow::anyhow!("--encrypt-keys needs a text output format; binary records hold raw keys"));
+    }
+
+    // Databases and object storage credentials are checked now rather than after generation
+    let to_postgres = is_postgres_url(&args.output);
+    if to_postgres && (args.output_format.is_some() || args.pubkey_format == PubkeyFormat::SdkJson || args.include_uncompressed || args.dual_address) {
+        return Err(anyhow::anyhow!("--output-format, --pubkey-format sdk-json, --include-uncompressed and --dual-address do not apply to postgres:// outputs"));
+    }
+    #[cfg(not(feature = "postgres"))]
+    if to_postgres {
+        return Err(anyhow::anyhow!("postgres:// outputs need a build with --features postgres"));
+    }
+    #[cfg(feature = "postgres")]
+    let database = match to_postgres && !args.dry_run {
+        true => Some(wallet_generator::pg::PostgresOutput::connect(&args.output, &args.table, args.copy_batch)?),
+        false => None,
+    };
+    let object_output = match ObjectUrl::parse(&args.output)? {
+        Some(url) => {
+            let url = url.with_default_name(&format!("wallets.{}", output_format.extension()));
+            let store = ObjectStore::from_env(&url)?;
+            if args.part_size * 1024 * 1024 < MIN_PART_SIZE {
+                return Err(anyhow::anyhow!("--part-size must be at least {} MiB", MIN_PART_SIZE / (1024 * 1024)));
+            }
+            Some((url, store))
+        }
+        None => None,
+    };
+    let output_name = match &object_output {
+        Some((url, _)) => url.to_string(),
+        None if to_stdout => "stdout".to_string(),
+        None if rotating => rotate::manifest_path(&args.output).display().to_string(),
+        None => args.output.clone(),
+    };
+    #[cfg(feature = "postgres")]
+    let output_name = match &database {
+        Some(database) => database.name().to_string(),
+        None if to_postgres => wallet_generator::pg::PostgresOutput::check(&args.output, &args.table)?,
+        None => output_name,
+    };
 
-    // Estimate memory usage
-    let estimated_memory_mb = (args.count * 400) / (1024 * 1024);
-    if estimated_memory_mb > 100_000 {
-        println!("Warning: Estimated memory usage: ~{}GB", estimated_memory_mb / 1024);
-        println!("   Ensure you have sufficient RAM available.");
+    // Checked now rather than after generation; rotated parts go beside --output
+    if !to_stdout && !to_postgres && object_output.is_none() {
+        permissions.check_directory(Path::new(&args.output))?;
+    }
+
+    // Summary output, skipped with --quiet
+    let console = Console { quiet: args.quiet, to_stdout };
 
-        // Give user a chance to abort
-        println!("\nPress Enter to continue or Ctrl+C to abort...");
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
+    // Validate count
+    const MAX_WALLETS: usize = 1_000_000_000;
+    if count > MAX_WALLETS {
+        return Err(anyhow::anyhow!("Too many wallets requested. Maximum is {} billion", MAX_WALLETS / 1_000_000_000));
     }
 
     // Default mnemonic if not specified
     const DEFAULT_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
 
+    // An encrypted mnemonic file is decrypted in memory only
+    #[cfg(feature = "age")]
+    let file_seed = args.mnemonic_file.as_deref().map(|path| mnemonic_file_seed(path, args.identity.as_deref())).transpose()?;
+    #[cfg(not(feature = "age"))]
+    let file_seed: Option<[u8; 64]> = None;
+
     // Get mnemonic
-    let mnemonic_str = if let Some(m) = args.mnemonic {
-        m
-    } else {
-        println!("No mnemonic provided. Using default mnemonic: {}", DEFAULT_MNEMONIC);
-        DEFAULT_MNEMONIC.to_string()
+    let default_mnemonic = args.mnemonic.is_none() && file_seed.is_none();
+    let seed = match file_seed {
+        Some(seed) => seed,
+        None => {
+            let mnemonic_str = if let Some(m) = args.mnemonic.clone() {
+                m
+            } else {
+                warn!(mnemonic = DEFAULT_MNEMONIC, "No mnemonic provided; using the default test mnemonic");
+                DEFAULT_MNEMONIC.to_string()
+            };
+
+            // Parse mnemonic and create seed
+            debug!("Parsing mnemonic and generating seed");
+            let mnemonic = parse_mnemonic(&mnemonic_str)?;
+            mnemonic.to_seed("")
+        }
     };
 
-    // Parse mnemonic and create seed
-    println!("Parsing mnemonic and generating seed...");
-    let mnemonic = Mnemonic::parse(&mnemonic_str)
-        .map_err(|e| anyhow::anyhow!("Invalid mnemonic: {}", e))?;
-    let seed = mnemonic.to_seed("");
+    // Chain preset first, explicit flags on top
+    let mut config = match (&args.chain, args.bitcoin) {
+        (Some(name), _) => file_config.chain(name)?,
+        (None, Some(_)) => file_config.chain("bitcoin")?,
+        (None, None) => DerivationConfig::new(&args.prefix, args.key_type.clone()),
+    };
+    if let Some(address) = args.bitcoin {
+        if !matches!(config.family, ChainFamily::Bitcoin(_)) {
+            return Err(anyhow::anyhow!("--bitcoin needs a Bitcoin --chain: bitcoin, bitcoin-testnet or bitcoin-regtest"));
+        }
+        config.family = ChainFamily::Bitcoin(address);
+    }
+    if config.family != ChainFamily::Cosmos && (args.encoding.is_some() || args.pubkey_format == PubkeyFormat::SdkJson) {
+        return Err(anyhow::anyhow!("--encoding and --pubkey-format sdk-json do not apply to Bitcoin or Solana wallets"));
+    }
+    if let Some(coin_type) = args.coin_type {
+        config.coin_type = coin_type;
+    }
+    if let Some(encoding) = args.encoding {
+        config.encoding = encoding;
+    }
+    if config.family == ChainFamily::Solana && args.privkey_format == PrivateKeyFormat::Wif {
+        return Err(anyhow::anyhow!("--privkey-format wif needs secp256k1 keys; use hex or base64 for Solana"));
+    }
+    if config.family == ChainFamily::Solana && (args.pubkey_format == PubkeyFormat::UncompressedHex || args.include_uncompressed) {
+        return Err(anyhow::anyhow!("ed25519 public keys have no uncompressed form; use --pubkey-format base64 or hex for Solana"));
+    }
+    if args.mmap {
+        if output_format != OutputFormat::Csv || to_stdout || to_postgres || object_output.is_some() {
+            return Err(anyhow::anyhow!("--mmap writes a local file with --output-format csv"));
+        }
+        if matches!(config.family, ChainFamily::Solana | ChainFamily::Bitcoin(BitcoinAddress::P2pkh)) {
+            return Err(anyhow::anyhow!("--mmap needs fixed-width records; base58 addresses vary in length"));
+        }
+    }
+    config.scheme = args.scheme;
+    if let Some(range) = path_range {
+        if config.family == ChainFamily::Solana && !range.is_hardened() {
+            return Err(anyhow::anyhow!("ed25519 keys have only hardened children; mark every level of --path with '"));
+        }
+        config.path_range = Some(range);
+    }
+    config.private_key_format = args.privkey_format;
+    config.pubkey_format = args.pubkey_format;
+    config.include_uncompressed = args.include_uncompressed;
+    config.dual_address = args.dual_address;
+    if config.dual_address && (config.family != ChainFamily::Cosmos || !matches!(config.key_type, KeyType::Ethsecp256k1)) {
+        return Err(anyhow::anyhow!("--dual-address needs ethsecp256k1 keys, whose address is not the SHA256/RIPEMD160 one"));
+    }
+
+    // The passphrase is stretched once; workers seal each key as they generate it
+    let cipher = match args.encrypt_keys {
+        true => Some(KeyCipher::new(&key_passphrase(args.key_passphrase.clone(), true)?)?),
+        false => None,
+    };
+
+    let targets = match &args.match_file {
+        Some(path) => {
+            let targets = TargetSet::load(path)?;
+            if targets.is_empty() {
+                return Err(anyhow::anyhow!("{} lists no addresses", path));
+            }
+            info!(targets = targets.len(), file = %path, "Loaded target addresses");
+            Some(targets)
+        }
+        None => None,
+    };
+    let target_count = targets.as_ref().map(TargetSet::len);
+    let filter = WalletFilter::new(args.filter_address.as_deref(), args.filter_evm.as_deref(), targets)?;
+    if let Some(filter) = &filter {
+        if filter.needs_evm_address() && (config.family != ChainFamily::Cosmos || !matches!(config.key_type, KeyType::Ethsecp256k1)) {
+            return Err(anyhow::anyhow!("--filter-evm needs ethsecp256k1 keys, the only ones with an EVM address"));
+        }
+        if args.mmap {
+            return Err(anyhow::anyhow!("--mmap lays out every index in turn and cannot skip filtered wallets"));
+        }
+    }
+
+    // The wallet at the highest existing index must come out the same, or the file was derived
+    // from another mnemonic or other settings
+    if let Some((index, address)) = existing.as_ref().and_then(|existing| existing.highest.as_ref()) {
+        let wallet = generate_wallets_batch(&seed, *index as usize, 1, &config, Arc::new(AtomicUsize::new(0)))?.remove(0);
+        if wallet.address != *address {
+            return Err(anyhow::anyhow!(
+                "Index {} of {} is {}, but this mnemonic and these settings derive {}; refusing to append",
+                index,
+                args.output,
+                address,
+                wallet.address
+            ));
+        }
+    }
 
     // Configure thread pool
     let num_threads = if args.threads > 0 {
@@ -58,56 +2244,260 @@ fn main() -> Result<()> {
         num_cpus::get()
     };
 
+    // Workers inherit the priority of this thread
+    if let Some(nice) = args.nice {
+        throttle::lower_priority(nice).map_err(|e| anyhow::anyhow!("Failed to lower the priority to niceness {}: {}", nice, e))?;
+    }
     rayon::ThreadPoolBuilder::new()
         .num_threads(num_threads)
         .build_global()
         .unwrap();
 
-    println!("\nCosmos Wallet Generator");
-    println!("Key type: {:?}", args.key_type);
-    println!("Platform: {} {}", std::env::consts::OS, std::env::consts::ARCH);
-    println!("Threads: {}", num_threads);
-    println!("Memory: High-performance mode (using available RAM)");
+    info!(
+        key_type = ?config.key_type,
+        platform = %format_args!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        threads = num_threads,
+        "Cosmos Wallet Generator"
+    );
+    match (config.family, &config.key_type) {
+        (ChainFamily::Bitcoin(address), _) => info!(address_type = ?address, "Using Bitcoin addresses (SHA256+RIPEMD160 hashing)"),
+        (ChainFamily::Solana, _) => info!("Using Solana ed25519 keys (SLIP-0010 derivation, base58 addresses)"),
+        (ChainFamily::Cosmos, KeyType::Ethsecp256k1) => info!("Using ethsecp256k1 (Keccak256 hashing)"),
+        (ChainFamily::Cosmos, KeyType::Secp256k1) => info!("Using standard secp256k1 (SHA256+RIPEMD160 hashing)"),
+    }
+    info!(derivation_path = %config.path_template(), prefix = %config.prefix, encoding = ?config.encoding, "Derivation");
 
-    if matches!(args.key_type, KeyType::Ethsecp256k1) {
-        println!("Note: Using ethsecp256k1 (Keccak256 hashing)");
-    } else {
-        println!("Note: Using standard secp256k1 (SHA256+RIPEMD160 hashing)");
+    // Wallets generated per segment: all segments in flight fit the budget, measured on a real
+    // wallet; a duplicate check needs every wallet before anything is written
+    let mut sample = generate_wallets_batch(&seed, 0, 1, &config, Arc::new(AtomicUsize::new(0)))?.remove(0);
+    if let Some(cipher) = &cipher {
+        cipher.encrypt_wallet(&mut sample);
     }
+    let wallet_size = sample.memory_size();
+    let write_buffer = args.max_memory.map_or(WRITE_BUFFER, |max_memory| WRITE_BUFFER.min(max_memory * 1024 * 1024 / 4));
+
+    // Recorded in the manifest and the report
+    let parameters = RunParameters {
+        prefix: config.prefix.clone(),
+        key_type: config.key_type.clone(),
+        coin_type: config.coin_type,
+        derivation_path: config.path_template(),
+        encoding: config.encoding,
+        scheme: config.scheme,
+        family: config.family,
+        pubkey_format: args.pubkey_format,
+        privkey_format: config.private_key_format,
+        include_uncompressed: config.include_uncompressed,
+        dual_address: config.dual_address,
+        encrypt_keys: args.encrypt_keys,
+        output_format: (!to_postgres).then_some(output_format),
+        chain: args.chain.clone(),
+        threads: num_threads,
+        chunk_size: args.chunk_size,
+        rate_limit: args.rate_limit,
+        nice: args.nice,
+        start_index: start_index as u64,
+        default_mnemonic,
+        check_duplicates: args.check_duplicates,
+        max_memory_mb: args.max_memory,
+        filter_address: args.filter_address.clone(),
+        filter_evm: args.filter_evm.clone(),
+        match_file: args.match_file.clone(),
+        rotate_every: rotation.records,
+        rotate_size_bytes: rotation.bytes,
+        duration_secs: time_budget.map(|budget| budget.as_secs()),
+    };
 
-    println!("\nGenerating {} wallets...",
-        args.count.to_string()
-            .as_bytes()
-            .rchunks(3)
-            .rev()
-            .map(|chunk| std::str::from_utf8(chunk).unwrap())
-            .collect::<Vec<_>>()
-            .join(","));
+    // A mapped file is written through the page cache rather than a buffer
+    let buffered = match (&object_output, to_postgres || args.mmap) {
+        (Some(_), _) => args.part_size * 1024 * 1024,
+        (None, true) => 0,
+        (None, false) => write_buffer,
+    };
+    let segment_size = match args.max_memory {
+        Some(max_memory) => {
+            // Output buffers come out of the budget too
+            let budget = max_memory * 1024 * 1024;
+            let held = budget.saturating_sub(buffered) / wallet_size;
+            if held == 0 {
+                return Err(anyhow::anyhow!(
+                    "--max-memory {} MiB leaves no room for wallets beside a {} MiB output buffer",
+                    max_memory,
+                    buffered / (1024 * 1024)
+                ));
+            }
+            (held / PIPELINE_SLOTS).clamp(1, SEGMENT_SIZE)
+        }
+        None if args.check_duplicates => {
+            let needed = wallet_size as u64 * count as u64;
+            if let Some(available) = available_memory().filter(|available| needed > *available) {
+                return Err(anyhow::anyhow!(
+                    "Checking {} wallets for duplicates holds them all, about {} MiB, but {} MiB is available",
+                    count,
+                    needed / (1024 * 1024),
+                    available / (1024 * 1024)
+                ));
+            }
+            count.max(1)
+        }
+        None => SEGMENT_SIZE,
+    };
 
     let start_time = Instant::now();
+    let state = Arc::new(RunState::new(count, num_threads).with_rate_limit(args.rate_limit).with_time_budget(time_budget));
+    if let Some(addr) = args.metrics_addr.filter(|_| !args.dry_run) {
+        let (metrics_state, started) = (state.clone(), start_time);
+        let addr = metrics::serve(addr, move || metrics::run_metrics(&metrics_state, started))
+            .map_err(|e| anyhow::anyhow!("Failed to serve metrics on {}: {}", addr, e))?;
+        info!(url = %format!("http://{}/metrics", addr), "Serving metrics");
+    }
 
-    // Setup progress bar
-    let pb = ProgressBar::new(args.count as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) | {msg}")?
-            .progress_chars("#>-"),
-    );
+    #[cfg(feature = "tui")]
+    let dashboard = args.tui;
+    #[cfg(not(feature = "tui"))]
+    let dashboard = false;
+    if dashboard && to_stdout {
+        return Err(anyhow::anyhow!("--tui draws on stdout and cannot be combined with --output -"));
+    }
+
+    if args.dry_run {
+        // The whole index range must derive, e.g. ledger-live accounts stay below 2^31
+        if count > 1 {
+            generate_wallets_batch(&seed, start_index + count - 1, 1, &config, Arc::new(AtomicUsize::new(0)))?;
+        }
+        if rotating {
+            check_output(&rotate::part_path(&args.output, 1).to_string_lossy())?;
+        } else if object_output.is_none() && !to_postgres {
+            check_output(&args.output)?;
+        }
+
+        // Calibrate on real wallets from the start of the range, serialized to count their size
+        let sdk_pubkey_type = (args.pubkey_format == PubkeyFormat::SdkJson).then(|| config.key_type.pubkey_type_url());
+        let mut records = RecordWriter::new(DigestWriter::new(std::io::sink()), output_format, sdk_pubkey_type, &config)?;
+        let mut calibrated = 0;
+        let mut calibration_time = Duration::ZERO;
+        while calibrated < count && calibration_time < CALIBRATION_TIME {
+            let burst = (num_threads * PAUSE_CHUNK).min(count - calibrated);
+            let burst_start = Instant::now();
+            let mut wallets = generate_wallets_parallel(&seed, start_index + calibrated, burst, &config, PAUSE_CHUNK, Arc::new(AtomicUsize::new(0)))?;
+            if let Some(filter) = &filter {
+                wallets.retain(|wallet| filter.matches(wallet));
+            }
+            if let Some(cipher) = &cipher {
+                wallets.iter_mut().for_each(|wallet| cipher.encrypt_wallet(wallet));
+            }
+            wallets.iter().try_for_each(|wallet| records.write(wallet))?;
+            calibration_time += burst_start.elapsed();
+            calibrated += burst;
+        }
+        let matched = records.records();
+        let record_bytes = records.finish()?.bytes();
+        let scale = count as f64 / calibrated.max(1) as f64;
+        let segments = count.div_ceil(segment_size).max(1);
+
+        say!(console, "\nDry Run Summary:");
+        say!(console, "────────────────────");
+        say!(console, "Wallets: {}", group_digits(count));
+        say!(console, 
+            "Derivation path: {} (index {} to {})",
+            config.path_template(),
+            group_digits(start_index),
+            group_digits((start_index + count).saturating_sub(1))
+        );
+        say!(console, "Output: {}", output_name);
+        if let Some(existing) = &existing {
+            say!(console, "Appending after: {} existing wallets", group_digits(existing.records));
+        }
+        say!(console, 
+            "Calibration: {} wallets in {:.2}s ({:.0} wallets/sec)",
+            group_digits(calibrated),
+            calibration_time.as_secs_f64(),
+            calibrated as f64 / calibration_time.as_secs_f64()
+        );
+        match args.rate_limit {
+            Some(rate) => {
+                let runtime = calibration_time.mul_f64(scale).max(Duration::from_secs_f64(count as f64 / rate as f64));
+                say!(console, "Estimated runtime: {} (at most {} wallets/sec)", format_duration(runtime), rate);
+            }
+            None => say!(console, "Estimated runtime: {}", format_duration(calibration_time.mul_f64(scale))),
+        }
+        if let Some(targets) = target_count {
+            say!(console, "Target addresses: {}", group_digits(targets));
+        }
+        if filter.is_some() {
+            say!(console, 
+                "Estimated matches: {} ({} of {} calibration wallets matched)",
+                group_digits((matched as f64 * scale).round() as usize),
+                group_digits(matched),
+                group_digits(calibrated)
+            );
+        }
+        if !to_postgres {
+            say!(console, "Estimated output size: {:.2} MB", record_bytes as f64 * scale / (1024.0 * 1024.0));
+        }
+        if rotating {
+            let by_records = rotation.records.map_or(1, |records| (count as u64).div_ceil(records));
+            let by_size = rotation.bytes.map_or(1, |bytes| ((record_bytes as f64 * scale) as u64).div_ceil(bytes));
+            say!(console, "Estimated files: {} (listed in {})", by_records.max(by_size).max(1), output_name);
+        }
+        say!(console, 
+            "Estimated memory: {:.0} MiB ({} segment{} of up to {} wallets, {} bytes each, {} in flight)",
+            (segment_size.min(count) * PIPELINE_SLOTS.min(segments) * wallet_size + buffered) as f64 / (1024.0 * 1024.0),
+            segments,
+            if segments == 1 { "" } else { "s" },
+            group_digits(segment_size.min(count)),
+            wallet_size,
+            PIPELINE_SLOTS.min(segments)
+        );
+        let chunk_size = args.chunk_size.unwrap_or_else(|| default_chunk_size(segment_size.min(count), num_threads, args.rate_limit));
+        say!(console, 
+            "Work items: {} wallets, {} per segment across {} threads",
+            group_digits(chunk_size),
+            group_digits(segment_size.min(count).div_ceil(chunk_size)),
+            num_threads
+        );
+        say!(console, "Nothing was written.");
+        return Ok(());
+    }
+
+    // The log names the mnemonic by fingerprint only
+    let audit = match &args.audit_log {
+        Some(_) => Some(AuditEntry::new(wallet_generator::derivation::master_fingerprint(&seed)?, parameters.clone())),
+        None => None,
+    };
+
+    // Setup progress bar; a timed run has no total to fill
+    let pb = match time_budget {
+        Some(_) => ProgressBar::new_spinner().with_style(ProgressStyle::default_spinner().template("{spinner:.green} [{elapsed_precise}] {pos} wallets | {msg}")?),
+        None => ProgressBar::new(count as u64).with_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) | {msg}")?
+                .progress_chars("#>-"),
+        ),
+    };
+    if dashboard || args.quiet {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
 
-    // Progress counter
-    let progress = Arc::new(AtomicUsize::new(0));
-    let progress_clone = progress.clone();
+    info!(count, threads = num_threads, wallets_per_segment = segment_size.min(count), wallet_bytes = wallet_size, "Generating wallets");
 
-    // Spawn progress updater thread
+    // Spawn progress updater thread; the dashboard stays up until the output is written
+    let state_clone = state.clone();
+    let filtering = filter.is_some();
     let pb_clone = pb.clone();
-    let progress_handle = std::thread::spawn(move || {
+    let progress_handle = std::thread::spawn(move || -> Result<()> {
+        #[cfg(feature = "tui")]
+        if dashboard {
+            return wallet_generator::tui::run(&state_clone, "Cosmos Wallet Generator");
+        }
+
         let mut last_count = 0;
         let mut last_time = Instant::now();
 
         loop {
             std::thread::sleep(std::time::Duration::from_millis(100));
 
-            let current_count = progress_clone.load(Ordering::Relaxed);
+            let current_count = state_clone.generated();
             pb_clone.set_position(current_count as u64);
 
             let now = Instant::now();
@@ -115,95 +2505,310 @@ fn main() -> Result<()> {
 
             if time_diff > 0.5 {
                 let rate = (current_count - last_count) as f64 / time_diff;
-                pb_clone.set_message(format!("{:.0} wallets/sec", rate));
+                match filtering {
+                    true => pb_clone.set_message(format!("{:.0} wallets/sec | {} matched", rate, state_clone.matched())),
+                    false => pb_clone.set_message(format!("{:.0} wallets/sec", rate)),
+                }
                 last_count = current_count;
                 last_time = now;
             }
 
-            if current_count >= args.count {
-                break;
+            if current_count >= count || state_clone.is_writing() || state_clone.is_finished() {
+                return Ok(());
             }
         }
     });
 
-    // Calculate optimal batch size based on thread count
-    let wallets_per_thread = (args.count + num_threads - 1) / num_threads;
+    #[cfg(feature = "postgres")]
+    let mut destination = database.map(Destination::Database);
+    #[cfg(not(feature = "postgres"))]
+    let mut destination: Option<Destination> = None;
 
-    println!("Generating wallets using {} threads ({} wallets per thread)...",
-             num_threads, wallets_per_thread);
-
-    // Generate all wallets in parallel
-    let all_wallets: Vec<Wallet> = (0..num_threads)
-        .into_par_iter()
-        .flat_map(|thread_id| {
-            let start_idx = thread_id * wallets_per_thread;
-            let count = if thread_id == num_threads - 1 {
-                args.count.saturating_sub(start_idx)
-            } else {
-                wallets_per_thread.min(args.count.saturating_sub(start_idx))
+    let outcome = (|| -> Result<RunOutcome> {
+        let sdk_pubkey_type = (args.pubkey_format == PubkeyFormat::SdkJson).then(|| config.key_type.pubkey_type_url());
+        let mut object_output = object_output;
+        // Opened at the first write, so a failed duplicate check leaves no output behind
+        let mut open_writer = || -> Result<Destination> {
+            if rotating {
+                let output = RotatingOutput::new(&args.output, rotation, output_format, sdk_pubkey_type, &config, &parameters, write_buffer, *permissions);
+                return Ok(Destination::Rotating(Box::new(output)));
+            }
+            if args.mmap {
+                let layout = CsvLayout::new(&sample, 0)?;
+                return Ok(Destination::Mapped(MappedCsvWriter::create(&args.output, layout, count as u64, permissions)?));
+            }
+            if let Some(existing) = &existing {
+                // The digest covers the records already in the file too
+                let sink = append_output(&args.output, existing, write_buffer, permissions)?;
+                let output = DigestWriter::resume(sink, fs::File::open(&args.output)?)?;
+                let writer = WalletWriter::appending(output, output_format, sdk_pubkey_type, existing.records)?;
+                return Ok(Destination::Stream(RecordWriter::Wallets(writer)));
+            }
+            let sink: Box<dyn OutputSink> = match object_output.take() {
+                Some((url, store)) => Box::new(MultipartUpload::start(store, &url.key, args.part_size * 1024 * 1024)?),
+                None => create_output(&args.output, write_buffer, permissions)?,
             };
+            Ok(Destination::Stream(RecordWriter::new(DigestWriter::new(sink), output_format, sdk_pubkey_type, &config)?))
+        };
 
-            if count == 0 {
-                Vec::new()
-            } else {
-                generate_wallets_batch(&seed, start_idx, count, &args.prefix, &args.key_type, progress.clone())
-            }
-        })
-        .collect();
+        // Workers fill a bounded queue of segments from their own thread while this one writes, so
+        // serialization overlaps generation and at most PIPELINE_SLOTS segments are held
+        let (segments, queued) = mpsc::sync_channel::<Vec<Vec<Wallet>>>(PIPELINE_DEPTH);
+        let (produced, written, write_time, duplicates) = std::thread::scope(|scope| -> Result<_> {
+            let (seed, config, filter, cipher, state, pb) = (&seed, &config, filter.as_ref(), cipher.as_ref(), &state, &pb);
+            let producer = scope.spawn(move || -> Result<(Duration, usize), GeneratorError> {
+                let mut generation_time = Duration::ZERO;
+                let mut segment_start = 0;
+                while segment_start < count {
+                    let segment_count = segment_size.min(count - segment_start);
+                    let _span = info_span!("generate", start = start_index + segment_start, count = segment_count).entered();
+                    let generate_start = Instant::now();
+                    let chunk_size = args.chunk_size.unwrap_or_else(|| default_chunk_size(segment_count, num_threads, args.rate_limit));
+                    let (batches, generated) = generate_segment(seed, start_index + segment_start, segment_count, chunk_size, config, filter, cipher, state)?;
+                    generation_time += generate_start.elapsed();
 
-    // Wait for progress thread
-    progress_handle.join().unwrap();
-    pb.finish_with_message("Generation complete!");
+                    if state.is_aborted() {
+                        pb.abandon_with_message("Aborted");
+                        return Ok((generation_time, state.generated()));
+                    }
+                    segment_start += generated;
+                    let last = segment_start == count || !state.within_deadline();
+                    if last {
+                        pb.finish_with_message(if segment_start == count { "Generation complete!" } else { "Time is up" });
+                        state.start_writing();
+                    }
+                    // The writer has failed and dropped the queue
+                    state.segment_queued();
+                    if segments.send(batches).is_err() {
+                        state.segment_dequeued();
+                        break;
+                    }
+                    if last {
+                        break;
+                    }
+                }
+                Ok((generation_time, segment_start))
+            });
 
-    let generation_time = start_time.elapsed();
+            let mut write_time = Duration::ZERO;
+            let mut written = 0;
+            let mut duplicates = Vec::new();
+            let writing = (|| -> Result<()> {
+                for batches in queued.iter() {
+                    state.segment_dequeued();
+                    let segment_count = batches.iter().map(Vec::len).sum::<usize>();
 
-    println!("\nWriting {} wallets to file...", all_wallets.len());
-    let write_start = Instant::now();
+                    // Only reached with a single segment, which then holds every wallet
+                    if args.check_duplicates {
+                        let _span = info_span!("check_duplicates").entered();
+                        let mut checker = DuplicateChecker::new();
+                        for (record, wallet) in batches.iter().flatten().enumerate() {
+                            checker.add(wallet, "generated", record);
+                        }
+                        duplicates = checker.finish();
+                        if !duplicates.is_empty() {
+                            return Ok(());
+                        }
+                        info!("No duplicate addresses");
+                    }
 
-    // Create output directory if needed
-    if let Some(parent) = Path::new(&args.output).parent() {
-        fs::create_dir_all(parent)?;
+                    if target_count.is_some() {
+                        for wallet in batches.iter().flatten() {
+                            info!(index = wallet.index, address = %wallet.address, "Target address found");
+                        }
+                    }
+
+                    let _span = info_span!("write", start = written, records = segment_count, output = %output_name).entered();
+                    let write_start = Instant::now();
+                    let output = match destination.as_mut() {
+                        Some(output) => output,
+                        None => destination.insert(open_writer()?),
+                    };
+                    output.write(&batches, state)?;
+                    if let Some(bytes) = output.bytes() {
+                        state.set_bytes_written(bytes);
+                    }
+                    write_time += write_start.elapsed();
+                    written += segment_count;
+                }
+                Ok(())
+            })();
+
+            // Unblocks a producer waiting on a full queue after the writer stopped
+            drop(queued);
+            let produced = producer.join().expect("generator thread panicked");
+            writing?;
+            Ok((produced, written, write_time, duplicates))
+        })?;
+
+        let (generation_time, generated) = match produced {
+            Ok((generation_time, _)) if !duplicates.is_empty() => return Ok((generation_time, Duration::ZERO, None, duplicates, count)),
+            Ok((_, reached)) if state.is_aborted() => {
+                if written == 0 {
+                    return Err(anyhow::anyhow!("Aborted after {} of {} wallets; nothing written", reached, count));
+                }
+                // Close the segments already written as a valid, shorter output
+                if let Some(destination) = destination.take() {
+                    destination.finish()?;
+                }
+                return Err(anyhow::anyhow!(
+                    "Aborted after {} of {} wallets; the first {} were written to {}",
+                    reached,
+                    count,
+                    written,
+                    output_name
+                ));
+            }
+            Ok(produced) => produced,
+            Err(e) => {
+                pb.abandon_with_message("Generation failed");
+                // An appended JSON array is closed again
+                if let Some(destination) = destination.take().filter(|_| existing.is_some()) {
+                    destination.finish()?;
+                }
+                return Err(anyhow::Error::new(e).context("Wallet generation failed"));
+            }
+        };
+
+        // An empty run still leaves an (empty) output
+        let destination = match destination.take() {
+            Some(destination) => destination,
+            None => open_writer()?,
+        };
+        Ok((generation_time, write_time, destination.finish()?, Vec::new(), generated))
+    })();
+
+    // Wait for progress thread
+    state.finish();
+    progress_handle.join().unwrap()?;
+
+    // Failed runs are logged too, with whatever they left behind
+    let log_failure = |error: String| {
+        if let (Some(path), Some(mut entry)) = (&args.audit_log, audit.clone()) {
+            entry.finished_at = report::unix_time();
+            entry.written = state.written();
+            entry.artifacts = vec![AuditArtifact { path: if to_stdout { STDOUT.to_string() } else { output_name.clone() }, records: None, bytes: None, sha256: None }];
+            entry.error = Some(error);
+            if let Err(e) = entry.append(path) {
+                warn!(error = %e, "Failed to append to the audit log");
+            }
+        }
+    };
+    let (generation_time, write_time, digest, duplicates, count) = outcome.inspect_err(|e| log_failure(format!("{:#}", e)))?;
+    if !duplicates.is_empty() {
+        log_failure(format!("Found {} duplicate addresses", duplicates.len()));
+        return report_duplicates(&duplicates);
     }
+    let total_time = start_time.elapsed();
 
-    // Write all wallets to file at once with large buffer
-    let file = File::create(&args.output)?;
-    let mut writer = BufWriter::with_capacity(64 * 1024 * 1024, file);
+    // A single output file is described by a manifest of one part
+    let manifest_sha256 = match &digest {
+        Some((_, sha256)) if rotating => Some(sha256.clone()),
+        Some((bytes, sha256)) if args.manifest => {
+            let mut manifest = Manifest::new(output_format);
+            manifest.version = Some(env!("CARGO_PKG_VERSION").to_string());
+            manifest.parameters = Some(parameters.clone());
+            let output = Path::new(&args.output);
+            manifest.push(ManifestPart {
+                path: output.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+                records: (state.written() + existing.as_ref().map_or(0, |existing| existing.records)) as u64,
+                first_index: 0,
+                bytes: *bytes,
+                sha256: sha256.clone(),
+            });
+            Some(manifest.write(rotate::manifest_path(output))?)
+        }
+        _ => None,
+    };
 
-    // Write JSON array
-    writer.write_all(b"[")?;
-    for (i, wallet) in all_wallets.iter().enumerate() {
-        if i > 0 {
-            writer.write_all(b",")?;
+    say!(console, "\nSummary:");
+    say!(console, "────────────────────");
+    say!(console, "Wallets generated: {}", group_digits(count));
+    if filter.is_some() {
+        say!(console, "Wallets matched: {} ({:.4}%)", group_digits(state.matched()), state.matched() as f64 * 100.0 / count.max(1) as f64);
+    }
+    if let Some(targets) = target_count {
+        say!(console, "Target addresses found: {} of {}", group_digits(state.matched()), group_digits(targets));
+    }
+    if let Some(existing) = &existing {
+        say!(console, "Appended after: {} existing wallets (index {} onward)", group_digits(existing.records), group_digits(start_index));
+    }
+    say!(console, "Generation time: {:.2}s", generation_time.as_secs_f64());
+    say!(console, "Write time: {:.2}s", write_time.as_secs_f64());
+    say!(console, "Total time: {:.2}s", total_time.as_secs_f64());
+    say!(console, "Generation rate: {:.0} wallets/sec", count as f64 / generation_time.as_secs_f64());
+    if let Some(duration) = &args.duration {
+        match count {
+            0 => say!(console, "Time budget: {} (no wallet generated)", duration),
+            _ => say!(console, "Time budget: {} (final index {})", duration, group_digits(start_index + count - 1)),
         }
-        writer.write_all(b"\n  ")?;
-        serde_json::to_writer(&mut writer, wallet)?;
     }
-    writer.write_all(b"\n]")?;
-    writer.flush()?;
+    if let Some(rate) = args.rate_limit {
+        say!(console, "Rate limit: {} wallets/sec", rate);
+    }
+    if let Some((file_size, _)) = &digest {
+        say!(console, "File size: {:.2} MB", *file_size as f64 / (1024.0 * 1024.0));
+    }
+    if rotating {
+        say!(console, "Files: {}", Manifest::read(&output_name)?.files.len());
+    }
+    say!(console, "Output: {}", output_name);
+    if args.encrypt_keys {
+        say!(console, "Private keys: encrypted (decrypt with `wallet-generator decrypt`)");
+    }
+    if args.manifest && !rotating {
+        say!(console, "Manifest: {}", rotate::manifest_path(&args.output).display());
+    }
+    if let Some(sha256) = &manifest_sha256 {
+        say!(console, "Manifest SHA-256: {}", sha256);
+    }
 
-    let write_time = write_start.elapsed();
-    let total_time = start_time.elapsed();
+    if let (Some(path), Some(mut entry)) = (&args.audit_log, audit) {
+        entry.finished_at = report::unix_time();
+        entry.last_index = (count > 0).then(|| (start_index + count - 1) as u64);
+        entry.written = state.written();
+        entry.artifacts = match &digest {
+            Some(_) if rotating => {
+                let directory = Path::new(&output_name).parent().unwrap_or(Path::new(""));
+                let parts = Manifest::read(&output_name)?.files.into_iter().map(|part| AuditArtifact {
+                    path: directory.join(&part.path).display().to_string(),
+                    records: Some(part.records),
+                    bytes: Some(part.bytes),
+                    sha256: Some(part.sha256),
+                });
+                parts.collect()
+            }
+            Some((bytes, sha256)) => vec![AuditArtifact {
+                path: if to_stdout { STDOUT.to_string() } else { output_name.clone() },
+                records: Some((state.written() + existing.as_ref().map_or(0, |existing| existing.records)) as u64),
+                bytes: Some(*bytes),
+                sha256: Some(sha256.clone()),
+            }],
+            None => vec![AuditArtifact { path: output_name.clone(), records: Some(state.written() as u64), bytes: None, sha256: None }],
+        };
+        entry.manifest_sha256 = manifest_sha256.clone();
+        entry.append(path)?;
+        say!(console, "Audit log: {}", path);
+    }
 
-    // Get file size
-    let file_size = fs::metadata(&args.output)?.len();
-    let file_size_mb = file_size as f64 / (1024.0 * 1024.0);
-
-    println!("\nSummary:");
-    println!("────────────────────");
-    println!("Wallets generated: {}",
-        args.count.to_string()
-            .as_bytes()
-            .rchunks(3)
-            .rev()
-            .map(|chunk| std::str::from_utf8(chunk).unwrap())
-            .collect::<Vec<_>>()
-            .join(","));
-    println!("Generation time: {:.2}s", generation_time.as_secs_f64());
-    println!("Write time: {:.2}s", write_time.as_secs_f64());
-    println!("Total time: {:.2}s", total_time.as_secs_f64());
-    println!("Generation rate: {:.0} wallets/sec", args.count as f64 / generation_time.as_secs_f64());
-    println!("File size: {:.2} MB", file_size_mb);
-    println!("Output: {}", args.output);
+    if let Some(report_path) = &args.report_json {
+        let report = RunReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            wallets: count,
+            matched: filter.is_some().then(|| state.matched()),
+            generation_secs: generation_time.as_secs_f64(),
+            write_secs: write_time.as_secs_f64(),
+            total_secs: total_time.as_secs_f64(),
+            wallets_per_sec: count as f64 / generation_time.as_secs_f64(),
+            output: if to_stdout { STDOUT.to_string() } else { output_name.clone() },
+            output_bytes: digest.as_ref().map(|(bytes, _)| *bytes),
+            output_sha256: digest.map(|(_, sha256)| sha256),
+            manifest_sha256,
+            parameters,
+        };
+        report.write(report_path)?;
+        say!(console, "Report: {}", report_path);
+    }
 
     Ok(())
 }
\ No newline at end of file
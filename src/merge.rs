@@ -0,0 +1,360 @@
+//! Comparison and consolidation of wallet files, e.g. shards generated on separate machines.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::path::Path;
+
+use crate::generator::derivation_index;
+use crate::wallet::Wallet;
+use crate::wallet_file::{stream_wallets, WalletReader};
+
+/// A record whose address appears in both files with different contents.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Mismatch {
+    pub address: String,
+    /// Names of the differing fields
+    pub fields: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletDiff {
+    pub only_left: Vec<String>,
+    pub only_right: Vec<String>,
+    pub mismatched: Vec<Mismatch>,
+    /// Records identical in both files
+    pub matching: usize,
+}
+
+impl WalletDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+fn differing_fields(left: &Wallet, right: &Wallet) -> Vec<String> {
+    [
+        ("evmAddress", left.evm_address != right.evm_address),
+        ("pubkey", left.pubkey != right.pubkey),
+        ("privateKey", left.private_key != right.private_key),
+        ("derivationPath", left.derivation_path != right.derivation_path),
+    ]
+    .into_iter()
+    .filter(|(_, differs)| *differs)
+    .map(|(field, _)| field.to_string())
+    .collect()
+}
+
+/// Compare two wallet files by address. Only `right` is held in memory; `left` is streamed.
+pub fn diff<P: AsRef<Path>, Q: AsRef<Path>>(left: P, right: Q) -> Result<WalletDiff> {
+    let mut right_records: HashMap<String, Wallet> = HashMap::new();
+    for wallet in WalletReader::open(right)? {
+        let wallet = wallet?;
+        right_records.insert(wallet.address.clone(), wallet);
+    }
+
+    let mut result = WalletDiff::default();
+    for wallet in WalletReader::open(left)? {
+        let wallet = wallet?;
+        match right_records.remove(&wallet.address) {
+            None => result.only_left.push(wallet.address),
+            Some(other) => {
+                let fields = differing_fields(&wallet, &other);
+                if fields.is_empty() {
+                    result.matching += 1;
+                } else {
+                    result.mismatched.push(Mismatch { address: wallet.address, fields });
+                }
+            }
+        }
+    }
+    result.only_right = right_records.into_keys().collect();
+    result.only_right.sort_unstable();
+    Ok(result)
+}
+
+/// Position of a record in merged output: derivation index, then path and address.
+type OrderKey = (u64, String, String);
+
+/// The index is read from the path where it ends in an unhardened component, so files written
+/// before records carried `index` still order correctly; hardened paths such as Solana's use the
+/// record's `index`.
+fn order_key(wallet: &Wallet) -> OrderKey {
+    let index = derivation_index(&wallet.derivation_path).unwrap_or(wallet.index);
+    (index, wallet.derivation_path.clone(), wallet.address.clone())
+}
+
+/// Derivation indices spanned by merged output, and how many inside the span no record has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coverage {
+    pub first: u64,
+    pub last: u64,
+    pub missing: u64,
+}
+
+impl Coverage {
+    /// Extend `coverage` with the next index of an ascending sequence.
+    fn extend(coverage: &mut Option<Coverage>, index: u64) {
+        match coverage {
+            None => *coverage = Some(Coverage { first: index, last: index, missing: 0 }),
+            Some(span) if index > span.last => {
+                span.missing += index - span.last - 1;
+                span.last = index;
+            }
+            // Another path at the same index, e.g. a second account of a path range
+            Some(_) => {}
+        }
+    }
+
+    /// Whether record N of the output is wallet `first + N`.
+    pub fn is_contiguous(&self) -> bool {
+        self.missing == 0
+    }
+}
+
+/// Records of several files combined, with duplicates removed.
+pub struct Merged {
+    pub wallets: Vec<Wallet>,
+    pub duplicates: usize,
+    pub coverage: Option<Coverage>,
+}
+
+/// Combine wallet files, dropping repeated records and ordering the result by derivation index,
+/// then path and address, so the same shards always merge to the same file.
+///
+/// Fails if one address appears with different keys, since at least one shard is then corrupt.
+pub fn merge<P: AsRef<Path>>(inputs: &[P]) -> Result<Merged> {
+    let mut by_address: HashMap<String, Wallet> = HashMap::new();
+    let mut duplicates = 0;
+    for input in inputs {
+        for wallet in stream_wallets(input)? {
+            let wallet = wallet?;
+            match by_address.get(&wallet.address) {
+                None => {
+                    by_address.insert(wallet.address.clone(), wallet);
+                }
+                Some(existing) => {
+                    let fields = differing_fields(existing, &wallet);
+                    if !fields.is_empty() {
+                        return Err(anyhow!(
+                            "Conflicting records for {} in {} ({} differ)",
+                            wallet.address,
+                            input.as_ref().display(),
+                            fields.join(", ")
+                        ));
+                    }
+                    duplicates += 1;
+                }
+            }
+        }
+    }
+
+    let ordered: BTreeMap<_, _> = by_address.into_values().map(|wallet| (order_key(&wallet), wallet)).collect();
+    let mut coverage = None;
+    for (index, _, _) in ordered.keys() {
+        Coverage::extend(&mut coverage, *index);
+    }
+    Ok(Merged { wallets: ordered.into_values().collect(), duplicates, coverage })
+}
+
+/// The next record of one input of a [`SortedMerge`].
+struct Head {
+    key: OrderKey,
+    source: usize,
+    wallet: Wallet,
+}
+
+impl PartialEq for Head {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head {}
+
+impl PartialOrd for Head {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Head {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.key, self.source).cmp(&(&other.key, other.source))
+    }
+}
+
+struct Source {
+    name: String,
+    records: Box<dyn Iterator<Item = Result<Wallet>>>,
+    read: usize,
+    last: Option<OrderKey>,
+}
+
+/// Streaming [`merge`] of files that are each already in index order, as generation writes them
+/// (rotated parts and `--start-index` shards included). One record per input is held in memory,
+/// so shards of any size merge into one globally ordered file.
+///
+/// Identical records are dropped, and records at the same index, path and address with different
+/// keys fail the merge. An input out of order fails it too; merge those without sorting.
+pub struct SortedMerge {
+    sources: Vec<Source>,
+    heads: BinaryHeap<Reverse<Head>>,
+    /// Last record yielded, which repeats of it are compared against
+    previous: Option<(OrderKey, Wallet)>,
+    duplicates: usize,
+    coverage: Option<Coverage>,
+    failed: bool,
+}
+
+impl SortedMerge {
+    pub fn open<P: AsRef<Path>>(inputs: &[P]) -> Result<Self> {
+        let sources = inputs
+            .iter()
+            .map(|input| {
+                Ok(Source { name: input.as_ref().display().to_string(), records: stream_wallets(input)?, read: 0, last: None })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut merge = Self { sources, heads: BinaryHeap::new(), previous: None, duplicates: 0, coverage: None, failed: false };
+        for source in 0..merge.sources.len() {
+            merge.advance(source)?;
+        }
+        Ok(merge)
+    }
+
+    /// Repeated records dropped so far.
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Indices of the records yielded so far.
+    pub fn coverage(&self) -> Option<Coverage> {
+        self.coverage
+    }
+
+    /// Queue the next record of `source`, checking it does not go back in the order.
+    fn advance(&mut self, source: usize) -> Result<()> {
+        let input = &mut self.sources[source];
+        let Some(wallet) = input.records.next().transpose()? else {
+            return Ok(());
+        };
+        input.read += 1;
+        let key = order_key(&wallet);
+        if let Some(last) = &input.last {
+            if key < *last {
+                return Err(anyhow!(
+                    "{} is not in index order: record {} (index {}) follows index {}; merge it without --sorted",
+                    input.name,
+                    input.read,
+                    key.0,
+                    last.0
+                ));
+            }
+        }
+        input.last = Some(key.clone());
+        self.heads.push(Reverse(Head { key, source, wallet }));
+        Ok(())
+    }
+
+    fn next_record(&mut self) -> Result<Option<Wallet>> {
+        while let Some(Reverse(head)) = self.heads.pop() {
+            self.advance(head.source)?;
+            if let Some((key, previous)) = &self.previous {
+                if *key == head.key {
+                    let fields = differing_fields(previous, &head.wallet);
+                    if !fields.is_empty() {
+                        return Err(anyhow!(
+                            "Conflicting records for {} in {} ({} differ)",
+                            head.wallet.address,
+                            self.sources[head.source].name,
+                            fields.join(", ")
+                        ));
+                    }
+                    self.duplicates += 1;
+                    continue;
+                }
+            }
+            Coverage::extend(&mut self.coverage, head.key.0);
+            self.previous = Some((head.key, head.wallet.clone()));
+            return Ok(Some(head.wallet));
+        }
+        Ok(None)
+    }
+}
+
+impl Iterator for SortedMerge {
+    type Item = Result<Wallet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let next = self.next_record().transpose();
+        self.failed = matches!(next, Some(Err(_)));
+        next
+    }
+}
+
+/// Where a record was found.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Occurrence {
+    pub source: String,
+    /// Zero-based position of the record within `source`
+    pub record: usize,
+    pub derivation_path: String,
+}
+
+/// An address produced more than once.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Duplicate {
+    pub address: String,
+    pub occurrences: Vec<Occurrence>,
+}
+
+/// Streaming duplicate-address check. Only addresses and first occurrences are kept in memory.
+#[derive(Default)]
+pub struct DuplicateChecker {
+    first: HashMap<String, Occurrence>,
+    duplicates: BTreeMap<String, Vec<Occurrence>>,
+}
+
+impl DuplicateChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, wallet: &Wallet, source: &str, record: usize) {
+        let occurrence = Occurrence { source: source.to_string(), record, derivation_path: wallet.derivation_path.clone() };
+        match self.first.get(&wallet.address) {
+            None => {
+                self.first.insert(wallet.address.clone(), occurrence);
+            }
+            Some(first) => self
+                .duplicates
+                .entry(wallet.address.clone())
+                .or_insert_with(|| vec![first.clone()])
+                .push(occurrence),
+        }
+    }
+
+    /// Add every record of a wallet file.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let source = path.as_ref().display().to_string();
+        for (record, wallet) in WalletReader::open(&path)?.enumerate() {
+            self.add(&wallet?, &source, record);
+        }
+        Ok(())
+    }
+
+    /// Number of distinct addresses seen.
+    pub fn unique(&self) -> usize {
+        self.first.len()
+    }
+
+    pub fn finish(self) -> Vec<Duplicate> {
+        self.duplicates.into_iter().map(|(address, occurrences)| Duplicate { address, occurrences }).collect()
+    }
+}
@@ -0,0 +1,182 @@
+//! Merkle trees over (address, amount) pairs for airdrop claim contracts.
+//!
+//! Leaves are sorted, pairs are hashed in sorted order and an unpaired node is promoted to the
+//! next level unchanged. This matches `merkletreejs` with `{ sort: true }` as used by
+//! cw20-merkle-airdrop, and the balance tree of Uniswap's merkle-distributor.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeafFormat {
+    /// `sha256(address || amount)` over the bech32 address and decimal amount strings
+    CosmWasm,
+    /// `keccak256(abi.encodePacked(uint256 index, address account, uint256 amount))`
+    Evm,
+}
+
+impl LeafFormat {
+    fn hash(self, data: &[u8]) -> [u8; 32] {
+        match self {
+            LeafFormat::CosmWasm => Sha256::digest(data).into(),
+            LeafFormat::Evm => Keccak256::digest(data).into(),
+        }
+    }
+
+    /// Hex encoding used for roots and proofs by the target contracts.
+    pub fn encode(self, hash: &[u8; 32]) -> String {
+        match self {
+            LeafFormat::CosmWasm => hex::encode(hash),
+            LeafFormat::Evm => format!("0x{}", hex::encode(hash)),
+        }
+    }
+
+    /// Leaf hash of one claim. `address` is bech32 for CosmWasm and 0x hex for EVM.
+    pub fn leaf(self, index: usize, address: &str, amount: u128) -> Result<[u8; 32]> {
+        match self {
+            LeafFormat::CosmWasm => Ok(self.hash(format!("{}{}", address, amount).as_bytes())),
+            LeafFormat::Evm => {
+                let account = hex::decode(address.trim_start_matches("0x"))
+                    .ok()
+                    .filter(|bytes| bytes.len() == 20)
+                    .ok_or_else(|| anyhow!("Invalid EVM address {}", address))?;
+                let mut packed = [0u8; 84];
+                packed[24..32].copy_from_slice(&(index as u64).to_be_bytes());
+                packed[32..52].copy_from_slice(&account);
+                packed[68..84].copy_from_slice(&amount.to_be_bytes());
+                Ok(self.hash(&packed))
+            }
+        }
+    }
+
+    fn hash_pair(self, a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(first);
+        data[32..].copy_from_slice(second);
+        self.hash(&data)
+    }
+}
+
+pub struct MerkleTree {
+    format: LeafFormat,
+    /// Level 0 holds the sorted leaves; the last level holds the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(format: LeafFormat, mut leaves: Vec<[u8; 32]>) -> Result<Self> {
+        if leaves.is_empty() {
+            return Err(anyhow!("Cannot build a Merkle tree without leaves"));
+        }
+        leaves.sort_unstable();
+        if leaves.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(anyhow!("Duplicate leaves; every (address, amount) pair must be unique"));
+        }
+
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let level = levels.last().unwrap();
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => format.hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Ok(Self { format, levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    /// Sibling hashes from `leaf` up to the root, or `None` if `leaf` is not in the tree.
+    pub fn proof(&self, leaf: &[u8; 32]) -> Option<Vec<[u8; 32]>> {
+        let mut position = self.levels[0].binary_search(leaf).ok()?;
+        let mut proof = Vec::with_capacity(self.levels.len());
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(proof)
+    }
+
+    pub fn verify(&self, leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+        proof.iter().fold(*leaf, |hash, sibling| self.format.hash_pair(&hash, sibling)) == self.root()
+    }
+}
+
+/// Proof data for one address.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Claim {
+    pub index: usize,
+    pub amount: String,
+    pub proof: Vec<String>,
+}
+
+/// An airdrop recipient read from a wallet file, optionally carrying its own amount.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AirdropEntry {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+}
+
+/// Merkle root plus a claim for every address.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Airdrop {
+    pub root: String,
+    pub total_amount: String,
+    pub claims: BTreeMap<String, Claim>,
+}
+
+/// Build the tree and proofs. Entries without an `amount` receive `default_amount`.
+///
+/// EVM leaves use each entry's `evmAddress`, so they need ethsecp256k1 wallets.
+pub fn build_airdrop(entries: &[AirdropEntry], format: LeafFormat, default_amount: Option<u128>) -> Result<Airdrop> {
+    let mut total: u128 = 0;
+    let mut claims = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let amount = match &entry.amount {
+            Some(amount) => amount.parse().map_err(|e| anyhow!("Invalid amount {} for {}: {}", amount, entry.address, e))?,
+            None => default_amount.ok_or_else(|| anyhow!("No amount for {}; pass a default amount", entry.address))?,
+        };
+        let address = match format {
+            LeafFormat::CosmWasm => entry.address.clone(),
+            LeafFormat::Evm => entry
+                .evm_address
+                .clone()
+                .ok_or_else(|| anyhow!("{} has no EVM address; EVM airdrops need ethsecp256k1 wallets", entry.address))?,
+        };
+        total = total.checked_add(amount).ok_or_else(|| anyhow!("Total airdrop amount overflows"))?;
+        let leaf = format.leaf(index, &address, amount)?;
+        claims.push((address, index, amount, leaf));
+    }
+
+    let tree = MerkleTree::new(format, claims.iter().map(|claim| claim.3).collect())?;
+    let claims = claims
+        .into_iter()
+        .map(|(address, index, amount, leaf)| {
+            let proof = tree.proof(&leaf).expect("leaf is in the tree");
+            let claim = Claim { index, amount: amount.to_string(), proof: proof.iter().map(|h| format.encode(h)).collect() };
+            (address, claim)
+        })
+        .collect::<BTreeMap<_, _>>();
+    if claims.len() != entries.len() {
+        return Err(anyhow!("Duplicate addresses in airdrop input"));
+    }
+
+    Ok(Airdrop { root: format.encode(&tree.root()), total_amount: total.to_string(), claims })
+}
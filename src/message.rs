@@ -0,0 +1,186 @@
+//! Offline signatures over arbitrary data (ADR-36 and EIP-191), for proving ownership of an address.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId, Signature};
+use secp256k1::{Message as SecpMessage, PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::types::KeyType;
+use crate::generator::public_key_address;
+use crate::tx::sign_digest;
+
+/// Amino type of the single message in an ADR-36 sign doc.
+pub const MSG_SIGN_DATA_TYPE: &str = "sign/MsgSignData";
+
+/// Amino JSON public key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AminoPubKey {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Base64 compressed public key
+    pub value: String,
+}
+
+/// A signature together with the public key that made it, as returned by Keplr's `signArbitrary`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StdSignature {
+    pub pub_key: AminoPubKey,
+    /// Base64 64-byte compact signature
+    pub signature: String,
+}
+
+fn amino_pubkey_type(key_type: &KeyType) -> &'static str {
+    match key_type {
+        KeyType::Secp256k1 => "tendermint/PubKeySecp256k1",
+        KeyType::Ethsecp256k1 => "ethermint/PubKeyEthSecp256k1",
+    }
+}
+
+fn key_type_from_amino(type_name: &str) -> Result<KeyType> {
+    match type_name {
+        "tendermint/PubKeySecp256k1" => Ok(KeyType::Secp256k1),
+        "ethermint/PubKeyEthSecp256k1" => Ok(KeyType::Ethsecp256k1),
+        other => Err(anyhow!("Unsupported public key type {}", other)),
+    }
+}
+
+/// Whether `signer` is the address of `public_key`, under the signer's own prefix.
+fn signer_matches(signer: &str, public_key: &PublicKey, key_type: &KeyType) -> Result<bool> {
+    let (_, address) = bech32::decode(signer).with_context(|| format!("Invalid signer address {}", signer))?;
+    Ok(address == public_key_address(public_key, key_type))
+}
+
+/// Canonical amino JSON sign doc for `data` signed by `signer`.
+pub fn adr36_sign_doc(signer: &str, data: &[u8]) -> Vec<u8> {
+    // serde_json keeps object keys sorted, which is the ordering amino JSON signing requires
+    let doc = json!({
+        "account_number": "0",
+        "chain_id": "",
+        "fee": { "amount": [], "gas": "0" },
+        "memo": "",
+        "msgs": [{
+            "type": MSG_SIGN_DATA_TYPE,
+            "value": { "data": general_purpose::STANDARD.encode(data), "signer": signer },
+        }],
+        "sequence": "0",
+    });
+    serde_json::to_vec(&doc).expect("sign doc serializes")
+}
+
+/// Sign `data` as `signer`, which must be the address of `private_key`.
+pub fn sign_arbitrary(private_key: &[u8; 32], key_type: &KeyType, signer: &str, data: &[u8]) -> Result<StdSignature> {
+    let secp = Secp256k1::signing_only();
+    let secret_key = SecretKey::from_slice(private_key)?;
+    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+    if !signer_matches(signer, &public_key, key_type)? {
+        return Err(anyhow!("Key does not belong to {}", signer));
+    }
+
+    let signature = sign_digest(&secp, &secret_key, &adr36_sign_doc(signer, data), key_type);
+    Ok(StdSignature {
+        pub_key: AminoPubKey {
+            type_name: amino_pubkey_type(key_type).to_string(),
+            value: general_purpose::STANDARD.encode(public_key.serialize()),
+        },
+        signature: general_purpose::STANDARD.encode(signature),
+    })
+}
+
+/// Check that `signature` is a valid ADR-36 signature of `data` by `signer`.
+///
+/// Returns `Ok(false)` if the public key does not match the signer or the signature does not verify;
+/// malformed inputs are errors.
+pub fn verify_arbitrary(signer: &str, data: &[u8], signature: &StdSignature) -> Result<bool> {
+    let key_type = key_type_from_amino(&signature.pub_key.type_name)?;
+    let public_key = PublicKey::from_slice(&general_purpose::STANDARD.decode(&signature.pub_key.value).context("Invalid base64 public key")?)?;
+    if !signer_matches(signer, &public_key, &key_type)? {
+        return Ok(false);
+    }
+
+    let sig = Signature::from_compact(&general_purpose::STANDARD.decode(&signature.signature).context("Invalid base64 signature")?)?;
+    let sign_doc = adr36_sign_doc(signer, data);
+    let digest: [u8; 32] = match key_type {
+        KeyType::Secp256k1 => Sha256::digest(&sign_doc).into(),
+        KeyType::Ethsecp256k1 => Keccak256::digest(&sign_doc).into(),
+    };
+    Ok(Secp256k1::verification_only().verify_ecdsa(&SecpMessage::from_digest(digest), &sig, &public_key).is_ok())
+}
+
+/// An Ethereum signature (`personal_sign` or EIP-712) and the address that made it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PersonalSignature {
+    /// 0x-prefixed Ethereum address
+    pub address: String,
+    /// 0x-prefixed 65-byte `r || s || v` signature, with `v` of 27 or 28
+    pub signature: String,
+}
+
+/// Keccak256 of `data` behind the `"\x19Ethereum Signed Message:\n" + len` prefix.
+pub fn eip191_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(format!("\x19Ethereum Signed Message:\n{}", data.len()).as_bytes());
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Sign a 32-byte digest with a recoverable signature in Ethereum's `r || s || v` layout.
+pub(crate) fn eth_sign_digest(private_key: &[u8; 32], digest: [u8; 32]) -> Result<PersonalSignature> {
+    let secp = Secp256k1::signing_only();
+    let secret_key = SecretKey::from_slice(private_key)?;
+    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+
+    let (recovery_id, compact) = secp
+        .sign_ecdsa_recoverable(&SecpMessage::from_digest(digest), &secret_key)
+        .serialize_compact();
+    let mut signature = compact.to_vec();
+    signature.push(27 + recovery_id.to_i32() as u8);
+
+    Ok(PersonalSignature {
+        address: format!("0x{}", hex::encode(public_key_address(&public_key, &KeyType::Ethsecp256k1))),
+        signature: format!("0x{}", hex::encode(signature)),
+    })
+}
+
+/// Recover the 20-byte Ethereum address behind an `r || s || v` signature of `digest`.
+///
+/// Accepts `v` as 27/28 or 0/1.
+pub(crate) fn eth_recover(digest: [u8; 32], signature: &str) -> Result<[u8; 20]> {
+    let bytes = hex::decode(signature.trim_start_matches("0x")).context("Invalid hex signature")?;
+    if bytes.len() != 65 {
+        return Err(anyhow!("Signature must be 65 bytes, got {}", bytes.len()));
+    }
+    let v = bytes[64];
+    let recovery_id = RecoveryId::from_i32(i32::from(if v >= 27 { v - 27 } else { v }))?;
+    let signature = RecoverableSignature::from_compact(&bytes[..64], recovery_id)?;
+
+    let public_key = Secp256k1::verification_only().recover_ecdsa(&SecpMessage::from_digest(digest), &signature)?;
+    Ok(public_key_address(&public_key, &KeyType::Ethsecp256k1))
+}
+
+/// Raw address bytes of a 0x address or a bech32 address.
+pub fn signer_address_bytes(signer: &str) -> Result<Vec<u8>> {
+    match signer.strip_prefix("0x") {
+        Some(hex_address) => hex::decode(hex_address).with_context(|| format!("Invalid address {}", signer)),
+        None => Ok(bech32::decode(signer).with_context(|| format!("Invalid signer address {}", signer))?.1),
+    }
+}
+
+/// Sign `data` the way `personal_sign` does.
+pub fn personal_sign(private_key: &[u8; 32], data: &[u8]) -> Result<PersonalSignature> {
+    eth_sign_digest(private_key, eip191_hash(data))
+}
+
+/// Recover the 20-byte Ethereum address that produced a `personal_sign` signature over `data`.
+pub fn recover_personal_sign(data: &[u8], signature: &str) -> Result<[u8; 20]> {
+    eth_recover(eip191_hash(data), signature)
+}
+
+/// Check that `signature` is a `personal_sign` signature of `data` by `signer`,
+/// given as a 0x address or a bech32 address of the same key.
+pub fn verify_personal_sign(signer: &str, data: &[u8], signature: &str) -> Result<bool> {
+    Ok(recover_personal_sign(data, signature)? == signer_address_bytes(signer)?.as_slice())
+}
@@ -0,0 +1,165 @@
+//! Prometheus metrics in the text exposition format, and a minimal HTTP endpoint serving them, so
+//! multi-hour runs can be watched from standard dashboards.
+//!
+//! The endpoint runs on its own thread with blocking std sockets and answers `GET /metrics`; it
+//! needs no async runtime, so batch runs get it without the `server` feature.
+
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::progress::RunState;
+
+/// `Content-Type` of the text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// How long a scrape may take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+}
+
+impl Display for MetricType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+        })
+    }
+}
+
+/// Builds a scrape body one metric family at a time.
+#[derive(Debug, Default)]
+pub struct Exposition {
+    body: String,
+}
+
+impl Exposition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A family with a single unlabelled sample.
+    pub fn metric(&mut self, name: &str, kind: MetricType, help: &str, value: impl Display) -> &mut Self {
+        self.family(name, kind, help, [("", value)])
+    }
+
+    /// A family with one sample per value of `label`.
+    pub fn labelled<L: Display, V: Display>(
+        &mut self,
+        name: &str,
+        kind: MetricType,
+        help: &str,
+        label: &str,
+        samples: impl IntoIterator<Item = (L, V)>,
+    ) -> &mut Self {
+        let samples = samples.into_iter().map(|(value, sample)| (format!("{}=\"{}\"", label, value), sample));
+        self.family(name, kind, help, samples)
+    }
+
+    fn family<L: Display, V: Display>(&mut self, name: &str, kind: MetricType, help: &str, samples: impl IntoIterator<Item = (L, V)>) -> &mut Self {
+        // Writing to a String cannot fail
+        let _ = writeln!(self.body, "# HELP {} {}", name, help);
+        let _ = writeln!(self.body, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let labels = labels.to_string();
+            let _ = match labels.is_empty() {
+                true => writeln!(self.body, "{} {}", name, value),
+                false => writeln!(self.body, "{}{{{}}} {}", name, labels, value),
+            };
+        }
+        self
+    }
+
+    pub fn finish(&mut self) -> String {
+        std::mem::take(&mut self.body)
+    }
+}
+
+/// Metrics of a generation run, with rates averaged since `started`.
+pub fn run_metrics(state: &RunState, started: Instant) -> String {
+    let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+    let generated = state.generated();
+    let mut exposition = Exposition::new();
+    exposition
+        .metric("wallet_generator_wallets_target", MetricType::Gauge, "Wallets the run will generate", state.total())
+        .metric("wallet_generator_wallets_generated_total", MetricType::Counter, "Wallets derived so far", generated)
+        .labelled(
+            "wallet_generator_thread_wallets_generated_total",
+            MetricType::Counter,
+            "Wallets derived by each worker thread",
+            "thread",
+            state.thread_counts().into_iter().enumerate(),
+        )
+        .metric("wallet_generator_wallets_matched_total", MetricType::Counter, "Wallets kept by the filter", state.matched())
+        .metric("wallet_generator_wallets_written_total", MetricType::Counter, "Records written to the output", state.written())
+        .metric("wallet_generator_output_bytes", MetricType::Gauge, "Size of the output so far", state.bytes_written())
+        .metric(
+            "wallet_generator_generation_rate",
+            MetricType::Gauge,
+            "Wallets derived per second, averaged over the run",
+            generated as f64 / elapsed,
+        )
+        .metric(
+            "wallet_generator_write_rate",
+            MetricType::Gauge,
+            "Records written per second, averaged over the run",
+            state.written() as f64 / elapsed,
+        )
+        .metric("wallet_generator_queue_depth", MetricType::Gauge, "Generated segments waiting to be written", state.queue_depth())
+        .metric("wallet_generator_paused", MetricType::Gauge, "1 while the run is paused", u8::from(state.is_paused()))
+        .metric("wallet_generator_elapsed_seconds", MetricType::Gauge, "Seconds since the run started", elapsed);
+    exposition.finish()
+}
+
+/// Serve `render()` at `GET /metrics` on `addr` from a background thread, for the life of the
+/// process. Returns the bound address, which differs from `addr` for port 0.
+pub fn serve<F>(addr: SocketAddr, render: F) -> io::Result<SocketAddr>
+where
+    F: Fn() -> String + Send + 'static,
+{
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    thread::Builder::new().name("metrics".to_string()).spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // A client that stalls or hangs up only loses its own scrape
+            let _ = respond(stream, &render);
+        }
+    })?;
+    Ok(local)
+}
+
+fn respond(stream: TcpStream, render: &impl Fn() -> String) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; requests to this endpoint carry no body
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next().and_then(|target| target.split('?').next())) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", CONTENT_TYPE, render()),
+        (Some("GET"), _) => ("404 Not Found", "text/plain", "Metrics are served at /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "Only GET is supported\n".to_string()),
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
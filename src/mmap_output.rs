@@ -0,0 +1,169 @@
+//! CSV output written through a memory map of a file preallocated to its final size.
+//!
+//! Records of a run differ in width only by the digits of their index, which appears in both
+//! `derivationPath` and `index`, so the offset of every record is known before it is generated.
+//! Each worker then writes its batch into its own region of the file, with no shared writer.
+
+use anyhow::{anyhow, Context, Result};
+use memmap2::MmapMut;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+use crate::secure_output::OutputPermissions;
+use crate::wallet::Wallet;
+use crate::wallet_file::{write_csv_record, CSV_HEADER};
+
+/// Byte offsets of the records of a run, from the width of one record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvLayout {
+    /// Record width less the two renderings of its index
+    fixed: u64,
+    /// Index of the first record
+    start: u64,
+}
+
+impl CsvLayout {
+    /// Layout of records shaped like `sample`, for a run whose first index is `start`.
+    pub fn new(sample: &Wallet, start: u64) -> Result<Self> {
+        let mut record = Vec::new();
+        write_csv_record(&mut record, sample)?;
+        Ok(Self { fixed: record.len() as u64 - 2 * digits(sample.index), start })
+    }
+
+    /// Width of the record at `index`, line break included.
+    pub fn width(&self, index: u64) -> u64 {
+        self.fixed + 2 * digits(index)
+    }
+
+    /// Byte offset of the record at `index`, which is also the file size when `index` is one past
+    /// the last record.
+    pub fn offset(&self, index: u64) -> u64 {
+        let records = index - self.start;
+        header_len() + records * self.fixed + 2 * (digits_below(index) - digits_below(self.start))
+    }
+}
+
+/// Header line, line break included.
+fn header_len() -> u64 {
+    CSV_HEADER.len() as u64 + 1
+}
+
+/// Decimal digits of `n`.
+fn digits(n: u64) -> u64 {
+    n.checked_ilog10().map_or(1, |log| log as u64 + 1)
+}
+
+/// Decimal digits of all of `0..n` together.
+fn digits_below(n: u64) -> u64 {
+    let mut total = 0;
+    let (mut low, mut high, mut width) = (0u64, 10u64, 1);
+    while low < n {
+        total += (n.min(high) - low) * width;
+        low = high;
+        high = high.saturating_mul(10);
+        width += 1;
+    }
+    total
+}
+
+/// Writes CSV records in place into a file sized for the whole run up front. Records must arrive
+/// in index order; [`MappedCsvWriter::finish`] trims the file to the records written.
+pub struct MappedCsvWriter {
+    file: File,
+    map: MmapMut,
+    layout: CsvLayout,
+    /// Index of the next record
+    next: u64,
+}
+
+impl MappedCsvWriter {
+    /// Create `path` with `permissions` at the size of `count` records laid out as `layout`, with
+    /// the header written.
+    pub fn create<P: AsRef<Path>>(path: P, layout: CsvLayout, count: u64, permissions: &OutputPermissions) -> Result<Self> {
+        let path = path.as_ref();
+        permissions.check_directory(path)?;
+        let file = permissions.open(path, OpenOptions::new().read(true).write(true).create(true).truncate(true))?;
+        file.set_len(layout.offset(layout.start + count))
+            .with_context(|| format!("Failed to preallocate {}", path.display()))?;
+
+        // Safety: the file was just truncated and created by us, and is only written through the map
+        let mut map = unsafe { MmapMut::map_mut(&file) }.with_context(|| format!("Failed to map {}", path.display()))?;
+        let header = format!("{}\n", CSV_HEADER);
+        map[..header.len()].copy_from_slice(header.as_bytes());
+        Ok(Self { file, map, layout, next: layout.start })
+    }
+
+    /// Write the batches, which follow each other and the records already written, one per worker.
+    pub fn write(&mut self, batches: &[Vec<Wallet>]) -> Result<()> {
+        let layout = self.layout;
+        let total = batches.iter().map(Vec::len).sum::<usize>() as u64;
+        let end = layout.offset(self.next + total);
+        if end > self.map.len() as u64 {
+            return Err(anyhow!("More records than the {} bytes preallocated", self.map.len()));
+        }
+
+        // Split off each batch's region in turn, so workers hold disjoint slices of the map
+        let mut rest = &mut self.map[layout.offset(self.next) as usize..end as usize];
+        let mut regions = Vec::with_capacity(batches.len());
+        let mut index = self.next;
+        for batch in batches {
+            let batch_end = index + batch.len() as u64;
+            let (region, tail) = rest.split_at_mut((layout.offset(batch_end) - layout.offset(index)) as usize);
+            regions.push((index, region));
+            rest = tail;
+            index = batch_end;
+        }
+        regions
+            .into_par_iter()
+            .zip(batches.par_iter())
+            .try_for_each(|((first, region), batch)| write_region(region, first, batch, &layout))?;
+
+        self.next = index;
+        Ok(())
+    }
+
+    /// Records written so far.
+    pub fn records(&self) -> u64 {
+        self.next - self.layout.start
+    }
+
+    /// Bytes written so far, the header included.
+    pub fn bytes(&self) -> u64 {
+        self.layout.offset(self.next)
+    }
+
+    /// Flush the map and cut the file after the last record written, returning its size and hex
+    /// SHA-256.
+    pub fn finish(self) -> Result<(u64, String)> {
+        let len = self.layout.offset(self.next);
+        self.map.flush()?;
+        let sha256 = hex::encode(Sha256::digest(&self.map[..len as usize]));
+        drop(self.map);
+        self.file.set_len(len)?;
+        Ok((len, sha256))
+    }
+}
+
+/// Write `batch`, whose first record has index `first`, filling `region` exactly.
+fn write_region(mut region: &mut [u8], first: u64, batch: &[Wallet], layout: &CsvLayout) -> Result<()> {
+    for (index, wallet) in (first..).zip(batch) {
+        if wallet.index != index {
+            return Err(anyhow!("Record {} arrived where record {} was expected", wallet.index, index));
+        }
+        let width = layout.width(index);
+        let (slot, tail) = region.split_at_mut(width as usize);
+        let mut unwritten = &mut *slot;
+        if write_csv_record(&mut unwritten, wallet).is_err() || !unwritten.is_empty() {
+            return Err(anyhow!(
+                "Record {} ({}) is not {} bytes wide; memory-mapped output needs records of fixed width",
+                index,
+                wallet.address,
+                width
+            ));
+        }
+        region = tail;
+    }
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Diagnosis of invalid BIP-39 mnemonics: wrong word counts, words outside the wordlist and
+//! checksum failures, with the wordlist entries a typo most likely meant.
+
+use bip39::{Language, Mnemonic};
+
+/// Word counts BIP-39 allows.
+pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Suggestions listed per word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Edit distance within which a wordlist entry counts as a likely typo.
+const MAX_DISTANCE: usize = 2;
+
+/// One thing wrong with a mnemonic. Positions are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Not 12, 15, 18, 21 or 24 words
+    WordCount(usize),
+    /// A word outside the wordlist, with the closest entries; when it is the only one, entries
+    /// that also satisfy the checksum come first
+    UnknownWord { position: usize, word: String, suggestions: Vec<&'static str> },
+    /// Every word is in the wordlist but the checksum fails
+    Checksum { corrections: Vec<Correction> },
+}
+
+/// A single change that makes the checksum valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Correction {
+    /// Replace the word at `position` with a similar wordlist entry
+    Replace { position: usize, word: &'static str },
+    /// Swap the words at `position` and `position + 1`
+    Swap { position: usize },
+}
+
+/// Everything wrong with `phrase`; empty if it is a valid English mnemonic.
+pub fn check(phrase: &str) -> Vec<Problem> {
+    let words: Vec<String> = phrase.split_whitespace().map(str::to_string).collect();
+    let mut problems = Vec::new();
+    let count_ok = WORD_COUNTS.contains(&words.len());
+    if !count_ok {
+        problems.push(Problem::WordCount(words.len()));
+    }
+
+    let unknown: Vec<usize> = (0..words.len()).filter(|&i| Language::English.find_word(&words[i]).is_none()).collect();
+    for &i in &unknown {
+        let mut suggestions = similar_words(&words[i]);
+        // With one bad word and the right length, the checksum tells the candidates apart
+        if count_ok && unknown.len() == 1 {
+            suggestions.sort_by_key(|&candidate| !is_valid(&replaced(&words, i, candidate)));
+        }
+        suggestions.truncate(MAX_SUGGESTIONS);
+        problems.push(Problem::UnknownWord { position: i + 1, word: words[i].clone(), suggestions });
+    }
+
+    if count_ok && unknown.is_empty() && !is_valid(&words.join(" ")) {
+        let mut corrections = Vec::new();
+        for (i, word) in words.iter().enumerate() {
+            for candidate in similar_words(word) {
+                if is_valid(&replaced(&words, i, candidate)) {
+                    corrections.push(Correction::Replace { position: i + 1, word: candidate });
+                }
+            }
+        }
+        for i in 0..words.len() - 1 {
+            let mut swapped = words.clone();
+            swapped.swap(i, i + 1);
+            if words[i] != words[i + 1] && is_valid(&swapped.join(" ")) {
+                corrections.push(Correction::Swap { position: i + 1 });
+            }
+        }
+        problems.push(Problem::Checksum { corrections });
+    }
+    problems
+}
+
+/// Wordlist entries within [`MAX_DISTANCE`] edits of `word`, closest first. Entries sharing its
+/// first four letters rank first, since BIP-39 words are unique in their first four letters.
+fn similar_words(word: &str) -> Vec<&'static str> {
+    let prefix: String = word.chars().take(4).collect();
+    let mut candidates: Vec<(usize, &'static str)> = Language::English
+        .word_list()
+        .iter()
+        .filter(|&&candidate| candidate != word)
+        .filter_map(|&candidate| {
+            let distance = edit_distance(word, candidate);
+            let same_prefix = prefix.chars().count() == 4 && candidate.starts_with(&prefix);
+            match (same_prefix, distance <= MAX_DISTANCE) {
+                (true, _) => Some((0, candidate)),
+                (false, true) => Some((distance, candidate)),
+                (false, false) => None,
+            }
+        })
+        .collect();
+    candidates.sort();
+    candidates.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+fn replaced(words: &[String], position: usize, word: &str) -> String {
+    let mut words = words.to_vec();
+    words[position] = word.to_string();
+    words.join(" ")
+}
+
+fn is_valid(phrase: &str) -> bool {
+    Mnemonic::parse_in_normalized(Language::English, phrase).is_ok()
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions and adjacent
+/// transpositions each cost one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,399 @@
+//! Output straight to S3 or Google Cloud Storage through multipart uploads, so workers need no local
+//! disk for the wallet set. GCS is reached through its S3-compatible XML API with HMAC keys.
+//!
+//! Requests are signed with AWS Signature Version 4 using credentials from the standard `AWS_*`
+//! environment variables.
+
+use anyhow::{anyhow, Result};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::wallet_file::OutputSink;
+
+/// S3 rejects parts below 5 MiB, except the last one.
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Most parts a multipart upload may have.
+const MAX_PARTS: usize = 10_000;
+
+const RETRIES: u32 = 4;
+
+/// Base delay before the first retry; doubled on each attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    S3,
+    Gcs,
+}
+
+/// `s3://bucket/key` or `gs://bucket/key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectUrl {
+    pub provider: Provider,
+    pub bucket: String,
+    pub key: String,
+}
+
+impl ObjectUrl {
+    /// Parse an object storage URL; `Ok(None)` if `url` is not one (e.g. a local path).
+    pub fn parse(url: &str) -> Result<Option<Self>> {
+        let (provider, rest) = if let Some(rest) = url.strip_prefix("s3://") {
+            (Provider::S3, rest)
+        } else if let Some(rest) = url.strip_prefix("gs://") {
+            (Provider::Gcs, rest)
+        } else {
+            return Ok(None);
+        };
+        let (bucket, key) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            return Err(anyhow!("No bucket in {}", url));
+        }
+        Ok(Some(Self { provider, bucket: bucket.to_string(), key: key.to_string() }))
+    }
+
+    /// A key that is empty or ends in `/` names a prefix; append `file_name` to it.
+    pub fn with_default_name(mut self, file_name: &str) -> Self {
+        if self.key.is_empty() || self.key.ends_with('/') {
+            self.key.push_str(file_name);
+        }
+        self
+    }
+}
+
+impl fmt::Display for ObjectUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scheme = match self.provider {
+            Provider::S3 => "s3",
+            Provider::Gcs => "gs",
+        };
+        write!(f, "{}://{}/{}", scheme, self.bucket, self.key)
+    }
+}
+
+struct Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+/// Signed client for one bucket.
+pub struct ObjectStore {
+    /// Scheme and authority, e.g. `https://bucket.s3.us-east-1.amazonaws.com`
+    endpoint: String,
+    host: String,
+    /// `/bucket` for path-style addressing, empty for virtual-hosted buckets
+    bucket_path: String,
+    region: String,
+    credentials: Credentials,
+    agent: ureq::Agent,
+}
+
+impl ObjectStore {
+    /// Client for the bucket of `url`, configured from the environment:
+    ///
+    /// - `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` (GCS HMAC keys for `gs://`), `AWS_SESSION_TOKEN`
+    /// - `AWS_REGION` or `AWS_DEFAULT_REGION` (default `us-east-1`; `auto` for GCS)
+    /// - `AWS_ENDPOINT_URL` for other S3-compatible stores such as MinIO, addressed path-style
+    pub fn from_env(url: &ObjectUrl) -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let credentials = Credentials {
+            access_key_id: var("AWS_ACCESS_KEY_ID").ok_or_else(|| anyhow!("AWS_ACCESS_KEY_ID is required for {}", url))?,
+            secret_access_key: var("AWS_SECRET_ACCESS_KEY")
+                .ok_or_else(|| anyhow!("AWS_SECRET_ACCESS_KEY is required for {}", url))?,
+            session_token: var("AWS_SESSION_TOKEN"),
+        };
+        let default_region = match url.provider {
+            Provider::S3 => "us-east-1",
+            Provider::Gcs => "auto",
+        };
+        let region = var("AWS_REGION").or_else(|| var("AWS_DEFAULT_REGION")).unwrap_or_else(|| default_region.to_string());
+
+        let (endpoint, bucket_path) = match (url.provider, var("AWS_ENDPOINT_URL")) {
+            (_, Some(endpoint)) => (endpoint.trim_end_matches('/').to_string(), format!("/{}", url.bucket)),
+            (Provider::S3, None) => (format!("https://{}.s3.{}.amazonaws.com", url.bucket, region), String::new()),
+            (Provider::Gcs, None) => ("https://storage.googleapis.com".to_string(), format!("/{}", url.bucket)),
+        };
+        let host = endpoint
+            .split_once("://")
+            .map(|(_, authority)| authority.to_string())
+            .ok_or_else(|| anyhow!("Invalid endpoint {}", endpoint))?;
+
+        Ok(Self {
+            endpoint,
+            host,
+            bucket_path,
+            region,
+            credentials,
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(300)).build(),
+        })
+    }
+
+    /// Send a signed request, retrying transport errors, 429 and 5xx responses.
+    fn send(&self, method: &str, key: &str, query: &[(&str, &str)], body: &[u8]) -> Result<ureq::Response> {
+        let path = format!("{}/{}", self.bucket_path, uri_encode(key, false));
+        let mut query: Vec<(String, String)> = query.iter().map(|(k, v)| (uri_encode(k, true), uri_encode(v, true))).collect();
+        query.sort();
+        let query = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+        let url = format!("{}{}?{}", self.endpoint, path, query);
+        let payload_hash = hex::encode(Sha256::digest(body));
+        let mut attempt = 0;
+
+        loop {
+            let amz_date = amz_date(SystemTime::now());
+            let mut headers = vec![
+                ("host", self.host.clone()),
+                ("x-amz-content-sha256", payload_hash.clone()),
+                ("x-amz-date", amz_date.clone()),
+            ];
+            if let Some(token) = &self.credentials.session_token {
+                headers.push(("x-amz-security-token", token.clone()));
+            }
+            let authorization = self.authorization(method, &path, &query, &headers, &payload_hash, &amz_date);
+
+            let mut request = self.agent.request(method, &url).set("authorization", &authorization);
+            for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+                request = request.set(name, value);
+            }
+
+            let retryable = match request.send_bytes(body) {
+                Ok(response) => return Ok(response),
+                Err(ureq::Error::Status(code, response)) if code != 429 && code < 500 => {
+                    let body = response.into_string().unwrap_or_default();
+                    return Err(anyhow!("{} {} returned {}: {}", method, url, code, body));
+                }
+                Err(e) => e,
+            };
+
+            if attempt >= RETRIES {
+                return Err(anyhow!("{} {} failed after {} attempts: {}", method, url, attempt + 1, retryable));
+            }
+            std::thread::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// `Authorization` header value for a request; `headers` are lowercase and sorted by name.
+    fn authorization(
+        &self,
+        method: &str,
+        path: &str,
+        query: &str,
+        headers: &[(&str, String)],
+        payload_hash: &str,
+        amz_date: &str,
+    ) -> String {
+        let request = SignedRequest { method, path, query, headers, payload_hash, amz_date };
+        let signature = sign_v4(&request, &self.region, "s3", &self.credentials.secret_access_key);
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.credentials.access_key_id, signature.scope, signature.signed_headers, signature.signature
+        )
+    }
+}
+
+/// The parts of a request AWS Signature Version 4 covers.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedRequest<'a> {
+    pub method: &'a str,
+    /// URI-encoded path
+    pub path: &'a str,
+    /// Canonical query string: encoded, sorted by name, without the `?`
+    pub query: &'a str,
+    /// Lowercase names, sorted
+    pub headers: &'a [(&'a str, String)],
+    /// Hex SHA-256 of the body
+    pub payload_hash: &'a str,
+    /// `YYYYMMDDTHHMMSSZ`
+    pub amz_date: &'a str,
+}
+
+/// A request's Signature Version 4 with the strings it was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigV4 {
+    pub canonical_request: String,
+    pub string_to_sign: String,
+    /// `<date>/<region>/<service>/aws4_request`
+    pub scope: String,
+    pub signed_headers: String,
+    /// Hex HMAC-SHA256 of the string to sign
+    pub signature: String,
+}
+
+/// Sign `request` for `service` in `region` with AWS Signature Version 4.
+pub fn sign_v4(request: &SignedRequest, region: &str, service: &str, secret_access_key: &str) -> SigV4 {
+    let canonical_headers: String = request.headers.iter().map(|(name, value)| format!("{}:{}\n", name, value.trim())).collect();
+    let signed_headers = request.headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        request.method, request.path, request.query, canonical_headers, signed_headers, request.payload_hash
+    );
+
+    let date = &request.amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign =
+        format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", request.amz_date, scope, hex::encode(Sha256::digest(&canonical_request)));
+
+    let key = [date, region, service, "aws4_request"]
+        .iter()
+        .fold(format!("AWS4{}", secret_access_key).into_bytes(), |key, part| hmac_sha256(&key, part.as_bytes()));
+    let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+    SigV4 { canonical_request, string_to_sign, scope, signed_headers, signature }
+}
+
+/// Object being written as a multipart upload; each part is sent once `part_size` bytes are
+/// buffered. The object appears only after [`OutputSink::finish`]; an unfinished upload is aborted
+/// when dropped.
+pub struct MultipartUpload {
+    store: ObjectStore,
+    key: String,
+    upload_id: String,
+    part_size: usize,
+    buffer: Vec<u8>,
+    etags: Vec<String>,
+    completed: bool,
+}
+
+impl MultipartUpload {
+    pub fn start(store: ObjectStore, key: &str, part_size: usize) -> Result<Self> {
+        if part_size < MIN_PART_SIZE {
+            return Err(anyhow!("Part size must be at least {} MiB", MIN_PART_SIZE / (1024 * 1024)));
+        }
+        let response = store.send("POST", key, &[("uploads", "")], &[])?.into_string()?;
+        let upload_id = xml_value(&response, "UploadId").ok_or_else(|| anyhow!("No UploadId in response: {}", response))?;
+        Ok(Self {
+            store,
+            key: key.to_string(),
+            upload_id,
+            part_size,
+            buffer: Vec::with_capacity(part_size),
+            etags: Vec::new(),
+            completed: false,
+        })
+    }
+
+    fn upload_part(&mut self) -> Result<()> {
+        if self.etags.len() == MAX_PARTS {
+            return Err(anyhow!("Upload exceeds {} parts; raise the part size", MAX_PARTS));
+        }
+        let part_number = (self.etags.len() + 1).to_string();
+        let response = self.store.send(
+            "PUT",
+            &self.key,
+            &[("partNumber", &part_number), ("uploadId", &self.upload_id)],
+            &self.buffer,
+        )?;
+        let etag = response.header("etag").ok_or_else(|| anyhow!("No ETag for part {}", part_number))?;
+        self.etags.push(etag.to_string());
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn complete(&mut self) -> Result<()> {
+        // An empty object is still one (empty) part
+        if !self.buffer.is_empty() || self.etags.is_empty() {
+            self.upload_part()?;
+        }
+        let parts: String = self
+            .etags
+            .iter()
+            .enumerate()
+            .map(|(i, etag)| format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", i + 1, etag))
+            .collect();
+        let body = format!("<CompleteMultipartUpload>{}</CompleteMultipartUpload>", parts);
+        let response = self.store.send("POST", &self.key, &[("uploadId", &self.upload_id)], body.as_bytes())?.into_string()?;
+        // Completion can fail after the 200 status has been sent
+        if response.contains("<Error>") {
+            return Err(anyhow!("Completing upload of {} failed: {}", self.key, response));
+        }
+        self.completed = true;
+        Ok(())
+    }
+}
+
+impl Write for MultipartUpload {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.part_size - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() == self.part_size {
+            self.upload_part().map_err(io::Error::other)?;
+        }
+        Ok(len)
+    }
+
+    /// Parts are sent as they fill; the rest goes with [`OutputSink::finish`].
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl OutputSink for MultipartUpload {
+    fn finish(&mut self) -> Result<()> {
+        self.complete()
+    }
+}
+
+impl Drop for MultipartUpload {
+    fn drop(&mut self) {
+        if !self.completed {
+            // Best effort: stored parts of an abandoned upload are billed until it is aborted
+            let _ = self.store.send("DELETE", &self.key, &[("uploadId", &self.upload_id)], &[]);
+        }
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode all but unreserved characters; `/` too if `encode_slash`.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// `YYYYMMDDTHHMMSSZ` in UTC.
+fn amz_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+/// Text of the first `<tag>` element of an XML response.
+fn xml_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{}>", tag))?;
+    Some(xml[start..end].to_string())
+}
@@ -0,0 +1,153 @@
+//! Derivation path specs with ranges, such as `m/44'/118'/0-4'/0/0-999`, that stand for every
+//! combination of their levels.
+//!
+//! Combinations are numbered in path order, the last level varying fastest, so a spec plugs into
+//! the batch generators as a wallet index: `m/44'/118'/0-4'/0/0-999` numbers
+//! `m/44'/118'/0'/0/0` to `m/44'/118'/0'/0/999` as 0 to 999, then continues with account `1'`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::GeneratorError;
+
+/// Highest BIP-32 child index, hardened or not.
+const MAX_CHILD: u32 = (1 << 31) - 1;
+
+/// One level of a path spec: the child indices `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Level {
+    start: u32,
+    end: u32,
+    hardened: bool,
+    /// Written as `*`, every child index
+    wildcard: bool,
+}
+
+impl Level {
+    fn size(&self) -> u64 {
+        u64::from(self.end - self.start) + 1
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.wildcard, self.start == self.end) {
+            (true, _) => write!(f, "*")?,
+            (false, true) => write!(f, "{}", self.start)?,
+            (false, false) => write!(f, "{}-{}", self.start, self.end)?,
+        }
+        if self.hardened {
+            write!(f, "'")?;
+        }
+        Ok(())
+    }
+}
+
+/// A derivation path whose levels may be ranges (`0-999`) or `*` (0 to 2^31 - 1), with `'` or
+/// `h` marking hardened levels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PathRange {
+    levels: Vec<Level>,
+}
+
+impl PathRange {
+    /// Number of paths the spec stands for, or `None` past `u64::MAX`.
+    pub fn count(&self) -> Option<u64> {
+        self.levels.iter().try_fold(1u64, |len, level| len.checked_mul(level.size()))
+    }
+
+    /// Whether every level is hardened, as ed25519 derivation requires.
+    pub fn is_hardened(&self) -> bool {
+        self.levels.iter().all(|level| level.hardened)
+    }
+
+    /// The path numbered `index`; indices past [`count`](Self::count) wrap around.
+    pub fn path(&self, index: u64) -> String {
+        let mut rest = index;
+        let mut children: Vec<(u32, bool)> = self
+            .levels
+            .iter()
+            .rev()
+            .map(|level| {
+                let child = level.start + (rest % level.size()) as u32;
+                rest /= level.size();
+                (child, level.hardened)
+            })
+            .collect();
+        children.reverse();
+
+        let mut path = String::from("m");
+        for (child, hardened) in children {
+            path.push('/');
+            path.push_str(&child.to_string());
+            if hardened {
+                path.push('\'');
+            }
+        }
+        path
+    }
+}
+
+impl FromStr for PathRange {
+    type Err = GeneratorError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| GeneratorError::InvalidPath { spec: spec.to_string(), reason };
+        let mut components = spec.trim().split('/');
+        if components.next() != Some("m") {
+            return Err(invalid("paths start with m/".to_string()));
+        }
+
+        let levels = components
+            .map(|component| {
+                let (body, hardened) = match component.strip_suffix(['\'', 'h']) {
+                    Some(body) => (body, true),
+                    None => (component, false),
+                };
+                let child = |text: &str| match text.parse::<u32>() {
+                    Ok(child) if child <= MAX_CHILD => Ok(child),
+                    _ => Err(invalid(format!("{:?} is not a child index from 0 to {}", text, MAX_CHILD))),
+                };
+                let (start, end, wildcard) = match body.split_once('-') {
+                    _ if body == "*" => (0, MAX_CHILD, true),
+                    Some((start, end)) => (child(start)?, child(end)?, false),
+                    None => (child(body)?, child(body)?, false),
+                };
+                if start > end {
+                    return Err(invalid(format!("range {} is empty", body)));
+                }
+                Ok(Level { start, end, hardened, wildcard })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if levels.is_empty() {
+            return Err(invalid("the path has no levels".to_string()));
+        }
+        Ok(Self { levels })
+    }
+}
+
+impl TryFrom<String> for PathRange {
+    type Error = GeneratorError;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        spec.parse()
+    }
+}
+
+impl From<PathRange> for String {
+    fn from(range: PathRange) -> Self {
+        range.to_string()
+    }
+}
+
+impl fmt::Display for PathRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for level in &self.levels {
+            write!(f, "/{}", level)?;
+        }
+        Ok(())
+    }
+}
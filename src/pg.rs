@@ -0,0 +1,130 @@
+//! `--output postgres://...`: wallets loaded into a table with binary COPY, skipping the JSON
+//! intermediate. Rows go in batches, each its own COPY and transaction.
+
+use anyhow::{anyhow, Context, Result};
+use std::io::Write;
+
+use postgres::config::Host;
+use postgres::{Client, Config, NoTls};
+
+use crate::wallet::Wallet;
+
+/// Columns in COPY order; names follow the JSON fields.
+const COLUMNS: &str = "index, address, evm_address, pubkey, pubkey_hex, pubkey_type, private_key, derivation_path";
+
+/// Signature, flags and header extension length that open a binary COPY stream.
+const COPY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+pub struct PostgresOutput {
+    client: Client,
+    /// Quoted, possibly schema-qualified table name
+    table: String,
+    batch_size: usize,
+    name: String,
+}
+
+impl PostgresOutput {
+    /// Connect and create `table` (`name` or `schema.name`) if it does not exist yet.
+    pub fn connect(url: &str, table: &str, batch_size: usize) -> Result<Self> {
+        let config: Config = url.parse().context("Invalid PostgreSQL URL")?;
+        let mut client = config.connect(NoTls).context("Failed to connect to PostgreSQL")?;
+        let quoted = quote_table(table)?;
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {} (
+                    index BIGINT NOT NULL,
+                    address TEXT NOT NULL,
+                    evm_address TEXT,
+                    pubkey TEXT NOT NULL,
+                    pubkey_hex TEXT,
+                    pubkey_type TEXT,
+                    private_key TEXT NOT NULL,
+                    derivation_path TEXT NOT NULL
+                )",
+                quoted
+            ))
+            .with_context(|| format!("Failed to create table {}", table))?;
+
+        Ok(Self { client, table: quoted, batch_size: batch_size.max(1), name: describe(&config, table) })
+    }
+
+    /// Connect without creating anything, for `--dry-run`. Returns the target as [`Self::name`] would.
+    pub fn check(url: &str, table: &str) -> Result<String> {
+        let config: Config = url.parse().context("Invalid PostgreSQL URL")?;
+        quote_table(table)?;
+        config.connect(NoTls).context("Failed to connect to PostgreSQL")?.close()?;
+        Ok(describe(&config, table))
+    }
+
+    /// Database and table, without credentials.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// COPY `wallets` into the table, committing every batch. Returns the rows written.
+    pub fn write<'a, I: IntoIterator<Item = &'a Wallet>>(&mut self, wallets: I) -> Result<u64> {
+        let statement = format!("COPY {} ({}) FROM STDIN (FORMAT binary)", self.table, COLUMNS);
+        let mut wallets = wallets.into_iter().peekable();
+        let mut rows = 0;
+
+        while wallets.peek().is_some() {
+            let mut transaction = self.client.transaction()?;
+            let mut writer = transaction.copy_in(&statement)?;
+            writer.write_all(&encode_copy(wallets.by_ref().take(self.batch_size))?)?;
+            rows += writer.finish()?;
+            transaction.commit()?;
+        }
+        Ok(rows)
+    }
+}
+
+/// Encode `wallets` as a binary COPY stream of [`COLUMNS`]: header, one tuple per wallet, trailer.
+pub fn encode_copy<'a, I: IntoIterator<Item = &'a Wallet>>(wallets: I) -> Result<Vec<u8>> {
+    let mut out = COPY_HEADER.to_vec();
+    for wallet in wallets {
+        let index = i64::try_from(wallet.index)?;
+        out.extend_from_slice(&8i16.to_be_bytes());
+        out.extend_from_slice(&8i32.to_be_bytes());
+        out.extend_from_slice(&index.to_be_bytes());
+        for field in [
+            Some(&wallet.address),
+            wallet.evm_address.as_ref(),
+            Some(&wallet.pubkey),
+            wallet.pubkey_hex.as_ref(),
+            wallet.pubkey_type.as_ref(),
+            Some(&wallet.private_key),
+            Some(&wallet.derivation_path),
+        ] {
+            match field {
+                Some(text) => {
+                    out.extend_from_slice(&i32::try_from(text.len())?.to_be_bytes());
+                    out.extend_from_slice(text.as_bytes());
+                }
+                // NULL
+                None => out.extend_from_slice(&(-1i32).to_be_bytes()),
+            }
+        }
+    }
+    out.extend_from_slice(&(-1i16).to_be_bytes());
+    Ok(out)
+}
+
+/// Database and table; the URL may carry a password, so it is not repeated.
+fn describe(config: &Config, table: &str) -> String {
+    let host = match config.get_hosts().first() {
+        Some(Host::Tcp(host)) => host.clone(),
+        #[cfg(unix)]
+        Some(Host::Unix(path)) => path.display().to_string(),
+        None => "localhost".to_string(),
+    };
+    format!("postgres://{}/{} table {}", host, config.get_dbname().unwrap_or_default(), table)
+}
+
+/// Quote each part of `schema.table` as an identifier.
+fn quote_table(table: &str) -> Result<String> {
+    let parts: Vec<&str> = table.split('.').collect();
+    if parts.len() > 2 || parts.iter().any(|part| part.is_empty()) {
+        return Err(anyhow!("Invalid table name {}", table));
+    }
+    Ok(parts.iter().map(|part| format!("\"{}\"", part.replace('"', "\"\""))).collect::<Vec<_>>().join("."))
+}
@@ -0,0 +1,337 @@
+//! Signing with a secp256k1 key held in an HSM or token through its PKCS#11 module, so a key
+//! that holds value, such as the funder key, never has to be on disk.
+//!
+//! Only the calls needed to find a key pair by label and sign with `CKM_ECDSA` are bound. The
+//! token signs a digest computed here (SHA-256, or Keccak256 for ethsecp256k1), and the `r || s`
+//! it returns is normalized to a low S, as Cosmos SDK chains require, and checked against the
+//! public key before use.
+
+use anyhow::{anyhow, Context, Result};
+use libloading::Library;
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message as SecpMessage, PublicKey, Secp256k1};
+use std::ffi::c_void;
+use std::os::raw::c_ulong;
+use std::path::Path;
+use std::ptr;
+
+use crate::signer::Signer;
+use crate::tx::payload_digest;
+use crate::types::KeyType;
+
+type CkUlong = c_ulong;
+type CkRv = CkUlong;
+/// An entry of the function list this module does not call.
+type Unused = *const c_void;
+
+const CKR_OK: CkRv = 0x0;
+const CKR_SLOT_ID_INVALID: CkRv = 0x3;
+const CKR_FUNCTION_NOT_SUPPORTED: CkRv = 0x54;
+const CKR_KEY_HANDLE_INVALID: CkRv = 0x60;
+const CKR_MECHANISM_INVALID: CkRv = 0x70;
+const CKR_PIN_INCORRECT: CkRv = 0xA0;
+const CKR_PIN_LOCKED: CkRv = 0xA4;
+const CKR_TOKEN_NOT_PRESENT: CkRv = 0xE0;
+const CKR_USER_ALREADY_LOGGED_IN: CkRv = 0x100;
+const CKR_USER_NOT_LOGGED_IN: CkRv = 0x101;
+const CKR_CRYPTOKI_ALREADY_INITIALIZED: CkRv = 0x191;
+
+const CKF_SERIAL_SESSION: CkUlong = 0x4;
+const CKU_USER: CkUlong = 1;
+const CKA_CLASS: CkUlong = 0x0;
+const CKA_LABEL: CkUlong = 0x3;
+const CKA_KEY_TYPE: CkUlong = 0x100;
+const CKA_EC_PARAMS: CkUlong = 0x180;
+const CKA_EC_POINT: CkUlong = 0x181;
+const CKO_PUBLIC_KEY: CkUlong = 0x2;
+const CKO_PRIVATE_KEY: CkUlong = 0x3;
+const CKK_EC: CkUlong = 0x3;
+const CKM_ECDSA: CkUlong = 0x1041;
+
+/// DER object identifier of secp256k1 (1.3.132.0.10), the `CKA_EC_PARAMS` of its keys.
+const SECP256K1_OID: [u8; 7] = [0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x0A];
+
+#[repr(C)]
+#[cfg_attr(windows, repr(packed))]
+struct CkVersion {
+    major: u8,
+    minor: u8,
+}
+
+#[repr(C)]
+#[cfg_attr(windows, repr(packed))]
+struct CkAttribute {
+    kind: CkUlong,
+    value: *mut c_void,
+    len: CkUlong,
+}
+
+#[repr(C)]
+#[cfg_attr(windows, repr(packed))]
+struct CkMechanism {
+    mechanism: CkUlong,
+    parameter: *mut c_void,
+    len: CkUlong,
+}
+
+/// `CK_FUNCTION_LIST` up to `C_Sign`, in the order the standard fixes.
+#[repr(C)]
+#[cfg_attr(windows, repr(packed))]
+struct FunctionList {
+    version: CkVersion,
+    initialize: unsafe extern "C" fn(*mut c_void) -> CkRv,
+    finalize: unsafe extern "C" fn(*mut c_void) -> CkRv,
+    get_info: Unused,
+    get_function_list: Unused,
+    get_slot_list: unsafe extern "C" fn(u8, *mut CkUlong, *mut CkUlong) -> CkRv,
+    get_slot_info: Unused,
+    get_token_info: Unused,
+    get_mechanism_list: Unused,
+    get_mechanism_info: Unused,
+    init_token: Unused,
+    init_pin: Unused,
+    set_pin: Unused,
+    open_session: unsafe extern "C" fn(CkUlong, CkUlong, *mut c_void, *mut c_void, *mut CkUlong) -> CkRv,
+    close_session: unsafe extern "C" fn(CkUlong) -> CkRv,
+    close_all_sessions: Unused,
+    get_session_info: Unused,
+    get_operation_state: Unused,
+    set_operation_state: Unused,
+    login: unsafe extern "C" fn(CkUlong, CkUlong, *const u8, CkUlong) -> CkRv,
+    logout: Unused,
+    create_object: Unused,
+    copy_object: Unused,
+    destroy_object: Unused,
+    get_object_size: Unused,
+    get_attribute_value: unsafe extern "C" fn(CkUlong, CkUlong, *mut CkAttribute, CkUlong) -> CkRv,
+    set_attribute_value: Unused,
+    find_objects_init: unsafe extern "C" fn(CkUlong, *mut CkAttribute, CkUlong) -> CkRv,
+    find_objects: unsafe extern "C" fn(CkUlong, *mut CkUlong, CkUlong, *mut CkUlong) -> CkRv,
+    find_objects_final: unsafe extern "C" fn(CkUlong) -> CkRv,
+    encrypt_init: Unused,
+    encrypt: Unused,
+    encrypt_update: Unused,
+    encrypt_final: Unused,
+    decrypt_init: Unused,
+    decrypt: Unused,
+    decrypt_update: Unused,
+    decrypt_final: Unused,
+    digest_init: Unused,
+    digest: Unused,
+    digest_update: Unused,
+    digest_key: Unused,
+    digest_final: Unused,
+    sign_init: unsafe extern "C" fn(CkUlong, *mut CkMechanism, CkUlong) -> CkRv,
+    sign: unsafe extern "C" fn(CkUlong, *const u8, CkUlong, *mut u8, *mut CkUlong) -> CkRv,
+}
+
+type GetFunctionList = unsafe extern "C" fn(*mut *const FunctionList) -> CkRv;
+
+fn check(rv: CkRv, call: &str) -> Result<()> {
+    let name = match rv {
+        CKR_OK => return Ok(()),
+        CKR_SLOT_ID_INVALID => "CKR_SLOT_ID_INVALID",
+        CKR_FUNCTION_NOT_SUPPORTED => "CKR_FUNCTION_NOT_SUPPORTED",
+        CKR_KEY_HANDLE_INVALID => "CKR_KEY_HANDLE_INVALID",
+        CKR_MECHANISM_INVALID => "CKR_MECHANISM_INVALID",
+        CKR_PIN_INCORRECT => "CKR_PIN_INCORRECT",
+        CKR_PIN_LOCKED => "CKR_PIN_LOCKED",
+        CKR_TOKEN_NOT_PRESENT => "CKR_TOKEN_NOT_PRESENT",
+        CKR_USER_NOT_LOGGED_IN => "CKR_USER_NOT_LOGGED_IN (pass --pkcs11-pin)",
+        _ => return Err(anyhow!("{} failed with CK_RV 0x{:x}", call, rv)),
+    };
+    Err(anyhow!("{} failed with {}", call, name))
+}
+
+fn ulong_attribute(kind: CkUlong, value: &mut CkUlong) -> CkAttribute {
+    CkAttribute { kind, value: value as *mut CkUlong as *mut c_void, len: std::mem::size_of::<CkUlong>() as CkUlong }
+}
+
+/// Where to find a key pair on a token.
+#[derive(Debug, Clone)]
+pub struct Pkcs11Config<'a> {
+    /// The vendor's PKCS#11 module, e.g. `/usr/lib/softhsm/libsofthsm2.so`
+    pub module: &'a Path,
+    /// Slot holding the token; the first slot with a token when `None`
+    pub slot: Option<u64>,
+    /// `CKA_LABEL` shared by the private and public key objects
+    pub label: &'a str,
+    /// User PIN; the session is not logged in when `None`
+    pub pin: Option<&'a str>,
+}
+
+/// An open session on a token; closed, and the module finalized, when dropped.
+struct Session {
+    functions: *const FunctionList,
+    handle: CkUlong,
+    // Dropped last: the function list points into the module
+    _module: Library,
+}
+
+impl Session {
+    fn open(config: &Pkcs11Config) -> Result<Self> {
+        let module = unsafe { Library::new(config.module) }
+            .with_context(|| format!("Failed to load PKCS#11 module {}", config.module.display()))?;
+        let functions = unsafe {
+            let get_function_list = module
+                .get::<GetFunctionList>(b"C_GetFunctionList\0")
+                .with_context(|| format!("{} is not a PKCS#11 module", config.module.display()))?;
+            let mut functions: *const FunctionList = ptr::null();
+            check(get_function_list(&mut functions), "C_GetFunctionList")?;
+            if functions.is_null() {
+                return Err(anyhow!("{} returned no function list", config.module.display()));
+            }
+            functions
+        };
+        let f = unsafe { &*functions };
+
+        let rv = unsafe { (f.initialize)(ptr::null_mut()) };
+        if rv != CKR_CRYPTOKI_ALREADY_INITIALIZED {
+            check(rv, "C_Initialize")?;
+        }
+
+        let slot = match config.slot {
+            Some(slot) => slot as CkUlong,
+            None => {
+                let mut count: CkUlong = 0;
+                check(unsafe { (f.get_slot_list)(1, ptr::null_mut(), &mut count) }, "C_GetSlotList")?;
+                let mut slots = vec![0 as CkUlong; count as usize];
+                check(unsafe { (f.get_slot_list)(1, slots.as_mut_ptr(), &mut count) }, "C_GetSlotList")?;
+                *slots.first().ok_or_else(|| anyhow!("No PKCS#11 slot holds a token"))?
+            }
+        };
+
+        let mut handle: CkUlong = 0;
+        check(
+            unsafe { (f.open_session)(slot, CKF_SERIAL_SESSION, ptr::null_mut(), ptr::null_mut(), &mut handle) },
+            "C_OpenSession",
+        )?;
+        let session = Self { functions, handle, _module: module };
+        if let Some(pin) = config.pin {
+            let rv = unsafe { (f.login)(handle, CKU_USER, pin.as_ptr(), pin.len() as CkUlong) };
+            if rv != CKR_USER_ALREADY_LOGGED_IN {
+                check(rv, "C_Login")?;
+            }
+        }
+        Ok(session)
+    }
+
+    fn functions(&self) -> &FunctionList {
+        unsafe { &*self.functions }
+    }
+
+    /// The one EC key object of `class` labelled `label`.
+    fn find(&self, class: CkUlong, label: &str) -> Result<CkUlong> {
+        let f = self.functions();
+        let (mut class_value, mut key_type) = (class, CKK_EC);
+        let mut template = [
+            ulong_attribute(CKA_CLASS, &mut class_value),
+            ulong_attribute(CKA_KEY_TYPE, &mut key_type),
+            CkAttribute { kind: CKA_LABEL, value: label.as_ptr() as *mut c_void, len: label.len() as CkUlong },
+        ];
+        check(
+            unsafe { (f.find_objects_init)(self.handle, template.as_mut_ptr(), template.len() as CkUlong) },
+            "C_FindObjectsInit",
+        )?;
+        let mut objects = [0 as CkUlong; 2];
+        let mut found: CkUlong = 0;
+        let rv = unsafe { (f.find_objects)(self.handle, objects.as_mut_ptr(), objects.len() as CkUlong, &mut found) };
+        check(unsafe { (f.find_objects_final)(self.handle) }, "C_FindObjectsFinal")?;
+        check(rv, "C_FindObjects")?;
+
+        let kind = if class == CKO_PRIVATE_KEY { "private" } else { "public" };
+        match found {
+            1 => Ok(objects[0]),
+            0 => Err(anyhow!("No EC {} key labelled {:?} on the token", kind, label)),
+            _ => Err(anyhow!("Several EC {} keys are labelled {:?}; labels must be unique", kind, label)),
+        }
+    }
+
+    fn attribute(&self, object: CkUlong, kind: CkUlong) -> Result<Vec<u8>> {
+        let f = self.functions();
+        let mut attribute = CkAttribute { kind, value: ptr::null_mut(), len: 0 };
+        check(unsafe { (f.get_attribute_value)(self.handle, object, &mut attribute, 1) }, "C_GetAttributeValue")?;
+        let mut value = vec![0u8; attribute.len as usize];
+        attribute.value = value.as_mut_ptr() as *mut c_void;
+        check(unsafe { (f.get_attribute_value)(self.handle, object, &mut attribute, 1) }, "C_GetAttributeValue")?;
+        value.truncate(attribute.len as usize);
+        Ok(value)
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let f = self.functions();
+        unsafe {
+            (f.close_session)(self.handle);
+            (f.finalize)(ptr::null_mut());
+        }
+    }
+}
+
+/// A secp256k1 key pair on a PKCS#11 token.
+pub struct Pkcs11Signer {
+    session: Session,
+    key: CkUlong,
+    public_key: PublicKey,
+    key_type: KeyType,
+}
+
+impl Pkcs11Signer {
+    /// Load `config.module`, open a session on the token and find the key pair labelled
+    /// `config.label`. The public key object must hold a secp256k1 point.
+    pub fn open(config: &Pkcs11Config, key_type: KeyType) -> Result<Self> {
+        let session = Session::open(config)?;
+        let key = session.find(CKO_PRIVATE_KEY, config.label)?;
+        let public = session.find(CKO_PUBLIC_KEY, config.label)?;
+        if session.attribute(public, CKA_EC_PARAMS)? != SECP256K1_OID {
+            return Err(anyhow!("Key {:?} is not a secp256k1 key", config.label));
+        }
+        let public_key = parse_ec_point(&session.attribute(public, CKA_EC_POINT)?)
+            .with_context(|| format!("Invalid public key for {:?}", config.label))?;
+        Ok(Self { session, key, public_key, key_type })
+    }
+}
+
+/// A `CKA_EC_POINT`: an uncompressed point, usually wrapped in a DER OCTET STRING.
+fn parse_ec_point(point: &[u8]) -> Result<PublicKey> {
+    let point = match point {
+        [0x04, len, rest @ ..] if usize::from(*len) == rest.len() => rest,
+        _ => point,
+    };
+    Ok(PublicKey::from_slice(point)?)
+}
+
+impl Signer for Pkcs11Signer {
+    fn key_type(&self) -> &KeyType {
+        &self.key_type
+    }
+
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<[u8; 64]> {
+        let f = self.session.functions();
+        let session = self.session.handle;
+        let digest = payload_digest(payload, &self.key_type);
+        let mut mechanism = CkMechanism { mechanism: CKM_ECDSA, parameter: ptr::null_mut(), len: 0 };
+        check(unsafe { (f.sign_init)(session, &mut mechanism, self.key) }, "C_SignInit")?;
+        let mut signature = [0u8; 64];
+        let mut len = signature.len() as CkUlong;
+        check(
+            unsafe { (f.sign)(session, digest.as_ptr(), digest.len() as CkUlong, signature.as_mut_ptr(), &mut len) },
+            "C_Sign",
+        )?;
+        if len as usize != signature.len() {
+            return Err(anyhow!("C_Sign returned a {}-byte signature; expected 64", len));
+        }
+
+        let mut signature = Signature::from_compact(&signature)?;
+        signature.normalize_s();
+        Secp256k1::verification_only()
+            .verify_ecdsa(&SecpMessage::from_digest(digest), &signature, &self.public_key)
+            .map_err(|_| anyhow!("The token's signature does not match its public key"))?;
+        Ok(signature.serialize_compact())
+    }
+}
@@ -0,0 +1,224 @@
+//! Shared state of a generation run: per-thread progress, write progress, pause and abort.
+//!
+//! Workers count into their own counter and check [`RunState::proceed`] between chunks; a progress
+//! bar or dashboard reads the counters from another thread. With a rate limit, workers also wait
+//! for their turn with [`RunState::throttle`], and with a time budget they start no chunk once
+//! [`RunState::within_deadline`] turns false.
+
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::throttle::RateLimiter;
+
+pub struct RunState {
+    total: usize,
+    threads: Vec<Arc<AtomicUsize>>,
+    /// Wallets kept by a filter
+    matched: AtomicUsize,
+    written: AtomicUsize,
+    /// Size of the output so far
+    bytes_written: AtomicU64,
+    /// Generated segments waiting for the writer
+    queued: AtomicUsize,
+    writing: AtomicBool,
+    paused: AtomicBool,
+    aborted: AtomicBool,
+    finished: AtomicBool,
+    rate_limit: Option<RateLimiter>,
+    deadline: Option<Instant>,
+}
+
+impl RunState {
+    pub fn new(total: usize, threads: usize) -> Self {
+        Self {
+            total,
+            threads: (0..threads.max(1)).map(|_| Arc::new(AtomicUsize::new(0))).collect(),
+            matched: AtomicUsize::new(0),
+            written: AtomicUsize::new(0),
+            bytes_written: AtomicU64::new(0),
+            queued: AtomicUsize::new(0),
+            writing: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            aborted: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            rate_limit: None,
+            deadline: None,
+        }
+    }
+
+    /// Cap generation at `per_second` wallets across all workers.
+    pub fn with_rate_limit(mut self, per_second: Option<u64>) -> Self {
+        self.rate_limit = per_second.map(RateLimiter::new);
+        self
+    }
+
+    /// Stop starting work `budget` from now.
+    pub fn with_time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.deadline = budget.map(|budget| Instant::now() + budget);
+        self
+    }
+
+    /// Whether the time budget, if any, has time left.
+    pub fn within_deadline(&self) -> bool {
+        self.deadline.is_none_or(|deadline| Instant::now() < deadline)
+    }
+
+    /// Wallets the run will generate, at most with a time budget.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Progress counter of worker `thread`, to pass to the generator.
+    pub fn thread_counter(&self, thread: usize) -> Arc<AtomicUsize> {
+        self.threads[thread].clone()
+    }
+
+    /// Wallets generated by each worker.
+    pub fn thread_counts(&self) -> Vec<usize> {
+        self.threads.iter().map(|counter| counter.load(Ordering::Relaxed)).collect()
+    }
+
+    /// Wallets generated by all workers.
+    pub fn generated(&self) -> usize {
+        self.threads.iter().map(|counter| counter.load(Ordering::Relaxed)).sum()
+    }
+
+    pub fn records_matched(&self, count: usize) {
+        self.matched.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Wallets a filter has kept, of those generated.
+    pub fn matched(&self) -> usize {
+        self.matched.load(Ordering::Relaxed)
+    }
+
+    pub fn start_writing(&self) {
+        self.writing.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_writing(&self) -> bool {
+        self.writing.load(Ordering::Relaxed)
+    }
+
+    pub fn record_written(&self) {
+        self.written.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn records_written(&self, count: usize) {
+        self.written.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn written(&self) -> usize {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    pub fn set_bytes_written(&self, bytes: u64) {
+        self.bytes_written.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Size of the output so far, where the output is a byte stream or file.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// A segment entered the queue to the writer.
+    pub fn segment_queued(&self) {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A segment left the queue, taken by the writer or never sent.
+    pub fn segment_dequeued(&self) {
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Segments generated and waiting to be written.
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Ask workers to stop at their next chunk boundary.
+    pub fn abort(&self) {
+        self.aborted.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Relaxed)
+    }
+
+    /// Mark the run over, successful or not, so observers stop.
+    pub fn finish(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// Block while the run is paused; false once it has been aborted.
+    pub fn proceed(&self) -> bool {
+        while self.is_paused() && !self.is_aborted() {
+            thread::sleep(Duration::from_millis(50));
+        }
+        !self.is_aborted()
+    }
+
+    /// Block until the rate limit allows `wallets` more wallets; false once the run has been
+    /// aborted or its time budget runs out first. Returns at once without a rate limit.
+    pub fn throttle(&self, wallets: usize) -> bool {
+        let Some(limiter) = &self.rate_limit else {
+            return !self.is_aborted();
+        };
+        let until = Instant::now() + limiter.reserve(wallets);
+        while let Some(wait) = until.checked_duration_since(Instant::now()).filter(|wait| !wait.is_zero()) {
+            if self.is_aborted() || !self.within_deadline() {
+                return false;
+            }
+            thread::sleep(wait.min(Duration::from_millis(50)));
+        }
+        !self.is_aborted()
+    }
+}
+
+/// Parse a duration such as `10m`, `90s`, `1h30m` or `2.5h`: numbers with a unit of `ms`, `s`,
+/// `m`, `h` or `d`, summed, or seconds without a unit.
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let invalid = || anyhow!("Invalid duration {:?}; use e.g. 90s, 10m, 1h30m or 2.5h", value);
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    while !rest.is_empty() {
+        let split = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+        let (number, after) = rest.split_at(split);
+        let unit_len = after.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        let seconds = match unit.trim().to_ascii_lowercase().as_str() {
+            "ms" => number / 1_000.0,
+            "" | "s" => number,
+            "m" => number * 60.0,
+            "h" => number * 3_600.0,
+            "d" => number * 86_400.0,
+            _ => return Err(invalid()),
+        };
+        total += Duration::try_from_secs_f64(seconds).map_err(|_| invalid())?;
+        rest = after;
+    }
+    if total.is_zero() {
+        return Err(anyhow!("Duration {:?} is zero", value));
+    }
+    Ok(total)
+}
@@ -0,0 +1,116 @@
+//! PyO3 bindings exposed as the `wallet_factory` Python module.
+//!
+//! Derivation runs with the GIL released and is split across rayon workers,
+//! so Python callers get the same throughput as the CLI without going through
+//! a subprocess and a JSON round-trip.
+
+use bip39::Mnemonic;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use crate::types::KeyType;
+use crate::generator::{generate_addresses, generate_wallets_parallel, DerivationConfig};
+use crate::wallet::Wallet;
+
+/// Wallets derived per rayon task.
+const CHUNK_SIZE: usize = 10_000;
+
+fn value_error<E: std::fmt::Display>(e: E) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+fn parse_key_type(key_type: &str) -> PyResult<KeyType> {
+    key_type.parse().map_err(|_| value_error(format!("Unknown key type: {}", key_type)))
+}
+
+fn wallet_to_dict<'py>(py: Python<'py>, wallet: &Wallet) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("address", &wallet.address)?;
+    if let Some(evm) = &wallet.evm_address {
+        dict.set_item("evmAddress", evm)?;
+    }
+    dict.set_item("pubkey", &wallet.pubkey)?;
+    dict.set_item("privateKey", &wallet.private_key)?;
+    dict.set_item("derivationPath", &wallet.derivation_path)?;
+    dict.set_item("index", wallet.index)?;
+    if let Some(pubkey_hex) = &wallet.pubkey_hex {
+        dict.set_item("pubkeyHex", pubkey_hex)?;
+    }
+    if let Some(pubkey_type) = &wallet.pubkey_type {
+        dict.set_item("pubkeyType", pubkey_type)?;
+    }
+    if let Some(wif) = &wallet.wif {
+        dict.set_item("wif", wif)?;
+    }
+    Ok(dict)
+}
+
+/// Generate `count` wallets from a BIP39 mnemonic.
+///
+/// Returns a list of dicts with the same keys as the CLI's JSON output.
+#[pyfunction]
+#[pyo3(signature = (mnemonic, count, start_index=0, prefix="cosmos", key_type="secp256k1"))]
+fn generate_wallets<'py>(
+    py: Python<'py>,
+    mnemonic: &str,
+    count: usize,
+    start_index: usize,
+    prefix: &str,
+    key_type: &str,
+) -> PyResult<Bound<'py, PyList>> {
+    let key_type = parse_key_type(key_type)?;
+    let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| value_error(format!("Invalid mnemonic: {}", e)))?;
+    let seed = mnemonic.to_seed("");
+
+    let wallets = py
+        .allow_threads(|| {
+            let progress = Arc::new(AtomicUsize::new(0));
+            generate_wallets_parallel(&seed, start_index, count, &DerivationConfig::new(prefix, key_type), CHUNK_SIZE, progress)
+        })
+        .map_err(value_error)?;
+
+    let list = PyList::empty(py);
+    for wallet in &wallets {
+        list.append(wallet_to_dict(py, wallet)?)?;
+    }
+    Ok(list)
+}
+
+/// Derive the address set for a single hex-encoded private key.
+#[pyfunction]
+#[pyo3(signature = (private_key_hex, prefix="cosmos", key_type="secp256k1"))]
+fn derive_addresses<'py>(
+    py: Python<'py>,
+    private_key_hex: &str,
+    prefix: &str,
+    key_type: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let key_type = parse_key_type(key_type)?;
+    let bytes = hex::decode(private_key_hex.trim_start_matches("0x"))
+        .map_err(|e| value_error(format!("Invalid private key hex: {}", e)))?;
+    let private_key: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| value_error("Private key must be 32 bytes"))?;
+
+    let (address, evm_address, pubkey, private_key_hex) =
+        generate_addresses(&private_key, prefix, &key_type).map_err(value_error)?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("address", address)?;
+    if let Some(evm) = evm_address {
+        dict.set_item("evmAddress", evm)?;
+    }
+    dict.set_item("pubkey", pubkey)?;
+    dict.set_item("privateKey", private_key_hex)?;
+    Ok(dict)
+}
+
+#[pymodule]
+fn wallet_factory(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(generate_wallets, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_addresses, m)?)?;
+    Ok(())
+}
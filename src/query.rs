@@ -0,0 +1,209 @@
+//! Record lookup in wallet files, with a sidecar index for large files.
+//!
+//! The index (`<file>.idx`) is a sorted table of `(key hash, byte offset)` pairs covering each
+//! record's address, EVM address and derivation index. Lookups binary-search the table on disk and
+//! re-check every candidate record, so hash collisions cannot return a wrong wallet.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::generator::derivation_index;
+use crate::wallet::Wallet;
+use crate::wallet_file::{record_body, WalletReader};
+
+const INDEX_MAGIC: &[u8; 8] = b"WFIDX001";
+/// Magic, source length, source mtime and entry count.
+const INDEX_HEADER_LEN: u64 = 32;
+const ENTRY_LEN: u64 = 16;
+
+/// Files at least this large get an index built on first query.
+pub const AUTO_INDEX_SIZE: u64 = 256 * 1024 * 1024;
+
+/// What to look a record up by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordKey {
+    Address(String),
+    EvmAddress(String),
+    /// Last component of the derivation path
+    Index(u64),
+}
+
+impl RecordKey {
+    fn hash(&self) -> u64 {
+        match self {
+            RecordKey::Address(address) => fnv1a(b'a', address.as_bytes()),
+            RecordKey::EvmAddress(address) => fnv1a(b'e', address.to_ascii_lowercase().as_bytes()),
+            RecordKey::Index(index) => fnv1a(b'i', &index.to_le_bytes()),
+        }
+    }
+
+    pub fn matches(&self, wallet: &Wallet) -> bool {
+        match self {
+            RecordKey::Address(address) => wallet.address == *address,
+            RecordKey::EvmAddress(address) => wallet.evm_address.as_deref().is_some_and(|evm| evm.eq_ignore_ascii_case(address)),
+            RecordKey::Index(index) => derivation_index(&wallet.derivation_path) == Some(*index),
+        }
+    }
+}
+
+/// Keys a record can be found by.
+fn record_keys(wallet: &Wallet) -> impl Iterator<Item = RecordKey> + '_ {
+    std::iter::once(RecordKey::Address(wallet.address.clone()))
+        .chain(wallet.evm_address.clone().map(RecordKey::EvmAddress))
+        .chain(derivation_index(&wallet.derivation_path).map(RecordKey::Index))
+}
+
+/// FNV-1a over a kind tag and the key bytes; stable across builds, unlike `DefaultHasher`.
+fn fnv1a(kind: u8, bytes: &[u8]) -> u64 {
+    std::iter::once(kind).chain(bytes.iter().copied()).fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Parse the record starting at `offset`, if the line holds one.
+fn read_record_at(reader: &mut BufReader<File>, offset: u64, line: &mut String) -> Result<Option<Wallet>> {
+    reader.seek(SeekFrom::Start(offset))?;
+    line.clear();
+    reader.read_line(line)?;
+    let body = record_body(line);
+    if body.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(body).with_context(|| format!("Invalid record at byte {}", offset))?))
+}
+
+pub fn index_path(path: &Path) -> PathBuf {
+    let mut index = path.as_os_str().to_owned();
+    index.push(".idx");
+    PathBuf::from(index)
+}
+
+/// Length and modification time identifying the version of a file an index was built from.
+fn source_stamp(path: &Path) -> Result<(u64, u64)> {
+    let metadata = fs::metadata(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mtime = metadata.modified()?.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    Ok((metadata.len(), mtime))
+}
+
+/// Build `<path>.idx`, returning the number of records indexed.
+pub fn build_index(path: &Path) -> Result<usize> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut reader = BufReader::with_capacity(8 * 1024 * 1024, file);
+    let mut entries: Vec<(u64, u64)> = Vec::new();
+    let mut line = String::new();
+    let mut offset = 0u64;
+    let mut records = 0;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 {
+            break;
+        }
+        let body = record_body(&line);
+        if !body.is_empty() {
+            let wallet: Wallet = serde_json::from_str(body).with_context(|| format!("Invalid record at byte {}", offset))?;
+            entries.extend(record_keys(&wallet).map(|key| (key.hash(), offset)));
+            records += 1;
+        }
+        offset += read as u64;
+    }
+    entries.sort_unstable();
+
+    let (len, mtime) = source_stamp(path)?;
+    let index = index_path(path);
+    let mut writer = BufWriter::with_capacity(8 * 1024 * 1024, File::create(&index).with_context(|| format!("Failed to create {}", index.display()))?);
+    writer.write_all(INDEX_MAGIC)?;
+    for value in [len, mtime, entries.len() as u64] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    for (hash, offset) in entries {
+        writer.write_all(&hash.to_le_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+    }
+    writer.flush()?;
+    Ok(records)
+}
+
+/// An open sidecar index.
+pub struct WalletIndex {
+    index: File,
+    entries: u64,
+}
+
+impl WalletIndex {
+    /// Open the index of `path`, or `None` if it is missing or was built from a different version of the file.
+    pub fn open(path: &Path) -> Result<Option<Self>> {
+        let Ok(mut index) = File::open(index_path(path)) else {
+            return Ok(None);
+        };
+        let mut header = [0u8; INDEX_HEADER_LEN as usize];
+        if index.read_exact(&mut header).is_err() || &header[..8] != INDEX_MAGIC {
+            return Ok(None);
+        }
+        let field = |i: usize| u64::from_le_bytes(header[8 * i..8 * (i + 1)].try_into().unwrap());
+        if (field(1), field(2)) != source_stamp(path)? {
+            return Ok(None);
+        }
+        Ok(Some(Self { index, entries: field(3) }))
+    }
+
+    fn entry(&mut self, position: u64) -> Result<(u64, u64)> {
+        let mut entry = [0u8; ENTRY_LEN as usize];
+        self.index.seek(SeekFrom::Start(INDEX_HEADER_LEN + position * ENTRY_LEN))?;
+        self.index.read_exact(&mut entry)?;
+        Ok((u64::from_le_bytes(entry[..8].try_into().unwrap()), u64::from_le_bytes(entry[8..].try_into().unwrap())))
+    }
+
+    /// Byte offsets of records whose key hashes like `key`.
+    fn candidates(&mut self, key: &RecordKey) -> Result<Vec<u64>> {
+        let hash = key.hash();
+        let (mut low, mut high) = (0, self.entries);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.entry(mid)?.0 < hash {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let mut offsets = Vec::new();
+        while low < self.entries {
+            let (entry_hash, offset) = self.entry(low)?;
+            if entry_hash != hash {
+                break;
+            }
+            offsets.push(offset);
+            low += 1;
+        }
+        Ok(offsets)
+    }
+}
+
+/// Every record in `path` matching `key`, using the sidecar index when one is up to date.
+pub fn find_records(path: &Path, key: &RecordKey, use_index: bool) -> Result<Vec<Wallet>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut reader = BufReader::with_capacity(64 * 1024, file);
+    let mut line = String::new();
+
+    if use_index {
+        if let Some(mut index) = WalletIndex::open(path)? {
+            let mut found = Vec::new();
+            for offset in index.candidates(key)? {
+                if let Some(wallet) = read_record_at(&mut reader, offset, &mut line)?.filter(|w| key.matches(w)) {
+                    found.push(wallet);
+                }
+            }
+            return Ok(found);
+        }
+    }
+
+    WalletReader::open(path)?
+        .filter(|record| record.as_ref().map_or(true, |wallet| key.matches(wallet)))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| anyhow!("Failed to scan {}: {}", path.display(), e))
+}
@@ -0,0 +1,131 @@
+//! HD wallet recovery: discover every used account of a mnemonic from live chain state.
+//!
+//! Follows BIP-44 account discovery: indices of an account are scanned until `gap_limit`
+//! consecutive addresses are unused, and accounts are scanned until one has no used address.
+
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::types::KeyType;
+use crate::coin::Coin;
+use crate::generator::{derive_wallet, INDEX_PLACEHOLDER};
+use crate::lcd::LcdClient;
+use crate::wallet::Wallet;
+
+/// Placeholder for the BIP-44 account in path templates such as `m/44'/118'/{account}'/0/{index}`.
+pub const ACCOUNT_PLACEHOLDER: &str = "{account}";
+
+/// A derived address with on-chain activity.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredAccount {
+    #[serde(flatten)]
+    pub wallet: Wallet,
+    /// Missing when the address holds funds but has no auth account yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
+    pub balances: Vec<Coin>,
+}
+
+pub struct RecoverConfig<'a> {
+    /// Path templates containing `{account}` and/or `{index}`
+    pub templates: &'a [String],
+    pub prefix: &'a str,
+    pub key_type: &'a KeyType,
+    /// Consecutive unused indices after which an account is considered exhausted
+    pub gap_limit: u64,
+    /// Upper bound on accounts scanned per template
+    pub max_accounts: u64,
+    pub concurrency: usize,
+}
+
+fn expand(template: &str, account: u64, index: u64) -> String {
+    template
+        .replace(ACCOUNT_PLACEHOLDER, &account.to_string())
+        .replace(INDEX_PLACEHOLDER, &index.to_string())
+}
+
+/// Derive and look up one address, returning it if it exists on chain or holds funds.
+fn check(client: &LcdClient, seed: &[u8], path: &str, config: &RecoverConfig) -> Result<Option<RecoveredAccount>> {
+    let wallet = derive_wallet(seed, path, config.prefix, config.key_type)?;
+    let info = client.account(&wallet.address)?;
+    let balances = client.balances(&wallet.address)?;
+    if info.is_none() && balances.iter().all(|coin| coin.amount_u128() == 0) {
+        return Ok(None);
+    }
+    Ok(Some(RecoveredAccount {
+        wallet,
+        account_number: info.map(|i| i.account_number),
+        sequence: info.map(|i| i.sequence),
+        balances,
+    }))
+}
+
+/// Scan the indices of one account until `gap_limit` consecutive addresses are unused.
+fn scan_account(
+    client: &LcdClient,
+    seed: &[u8],
+    template: &str,
+    account: u64,
+    config: &RecoverConfig,
+    progress: &AtomicUsize,
+) -> Result<Vec<RecoveredAccount>> {
+    if !template.contains(INDEX_PLACEHOLDER) {
+        progress.fetch_add(1, Ordering::Relaxed);
+        return Ok(check(client, seed, &expand(template, account, 0), config)?.into_iter().collect());
+    }
+
+    let mut used = Vec::new();
+    let mut last_used: Option<u64> = None;
+    let mut next = 0;
+    loop {
+        let window_end = last_used.map_or(0, |index| index + 1) + config.gap_limit;
+        if next >= window_end {
+            break;
+        }
+        let found = (next..window_end)
+            .into_par_iter()
+            .map(|index| {
+                let result = check(client, seed, &expand(template, account, index), config);
+                progress.fetch_add(1, Ordering::Relaxed);
+                result.map(|found| found.map(|account| (index, account)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (index, recovered) in found.into_iter().flatten() {
+            last_used = Some(last_used.map_or(index, |last| last.max(index)));
+            used.push(recovered);
+        }
+        next = window_end;
+    }
+    Ok(used)
+}
+
+/// Discover used addresses for every template, in template, account and index order.
+pub fn recover(client: &LcdClient, seed: &[u8], config: &RecoverConfig, progress: &AtomicUsize) -> Result<Vec<RecoveredAccount>> {
+    if config.gap_limit == 0 {
+        return Err(anyhow!("Gap limit must be at least 1"));
+    }
+    if let Some(template) = config.templates.iter().find(|t| !t.contains(ACCOUNT_PLACEHOLDER) && !t.contains(INDEX_PLACEHOLDER)) {
+        return Err(anyhow!("Path template {} has neither {} nor {}", template, ACCOUNT_PLACEHOLDER, INDEX_PLACEHOLDER));
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(config.concurrency.max(1)).build()?;
+    pool.install(|| {
+        let mut recovered = Vec::new();
+        for template in config.templates {
+            let accounts = if template.contains(ACCOUNT_PLACEHOLDER) { config.max_accounts } else { 1 };
+            for account in 0..accounts {
+                let used = scan_account(client, seed, template, account, config, progress)?;
+                if used.is_empty() {
+                    break;
+                }
+                recovered.extend(used);
+            }
+        }
+        Ok(recovered)
+    })
+}
@@ -0,0 +1,68 @@
+//! Existing wallets carried over to another chain: addresses and key encodings recomputed from
+//! the private keys a wallet file already holds, without the mnemonic.
+//!
+//! The keys, derivation paths and indices stay as they were, so a file generated for Cosmos Hub
+//! at coin type 118 becomes the same keys on Osmosis, or on an EVM chain with Keccak addresses.
+//! A wallet re-derived this way matches the chain's own wallets only if they use the same path.
+
+use anyhow::{anyhow, Result};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+use crate::address_encoder;
+use crate::bitcoin::{self, Network};
+use crate::generator::{DerivationConfig, WalletEncoder};
+use crate::sign::wallet_private_key;
+use crate::types::{BitcoinAddress, ChainFamily};
+use crate::wallet::Wallet;
+
+/// `wallet` with the addresses and key encodings `config` would have given it, keeping its key,
+/// derivation path and index. A `pubkeyHex` in the record must match the private key.
+pub fn rederive(wallet: &Wallet, config: &DerivationConfig) -> Result<Wallet> {
+    if config.family == ChainFamily::Solana {
+        return Err(anyhow!("Solana keys are derived with SLIP-0010 rather than BIP-32; generate them from the mnemonic"));
+    }
+    if wallet.pubkey_hex.as_deref().is_some_and(|key| key.len() == 64) {
+        return Err(anyhow!("{} holds an ed25519 key, which has no secp256k1 address", wallet.address));
+    }
+
+    let private_key = wallet_private_key(wallet)?;
+    let public_key = PublicKey::from_secret_key(&Secp256k1::signing_only(), &SecretKey::from_slice(&private_key)?);
+    let pubkey_hex = hex::encode(public_key.serialize());
+    if wallet.pubkey_hex.as_deref().is_some_and(|key| !key.eq_ignore_ascii_case(&pubkey_hex)) {
+        return Err(anyhow!("The private key of {} does not match its pubkeyHex", wallet.address));
+    }
+
+    let (pubkey, pubkey_uncompressed) = config.encode_public_key(&public_key);
+    let mut rederived = Wallet {
+        address: String::new(),
+        evm_address: None,
+        pubkey,
+        private_key: config.encode_private_key(&private_key)?,
+        derivation_path: wallet.derivation_path.clone(),
+        index: wallet.index,
+        pubkey_hex: Some(pubkey_hex),
+        pubkey_type: None,
+        pubkey_uncompressed,
+        secp256k1_address: None,
+        wif: None,
+    };
+    match config.family {
+        ChainFamily::Bitcoin(address_type) => {
+            let network = Network::from_hrp(&config.prefix)?;
+            let hash = bitcoin::hash160(&public_key.serialize());
+            rederived.address = match address_type {
+                BitcoinAddress::P2pkh => bitcoin::p2pkh(&hash, network),
+                BitcoinAddress::P2wpkh => bitcoin::p2wpkh(&hash, network)?,
+            };
+            rederived.wif = Some(bitcoin::wif(&private_key, network));
+        }
+        _ => {
+            let addresses = address_encoder::for_config(config)?.encode(&public_key, &mut WalletEncoder::new(config))?;
+            rederived.address = addresses.address;
+            rederived.evm_address = addresses.evm_address;
+            rederived.secp256k1_address = addresses.secp256k1_address;
+            rederived.pubkey_type = Some(config.key_type.pubkey_type_url().to_string());
+        }
+    }
+    Ok(rederived)
+}
@@ -0,0 +1,245 @@
+//! Machine-readable summary of a generation run, for pipelines that assert on results.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::{AddressEncoding, ChainFamily, DerivationScheme, KeyType, OutputFormat, PrivateKeyFormat, PubkeyFormat};
+
+/// Settings a run was made with. The mnemonic itself is never included.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RunParameters {
+    pub prefix: String,
+    pub key_type: KeyType,
+    pub coin_type: u32,
+    /// Path template with `{index}` for the incremented level
+    pub derivation_path: String,
+    pub encoding: AddressEncoding,
+    pub scheme: DerivationScheme,
+    /// `"cosmos"`, `"solana"` or `{"bitcoin": <address type>}`
+    pub family: ChainFamily,
+    pub pubkey_format: PubkeyFormat,
+    pub privkey_format: PrivateKeyFormat,
+    pub include_uncompressed: bool,
+    /// Whether ethsecp256k1 records carry `secp256k1Address` too
+    #[serde(default)]
+    pub dual_address: bool,
+    /// Whether private keys were encrypted with `--encrypt-keys`
+    #[serde(default)]
+    pub encrypt_keys: bool,
+    /// Absent for database outputs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    pub threads: usize,
+    /// Wallets per work item, when set with --chunk-size rather than sized to the segments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<usize>,
+    /// Wallets per second the run was capped at with --rate-limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<u64>,
+    /// Niceness the run was lowered to with --nice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
+    /// Index of the first wallet; above 0 when appending to an existing file
+    pub start_index: u64,
+    /// Whether the well-known test mnemonic was used because none was given
+    pub default_mnemonic: bool,
+    pub check_duplicates: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_memory_mb: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_evm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotate_every: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotate_size_bytes: Option<u64>,    /// Time budget of a --duration run, which generated as many wallets as it could within it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunReport {
+    /// Version of the generator that produced the output
+    pub version: String,
+    pub wallets: usize,
+    /// Wallets kept by `--filter-address`/`--filter-evm`, and written; absent without a filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched: Option<usize>,
+    pub generation_secs: f64,
+    pub write_secs: f64,
+    pub total_secs: f64,
+    pub wallets_per_sec: f64,
+    /// Output path, object URL or database table, or `-` for stdout
+    pub output: String,
+    /// Absent for database outputs, which are not a byte stream; all files for rotated output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_bytes: Option<u64>,
+    /// Hex SHA-256 of the output as written; of the manifest for rotated output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_sha256: Option<String>,
+    /// Hex SHA-256 of the manifest, for rotated output or `--manifest`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_sha256: Option<String>,
+    pub parameters: RunParameters,
+}
+
+impl RunReport {
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// A file a run wrote, as listed in the audit log.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditArtifact {
+    /// File path, object URL or database table, or `-` for stdout
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub records: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// One line of `--audit-log`: which root secret and index range a run's artifacts came from.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub version: String,
+    /// Unix seconds
+    pub started_at: u64,
+    pub finished_at: u64,
+    /// Hex BIP-32 fingerprint of the master key, which identifies the mnemonic without revealing it
+    pub mnemonic_fingerprint: String,
+    pub first_index: u64,
+    /// Index of the last wallet derived; absent for empty and failed runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_index: Option<u64>,
+    /// Wallets written, after any filter
+    pub written: usize,
+    pub artifacts: Vec<AuditArtifact>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_sha256: Option<String>,
+    /// Why the run ended early; absent for completed runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub parameters: RunParameters,
+}
+
+impl AuditEntry {
+    /// An entry for a run starting now.
+    pub fn new(mnemonic_fingerprint: [u8; 4], parameters: RunParameters) -> Self {
+        let now = unix_time();
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: now,
+            finished_at: now,
+            mnemonic_fingerprint: hex::encode(mnemonic_fingerprint),
+            first_index: parameters.start_index,
+            last_index: None,
+            written: 0,
+            artifacts: Vec::new(),
+            manifest_sha256: None,
+            error: None,
+            parameters,
+        }
+    }
+
+    /// Append the entry to `path` as one JSON line, synced before returning. Earlier lines are
+    /// never rewritten.
+    pub fn append<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_vec(self)?;
+        line.push(b'\n');
+        // One write per line, so concurrent runs appending to the same log do not interleave
+        let mut file = OpenOptions::new().create(true).append(true).open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        file.write_all(&line).and_then(|_| file.sync_data()).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Every entry of an audit log, oldest first.
+    pub fn read_log<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        let path = path.as_ref();
+        let log = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        log.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| serde_json::from_str(line).with_context(|| format!("{} line {}: invalid entry", path.display(), number + 1)))
+            .collect()
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Passes writes through, counting and hashing the bytes, so the output can be described without
+/// reading it back (it may be stdout).
+pub struct DigestWriter<W> {
+    inner: W,
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl<W: Write> DigestWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, hasher: Sha256::new(), bytes: 0 }
+    }
+
+    /// Continue the digest of output that already holds the bytes of `existing`, e.g. a file
+    /// being appended to.
+    pub fn resume<R: Read>(inner: W, mut existing: R) -> io::Result<Self> {
+        let mut writer = Self::new(inner);
+        writer.bytes = io::copy(&mut existing, &mut writer.hasher)?;
+        Ok(writer)
+    }
+
+    /// Bytes written so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Hex SHA-256 of the bytes written so far.
+    pub fn sha256(&self) -> String {
+        hex::encode(self.hasher.clone().finalize())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for DigestWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,210 @@
+//! Output split across numbered files, each a complete document, with a manifest of the sequence.
+//!
+//! The manifest records the size, SHA-256 and record count of every file and the settings the
+//! wallets were generated with, so a copy moved between machines can be checked against it. A
+//! single output file gets one with `--manifest`.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::binary::BinaryReader;
+use crate::report::RunParameters;
+use crate::types::OutputFormat;
+use crate::wallet_file::WalletReader;
+
+/// Bytes kept free below a size limit beyond the largest record so far: enough for the next
+/// record's index to gain a digit in both its path and its index field, and for a JSON array's
+/// closing bracket.
+const SIZE_MARGIN: u64 = 16;
+
+/// When a rotating output rolls to its next file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rotation {
+    /// Records per file
+    pub records: Option<u64>,
+    /// Bytes a file may not exceed
+    pub bytes: Option<u64>,
+}
+
+impl Rotation {
+    /// Whether a file holding `records` records in `bytes` bytes is full before the next record,
+    /// judged by `largest`, the widest record written so far. A file always takes one record.
+    pub fn is_full(&self, records: u64, bytes: u64, largest: u64) -> bool {
+        records > 0
+            && (self.records.is_some_and(|limit| records >= limit)
+                || self.bytes.is_some_and(|limit| bytes + largest + SIZE_MARGIN > limit))
+    }
+}
+
+/// Parse a size such as `1GB`, `512MiB` or `1048576`: decimal units (KB, MB, GB, TB), binary
+/// units (KiB, MiB, GiB, TiB), or bytes without a unit.
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" | "k" => 1_000,
+        "mb" | "m" => 1_000_000,
+        "gb" | "g" => 1_000_000_000,
+        "tb" | "t" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return Err(anyhow!("Unknown size unit in {:?}; use B, KB, MB, GB, TB or KiB, MiB, GiB, TiB", value)),
+    };
+    let number: f64 = number.parse().map_err(|_| anyhow!("Invalid size {:?}", value))?;
+    let bytes = (number * multiplier as f64) as u64;
+    if bytes == 0 {
+        return Err(anyhow!("Size {:?} is zero", value));
+    }
+    Ok(bytes)
+}
+
+/// Path of file `part` (from 1) of a rotating output at `path`: `wallets.json` becomes
+/// `wallets.00001.json`.
+pub fn part_path<P: AsRef<Path>>(path: P, part: usize) -> PathBuf {
+    let path = path.as_ref();
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{:05}.{}", stem, part, extension.to_string_lossy()),
+        None => format!("{}.{:05}", stem, part),
+    };
+    path.with_file_name(name)
+}
+
+/// Path of the manifest of a rotating output at `path`: `wallets.json` becomes
+/// `wallets.manifest.json`.
+pub fn manifest_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    path.with_file_name(format!("{}.manifest.json", path.file_stem().unwrap_or_default().to_string_lossy()))
+}
+
+/// One file of a rotating output.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestPart {
+    /// File name, in the manifest's directory
+    pub path: String,
+    pub records: u64,
+    /// Index of the first wallet; wallets follow in index order
+    pub first_index: u64,
+    pub bytes: u64,
+    /// Hex SHA-256 of the file
+    pub sha256: String,
+}
+
+/// A file of a manifest as found on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartCheck {
+    pub bytes: u64,
+    /// Hex SHA-256 of the file
+    pub sha256: String,
+    /// Records read back, counted only when the digest matches; `None` for Arrow files
+    pub records: Option<u64>,
+}
+
+impl ManifestPart {
+    /// Hash the file in `dir` and, if it matches, count its records; `None` if it is missing.
+    pub fn check<P: AsRef<Path>>(&self, dir: P, format: OutputFormat) -> Result<Option<PartCheck>> {
+        let path = dir.as_ref().join(&self.path);
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(anyhow::Error::new(e).context(format!("Failed to open {}", path.display()))),
+        };
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 1 << 20];
+        let mut bytes = 0u64;
+        loop {
+            let read = file.read(&mut buffer).with_context(|| format!("Failed to read {}", path.display()))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            bytes += read as u64;
+        }
+        let sha256 = hex::encode(hasher.finalize());
+
+        let records = match format {
+            _ if sha256 != self.sha256 => None,
+            OutputFormat::Bin => Some(BinaryReader::open(&path)?.try_fold(0u64, |count, wallet| wallet.map(|_| count + 1))?),
+            OutputFormat::Json | OutputFormat::Ndjson => {
+                Some(WalletReader::open(&path)?.try_fold(0u64, |count, wallet| wallet.map(|_| count + 1))?)
+            }
+            // Rows after the header; no field holds a line break
+            OutputFormat::Csv => {
+                let lines = BufReader::new(File::open(&path)?).lines();
+                Some(lines.skip(1).try_fold(0u64, |count, line| line.map(|line| count + u64::from(!line.is_empty())))?)
+            }
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow => None,
+        };
+        Ok(Some(PartCheck { bytes, sha256, records }))
+    }
+
+    /// How `found` differs from this entry; empty when the file is intact.
+    pub fn differences(&self, found: &PartCheck) -> Vec<String> {
+        let mut differences = Vec::new();
+        if found.bytes != self.bytes {
+            differences.push(format!("{} bytes, expected {}", found.bytes, self.bytes));
+        }
+        if found.sha256 != self.sha256 {
+            differences.push("SHA-256 differs".to_string());
+        }
+        if let Some(records) = found.records.filter(|records| *records != self.records) {
+            differences.push(format!("{} records, expected {}", records, self.records));
+        }
+        differences
+    }
+}
+
+/// The files of an output, in order, and how they were generated.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    /// Version of the generator that wrote the files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub format: OutputFormat,
+    pub wallets: u64,
+    pub files: Vec<ManifestPart>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<RunParameters>,
+}
+
+impl Manifest {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { version: None, format, wallets: 0, files: Vec::new(), parameters: None }
+    }
+
+    /// Record a closed file.
+    pub fn push(&mut self, part: ManifestPart) {
+        self.wallets += part.records;
+        self.files.push(part);
+    }
+
+    /// Total bytes of the files.
+    pub fn bytes(&self) -> u64 {
+        self.files.iter().map(|part| part.bytes).sum()
+    }
+
+    /// Write the manifest to `path`, returning its hex SHA-256.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)? + "\n";
+        fs::write(path, &json).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(hex::encode(Sha256::digest(json)))
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("Invalid manifest {}", path.display()))
+    }
+}
@@ -0,0 +1,119 @@
+//! Minimal blocking client for the CometBFT JSON-RPC API.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Base delay before the first retry; doubled on each attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastMode {
+    /// Return immediately without waiting for CheckTx
+    Async,
+    /// Wait for CheckTx
+    Sync,
+    /// Wait until the transaction is included in a block
+    Commit,
+}
+
+impl BroadcastMode {
+    fn method(self) -> &'static str {
+        match self {
+            BroadcastMode::Async => "broadcast_tx_async",
+            BroadcastMode::Sync => "broadcast_tx_sync",
+            BroadcastMode::Commit => "broadcast_tx_commit",
+        }
+    }
+}
+
+/// Node response to a broadcast.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BroadcastResult {
+    pub hash: String,
+    /// ABCI code; 0 means accepted (for `commit`, the first non-zero of CheckTx and execution)
+    pub code: u32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub log: String,
+    /// Block height, `commit` mode only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+}
+
+/// `code` and `log` of an ABCI response.
+fn abci_status(value: &Value) -> (u32, String) {
+    let code = value["code"].as_u64().unwrap_or(0) as u32;
+    let log = value["log"].as_str().unwrap_or_default().to_string();
+    (code, log)
+}
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+    retries: u32,
+}
+
+impl RpcClient {
+    pub fn new(url: &str, timeout: Duration, retries: u32) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+            retries,
+        }
+    }
+
+    /// Call a JSON-RPC method, retrying transport errors, 429 and 5xx responses.
+    pub fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut attempt = 0;
+
+        loop {
+            let retryable = match self.agent.post(&self.url).send_json(&request) {
+                Ok(response) => {
+                    let mut body: Value = response.into_json()?;
+                    if let Some(error) = body.get("error").filter(|e| !e.is_null()) {
+                        let message = error["message"].as_str().unwrap_or_default();
+                        let data = error["data"].as_str().unwrap_or_default();
+                        return Err(anyhow!("{} failed: {} {}", method, message, data));
+                    }
+                    return Ok(body["result"].take());
+                }
+                Err(ureq::Error::Status(code, response)) if code != 429 && code < 500 => {
+                    let body = response.into_string().unwrap_or_default();
+                    return Err(anyhow!("{} returned {}: {}", method, code, body));
+                }
+                Err(e) => e,
+            };
+
+            if attempt >= self.retries {
+                return Err(anyhow!("{} failed after {} attempts: {}", method, attempt + 1, retryable));
+            }
+            std::thread::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// Submit protobuf `TxRaw` bytes.
+    pub fn broadcast_tx(&self, tx: &[u8], mode: BroadcastMode) -> Result<BroadcastResult> {
+        let result = self.call(mode.method(), json!({ "tx": general_purpose::STANDARD.encode(tx) }))?;
+        let hash = result["hash"].as_str().unwrap_or_default().to_string();
+
+        let (code, log, height) = match mode {
+            BroadcastMode::Commit => {
+                let (check_code, check_log) = abci_status(&result["check_tx"]);
+                // CometBFT 0.38 renamed deliver_tx to tx_result
+                let executed = result.get("tx_result").unwrap_or(&result["deliver_tx"]);
+                let (code, log) = if check_code != 0 { (check_code, check_log) } else { abci_status(executed) };
+                let height = result["height"].as_str().and_then(|h| h.parse().ok());
+                (code, log, height)
+            }
+            _ => {
+                let (code, log) = abci_status(&result);
+                (code, log, None)
+            }
+        };
+        Ok(BroadcastResult { hash, code, log, height })
+    }
+}
@@ -0,0 +1,119 @@
+//! Balance and account scanning of generated wallets against a live chain.
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::coin::Coin;
+use crate::lcd::{AccountInfo, LcdClient};
+use crate::wallet::Wallet;
+
+/// A wallet record annotated with its on-chain balances.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScannedWallet {
+    #[serde(flatten)]
+    pub wallet: Wallet,
+    /// Missing when the query failed; see `error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balances: Option<Vec<Coin>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ScannedWallet {
+    /// Whether any balance is non-zero, optionally restricted to one denom.
+    pub fn is_funded(&self, denom: Option<&str>) -> bool {
+        self.balances.iter().flatten().any(|coin| {
+            coin.amount_u128() > 0 && denom.is_none_or(|d| coin.denom == d)
+        })
+    }
+}
+
+/// Query balances for every wallet on a pool of `concurrency` threads.
+///
+/// Failed queries do not abort the scan; they are reported per record.
+/// Results keep the input order.
+pub fn scan_balances(
+    client: &LcdClient,
+    wallets: Vec<Wallet>,
+    concurrency: usize,
+    progress: &AtomicUsize,
+) -> anyhow::Result<Vec<ScannedWallet>> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency.max(1)).build()?;
+
+    Ok(pool.install(|| {
+        wallets
+            .into_par_iter()
+            .map(|wallet| {
+                let result = client.balances(&wallet.address);
+                progress.fetch_add(1, Ordering::Relaxed);
+                match result {
+                    Ok(balances) => ScannedWallet { wallet, balances: Some(balances), error: None },
+                    Err(e) => ScannedWallet { wallet, balances: None, error: Some(e.to_string()) },
+                }
+            })
+            .collect()
+    }))
+}
+
+/// A wallet record annotated with its on-chain account number and sequence.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AnnotatedWallet {
+    #[serde(flatten)]
+    pub wallet: Wallet,
+    /// Missing when the query failed; see `error`
+    #[serde(rename = "accountExists", skip_serializing_if = "Option::is_none")]
+    pub account_exists: Option<bool>,
+    /// Missing until the account exists, which happens when it first receives funds
+    #[serde(rename = "accountNumber", skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AnnotatedWallet {
+    fn new(wallet: Wallet, result: anyhow::Result<Option<AccountInfo>>) -> Self {
+        match result {
+            Ok(Some(info)) => Self {
+                wallet,
+                account_exists: Some(true),
+                account_number: Some(info.account_number),
+                sequence: Some(info.sequence),
+                error: None,
+            },
+            Ok(None) => Self { wallet, account_exists: Some(false), account_number: None, sequence: None, error: None },
+            Err(e) => Self { wallet, account_exists: None, account_number: None, sequence: None, error: Some(e.to_string()) },
+        }
+    }
+
+    /// Account number and sequence, if the account exists.
+    pub fn account(&self) -> Option<AccountInfo> {
+        Some(AccountInfo { account_number: self.account_number?, sequence: self.sequence? })
+    }
+}
+
+/// Look up the account of every wallet on a pool of `concurrency` threads.
+///
+/// Failed queries do not abort the scan; they are reported per record.
+/// Results keep the input order.
+pub fn annotate_accounts(
+    client: &LcdClient,
+    wallets: Vec<Wallet>,
+    concurrency: usize,
+    progress: &AtomicUsize,
+) -> anyhow::Result<Vec<AnnotatedWallet>> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency.max(1)).build()?;
+
+    Ok(pool.install(|| {
+        wallets
+            .into_par_iter()
+            .map(|wallet| {
+                let result = client.account(&wallet.address);
+                progress.fetch_add(1, Ordering::Relaxed);
+                AnnotatedWallet::new(wallet, result)
+            })
+            .collect()
+    }))
+}
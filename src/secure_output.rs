@@ -0,0 +1,216 @@
+//! Permissions of the files private keys are written to. Wallet outputs are created readable by
+//! their owner only, whatever the process umask, and are kept out of directories other users can
+//! read unless the caller opts out.
+//!
+//! On Unix a file gets `0666 & !umask`, 0600 under the default umask of 077, and missing parent
+//! directories `0777 & !umask`. On Windows a umask that masks all group and other bits replaces
+//! the file's inherited ACL with one granting only the current user; any other umask keeps the
+//! inherited ACL, and directories are not checked.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+/// The umask wallet outputs are created under unless overridden: owner read and write only.
+pub const DEFAULT_UMASK: u32 = 0o077;
+
+/// How wallet outputs are created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputPermissions {
+    /// Permission bits removed from new files and directories, as with `umask(2)`
+    pub umask: u32,
+    /// Allow writing into directories other users can read or write
+    pub insecure: bool,
+}
+
+impl Default for OutputPermissions {
+    fn default() -> Self {
+        Self { umask: DEFAULT_UMASK, insecure: false }
+    }
+}
+
+/// Parse an octal umask such as `077` or `0027`.
+pub fn parse_umask(umask: &str) -> Result<u32> {
+    let digits = umask.trim().trim_start_matches("0o");
+    match u32::from_str_radix(digits, 8) {
+        Ok(mask) if !digits.is_empty() && mask <= 0o777 => Ok(mask),
+        _ => Err(anyhow!("Invalid umask {}: expected up to three octal digits, e.g. 077", umask)),
+    }
+}
+
+impl OutputPermissions {
+    /// Unix permission bits of new files.
+    pub fn file_mode(&self) -> u32 {
+        0o666 & !self.umask
+    }
+
+    /// Unix permission bits of new directories.
+    pub fn dir_mode(&self) -> u32 {
+        0o777 & !self.umask
+    }
+
+    /// Whether only the owner can access new files.
+    pub fn owner_only(&self) -> bool {
+        self.umask & 0o077 == 0o077
+    }
+
+    /// Create or truncate the file at `path` for writing, creating missing parent directories.
+    pub fn create(&self, path: &Path) -> Result<File> {
+        self.open(path, OpenOptions::new().write(true).create(true).truncate(true))
+    }
+
+    /// Open `path` with `options`, creating missing parent directories, and give the file these
+    /// permissions. A file that already existed is tightened too, as creation modes only apply to
+    /// new files.
+    pub fn open(&self, path: &Path, options: &mut OpenOptions) -> Result<File> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            self.create_dir_all(parent)?;
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(options, self.file_mode());
+        let file = options.open(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        self.restrict(&file, path).with_context(|| format!("Failed to set the permissions of {}", path.display()))?;
+        Ok(file)
+    }
+
+    /// Create `dir` and its missing ancestors with these permissions.
+    pub fn create_dir_all(&self, dir: &Path) -> Result<()> {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, self.dir_mode());
+        builder.create(dir).with_context(|| format!("Failed to create {}", dir.display()))
+    }
+
+    /// Refuse a wallet output at `path` whose directory other users can read or write, unless
+    /// `insecure` is set. A directory that does not exist yet is created private, so passes.
+    pub fn check_directory(&self, path: &Path) -> Result<()> {
+        if self.insecure {
+            return Ok(());
+        }
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        match exposed_to_others(dir) {
+            Ok(true) => Err(anyhow!(
+                "{} is readable or writable by other users; private keys are not written there (restrict it with `chmod o-rwx`, or pass --insecure-output)",
+                dir.display()
+            )),
+            Ok(false) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(anyhow::Error::new(e).context(format!("Failed to read the permissions of {}", dir.display()))),
+        }
+    }
+
+    #[cfg(unix)]
+    fn restrict(&self, file: &File, _path: &Path) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(self.file_mode()))
+    }
+
+    #[cfg(windows)]
+    fn restrict(&self, _file: &File, path: &Path) -> io::Result<()> {
+        match self.owner_only() {
+            true => windows::restrict_to_current_user(path),
+            false => Ok(()),
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn restrict(&self, _file: &File, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn exposed_to_others(dir: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(fs::metadata(dir)?.permissions().mode() & 0o006 != 0)
+}
+
+#[cfg(not(unix))]
+fn exposed_to_others(dir: &Path) -> io::Result<bool> {
+    fs::metadata(dir).map(|_| false)
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+    use std::ptr;
+
+    use windows_sys::Win32::Foundation::{CloseHandle, LocalFree, GENERIC_ALL, HANDLE};
+    use windows_sys::Win32::Security::Authorization::{
+        SetEntriesInAclW, SetNamedSecurityInfoW, EXPLICIT_ACCESS_W, NO_MULTIPLE_TRUSTEE, SET_ACCESS, SE_FILE_OBJECT, TRUSTEE_IS_SID, TRUSTEE_IS_USER, TRUSTEE_W,
+    };
+    use windows_sys::Win32::Security::{GetTokenInformation, TokenUser, ACL, DACL_SECURITY_INFORMATION, NO_INHERITANCE, PROTECTED_DACL_SECURITY_INFORMATION, TOKEN_QUERY, TOKEN_USER};
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    /// Replace the ACL of `path` with one entry giving the current user full control, without
+    /// inheriting from the directory.
+    pub fn restrict_to_current_user(path: &Path) -> io::Result<()> {
+        let user = current_user()?;
+        let sid = user.as_ptr() as *const TOKEN_USER;
+        let name: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        // SAFETY: `user` holds the TOKEN_USER GetTokenInformation filled in, whose SID it also
+        // holds; the ACL SetEntriesInAclW allocates is freed once applied
+        unsafe {
+            let access = EXPLICIT_ACCESS_W {
+                grfAccessPermissions: GENERIC_ALL,
+                grfAccessMode: SET_ACCESS,
+                grfInheritance: NO_INHERITANCE,
+                Trustee: TRUSTEE_W {
+                    pMultipleTrustee: ptr::null_mut(),
+                    MultipleTrusteeOperation: NO_MULTIPLE_TRUSTEE,
+                    TrusteeForm: TRUSTEE_IS_SID,
+                    TrusteeType: TRUSTEE_IS_USER,
+                    ptstrName: (*sid).User.Sid as *mut u16,
+                },
+            };
+            let mut acl: *mut ACL = ptr::null_mut();
+            let status = SetEntriesInAclW(1, &access, ptr::null(), &mut acl);
+            if status != 0 {
+                return Err(io::Error::from_raw_os_error(status as i32));
+            }
+            let status = SetNamedSecurityInfoW(
+                name.as_ptr(),
+                SE_FILE_OBJECT,
+                DACL_SECURITY_INFORMATION | PROTECTED_DACL_SECURITY_INFORMATION,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                acl,
+                ptr::null(),
+            );
+            LocalFree(acl as *mut c_void);
+            match status {
+                0 => Ok(()),
+                _ => Err(io::Error::from_raw_os_error(status as i32)),
+            }
+        }
+    }
+
+    /// The TOKEN_USER of this process, in a buffer aligned for it.
+    fn current_user() -> io::Result<Vec<u64>> {
+        // SAFETY: the token handle is closed on every path, and the buffer is as large as asked for
+        unsafe {
+            let mut token: HANDLE = ptr::null_mut();
+            if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut needed = 0u32;
+            GetTokenInformation(token, TokenUser, ptr::null_mut(), 0, &mut needed);
+            let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+            let filled = GetTokenInformation(token, TokenUser, buffer.as_mut_ptr() as *mut c_void, needed, &mut needed);
+            let error = io::Error::last_os_error();
+            CloseHandle(token);
+            match filled {
+                0 => Err(error),
+                _ => Ok(buffer),
+            }
+        }
+    }
+}
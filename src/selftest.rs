@@ -0,0 +1,132 @@
+//! Known-answer checks against published wallets of the BIP-39 test mnemonic, so the derivation
+//! can be trusted before generating at scale.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use bip39::Mnemonic;
+
+use crate::chain;
+use crate::generator::generate_wallets_batch;
+use crate::wallet::Wallet;
+
+/// The BIP-39 test mnemonic most wallets publish their derivations for.
+pub const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// A wallet a chain's reference tooling derives from [`MNEMONIC`].
+#[derive(Debug, Clone)]
+pub struct Vector {
+    /// Chain preset the wallet is derived with
+    pub chain: &'static str,
+    pub index: u64,
+    pub derivation_path: &'static str,
+    pub address: &'static str,
+    pub evm_address: Option<&'static str>,
+    /// Hex private key
+    pub private_key: &'static str,
+}
+
+/// Cosmos Hub and Osmosis match `gaiad`/`osmosisd keys add --recover` and Keplr; Evmos matches
+/// `evmosd` and MetaMask, whose first accounts of this mnemonic are 0x9858...da94 and 0x6Fac...b9C0.
+pub const VECTORS: &[Vector] = &[
+    Vector {
+        chain: "cosmoshub",
+        index: 0,
+        derivation_path: "m/44'/118'/0'/0/0",
+        address: "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4",
+        evm_address: None,
+        private_key: "c4a48e2fce1481cd3294b4490f6678090ea98d3d0e5cd984558ab0968741b104",
+    },
+    Vector {
+        chain: "cosmoshub",
+        index: 2,
+        derivation_path: "m/44'/118'/0'/0/2",
+        address: "cosmos1kng7tv83qesgvv2ze7hxlw4urfrjk8vqqnpqdt",
+        evm_address: None,
+        private_key: "42cb671a145903fcb5be9dd2a2446eec676bd000c63eb0a7efd181c9fa5a28ac",
+    },
+    Vector {
+        chain: "osmosis",
+        index: 0,
+        derivation_path: "m/44'/118'/0'/0/0",
+        address: "osmo19rl4cm2hmr8afy4kldpxz3fka4jguq0a5m7df8",
+        evm_address: None,
+        private_key: "c4a48e2fce1481cd3294b4490f6678090ea98d3d0e5cd984558ab0968741b104",
+    },
+    Vector {
+        chain: "evmos",
+        index: 0,
+        derivation_path: "m/44'/60'/0'/0/0",
+        address: "evmos1npvwllfr9dqr8erajqqr6s0vxnk2ak55t3r99j",
+        evm_address: Some("0x9858effd232b4033e47d90003d41ec34ecaeda94"),
+        private_key: "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727",
+    },
+    Vector {
+        chain: "evmos",
+        index: 1,
+        derivation_path: "m/44'/60'/0'/0/1",
+        address: "evmos1d7ky6xxfzg6rh7r05uzfxexafepy4wwqfaydlh",
+        evm_address: Some("0x6fac4d18c912343bf86fa7049364dd4e424ab9c0"),
+        private_key: "9a983cb3d832fbde5ab49d692b7a8bf5b5d232479c99333d0fc8e1d21f1b55b6",
+    },
+];
+
+/// A field that differs from the published value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Result of checking one vector; it passed if `error` is unset and there are no mismatches.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub vector: &'static Vector,
+    pub mismatches: Vec<Mismatch>,
+    /// Derivation or preset lookup failure
+    pub error: Option<String>,
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.mismatches.is_empty()
+    }
+}
+
+/// Derive every vector with the batch generator used for real runs and compare it byte for byte.
+pub fn run() -> Vec<Outcome> {
+    let seed = Mnemonic::parse(MNEMONIC).expect("test mnemonic is valid").to_seed("");
+    VECTORS.iter().map(|vector| check(&seed, vector)).collect()
+}
+
+fn check(seed: &[u8], vector: &'static Vector) -> Outcome {
+    let derived = chain::preset(vector.chain).map_err(|e| e.to_string()).and_then(|preset| {
+        // From index 0, so levels shared between neighbouring paths are reused as in a real batch
+        let count = vector.index as usize + 1;
+        let wallets = generate_wallets_batch(seed, 0, count, &preset.config(), Arc::new(AtomicUsize::new(0)));
+        wallets.map_err(|e| e.to_string())?.pop().ok_or_else(|| "no wallet derived".to_string())
+    });
+    match derived {
+        Ok(wallet) => Outcome { vector, mismatches: compare(vector, &wallet), error: None },
+        Err(error) => Outcome { vector, mismatches: Vec::new(), error: Some(error) },
+    }
+}
+
+fn compare(vector: &Vector, wallet: &Wallet) -> Vec<Mismatch> {
+    let fields = [
+        ("derivationPath", Some(vector.derivation_path), Some(wallet.derivation_path.as_str())),
+        ("address", Some(vector.address), Some(wallet.address.as_str())),
+        ("evmAddress", vector.evm_address, wallet.evm_address.as_deref()),
+        ("privateKey", Some(vector.private_key), Some(wallet.private_key.as_str())),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, expected, actual)| expected != actual)
+        .map(|(field, expected, actual)| Mismatch {
+            field,
+            expected: expected.unwrap_or("none").to_string(),
+            actual: actual.unwrap_or("none").to_string(),
+        })
+        .collect()
+}
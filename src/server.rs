@@ -0,0 +1,394 @@
+//! HTTP API for single derivations and asynchronous batch jobs.
+//!
+//! Endpoints:
+//! - `POST /derive`: mnemonic or xpub + index or path to one wallet
+//! - `POST /generate`: start a batch job, returns its id
+//! - `GET /jobs/{id}`: job status and progress
+//! - `GET /jobs/{id}/wallets`: finished job results
+//! - `DELETE /jobs/{id}`: discard a job and its results
+//! - `GET /metrics`: Prometheus metrics of the jobs and derivations served
+//!
+//! Request bodies carry mnemonics, so they are never logged and never stored:
+//! mnemonics and seeds are wiped from memory when dropped, and only the derived
+//! wallets live in the job table, which is purged after the configured TTL.
+//! Private keys are only returned from `/derive` when explicitly requested.
+
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use bip39::Mnemonic;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use crate::derivation::{derive_public_key, parse_xpub};
+use crate::types::{AddressEncoding, KeyType};
+use crate::generator::{derivation_path, derive_wallet, encode_public_key, generate_wallets_parallel, DerivationConfig};
+use crate::metrics::{self, Exposition, MetricType};
+use crate::wallet::Wallet;
+
+/// Wallets derived per rayon task inside a job.
+const JOB_CHUNK_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub listen: SocketAddr,
+    pub auth_token: Option<String>,
+    pub max_job_size: usize,
+    pub max_jobs: usize,
+    /// Jobs queued or running at once; further `/generate` requests get 429
+    pub max_pending_jobs: usize,
+    pub job_ttl: Duration,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+struct Job {
+    state: JobState,
+    total: usize,
+    progress: Arc<AtomicUsize>,
+    wallets: Option<Arc<Vec<Wallet>>>,
+    error: Option<String>,
+    finished_at: Option<Instant>,
+}
+
+#[derive(Clone)]
+struct AppState {
+    config: Arc<ServerConfig>,
+    jobs: Arc<Mutex<HashMap<Uuid, Job>>>,
+    slots: Arc<Semaphore>,
+    /// Wallets of finished jobs, kept after the jobs are purged
+    generated: Arc<AtomicU64>,
+    derived: Arc<AtomicU64>,
+}
+
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+fn bad_request<E: std::fmt::Display>(e: E) -> ApiError {
+    ApiError(StatusCode::BAD_REQUEST, e.to_string())
+}
+
+fn internal<E: std::fmt::Display>(e: E) -> ApiError {
+    ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+fn not_found() -> ApiError {
+    ApiError(StatusCode::NOT_FOUND, "Job not found".to_string())
+}
+
+fn default_prefix() -> String {
+    "cosmos".to_string()
+}
+
+fn default_key_type() -> String {
+    "secp256k1".to_string()
+}
+
+fn parse_key_type(key_type: &str) -> Result<KeyType, ApiError> {
+    key_type.parse().map_err(|_| bad_request(format!("Unknown key type: {}", key_type)))
+}
+
+fn parse_seed(mnemonic: &str) -> Result<Zeroizing<[u8; 64]>, ApiError> {
+    let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| bad_request(format!("Invalid mnemonic: {}", e)))?;
+    Ok(Zeroizing::new(mnemonic.to_seed("")))
+}
+
+/// Run `task` on the blocking pool: PBKDF2 and key derivation would stall the executor.
+async fn blocking<T: Send + 'static>(task: impl FnOnce() -> Result<T, ApiError> + Send + 'static) -> Result<T, ApiError> {
+    tokio::task::spawn_blocking(task).await.map_err(internal)?
+}
+
+#[derive(Deserialize)]
+struct DeriveRequest {
+    mnemonic: Option<Zeroizing<String>>,
+    /// Account-level extended public key, for watch-only addresses instead of `mnemonic`
+    xpub: Option<String>,
+    /// Full derivation path, or below `xpub` a non-hardened one such as `0/5`; takes precedence
+    /// over `index`
+    path: Option<String>,
+    #[serde(default)]
+    index: usize,
+    #[serde(default = "default_prefix")]
+    prefix: String,
+    #[serde(default = "default_key_type")]
+    key_type: String,
+    #[serde(default)]
+    include_private_key: bool,
+}
+
+#[derive(Serialize)]
+struct DeriveResponse {
+    address: String,
+    #[serde(rename = "evmAddress", skip_serializing_if = "Option::is_none")]
+    evm_address: Option<String>,
+    pubkey: String,
+    #[serde(rename = "privateKey", skip_serializing_if = "Option::is_none")]
+    private_key: Option<String>,
+    #[serde(rename = "derivationPath")]
+    derivation_path: String,
+}
+
+async fn derive(State(state): State<AppState>, Json(req): Json<DeriveRequest>) -> Result<Json<DeriveResponse>, ApiError> {
+    state.derived.fetch_add(1, Ordering::Relaxed);
+    let key_type = parse_key_type(&req.key_type)?;
+    let mnemonic = match (req.mnemonic, req.xpub) {
+        (Some(mnemonic), None) => mnemonic,
+        (None, Some(xpub)) => {
+            if req.include_private_key {
+                return Err(bad_request("An xpub has no private keys"));
+            }
+            let path = req.path.unwrap_or_else(|| format!("0/{}", req.index));
+            return derive_watch_only(&xpub, path, &req.prefix, &key_type).map(Json);
+        }
+        _ => return Err(bad_request("Exactly one of mnemonic and xpub is required")),
+    };
+    let path = req.path.unwrap_or_else(|| derivation_path(key_type.coin_type(), req.index as u64));
+    let prefix = req.prefix;
+
+    let wallet = blocking(move || derive_wallet(&*parse_seed(&mnemonic)?, &path, &prefix, &key_type).map_err(bad_request)).await?;
+
+    Ok(Json(DeriveResponse {
+        address: wallet.address,
+        evm_address: wallet.evm_address,
+        pubkey: wallet.pubkey,
+        private_key: req.include_private_key.then_some(wallet.private_key),
+        derivation_path: wallet.derivation_path,
+    }))
+}
+
+/// The address of the non-hardened child `path` below `xpub`, as `from-pubkey --xpub` derives it.
+fn derive_watch_only(xpub: &str, path: String, prefix: &str, key_type: &KeyType) -> Result<DeriveResponse, ApiError> {
+    let xpub = parse_xpub(xpub).map_err(|e| bad_request(format!("Invalid xpub: {}", e)))?;
+    let public_key = secp256k1::PublicKey::from_slice(&derive_public_key(&xpub, &path).map_err(bad_request)?).map_err(bad_request)?;
+    let (address, evm_address, pubkey) = encode_public_key(&public_key, prefix, key_type, AddressEncoding::Bech32).map_err(bad_request)?;
+    Ok(DeriveResponse { address, evm_address, pubkey, private_key: None, derivation_path: path })
+}
+
+#[derive(Deserialize)]
+struct GenerateRequest {
+    mnemonic: Zeroizing<String>,
+    count: usize,
+    #[serde(default)]
+    start_index: usize,
+    #[serde(default = "default_prefix")]
+    prefix: String,
+    #[serde(default = "default_key_type")]
+    key_type: String,
+}
+
+#[derive(Serialize)]
+struct JobStatus {
+    id: Uuid,
+    state: JobState,
+    progress: usize,
+    total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+async fn generate(
+    State(state): State<AppState>,
+    Json(req): Json<GenerateRequest>,
+) -> Result<(StatusCode, Json<JobStatus>), ApiError> {
+    if req.count == 0 || req.count > state.config.max_job_size {
+        return Err(bad_request(format!("count must be between 1 and {}", state.config.max_job_size)));
+    }
+    let key_type = parse_key_type(&req.key_type)?;
+    let mnemonic = req.mnemonic;
+    let seed = blocking(move || parse_seed(&mnemonic)).await?;
+
+    let id = Uuid::new_v4();
+    let progress = Arc::new(AtomicUsize::new(0));
+    let mut jobs = state.jobs.lock().unwrap();
+    // Counted and inserted under one lock, so concurrent requests cannot overshoot the cap
+    let pending = jobs.values().filter(|job| matches!(job.state, JobState::Queued | JobState::Running)).count();
+    if pending >= state.config.max_pending_jobs.max(1) {
+        return Err(ApiError(StatusCode::TOO_MANY_REQUESTS, format!("{} jobs are already queued or running", pending)));
+    }
+    jobs.insert(id, Job {
+        state: JobState::Queued,
+        total: req.count,
+        progress: progress.clone(),
+        wallets: None,
+        error: None,
+        finished_at: None,
+    });
+    drop(jobs);
+
+    let jobs = state.jobs.clone();
+    let slots = state.slots.clone();
+    let generated = state.generated.clone();
+    tokio::spawn(async move {
+        let Ok(_permit) = slots.acquire_owned().await else { return };
+        set_state(&jobs, id, JobState::Running);
+
+        let result = tokio::task::spawn_blocking(move || {
+            generate_wallets_parallel(&*seed, req.start_index, req.count, &DerivationConfig::new(&req.prefix, key_type), JOB_CHUNK_SIZE, progress)
+        })
+        .await;
+
+        let mut jobs = jobs.lock().unwrap();
+        if let Some(job) = jobs.get_mut(&id) {
+            match result {
+                Ok(Ok(wallets)) => {
+                    job.state = JobState::Completed;
+                    job.wallets = Some(Arc::new(wallets));
+                }
+                Ok(Err(e)) => {
+                    job.state = JobState::Failed;
+                    job.error = Some(e.to_string());
+                }
+                Err(e) => {
+                    job.state = JobState::Failed;
+                    job.error = Some(format!("Generation task failed: {}", e));
+                }
+            }
+            job.finished_at = Some(Instant::now());
+            // Moved under the lock, so a scrape counts the job's wallets exactly once
+            generated.fetch_add(job.progress.load(Ordering::Relaxed).min(job.total) as u64, Ordering::Relaxed);
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(JobStatus { id, state: JobState::Queued, progress: 0, total: req.count, error: None })))
+}
+
+fn set_state(jobs: &Mutex<HashMap<Uuid, Job>>, id: Uuid, job_state: JobState) {
+    if let Some(job) = jobs.lock().unwrap().get_mut(&id) {
+        job.state = job_state;
+    }
+}
+
+async fn job_status(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<JobStatus>, ApiError> {
+    let jobs = state.jobs.lock().unwrap();
+    let job = jobs.get(&id).ok_or_else(not_found)?;
+    Ok(Json(JobStatus {
+        id,
+        state: job.state,
+        progress: job.progress.load(Ordering::Relaxed).min(job.total),
+        total: job.total,
+        error: job.error.clone(),
+    }))
+}
+
+async fn job_wallets(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<Arc<Vec<Wallet>>>, ApiError> {
+    let jobs = state.jobs.lock().unwrap();
+    let job = jobs.get(&id).ok_or_else(not_found)?;
+    match &job.wallets {
+        Some(wallets) => Ok(Json(wallets.clone())),
+        None => Err(ApiError(StatusCode::CONFLICT, format!("Job is {:?}", job.state).to_lowercase())),
+    }
+}
+
+async fn delete_job(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<StatusCode, ApiError> {
+    state.jobs.lock().unwrap().remove(&id).ok_or_else(not_found)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let jobs = state.jobs.lock().unwrap();
+    let count = |job_state: JobState| jobs.values().filter(|job| job.state == job_state).count();
+    let states = [JobState::Queued, JobState::Running, JobState::Completed, JobState::Failed];
+    let running: u64 = jobs
+        .values()
+        .filter(|job| job.state == JobState::Running)
+        .map(|job| job.progress.load(Ordering::Relaxed).min(job.total) as u64)
+        .sum();
+
+    let mut exposition = Exposition::new();
+    exposition
+        .labelled(
+            "wallet_generator_jobs",
+            MetricType::Gauge,
+            "Jobs in the job table by state",
+            "state",
+            states.map(|job_state| (format!("{:?}", job_state).to_lowercase(), count(job_state))),
+        )
+        .metric(
+            "wallet_generator_wallets_generated_total",
+            MetricType::Counter,
+            "Wallets derived by batch jobs, running jobs included",
+            state.generated.load(Ordering::Relaxed) + running,
+        )
+        .metric("wallet_generator_derive_requests_total", MetricType::Counter, "Requests to /derive", state.derived.load(Ordering::Relaxed))
+        .metric("wallet_generator_job_slots", MetricType::Gauge, "Jobs that may run at once", state.config.max_jobs.max(1))
+        .metric("wallet_generator_job_slots_free", MetricType::Gauge, "Job slots not taken by a running job", state.slots.available_permits());
+    ([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], exposition.finish())
+}
+
+/// Reject requests without the configured bearer token.
+async fn require_token(State(state): State<AppState>, request: Request, next: Next) -> Result<Response, ApiError> {
+    if let Some(expected) = &state.config.auth_token {
+        let provided = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .unwrap_or("");
+        // Compare digests so the check does not leak the token length or prefix through timing
+        if Sha256::digest(provided.as_bytes()) != Sha256::digest(expected.as_bytes()) {
+            return Err(ApiError(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token".to_string()));
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+/// Drop finished jobs older than the TTL.
+async fn purge_expired(jobs: Arc<Mutex<HashMap<Uuid, Job>>>, ttl: Duration) {
+    let mut interval = tokio::time::interval(Duration::from_secs(30).min(ttl.max(Duration::from_secs(1))));
+    loop {
+        interval.tick().await;
+        jobs.lock()
+            .unwrap()
+            .retain(|_, job| job.finished_at.is_none_or(|finished| finished.elapsed() < ttl));
+    }
+}
+
+/// Build the API router. Exposed for embedding in an existing axum app.
+pub fn router(config: ServerConfig) -> Router {
+    let state = AppState {
+        slots: Arc::new(Semaphore::new(config.max_jobs.max(1))),
+        config: Arc::new(config),
+        jobs: Arc::new(Mutex::new(HashMap::new())),
+        generated: Arc::new(AtomicU64::new(0)),
+        derived: Arc::new(AtomicU64::new(0)),
+    };
+    tokio::spawn(purge_expired(state.jobs.clone(), state.config.job_ttl));
+
+    Router::new()
+        .route("/derive", post(derive))
+        .route("/generate", post(generate))
+        .route("/jobs/:id", get(job_status).delete(delete_job))
+        .route("/jobs/:id/wallets", get(job_wallets))
+        .route("/metrics", get(metrics))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// Serve the API until the process is stopped.
+pub async fn serve(config: ServerConfig) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(config.listen).await?;
+    axum::serve(listener, router(config)).await
+}
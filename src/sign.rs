@@ -0,0 +1,151 @@
+//! Offline signing of transactions with keys from a wallet file.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::bitcoin;
+use crate::key_cipher;
+use crate::types::KeyType;
+use crate::coin::Coin;
+use crate::lcd::AccountInfo;
+use crate::signer::{Signer, SoftwareSigner};
+use crate::tx::{read_tx_file, Fee, Msg, PubKeyJson, SignerData, Tx};
+use crate::wallet::Wallet;
+
+/// A template for one `MsgSend` per wallet, used instead of prebuilt transactions.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SendSpec {
+    pub to_address: String,
+    pub amount: Vec<Coin>,
+    pub gas_limit: u64,
+    #[serde(default)]
+    pub fee: Vec<Coin>,
+    #[serde(default)]
+    pub memo: String,
+}
+
+impl SendSpec {
+    pub fn build_tx(&self, from_address: &str) -> Tx {
+        let msg = Msg::Send {
+            from_address: from_address.to_string(),
+            to_address: self.to_address.clone(),
+            amount: self.amount.clone(),
+        };
+        let fee = Fee { amount: self.fee.clone(), gas_limit: self.gas_limit, payer: String::new(), granter: String::new() };
+        Tx::new(vec![msg], fee, self.memo.clone())
+    }
+}
+
+/// Key type a wallet record was generated with.
+pub fn wallet_key_type(wallet: &Wallet) -> KeyType {
+    if wallet.evm_address.is_some() {
+        KeyType::Ethsecp256k1
+    } else {
+        KeyType::Secp256k1
+    }
+}
+
+/// Public key of a wallet in signer info form, from its private key or `pubkeyHex`; `None` when
+/// the record holds neither in readable form.
+pub fn wallet_public_key(wallet: &Wallet) -> Option<PubKeyJson> {
+    let key_type = wallet_key_type(wallet);
+    let key = match (key_cipher::is_encrypted(&wallet.private_key), &wallet.pubkey_hex) {
+        (false, _) => SoftwareSigner::new(&wallet_private_key(wallet).ok()?, key_type.clone()).ok()?.public_key().serialize().to_vec(),
+        (true, Some(pubkey_hex)) => hex::decode(pubkey_hex).ok().filter(|key| key.len() == 33)?,
+        (true, None) => return None,
+    };
+    Some(PubKeyJson { type_url: key_type.pubkey_type_url().to_string(), key: general_purpose::STANDARD.encode(key) })
+}
+
+/// Decode the private key stored in a wallet record, written as hex, base64 or WIF.
+pub fn wallet_private_key(wallet: &Wallet) -> Result<[u8; 32]> {
+    if key_cipher::is_encrypted(&wallet.private_key) {
+        return Err(anyhow!("The private key of {} is encrypted; run `wallet-generator decrypt` on the file first", wallet.address));
+    }
+    if let Some(key) = bitcoin::parse_wif(&wallet.private_key) {
+        return Ok(key);
+    }
+    let bytes = match hex::decode(&wallet.private_key) {
+        Ok(bytes) => bytes,
+        Err(_) => general_purpose::STANDARD
+            .decode(&wallet.private_key)
+            .with_context(|| format!("Invalid private key for {}: not hex, base64 or WIF", wallet.address))?,
+    };
+    bytes.try_into().map_err(|_| anyhow!("Private key for {} is not 32 bytes", wallet.address))
+}
+
+/// Load unsigned transactions from a directory of JSON files, a JSON array or a single transaction.
+pub fn load_txs<P: AsRef<Path>>(path: P) -> Result<Vec<Tx>> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        let mut files: Vec<_> = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        files.retain(|p| p.extension().is_some_and(|ext| ext == "json"));
+        files.sort();
+        return files.iter().map(read_tx_file).collect();
+    }
+
+    let contents = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents).with_context(|| format!("Invalid transaction array in {}", path.display()))
+    } else {
+        read_tx_file(path).map(|tx| vec![tx])
+    }
+}
+
+/// Signs transactions for any wallet in the set, tracking sequences per signer.
+pub struct WalletSigner<'a> {
+    wallets: HashMap<&'a str, &'a Wallet>,
+    /// Keys held outside the wallet file, such as a funder key in an HSM
+    signers: HashMap<String, Box<dyn Signer + 'a>>,
+    accounts: HashMap<String, AccountInfo>,
+    chain_id: String,
+    key_type: Option<KeyType>,
+}
+
+impl<'a> WalletSigner<'a> {
+    /// `key_type` overrides the per-wallet key type inferred from the record.
+    pub fn new(wallets: &'a [Wallet], accounts: HashMap<String, AccountInfo>, chain_id: String, key_type: Option<KeyType>) -> Self {
+        Self {
+            wallets: wallets.iter().map(|w| (w.address.as_str(), w)).collect(),
+            signers: HashMap::new(),
+            accounts,
+            chain_id,
+            key_type,
+        }
+    }
+
+    /// Sign the transactions of `address` with `signer` rather than a key from the wallet file.
+    pub fn add_signer(&mut self, address: String, signer: Box<dyn Signer + 'a>) {
+        self.signers.insert(address, signer);
+    }
+
+    /// Sign `tx` with its first message's signer and advance that signer's sequence.
+    pub fn sign(&mut self, tx: &mut Tx) -> Result<()> {
+        let signer = tx.body.messages.first().and_then(Msg::signer).ok_or_else(|| anyhow!("Transaction has no signer"))?;
+        let account = self
+            .accounts
+            .get_mut(signer)
+            .ok_or_else(|| anyhow!("No account number or sequence for {}", signer))?;
+        let signer_data = SignerData {
+            chain_id: self.chain_id.clone(),
+            account_number: account.account_number,
+            sequence: account.sequence,
+        };
+
+        match self.signers.get(signer) {
+            Some(key) => tx.sign_with(key.as_ref(), &signer_data)?,
+            None => {
+                let wallet = *self.wallets.get(signer).ok_or_else(|| anyhow!("No wallet for signer {}", signer))?;
+                let key_type = self.key_type.clone().unwrap_or_else(|| wallet_key_type(wallet));
+                tx.sign_with(&SoftwareSigner::new(&wallet_private_key(wallet)?, key_type)?, &signer_data)?;
+            }
+        }
+        account.sequence += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+//! Signing backends: the keys that sign transactions, whether held in memory or on a device.
+//!
+//! `sign`, `fund` and the commands built on them ask a [`Signer`] for a public key and a
+//! signature over a payload, so a key generated here and a key that never leaves an HSM are
+//! used the same way.
+
+use anyhow::Result;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+use crate::generator::{encode_address, parse_hrp, public_key_address};
+use crate::tx::sign_digest;
+use crate::types::{AddressEncoding, KeyType};
+
+/// A secp256k1 key that signs payloads for its chain.
+pub trait Signer {
+    /// Key type the signatures and addresses are computed for.
+    fn key_type(&self) -> &KeyType;
+
+    fn public_key(&self) -> PublicKey;
+
+    /// Hash `payload` the way the key type's chain expects and return a 64-byte compact
+    /// `r || s` signature with a low S.
+    fn sign(&self, payload: &[u8]) -> Result<[u8; 64]>;
+
+    /// Bech32 address of the key under `prefix`.
+    fn address(&self, prefix: &str) -> Result<String> {
+        let hash = public_key_address(&self.public_key(), self.key_type());
+        Ok(encode_address(parse_hrp(prefix)?, &hash, AddressEncoding::Bech32)?)
+    }
+}
+
+/// A private key held in memory, such as one read from a wallet file.
+pub struct SoftwareSigner {
+    secp: Secp256k1<secp256k1::SignOnly>,
+    secret_key: SecretKey,
+    public_key: PublicKey,
+    key_type: KeyType,
+}
+
+impl SoftwareSigner {
+    pub fn new(private_key: &[u8; 32], key_type: KeyType) -> Result<Self> {
+        let secp = Secp256k1::signing_only();
+        let secret_key = SecretKey::from_slice(private_key)?;
+        let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+        Ok(Self { secp, secret_key, public_key, key_type })
+    }
+}
+
+impl Signer for SoftwareSigner {
+    fn key_type(&self) -> &KeyType {
+        &self.key_type
+    }
+
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<[u8; 64]> {
+        Ok(sign_digest(&self.secp, &self.secret_key, payload, &self.key_type))
+    }
+}
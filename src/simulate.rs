@@ -0,0 +1,123 @@
+//! Gas estimation of built transactions against a node, so each wallet is funded for the fees
+//! its transactions will actually cost.
+
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::coin::{Coin, GasPrice};
+use crate::lcd::{AccountInfo, LcdClient};
+use crate::sign::wallet_public_key;
+use crate::tx::{Msg, SignMode, Tx};
+use crate::wallet::Wallet;
+
+#[derive(Debug, Clone)]
+pub struct SimulateOptions {
+    /// Sign mode of transactions that are not signed yet; signed ones keep their own
+    pub sign_mode: SignMode,
+    /// Factor applied to the gas used to get the gas limit
+    pub gas_adjustment: f64,
+    /// Price of the gas limit; no fee is computed without one
+    pub gas_price: Option<GasPrice>,
+}
+
+/// Estimated gas and fee of one transaction.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Simulation {
+    /// Position in the input
+    pub index: usize,
+    /// Address of the first message's signer, which pays the fee
+    pub signer: String,
+    pub sign_mode: String,
+    /// Missing when the simulation failed; see `error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+    /// Gas used times the adjustment, rounded up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// Fee for the gas limit at the gas price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Vec<Coin>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Simulate every transaction on a pool of `concurrency` threads.
+///
+/// Each signer's account is looked up once and every one of its transactions is simulated at the
+/// account's current sequence, since the node checks it; gas does not depend on the sequence.
+/// Public keys come from signed transactions' signer infos, or else from `wallets`. Failed
+/// simulations do not abort the run; they are reported per transaction. Results keep the input
+/// order.
+pub fn simulate_all(
+    client: &LcdClient,
+    txs: &[Tx],
+    wallets: &[Wallet],
+    options: &SimulateOptions,
+    concurrency: usize,
+    progress: &AtomicUsize,
+) -> Result<Vec<Simulation>> {
+    let wallets: HashMap<&str, &Wallet> = wallets.iter().map(|wallet| (wallet.address.as_str(), wallet)).collect();
+    let mut signers: Vec<&str> = txs.iter().filter_map(|tx| tx.body.messages.first().and_then(Msg::signer)).collect();
+    signers.sort_unstable();
+    signers.dedup();
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency.max(1)).build()?;
+    let accounts: HashMap<&str, Result<AccountInfo, String>> = pool.install(|| {
+        signers
+            .into_par_iter()
+            .map(|signer| {
+                let account = match client.account(signer) {
+                    Ok(Some(account)) => Ok(account),
+                    Ok(None) => Err(format!("Account {} does not exist on chain; fund it first", signer)),
+                    Err(e) => Err(e.to_string()),
+                };
+                (signer, account)
+            })
+            .collect()
+    });
+
+    Ok(pool.install(|| {
+        txs.par_iter()
+            .enumerate()
+            .map(|(index, tx)| {
+                let signer = tx.body.messages.first().and_then(Msg::signer).unwrap_or_default();
+                let signer_info = tx.auth_info.signer_infos.first();
+                let sign_mode = signer_info.map_or(Ok(options.sign_mode), |info| SignMode::from_name(&info.mode_info.single.mode));
+                let mut simulation = Simulation {
+                    index,
+                    signer: signer.to_string(),
+                    sign_mode: sign_mode.as_ref().map_or("", |mode| mode.name()).to_string(),
+                    gas_used: None,
+                    gas_limit: None,
+                    fee: None,
+                    error: None,
+                };
+
+                let gas_used = (|| -> Result<u64> {
+                    let account = accounts.get(signer).ok_or_else(|| anyhow!("Transaction has no signer"))?;
+                    let account = account.as_ref().map_err(|e| anyhow!("{}", e))?;
+                    let public_key = match signer_info {
+                        Some(info) => Some(info.public_key.clone()),
+                        None => wallets.get(signer).and_then(|wallet| wallet_public_key(wallet)),
+                    };
+                    client.simulate(&tx.simulation_bytes(public_key.as_ref(), sign_mode?, account.sequence)?)
+                })();
+                progress.fetch_add(1, Ordering::Relaxed);
+
+                match gas_used {
+                    Ok(gas_used) => {
+                        let gas_limit = (gas_used as f64 * options.gas_adjustment).ceil() as u64;
+                        simulation.gas_used = Some(gas_used);
+                        simulation.gas_limit = Some(gas_limit);
+                        simulation.fee = options.gas_price.as_ref().map(|price| vec![price.fee(gas_limit)]);
+                    }
+                    Err(e) => simulation.error = Some(e.to_string()),
+                }
+                simulation
+            })
+            .collect()
+    }))
+}
@@ -0,0 +1,16 @@
+//! Solana keys: ed25519 key pairs whose address is the base58 public key.
+
+use ed25519_dalek::SigningKey;
+
+/// SLIP-44 coin type of Solana.
+pub const COIN_TYPE: u32 = 501;
+
+/// The 32-byte ed25519 public key of a secret key.
+pub fn public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+    SigningKey::from_bytes(secret_key).verifying_key().to_bytes()
+}
+
+/// Base58 address of a public key.
+pub fn address(public_key: &[u8; 32]) -> String {
+    bs58::encode(public_key).into_string()
+}
@@ -0,0 +1,87 @@
+//! Async facade over the batch generator for embedding in tokio services.
+//!
+//! Derivation is CPU-bound, so all work runs on tokio's blocking pool and
+//! results are handed back through a bounded channel. Callers never block
+//! their executor threads.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::types::KeyType;
+use crate::error::GeneratorError;
+use crate::generator::{generate_wallets_batch, DerivationConfig};
+use crate::wallet::Wallet;
+
+/// Wallets derived per blocking call before results are handed to the stream.
+pub const DEFAULT_CHUNK_SIZE: usize = 1_000;
+
+/// One past the last index of `count` wallets from `start_index`.
+fn range_end(start_index: usize, count: usize) -> Result<usize, GeneratorError> {
+    start_index.checked_add(count).ok_or(GeneratorError::IndexOverflow { start: start_index, count })
+}
+
+/// Generate `count` wallets starting at `start_index` without blocking the caller.
+pub async fn generate_wallets(
+    seed: Vec<u8>,
+    start_index: usize,
+    count: usize,
+    prefix: String,
+    key_type: KeyType,
+) -> Result<Vec<Wallet>, GeneratorError> {
+    range_end(start_index, count)?;
+    tokio::task::spawn_blocking(move || {
+        let progress = Arc::new(AtomicUsize::new(0));
+        generate_wallets_batch(&seed, start_index, count, &DerivationConfig::new(&prefix, key_type), progress)
+    })
+    .await?
+}
+
+/// Stream wallets as they are derived.
+///
+/// Generation proceeds in chunks of `chunk_size` on the blocking pool and
+/// applies backpressure through a channel holding at most one chunk. Dropping
+/// the stream stops generation after the chunk in flight.
+///
+/// # Panics
+/// Outside a Tokio runtime, as generation is spawned onto its blocking pool.
+pub fn wallet_stream(
+    seed: Vec<u8>,
+    start_index: usize,
+    count: usize,
+    prefix: String,
+    key_type: KeyType,
+    chunk_size: usize,
+) -> Result<ReceiverStream<Result<Wallet, GeneratorError>>, GeneratorError> {
+    let end = range_end(start_index, count)?;
+    let chunk_size = chunk_size.max(1);
+    let (tx, rx) = mpsc::channel(chunk_size);
+
+    tokio::task::spawn_blocking(move || {
+        let config = DerivationConfig::new(&prefix, key_type);
+        let progress = Arc::new(AtomicUsize::new(0));
+        let mut next = start_index;
+
+        while next < end {
+            let batch = (end - next).min(chunk_size);
+            match generate_wallets_batch(&seed, next, batch, &config, progress.clone()) {
+                Ok(wallets) => {
+                    for wallet in wallets {
+                        if tx.blocking_send(Ok(wallet)).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(e));
+                    return;
+                }
+            }
+            next += batch;
+        }
+    });
+
+    Ok(ReceiverStream::new(rx))
+}
@@ -0,0 +1,123 @@
+//! Sets of target addresses to look for among generated wallets, e.g. to find at which index an
+//! old address lives.
+//!
+//! Addresses are compared by their account bytes, so a bech32 address matches under any prefix
+//! and an ethsecp256k1 account matches by its 0x address too. Almost every generated wallet is
+//! a miss, so a Bloom filter answers those before the exact set is consulted.
+
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::wallet::Wallet;
+
+/// Bloom filter bits per address: about a 1% false-positive rate with [`BLOOM_HASHES`] hashes.
+const BLOOM_BITS_PER_ADDRESS: usize = 10;
+const BLOOM_HASHES: u64 = 7;
+
+/// What an address is matched on: the account bytes of a 0x hex or bech32 address, whatever its
+/// prefix, or else the address text itself (base58 and SegWit addresses).
+fn address_key(address: &str) -> Box<[u8]> {
+    let bytes = match address.strip_prefix("0x").or_else(|| address.strip_prefix("0X")) {
+        Some(hex) => hex::decode(hex).ok(),
+        None => bech32::decode(address).ok().map(|(_, data)| data),
+    };
+    // Tagged so account bytes never collide with address text
+    let (tag, key) = match &bytes {
+        Some(bytes) => (0u8, bytes.as_slice()),
+        None => (1u8, address.as_bytes()),
+    };
+    [&[tag][..], key].concat().into_boxed_slice()
+}
+
+struct Bloom {
+    bits: Vec<u64>,
+}
+
+impl Bloom {
+    fn new(items: usize) -> Self {
+        Self { bits: vec![0; (items * BLOOM_BITS_PER_ADDRESS).div_ceil(64).max(1)] }
+    }
+
+    /// Bit positions of `key` in a filter of `len` bits, by double hashing.
+    fn positions(len: usize, key: &[u8]) -> impl Iterator<Item = usize> {
+        let hash = |seed: u8| {
+            let mut hasher = DefaultHasher::new();
+            seed.hash(&mut hasher);
+            key.hash(&mut hasher);
+            hasher.finish()
+        };
+        let (first, step) = (hash(0), hash(1) | 1);
+        (0..BLOOM_HASHES).map(move |i| (first.wrapping_add(i.wrapping_mul(step)) % len as u64) as usize)
+    }
+
+    fn insert(&mut self, key: &[u8]) {
+        for position in Self::positions(self.bits.len() * 64, key) {
+            self.bits[position / 64] |= 1 << (position % 64);
+        }
+    }
+
+    fn may_contain(&self, key: &[u8]) -> bool {
+        Self::positions(self.bits.len() * 64, key).all(|position| self.bits[position / 64] & (1 << (position % 64)) != 0)
+    }
+}
+
+/// Addresses to look for.
+pub struct TargetSet {
+    bloom: Bloom,
+    exact: HashSet<Box<[u8]>>,
+}
+
+impl TargetSet {
+    pub fn new<I, S>(addresses: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::from_keys(addresses.into_iter().map(|address| address_key(address.as_ref().trim())).collect())
+    }
+
+    /// Read one address per line; blank lines and lines starting with `#` are skipped.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut exact = HashSet::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                exact.insert(address_key(line));
+            }
+        }
+        Ok(Self::from_keys(exact))
+    }
+
+    fn from_keys(exact: HashSet<Box<[u8]>>) -> Self {
+        let mut bloom = Bloom::new(exact.len());
+        exact.iter().for_each(|key| bloom.insert(key));
+        Self { bloom, exact }
+    }
+
+    /// Distinct addresses in the set.
+    pub fn len(&self) -> usize {
+        self.exact.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty()
+    }
+
+    pub fn contains(&self, address: &str) -> bool {
+        let key = address_key(address);
+        self.bloom.may_contain(&key) && self.exact.contains(&key)
+    }
+
+    /// Whether the address or the EVM address of `wallet` is a target.
+    pub fn matches(&self, wallet: &Wallet) -> bool {
+        self.contains(&wallet.address) || wallet.evm_address.as_deref().is_some_and(|address| self.contains(address))
+    }
+}
@@ -0,0 +1,61 @@
+//! Keeping generation polite on shared hosts: a cap on wallets per second across all workers, and
+//! a lower scheduling priority for the process.
+
+use std::io;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Spaces work items out so that all workers together stay under a rate.
+///
+/// Each item reserves the next free slot of the schedule for its wallets, so threads start their
+/// items one after another rather than in bursts. Time spent idle is not banked for later.
+pub struct RateLimiter {
+    per_second: u64,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(per_second: u64) -> Self {
+        Self { per_second: per_second.max(1), next: Mutex::new(Instant::now()) }
+    }
+
+    /// Reserve the time for `wallets` wallets; returns how long the caller must wait before
+    /// generating them.
+    pub fn reserve(&self, wallets: usize) -> Duration {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let start = (*next).max(now);
+        *next = start + Duration::from_secs_f64(wallets as f64 / self.per_second as f64);
+        start - now
+    }
+}
+
+/// Run this process at niceness `nice` (0 to 19, higher is lower priority).
+///
+/// On Linux threads inherit the niceness of the thread that creates them, so this is called
+/// before any worker starts. Windows has priority classes rather than nice values: below normal
+/// up to 14, idle from 15.
+#[cfg(unix)]
+pub fn lower_priority(nice: i32) -> io::Result<()> {
+    // SAFETY: setpriority only reads its arguments
+    match unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+#[cfg(windows)]
+pub fn lower_priority(nice: i32) -> io::Result<()> {
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, SetPriorityClass, BELOW_NORMAL_PRIORITY_CLASS, IDLE_PRIORITY_CLASS};
+    let class = if nice >= 15 { IDLE_PRIORITY_CLASS } else { BELOW_NORMAL_PRIORITY_CLASS };
+    // SAFETY: the pseudo handle of the current process is always valid
+    match unsafe { SetPriorityClass(GetCurrentProcess(), class) } {
+        0 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn lower_priority(_nice: i32) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "thread priorities are not supported on this platform"))
+}
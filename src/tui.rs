@@ -0,0 +1,194 @@
+//! Full-screen dashboard for long generation runs: per-thread throughput, memory usage, ETA and
+//! write progress. `p` or space pauses and resumes, `q`, Esc or Ctrl+C aborts.
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Gauge, Paragraph, Row, Table};
+use ratatui::Frame;
+use std::time::{Duration, Instant};
+
+use crate::progress::RunState;
+
+/// Interval over which throughput is averaged.
+const RATE_WINDOW: Duration = Duration::from_secs(2);
+
+/// Throughput sampled over the last [`RATE_WINDOW`].
+struct Rates {
+    sampled_at: Instant,
+    counts: Vec<usize>,
+    per_thread: Vec<f64>,
+}
+
+impl Rates {
+    fn new(state: &RunState) -> Self {
+        let counts = state.thread_counts();
+        Self { sampled_at: Instant::now(), per_thread: vec![0.0; counts.len()], counts }
+    }
+
+    fn update(&mut self, state: &RunState) {
+        let elapsed = self.sampled_at.elapsed();
+        if elapsed < RATE_WINDOW {
+            return;
+        }
+        let counts = state.thread_counts();
+        self.per_thread = counts.iter().zip(&self.counts).map(|(now, before)| (now - before) as f64 / elapsed.as_secs_f64()).collect();
+        self.counts = counts;
+        self.sampled_at = Instant::now();
+    }
+
+    fn total(&self) -> f64 {
+        self.per_thread.iter().sum()
+    }
+}
+
+/// Resident set size of this process, where the platform exposes it.
+fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn ratio(done: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        (done as f64 / total as f64).min(1.0)
+    }
+}
+
+/// The dashboard of one run, drawn a frame at a time.
+pub struct Dashboard {
+    title: String,
+    started: Instant,
+    rates: Rates,
+}
+
+impl Dashboard {
+    pub fn new(state: &RunState, title: &str) -> Self {
+        Self { title: title.to_string(), started: Instant::now(), rates: Rates::new(state) }
+    }
+
+    /// Resample throughput and draw the current state into `frame`.
+    pub fn draw(&mut self, frame: &mut Frame, state: &RunState) {
+        self.rates.update(state);
+        draw(frame, state, &self.rates, &self.title, self.started);
+    }
+}
+
+/// Apply a key press: `p` or space toggles pause, `q`, Esc or Ctrl+C aborts. Ignored once writing starts.
+pub fn handle_key(state: &RunState, key: KeyEvent) {
+    if key.kind != KeyEventKind::Press || state.is_writing() {
+        return;
+    }
+    match key.code {
+        KeyCode::Char('p') | KeyCode::Char(' ') => state.set_paused(!state.is_paused()),
+        KeyCode::Char('q') | KeyCode::Esc => state.abort(),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => state.abort(),
+        _ => {}
+    }
+}
+
+fn draw(frame: &mut Frame, state: &RunState, rates: &Rates, title: &str, started: Instant) {
+    let [header, generation, writing, threads, footer] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let total = state.total();
+    let generated = state.generated();
+    let status = if state.is_aborted() {
+        "Aborting".red()
+    } else if state.is_writing() {
+        "Writing".cyan()
+    } else if state.is_paused() {
+        "Paused".yellow()
+    } else {
+        "Generating".green()
+    };
+    let rate = rates.total();
+    let eta = if state.is_writing() || generated >= total {
+        "-".to_string()
+    } else if rate > 0.0 {
+        format_duration(Duration::from_secs_f64((total - generated) as f64 / rate))
+    } else {
+        "--:--:--".to_string()
+    };
+    let memory = resident_memory().map_or_else(|| "n/a".to_string(), |bytes| format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)));
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(vec!["Status: ".into(), status, format!("   Elapsed: {}", format_duration(started.elapsed())).into()]),
+            Line::from(format!("Rate: {:.0} wallets/sec   ETA: {}   Memory: {}", rate, eta, memory)),
+        ])
+        .block(Block::bordered().title(title.to_string())),
+        header,
+    );
+
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title("Generated"))
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .ratio(ratio(generated, total))
+            .label(format!("{}/{}", generated, total)),
+        generation,
+    );
+    let written = state.written();
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title("Written"))
+            .gauge_style(Style::default().fg(Color::Blue))
+            .ratio(ratio(written, total))
+            .label(format!("{}/{}", written, total)),
+        writing,
+    );
+
+    let rows = state
+        .thread_counts()
+        .into_iter()
+        .zip(&rates.per_thread)
+        .enumerate()
+        .map(|(thread, (count, rate))| Row::new([thread.to_string(), count.to_string(), format!("{:.0}", rate)]));
+    frame.render_widget(
+        Table::new(rows, [Constraint::Length(8), Constraint::Length(16), Constraint::Length(16)])
+            .header(Row::new(["Thread", "Generated", "Wallets/sec"]).bold())
+            .block(Block::bordered().title("Threads")),
+        threads,
+    );
+
+    let keys = if state.is_writing() { "finishing write..." } else { "p pause/resume   q abort" };
+    frame.render_widget(Paragraph::new(keys).dim(), footer);
+}
+
+/// Show the dashboard until the run is finished, translating keys into pause and abort requests.
+pub fn run(state: &RunState, title: &str) -> Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let mut dashboard = Dashboard::new(state, title);
+
+    let result = (|| -> Result<()> {
+        while !state.is_finished() {
+            terminal.draw(|frame| dashboard.draw(frame, state))?;
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                handle_key(state, key);
+            }
+        }
+        Ok(())
+    })();
+
+    ratatui::try_restore()?;
+    result
+}
@@ -0,0 +1,488 @@
+//! Cosmos SDK transactions: JSON model, protobuf encoding and SIGN_MODE_DIRECT signing.
+//!
+//! The JSON model mirrors what `<appd> tx ... --generate-only` prints, so files
+//! written here can be inspected or signed with standard SDK tooling. Protobuf
+//! encoding follows the field order of the SDK definitions, which keeps the
+//! bytes we sign identical to the bytes we broadcast.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use secp256k1::{Message as SecpMessage, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::types::KeyType;
+use crate::coin::Coin;
+use crate::signer::{Signer, SoftwareSigner};
+
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const MSG_MULTI_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgMultiSend";
+pub const SIGN_MODE_DIRECT: &str = "SIGN_MODE_DIRECT";
+pub const SIGN_MODE_LEGACY_AMINO_JSON: &str = "SIGN_MODE_LEGACY_AMINO_JSON";
+
+/// Serialize `u64` as a decimal string, as proto-JSON does.
+mod u64_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Protobuf definitions for the subset of the SDK this crate encodes.
+pub mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Any {
+        #[prost(string, tag = "1")]
+        pub type_url: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub value: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Coin {
+        #[prost(string, tag = "1")]
+        pub denom: String,
+        #[prost(string, tag = "2")]
+        pub amount: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgSend {
+        #[prost(string, tag = "1")]
+        pub from_address: String,
+        #[prost(string, tag = "2")]
+        pub to_address: String,
+        #[prost(message, repeated, tag = "3")]
+        pub amount: Vec<Coin>,
+    }
+
+    /// `Input` and `Output` share this layout.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AddressCoins {
+        #[prost(string, tag = "1")]
+        pub address: String,
+        #[prost(message, repeated, tag = "2")]
+        pub coins: Vec<Coin>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgMultiSend {
+        #[prost(message, repeated, tag = "1")]
+        pub inputs: Vec<AddressCoins>,
+        #[prost(message, repeated, tag = "2")]
+        pub outputs: Vec<AddressCoins>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TxBody {
+        #[prost(message, repeated, tag = "1")]
+        pub messages: Vec<Any>,
+        #[prost(string, tag = "2")]
+        pub memo: String,
+        #[prost(uint64, tag = "3")]
+        pub timeout_height: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ModeInfoSingle {
+        #[prost(int32, tag = "1")]
+        pub mode: i32,
+    }
+
+    /// `ModeInfo` with only the `single` arm of its oneof.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ModeInfo {
+        #[prost(message, optional, tag = "1")]
+        pub single: Option<ModeInfoSingle>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SignerInfo {
+        #[prost(message, optional, tag = "1")]
+        pub public_key: Option<Any>,
+        #[prost(message, optional, tag = "2")]
+        pub mode_info: Option<ModeInfo>,
+        #[prost(uint64, tag = "3")]
+        pub sequence: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Fee {
+        #[prost(message, repeated, tag = "1")]
+        pub amount: Vec<Coin>,
+        #[prost(uint64, tag = "2")]
+        pub gas_limit: u64,
+        #[prost(string, tag = "3")]
+        pub payer: String,
+        #[prost(string, tag = "4")]
+        pub granter: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AuthInfo {
+        #[prost(message, repeated, tag = "1")]
+        pub signer_infos: Vec<SignerInfo>,
+        #[prost(message, optional, tag = "2")]
+        pub fee: Option<Fee>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SignDoc {
+        #[prost(bytes = "vec", tag = "1")]
+        pub body_bytes: Vec<u8>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub auth_info_bytes: Vec<u8>,
+        #[prost(string, tag = "3")]
+        pub chain_id: String,
+        #[prost(uint64, tag = "4")]
+        pub account_number: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TxRaw {
+        #[prost(bytes = "vec", tag = "1")]
+        pub body_bytes: Vec<u8>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub auth_info_bytes: Vec<u8>,
+        #[prost(bytes = "vec", repeated, tag = "3")]
+        pub signatures: Vec<Vec<u8>>,
+    }
+
+    /// Both `cosmos.crypto.secp256k1.PubKey` and the ethsecp256k1 variant.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PubKey {
+        #[prost(bytes = "vec", tag = "1")]
+        pub key: Vec<u8>,
+    }
+
+    /// `SignMode` enum value for SIGN_MODE_DIRECT.
+    pub const SIGN_MODE_DIRECT: i32 = 1;
+    /// `SignMode` enum value for SIGN_MODE_LEGACY_AMINO_JSON, used by Ledger devices.
+    pub const SIGN_MODE_LEGACY_AMINO_JSON: i32 = 127;
+}
+
+/// How a signer signs a transaction, as recorded in its signer info.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignMode {
+    Direct,
+    LegacyAminoJson,
+}
+
+impl SignMode {
+    /// Name of the mode in SDK JSON.
+    pub fn name(self) -> &'static str {
+        match self {
+            SignMode::Direct => SIGN_MODE_DIRECT,
+            SignMode::LegacyAminoJson => SIGN_MODE_LEGACY_AMINO_JSON,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            SIGN_MODE_DIRECT => Ok(SignMode::Direct),
+            SIGN_MODE_LEGACY_AMINO_JSON => Ok(SignMode::LegacyAminoJson),
+            _ => Err(anyhow!("Unsupported sign mode {}", name)),
+        }
+    }
+
+    fn proto(self) -> i32 {
+        match self {
+            SignMode::Direct => proto::SIGN_MODE_DIRECT,
+            SignMode::LegacyAminoJson => proto::SIGN_MODE_LEGACY_AMINO_JSON,
+        }
+    }
+}
+
+fn proto_signer_info(public_key: Option<&PubKeyJson>, mode: SignMode, sequence: u64) -> Result<proto::SignerInfo> {
+    let public_key = public_key
+        .map(|public_key| -> Result<proto::Any> {
+            let key = general_purpose::STANDARD.decode(&public_key.key).context("Invalid base64 public key in signer info")?;
+            Ok(encode_any(&public_key.type_url, &proto::PubKey { key }))
+        })
+        .transpose()?;
+    Ok(proto::SignerInfo {
+        public_key,
+        mode_info: Some(proto::ModeInfo { single: Some(proto::ModeInfoSingle { mode: mode.proto() }) }),
+        sequence,
+    })
+}
+
+impl From<&Coin> for proto::Coin {
+    fn from(coin: &Coin) -> Self {
+        proto::Coin { denom: coin.denom.clone(), amount: coin.amount.clone() }
+    }
+}
+
+fn proto_coins(coins: &[Coin]) -> Vec<proto::Coin> {
+    coins.iter().map(proto::Coin::from).collect()
+}
+
+fn encode_any<M: prost::Message>(type_url: &str, message: &M) -> proto::Any {
+    proto::Any { type_url: type_url.to_string(), value: message.encode_to_vec() }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AddressCoins {
+    pub address: String,
+    pub coins: Vec<Coin>,
+}
+
+/// Transaction messages supported by this crate.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "@type")]
+pub enum Msg {
+    #[serde(rename = "/cosmos.bank.v1beta1.MsgSend")]
+    Send {
+        from_address: String,
+        to_address: String,
+        amount: Vec<Coin>,
+    },
+    #[serde(rename = "/cosmos.bank.v1beta1.MsgMultiSend")]
+    MultiSend {
+        inputs: Vec<AddressCoins>,
+        outputs: Vec<AddressCoins>,
+    },
+}
+
+impl Msg {
+    pub fn to_any(&self) -> proto::Any {
+        match self {
+            Msg::Send { from_address, to_address, amount } => encode_any(
+                MSG_SEND_TYPE_URL,
+                &proto::MsgSend {
+                    from_address: from_address.clone(),
+                    to_address: to_address.clone(),
+                    amount: proto_coins(amount),
+                },
+            ),
+            Msg::MultiSend { inputs, outputs } => {
+                let convert = |entries: &[AddressCoins]| {
+                    entries
+                        .iter()
+                        .map(|e| proto::AddressCoins { address: e.address.clone(), coins: proto_coins(&e.coins) })
+                        .collect()
+                };
+                encode_any(MSG_MULTI_SEND_TYPE_URL, &proto::MsgMultiSend { inputs: convert(inputs), outputs: convert(outputs) })
+            }
+        }
+    }
+
+    /// Address expected to sign this message.
+    pub fn signer(&self) -> Option<&str> {
+        match self {
+            Msg::Send { from_address, .. } => Some(from_address),
+            Msg::MultiSend { inputs, .. } => inputs.first().map(|i| i.address.as_str()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TxBody {
+    pub messages: Vec<Msg>,
+    #[serde(default)]
+    pub memo: String,
+    #[serde(default, with = "u64_string")]
+    pub timeout_height: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PubKeyJson {
+    #[serde(rename = "@type")]
+    pub type_url: String,
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModeInfoSingleJson {
+    pub mode: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModeInfoJson {
+    pub single: ModeInfoSingleJson,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignerInfo {
+    pub public_key: PubKeyJson,
+    pub mode_info: ModeInfoJson,
+    #[serde(with = "u64_string")]
+    pub sequence: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Fee {
+    pub amount: Vec<Coin>,
+    #[serde(with = "u64_string")]
+    pub gas_limit: u64,
+    #[serde(default)]
+    pub payer: String,
+    #[serde(default)]
+    pub granter: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AuthInfo {
+    #[serde(default)]
+    pub signer_infos: Vec<SignerInfo>,
+    pub fee: Fee,
+}
+
+/// A transaction in SDK JSON form. `signatures` holds base64 strings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Tx {
+    pub body: TxBody,
+    pub auth_info: AuthInfo,
+    #[serde(default)]
+    pub signatures: Vec<String>,
+}
+
+/// Values a signer must know that are not part of the transaction itself.
+#[derive(Debug, Clone)]
+pub struct SignerData {
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+impl Tx {
+    pub fn new(messages: Vec<Msg>, fee: Fee, memo: String) -> Self {
+        Tx {
+            body: TxBody { messages, memo, timeout_height: 0 },
+            auth_info: AuthInfo { signer_infos: Vec::new(), fee },
+            signatures: Vec::new(),
+        }
+    }
+
+    pub fn body_bytes(&self) -> Vec<u8> {
+        prost::Message::encode_to_vec(&proto::TxBody {
+            messages: self.body.messages.iter().map(Msg::to_any).collect(),
+            memo: self.body.memo.clone(),
+            timeout_height: self.body.timeout_height,
+        })
+    }
+
+    pub fn auth_info_bytes(&self) -> Result<Vec<u8>> {
+        let signer_infos = self
+            .auth_info
+            .signer_infos
+            .iter()
+            .map(|info| proto_signer_info(Some(&info.public_key), SignMode::from_name(&info.mode_info.single.mode)?, info.sequence))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.encode_auth_info(signer_infos))
+    }
+
+    fn encode_auth_info(&self, signer_infos: Vec<proto::SignerInfo>) -> Vec<u8> {
+        let fee = &self.auth_info.fee;
+        prost::Message::encode_to_vec(&proto::AuthInfo {
+            signer_infos,
+            fee: Some(proto::Fee {
+                amount: proto_coins(&fee.amount),
+                gas_limit: fee.gas_limit,
+                payer: fee.payer.clone(),
+                granter: fee.granter.clone(),
+            }),
+        })
+    }
+
+    /// Bytes of the `SignDoc` for SIGN_MODE_DIRECT.
+    pub fn sign_doc_bytes(&self, signer: &SignerData) -> Result<Vec<u8>> {
+        Ok(prost::Message::encode_to_vec(&proto::SignDoc {
+            body_bytes: self.body_bytes(),
+            auth_info_bytes: self.auth_info_bytes()?,
+            chain_id: signer.chain_id.clone(),
+            account_number: signer.account_number,
+        }))
+    }
+
+    /// Sign with SIGN_MODE_DIRECT as the single signer, replacing any previous signature.
+    pub fn sign_direct(&mut self, private_key: &[u8; 32], key_type: &KeyType, signer: &SignerData) -> Result<()> {
+        self.sign_with(&SoftwareSigner::new(private_key, key_type.clone())?, signer)
+    }
+
+    /// [`sign_direct`](Self::sign_direct) with any signing backend, such as a key held in an HSM.
+    pub fn sign_with(&mut self, key: &dyn Signer, signer: &SignerData) -> Result<()> {
+        self.auth_info.signer_infos = vec![SignerInfo {
+            public_key: PubKeyJson {
+                type_url: key.key_type().pubkey_type_url().to_string(),
+                key: general_purpose::STANDARD.encode(key.public_key().serialize()),
+            },
+            mode_info: ModeInfoJson { single: ModeInfoSingleJson { mode: SIGN_MODE_DIRECT.to_string() } },
+            sequence: signer.sequence,
+        }];
+
+        let signature = key.sign(&self.sign_doc_bytes(signer)?)?;
+        self.signatures = vec![general_purpose::STANDARD.encode(signature)];
+        Ok(())
+    }
+
+    /// Protobuf `TxRaw` bytes as accepted by `broadcast_tx_*`.
+    pub fn to_raw_bytes(&self) -> Result<Vec<u8>> {
+        let signatures = self
+            .signatures
+            .iter()
+            .map(|s| general_purpose::STANDARD.decode(s).context("Invalid base64 signature"))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(prost::Message::encode_to_vec(&proto::TxRaw {
+            body_bytes: self.body_bytes(),
+            auth_info_bytes: self.auth_info_bytes()?,
+            signatures,
+        }))
+    }
+
+    /// Protobuf `TxRaw` bytes for gas simulation: the transaction with one signer at `sequence`
+    /// signing with `mode`, and a zeroed signature of real length. Nodes skip signature checks when
+    /// simulating but charge for the bytes, so the estimate matches the signed transaction. Without
+    /// a public key the node simulates with a placeholder secp256k1 key.
+    pub fn simulation_bytes(&self, public_key: Option<&PubKeyJson>, mode: SignMode, sequence: u64) -> Result<Vec<u8>> {
+        let signer_info = proto_signer_info(public_key, mode, sequence)?;
+        Ok(prost::Message::encode_to_vec(&proto::TxRaw {
+            body_bytes: self.body_bytes(),
+            auth_info_bytes: self.encode_auth_info(vec![signer_info]),
+            signatures: vec![vec![0; 64]],
+        }))
+    }
+}
+
+/// Hash `payload` the way the key type's chain expects and return a 64-byte compact signature.
+pub fn sign_digest<C: secp256k1::Signing>(secp: &Secp256k1<C>, secret_key: &SecretKey, payload: &[u8], key_type: &KeyType) -> [u8; 64] {
+    secp.sign_ecdsa(&SecpMessage::from_digest(payload_digest(payload, key_type)), secret_key).serialize_compact()
+}
+
+/// The digest of `payload` a key type's chain signs: SHA-256, or Keccak256 for ethsecp256k1.
+pub fn payload_digest(payload: &[u8], key_type: &KeyType) -> [u8; 32] {
+    match key_type {
+        KeyType::Secp256k1 => Sha256::digest(payload).into(),
+        KeyType::Ethsecp256k1 => Keccak256::digest(payload).into(),
+    }
+}
+
+/// Write a transaction as pretty-printed SDK JSON.
+pub fn write_tx_file<P: AsRef<Path>>(path: P, tx: &Tx) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, tx)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read a transaction written by [`write_tx_file`] or `--generate-only`.
+pub fn read_tx_file<P: AsRef<Path>>(path: P) -> Result<Tx> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    serde_json::from_reader(BufReader::new(file)).with_context(|| format!("Invalid transaction in {}", path.display()))
+}
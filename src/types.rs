@@ -0,0 +1,160 @@
+//! Key types, address encodings and output formats shared by the library and the CLI.
+//!
+//! These derive `clap::ValueEnum` only with the `cli` feature, so embedding the generator
+//! does not pull in clap.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyType {
+    /// Standard secp256k1 (SHA256 + RIPEMD160)
+    Secp256k1,
+    /// Ethereum-compatible secp256k1 (Keccak256)
+    Ethsecp256k1,
+}
+
+impl KeyType {
+    /// Default BIP-44 coin type: 118 (Cosmos) for secp256k1, 60 (Ethereum) for ethsecp256k1,
+    /// matching Keplr and MetaMask derivations.
+    pub fn coin_type(&self) -> u32 {
+        match self {
+            KeyType::Secp256k1 => 118,
+            KeyType::Ethsecp256k1 => 60,
+        }
+    }
+
+    /// Protobuf type URL of the public key, as used in signer infos and genesis accounts.
+    pub fn pubkey_type_url(&self) -> &'static str {
+        match self {
+            KeyType::Secp256k1 => "/cosmos.crypto.secp256k1.PubKey",
+            KeyType::Ethsecp256k1 => "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+        }
+    }
+}
+
+impl FromStr for KeyType {
+    type Err = String;
+
+    /// Parse a key type name, ignoring case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "secp256k1" => Ok(KeyType::Secp256k1),
+            "ethsecp256k1" => Ok(KeyType::Ethsecp256k1),
+            _ => Err(format!("unknown key type: {}", name)),
+        }
+    }
+}
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressEncoding {
+    /// Classic Bech32 checksum (BIP-173), used by Cosmos SDK chains
+    Bech32,
+    /// Bech32m checksum (BIP-350)
+    Bech32m,
+}
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BitcoinAddress {
+    /// Legacy base58check address (1..., m.../n... on test networks) at BIP-44 m/44'/{coin}'/0'/0/{i}
+    P2pkh,
+    /// Native SegWit bech32 address (bc1q...) at BIP-84 m/84'/{coin}'/0'/0/{i}
+    P2wpkh,
+}
+
+impl BitcoinAddress {
+    /// BIP-43 purpose level of the derivation path.
+    pub fn purpose(self) -> u32 {
+        match self {
+            BitcoinAddress::P2pkh => 44,
+            BitcoinAddress::P2wpkh => 84,
+        }
+    }
+}
+
+/// The kind of chain a run derives keys and addresses for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChainFamily {
+    /// Bech32 account addresses of Cosmos SDK chains, hashed per key type
+    Cosmos,
+    /// Bitcoin addresses of this type, with WIF keys; the prefix names the network
+    Bitcoin(BitcoinAddress),
+    /// SLIP-0010 ed25519 keys with base58 public key addresses; prefix and key type are unused
+    Solana,
+}
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DerivationScheme {
+    /// Increment the address index: m/44'/{coin}'/0'/0/{i}
+    Standard,
+    /// Increment the account, as Ledger Live does: m/44'/{coin}'/{i}'/0/0
+    LedgerLive,
+}
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PubkeyFormat {
+    /// Base64 compressed key
+    #[default]
+    Base64,
+    /// Cosmos SDK proto-JSON object: {"@type": "/cosmos.crypto.secp256k1.PubKey", "key": "<base64>"}
+    SdkJson,
+    /// Hex compressed key (33 bytes)
+    Hex,
+    /// Hex uncompressed key (65 bytes, 0x04 prefix), as EVM tooling expects
+    UncompressedHex,
+}
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrivateKeyFormat {
+    /// Hex-encoded 32 bytes
+    #[default]
+    Hex,
+    /// Wallet Import Format for a compressed public key; mainnet unless the run is on a Bitcoin test network
+    Wif,
+    /// Base64-encoded 32 bytes
+    Base64,
+}
+
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// JSON array with one record per line
+    Json,
+    /// One JSON record per line, no enclosing array
+    Ndjson,
+    /// Header row, then one wallet per row
+    Csv,
+    /// Fixed-size records of raw address, key and public key bytes after a JSON header
+    Bin,
+    /// Arrow IPC file (Feather v2) of record batches
+    #[cfg(feature = "arrow")]
+    Arrow,
+}
+
+impl OutputFormat {
+    /// File extension, used to name objects written under an `s3://` or `gs://` prefix.
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Bin => "bin",
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow => "arrow",
+        }
+    }
+}
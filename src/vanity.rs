@@ -0,0 +1,154 @@
+//! Vanity address patterns and how hard they are to find.
+//!
+//! Address characters after the prefix are uniformly distributed: the 20-byte account hash fills
+//! exactly 32 bech32 characters, and the 6 checksum characters behave the same, while a hex EVM
+//! address is 40 hex digits. Each bech32 character a pattern fixes divides the odds by 32, each
+//! hex digit by 16, and each hex letter whose EIP-55 checksum case must match by another 2.
+
+use anyhow::{anyhow, Result};
+
+/// Characters of the bech32 data part, in value order.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Data and checksum characters of a bech32 address of a 20-byte hash.
+pub const BECH32_ADDRESS_CHARS: usize = 38;
+
+/// Hex digits of an EVM address, without `0x`.
+pub const HEX_ADDRESS_CHARS: usize = 40;
+
+/// The part of an address a pattern is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// Bech32 data part, after the prefix and its `1` separator
+    Bech32,
+    /// EVM address of an ethsecp256k1 key, after `0x`
+    Hex,
+}
+
+impl Alphabet {
+    /// Characters each position can take.
+    pub fn size(self) -> f64 {
+        match self {
+            Alphabet::Bech32 => 32.0,
+            Alphabet::Hex => 16.0,
+        }
+    }
+
+    /// Characters of a whole address that a pattern can fall on.
+    pub fn address_chars(self) -> usize {
+        match self {
+            Alphabet::Bech32 => BECH32_ADDRESS_CHARS,
+            Alphabet::Hex => HEX_ADDRESS_CHARS,
+        }
+    }
+}
+
+/// Where in the address the pattern must appear.
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Position {
+    /// Right after the prefix (bech32) or 0x (hex)
+    #[default]
+    Prefix,
+    /// At the end of the address, checksum included for bech32
+    Suffix,
+    /// Anywhere in the address
+    Contains,
+}
+
+/// A pattern to look for in addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    text: String,
+    alphabet: Alphabet,
+    position: Position,
+    /// Whether hex letters must match the EIP-55 checksum case
+    case_sensitive: bool,
+}
+
+impl Pattern {
+    /// Validate `text` against the characters addresses of `alphabet` can hold. Bech32 patterns
+    /// are lowercased, as are hex patterns unless `case_sensitive`.
+    pub fn new(text: &str, alphabet: Alphabet, position: Position, case_sensitive: bool) -> Result<Self> {
+        if text.is_empty() {
+            return Err(anyhow!("The pattern is empty"));
+        }
+        if text.chars().count() > alphabet.address_chars() {
+            return Err(anyhow!("{:?} is longer than the {} characters of an address", text, alphabet.address_chars()));
+        }
+        let text = match alphabet {
+            Alphabet::Bech32 if case_sensitive => {
+                return Err(anyhow!("Bech32 addresses are lowercase; case-sensitive matching applies to hex patterns"));
+            }
+            Alphabet::Bech32 => {
+                let text = text.to_ascii_lowercase();
+                if let Some(c) = text.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+                    return Err(anyhow!("{:?} cannot appear in a bech32 address (no 1, b, i or o)", c));
+                }
+                text
+            }
+            Alphabet::Hex => {
+                if let Some(c) = text.chars().find(|c| !c.is_ascii_hexdigit()) {
+                    return Err(anyhow!("{:?} is not a hex digit", c));
+                }
+                if case_sensitive { text.to_string() } else { text.to_ascii_lowercase() }
+            }
+        };
+        Ok(Self { text, alphabet, position, case_sensitive })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Chance that one address matches. For [`Position::Contains`] this counts each place the
+    /// pattern could start once, which slightly overstates the odds when matches can overlap.
+    pub fn probability(&self) -> f64 {
+        let len = self.text.chars().count();
+        let checksum_letters = match self.case_sensitive {
+            true => self.text.chars().filter(char::is_ascii_alphabetic).count(),
+            false => 0,
+        };
+        let at_one_place = self.alphabet.size().powi(-(len as i32)) * 0.5f64.powi(checksum_letters as i32);
+        match self.position {
+            Position::Prefix | Position::Suffix => at_one_place,
+            Position::Contains => (at_one_place * (self.alphabet.address_chars() - len + 1) as f64).min(1.0),
+        }
+    }
+
+    /// Mean number of addresses to try before one matches.
+    pub fn expected_attempts(&self) -> f64 {
+        1.0 / self.probability()
+    }
+
+    /// Addresses to try to find a match with probability `confidence` (between 0 and 1).
+    pub fn attempts_for(&self, confidence: f64) -> f64 {
+        let probability = self.probability();
+        if probability >= 1.0 {
+            return 1.0;
+        }
+        ((1.0 - confidence).ln() / (-probability).ln_1p()).ceil().max(1.0)
+    }
+
+    /// Whether `address` (the data part of a bech32 address, or the hex digits of an EVM address,
+    /// EIP-55 cased for case-sensitive patterns) matches.
+    pub fn matches(&self, address: &str) -> bool {
+        let matches = |address: &str| match self.position {
+            Position::Prefix => address.starts_with(&self.text),
+            Position::Suffix => address.ends_with(&self.text),
+            Position::Contains => address.contains(&self.text),
+        };
+        match self.case_sensitive {
+            true => matches(address),
+            false => matches(&address.to_ascii_lowercase()),
+        }
+    }
+}
@@ -1,13 +1,74 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Wallet {
     pub address: String,  // Bech32 address
     #[serde(rename = "evmAddress", skip_serializing_if = "Option::is_none")]
     pub evm_address: Option<String>,  // EVM address
-    pub pubkey: String,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub pubkey: String,  // Compressed pubkey, base64 unless another --pubkey-format was chosen
     #[serde(rename = "privateKey")]
     pub private_key: String,
     #[serde(rename = "derivationPath")]
     pub derivation_path: String,
-}
\ No newline at end of file
+    #[serde(default)]
+    pub index: u64,  // Last derivation path component
+    #[serde(rename = "pubkeyHex", default, skip_serializing_if = "Option::is_none")]
+    pub pubkey_hex: Option<String>,  // Hex-encoded compressed pubkey
+    #[serde(rename = "pubkeyType", default, skip_serializing_if = "Option::is_none")]
+    pub pubkey_type: Option<String>,  // Protobuf type URL of the pubkey
+    #[serde(rename = "pubkeyUncompressed", default, skip_serializing_if = "Option::is_none")]
+    pub pubkey_uncompressed: Option<String>,  // Hex-encoded uncompressed pubkey, when requested
+    #[serde(rename = "secp256k1Address", default, skip_serializing_if = "Option::is_none")]
+    pub secp256k1_address: Option<String>,  // SHA256/RIPEMD160 bech32 address of an ethsecp256k1 key, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wif: Option<String>,  // WIF private key (Bitcoin wallets)
+}
+
+/// Bookkeeping the allocator adds to each heap allocation, roughly.
+const ALLOCATION_OVERHEAD: usize = 16;
+
+impl Wallet {
+    /// Approximate bytes the record occupies in memory, heap allocations included.
+    pub fn memory_size(&self) -> usize {
+        let strings = [
+            Some(&self.address),
+            self.evm_address.as_ref(),
+            Some(&self.pubkey),
+            Some(&self.private_key),
+            Some(&self.derivation_path),
+            self.pubkey_hex.as_ref(),
+            self.pubkey_type.as_ref(),
+            self.pubkey_uncompressed.as_ref(),
+            self.secp256k1_address.as_ref(),
+            self.wif.as_ref(),
+        ];
+        std::mem::size_of::<Self>()
+            + strings.iter().flatten().map(|s| s.capacity() + ALLOCATION_OVERHEAD).sum::<usize>()
+    }
+
+    /// The record with `pubkey` as the Cosmos SDK proto-JSON object `{"@type": ..., "key": ...}`,
+    /// as accepted by `keys add --pubkey` and genesis files.
+    pub fn to_sdk_json(&self, default_type_url: &str) -> Value {
+        let mut record = json!(self);
+        record["pubkey"] = json!({
+            "@type": self.pubkey_type.as_deref().unwrap_or(default_type_url),
+            "key": self.pubkey,
+        });
+        record
+    }
+}
+
+/// Accept `pubkey` as bare base64 or as the SDK proto-JSON object.
+fn deserialize_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PubKey {
+        Base64(String),
+        Sdk { key: String },
+    }
+    Ok(match PubKey::deserialize(deserializer)? {
+        PubKey::Base64(key) | PubKey::Sdk { key } => key,
+    })
+}
@@ -0,0 +1,422 @@
+//! Reading and writing wallet output files.
+//!
+//! The generator writes a JSON array with exactly one record per line, so the
+//! same line-oriented reader handles both that layout and NDJSON without
+//! loading multi-gigabyte files into memory.
+
+use anyhow::{anyhow, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::binary::{self, BinaryReader, Header};
+use crate::generator::DerivationConfig;
+use crate::secure_output::OutputPermissions;
+use crate::types::OutputFormat;
+use crate::wallet::Wallet;
+
+/// Streams records from a JSON-array (one record per line) or NDJSON file.
+pub struct RecordReader<T> {
+    reader: BufReader<File>,
+    line: String,
+    line_number: usize,
+    _record: PhantomData<T>,
+}
+
+pub type WalletReader = RecordReader<Wallet>;
+
+impl<T: DeserializeOwned> RecordReader<T> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if binary::is_binary(path)? {
+            return Err(anyhow!("{} is a binary wallet file; convert it to JSON first", path.display()));
+        }
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        Ok(Self {
+            reader: BufReader::with_capacity(8 * 1024 * 1024, file),
+            line: String::new(),
+            line_number: 0,
+            _record: PhantomData,
+        })
+    }
+}
+
+/// Strip JSON array punctuation around a single-line record.
+pub(crate) fn record_body(line: &str) -> &str {
+    line.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim()
+        .trim_end_matches(',')
+        .trim()
+}
+
+impl<T: DeserializeOwned> Iterator for RecordReader<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+            self.line_number += 1;
+
+            let body = record_body(&self.line);
+            if body.is_empty() {
+                continue;
+            }
+            return Some(
+                serde_json::from_str(body).with_context(|| format!("Invalid record on line {}", self.line_number)),
+            );
+        }
+    }
+}
+
+/// Read every wallet in a file, JSON, NDJSON or binary, into memory.
+pub fn read_wallets<P: AsRef<Path>>(path: P) -> Result<Vec<Wallet>> {
+    if binary::is_binary(&path)? {
+        return BinaryReader::open(path)?.collect();
+    }
+    WalletReader::open(path)?.collect()
+}
+
+/// Stream every wallet of a file, JSON, NDJSON or binary.
+pub fn stream_wallets<P: AsRef<Path>>(path: P) -> Result<Box<dyn Iterator<Item = Result<Wallet>>>> {
+    if binary::is_binary(&path)? {
+        return Ok(Box::new(BinaryReader::open(path)?));
+    }
+    Ok(Box::new(WalletReader::open(path)?))
+}
+
+/// `--output` value that streams records to stdout.
+pub const STDOUT: &str = "-";
+
+/// Buffer of file and stdout outputs, unless a memory budget calls for less.
+pub const WRITE_BUFFER: usize = 64 * 1024 * 1024;
+
+/// Destination of generated records. `finish` commits what was written, e.g. completes an upload.
+pub trait OutputSink: Write {
+    fn finish(&mut self) -> Result<()> {
+        Ok(self.flush()?)
+    }
+}
+
+impl<W: Write> OutputSink for BufWriter<W> {}
+
+/// Open `path` for buffered writing with `permissions`, creating parent directories, or stdout
+/// for [`STDOUT`].
+pub fn create_output(path: &str, buffer_size: usize, permissions: &OutputPermissions) -> Result<Box<dyn OutputSink>> {
+    if path == STDOUT {
+        return Ok(Box::new(BufWriter::with_capacity(buffer_size, io::stdout().lock())));
+    }
+    let path = Path::new(path);
+    permissions.check_directory(path)?;
+    let file = permissions.create(path)?;
+    Ok(Box::new(BufWriter::with_capacity(buffer_size, file)))
+}
+
+/// The parts of a record needed to find where a wallet file ends.
+#[derive(Deserialize)]
+struct IndexedRecord {
+    address: String,
+    index: u64,
+}
+
+/// An existing JSON or NDJSON wallet file that a run appends to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExistingOutput {
+    pub format: OutputFormat,
+    pub records: usize,
+    /// Highest index present and the address at it
+    pub highest: Option<(u64, String)>,
+}
+
+impl ExistingOutput {
+    /// Scan the wallet file at `path`, or `None` if it does not exist or is empty.
+    pub fn inspect<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(anyhow::Error::new(e).context(format!("Failed to open {}", path.display()))),
+        };
+        let mut start = [0u8; 64];
+        let read = file.read(&mut start)?;
+        let format = match start[..read].iter().find(|byte| !byte.is_ascii_whitespace()) {
+            None => return Ok(None),
+            Some(b'[') => OutputFormat::Json,
+            Some(b'{') => OutputFormat::Ndjson,
+            Some(_) => return Err(anyhow!("{} is not a JSON or NDJSON wallet file; only those can be appended to", path.display())),
+        };
+        if format == OutputFormat::Json && array_close(&mut file)?.is_none() {
+            return Err(anyhow!("{} does not end with the ] closing its array; was its run interrupted?", path.display()));
+        }
+
+        let mut records = 0;
+        let mut highest: Option<(u64, String)> = None;
+        for record in RecordReader::<IndexedRecord>::open(path)? {
+            let record = record.with_context(|| format!("Failed to read {}", path.display()))?;
+            records += 1;
+            if highest.as_ref().is_none_or(|(index, _)| record.index > *index) {
+                highest = Some((record.index, record.address));
+            }
+        }
+        Ok(Some(Self { format, records, highest }))
+    }
+
+    /// Index the appended wallets start from.
+    pub fn next_index(&self) -> u64 {
+        self.highest.as_ref().map_or(0, |(index, _)| index + 1)
+    }
+}
+
+/// Offset where the `]` closing a JSON array starts, whitespace before it included, if `]` is the
+/// last non-whitespace byte of `file`.
+fn array_close(file: &mut File) -> Result<Option<u64>> {
+    let len = file.seek(SeekFrom::End(0))?;
+    let tail_start = len.saturating_sub(256);
+    file.seek(SeekFrom::Start(tail_start))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+    let content = |bytes: &[u8]| bytes.iter().rposition(|byte| !byte.is_ascii_whitespace());
+    Ok(match content(&tail) {
+        Some(bracket) if tail[bracket] == b']' => {
+            Some(tail_start + content(&tail[..bracket]).map_or(0, |last| last as u64 + 1))
+        }
+        _ => None,
+    })
+}
+
+/// Reopen the wallet file at `path` to write records after the ones `existing` holds: a JSON
+/// array loses its closing line, which [`WalletWriter::finish`] writes back, and NDJSON gets a
+/// final line break if it lacks one. The file then holds exactly the bytes that precede the new
+/// records. The file is given `permissions`.
+pub fn append_output(path: &str, existing: &ExistingOutput, buffer_size: usize, permissions: &OutputPermissions) -> Result<Box<dyn OutputSink>> {
+    permissions.check_directory(Path::new(path))?;
+    let mut file = permissions.open(Path::new(path), OpenOptions::new().read(true).write(true))?;
+    match existing.format {
+        OutputFormat::Json => {
+            let close = array_close(&mut file)?.ok_or_else(|| anyhow!("{} no longer ends with ]", path))?;
+            file.set_len(close)?;
+        }
+        _ => {
+            let len = file.seek(SeekFrom::End(0))?;
+            let mut last = [0u8; 1];
+            if len > 0 {
+                file.seek(SeekFrom::Start(len - 1))?;
+                file.read_exact(&mut last)?;
+            }
+            if len > 0 && last[0] != b'\n' {
+                file.write_all(b"\n")?;
+            }
+        }
+    }
+    file.seek(SeekFrom::End(0))?;
+    Ok(Box::new(BufWriter::with_capacity(buffer_size, file)))
+}
+
+/// Check that [`create_output`] could write `path`, without creating it: `path` is not a directory
+/// and its nearest existing ancestor is a directory we can create files in.
+pub fn check_output(path: &str) -> Result<()> {
+    if path == STDOUT {
+        return Ok(());
+    }
+    let path = Path::new(path);
+    if path.is_dir() {
+        return Err(anyhow!("{} is a directory", path.display()));
+    }
+    if path.exists() {
+        OpenOptions::new().write(true).open(path).with_context(|| format!("Cannot write {}", path.display()))?;
+        return Ok(());
+    }
+    let dir = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.as_os_str().is_empty() || dir.exists())
+        .map(|dir| if dir.as_os_str().is_empty() { Path::new(".") } else { dir })
+        .unwrap_or(Path::new("."));
+    if !dir.is_dir() {
+        return Err(anyhow!("{} is not a directory", dir.display()));
+    }
+    let probe = dir.join(format!(".wallet-generator-{}.probe", std::process::id()));
+    File::create_new(&probe).with_context(|| format!("Cannot create files in {}", dir.display()))?;
+    fs::remove_file(&probe)?;
+    Ok(())
+}
+
+/// Write records as a JSON array with one record per line. Records holding private keys pass
+/// `permissions`, which checks the directory and creates the file with them.
+pub fn write_json_array<T, I, P>(path: P, records: I, permissions: Option<&OutputPermissions>) -> Result<()>
+where
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let file = match permissions {
+        Some(permissions) => {
+            permissions.check_directory(path)?;
+            permissions.create(path)?
+        }
+        None => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?
+        }
+    };
+    let mut writer = BufWriter::with_capacity(WRITE_BUFFER, file);
+    write_json_records(&mut writer, records)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write records to `writer` as a JSON array with one record per line.
+pub fn write_json_records<T, I, W>(writer: &mut W, records: I) -> Result<()>
+where
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+    W: Write,
+{
+    writer.write_all(b"[")?;
+    for (i, record) in records.into_iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        writer.write_all(b"\n  ")?;
+        serde_json::to_writer(&mut *writer, &record)?;
+    }
+    writer.write_all(b"\n]")?;
+    Ok(())
+}
+
+/// Columns of CSV output, named like the JSON fields.
+pub const CSV_HEADER: &str = "address,evmAddress,pubkey,privateKey,derivationPath,index,pubkeyHex,pubkeyType";
+
+/// Writes wallets one at a time in an [`OutputFormat`], so a run can write as it generates.
+/// [`WalletWriter::finish`] closes the document and returns the inner writer.
+pub struct WalletWriter<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    /// Default type URL when `pubkey` is written as the SDK proto-JSON object
+    sdk_pubkey_type: Option<&'static str>,
+    /// Header of binary output, and the buffer records are encoded into
+    binary: Option<(Box<Header>, Vec<u8>)>,
+    records: usize,
+}
+
+impl<W: Write> WalletWriter<W> {
+    pub fn new(mut writer: W, format: OutputFormat, sdk_pubkey_type: Option<&'static str>) -> Result<Self> {
+        match format {
+            OutputFormat::Json => writer.write_all(b"[")?,
+            OutputFormat::Ndjson => {}
+            OutputFormat::Csv if sdk_pubkey_type.is_some() => {
+                return Err(anyhow!("SDK proto-JSON pubkeys need a JSON output format"));
+            }
+            OutputFormat::Csv => writeln!(writer, "{}", CSV_HEADER)?,
+            OutputFormat::Bin => return Err(anyhow!("Binary output needs the run's derivation settings")),
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow => return Err(anyhow!("Arrow output is written by ArrowIpcWriter")),
+        }
+        Ok(Self { writer, format, sdk_pubkey_type, binary: None, records: 0 })
+    }
+
+    /// Continue a JSON or NDJSON document of `records` records, reopened with [`append_output`].
+    pub fn appending(writer: W, format: OutputFormat, sdk_pubkey_type: Option<&'static str>, records: usize) -> Result<Self> {
+        if !matches!(format, OutputFormat::Json | OutputFormat::Ndjson) {
+            return Err(anyhow!("Only JSON and NDJSON output can be appended to"));
+        }
+        Ok(Self { writer, format, sdk_pubkey_type, binary: None, records })
+    }
+
+    /// Binary output of wallets derived with `config`, which the file header records.
+    pub fn binary(mut writer: W, config: &DerivationConfig) -> Result<Self> {
+        let header = Header::new(config);
+        header.write(&mut writer)?;
+        let buffer = Vec::with_capacity(header.record_len());
+        Ok(Self { writer, format: OutputFormat::Bin, sdk_pubkey_type: None, binary: Some((Box::new(header), buffer)), records: 0 })
+    }
+
+    pub fn write(&mut self, wallet: &Wallet) -> Result<()> {
+        match self.format {
+            OutputFormat::Json => {
+                if self.records > 0 {
+                    self.writer.write_all(b",")?;
+                }
+                self.writer.write_all(b"\n  ")?;
+                self.write_json(wallet)?;
+            }
+            OutputFormat::Ndjson => {
+                self.write_json(wallet)?;
+                self.writer.write_all(b"\n")?;
+            }
+            OutputFormat::Csv => write_csv_record(&mut self.writer, wallet)?,
+            OutputFormat::Bin => {
+                let (header, buffer) = self.binary.as_mut().expect("binary writers are created with a header");
+                buffer.clear();
+                header.encode(wallet, buffer)?;
+                self.writer.write_all(buffer)?;
+            }
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow => unreachable!("WalletWriter::new rejects Arrow output"),
+        }
+        self.records += 1;
+        Ok(())
+    }
+
+    fn write_json(&mut self, wallet: &Wallet) -> Result<()> {
+        match self.sdk_pubkey_type {
+            Some(type_url) => serde_json::to_writer(&mut self.writer, &wallet.to_sdk_json(type_url))?,
+            None => serde_json::to_writer(&mut self.writer, wallet)?,
+        }
+        Ok(())
+    }
+
+    /// Wallets written so far.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        if self.format == OutputFormat::Json {
+            self.writer.write_all(b"\n]")?;
+        }
+        Ok(self.writer)
+    }
+}
+
+/// Write one CSV line with the [`CSV_HEADER`] columns.
+pub fn write_csv_record<W: Write>(writer: &mut W, wallet: &Wallet) -> io::Result<()> {
+    writeln!(
+        writer,
+        "{},{},{},{},{},{},{},{}",
+        csv_field(&wallet.address),
+        csv_field(wallet.evm_address.as_deref().unwrap_or_default()),
+        csv_field(&wallet.pubkey),
+        csv_field(&wallet.private_key),
+        csv_field(&wallet.derivation_path),
+        wallet.index,
+        csv_field(wallet.pubkey_hex.as_deref().unwrap_or_default()),
+        csv_field(wallet.pubkey_type.as_deref().unwrap_or_default()),
+    )
+}
+
+/// Quote a field if it holds a separator, quote or line break.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
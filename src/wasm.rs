@@ -0,0 +1,71 @@
+//! wasm-bindgen exports for browser and Node.js.
+//!
+//! These wrap the same derivation code the CLI uses, so records produced here
+//! serialize to the same JSON as the CLI output. Generation is single-threaded
+//! and capped at [`MAX_WASM_BATCH`] wallets per call.
+
+use bip39::Mnemonic;
+use serde::Serialize;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+use crate::types::KeyType;
+use crate::generator::{generate_addresses, generate_wallets_batch, DerivationConfig};
+
+/// Upper bound on wallets generated per `generateWallets` call.
+pub const MAX_WASM_BATCH: usize = 10_000;
+
+#[derive(Serialize)]
+struct DerivedAddresses {
+    address: String,
+    #[serde(rename = "evmAddress", skip_serializing_if = "Option::is_none")]
+    evm_address: Option<String>,
+    pubkey: String,
+    #[serde(rename = "privateKey")]
+    private_key: String,
+}
+
+fn parse_key_type(key_type: &str) -> Result<KeyType, JsError> {
+    key_type.parse().map_err(|_| JsError::new(&format!("Unknown key type: {}", key_type)))
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Derive addresses for a hex-encoded 32-byte private key.
+#[wasm_bindgen(js_name = generateAddresses)]
+pub fn generate_addresses_js(private_key_hex: &str, prefix: &str, key_type: &str) -> Result<JsValue, JsError> {
+    let key_type = parse_key_type(key_type)?;
+    let bytes = hex::decode(private_key_hex.trim_start_matches("0x"))
+        .map_err(|e| JsError::new(&format!("Invalid private key hex: {}", e)))?;
+    let private_key: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| JsError::new("Private key must be 32 bytes"))?;
+
+    let (address, evm_address, pubkey, private_key) = generate_addresses(&private_key, prefix, &key_type)?;
+    to_js(&DerivedAddresses { address, evm_address, pubkey, private_key })
+}
+
+/// Generate a small batch of wallets from a mnemonic.
+#[wasm_bindgen(js_name = generateWallets)]
+pub fn generate_wallets_js(
+    mnemonic: &str,
+    start_index: usize,
+    count: usize,
+    prefix: &str,
+    key_type: &str,
+) -> Result<JsValue, JsError> {
+    if count > MAX_WASM_BATCH {
+        return Err(JsError::new(&format!("Too many wallets requested. Maximum per call is {}", MAX_WASM_BATCH)));
+    }
+    let key_type = parse_key_type(key_type)?;
+    let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| JsError::new(&format!("Invalid mnemonic: {}", e)))?;
+    let seed = mnemonic.to_seed("");
+
+    let wallets = generate_wallets_batch(&seed, start_index, count, &DerivationConfig::new(prefix, key_type), Arc::new(AtomicUsize::new(0)))?;
+    to_js(&wallets)
+}
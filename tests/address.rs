@@ -0,0 +1,99 @@
+use wallet_generator::address;
+
+fn hex32(value: &str) -> [u8; 32] {
+    hex::decode(value).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn instantiate2_addresses_match_cosmwasm() {
+    // cosmwasm-std `instantiate2_address_impl_works`
+    let checksum = hex32("13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5");
+    let creator = hex::decode("9999999999aaaaaaaaaabbbbbbbbbbcccccccccc").unwrap();
+    let msg = br#"{"some":123,"structure":{"nested":["ok",true]}}"#;
+
+    let cases: [(Option<&[u8]>, &str); 4] = [
+        (None, "5e865d3e45ad3e961f77fd77d46543417ced44d924dc3e079b5415ff6775f847"),
+        (Some(b""), "5e865d3e45ad3e961f77fd77d46543417ced44d924dc3e079b5415ff6775f847"),
+        (Some(b"{}"), "0995499608947a5281e2c7ebd71bdb26a1ad981946dad57f6c4d3ee35de77835"),
+        (Some(msg), "83326e554723b15bac664ceabc8a5887e27003abe9fbd992af8c7bcea4745167"),
+    ];
+    for (init_msg, expected) in cases {
+        assert_eq!(hex::encode(address::instantiate2(&checksum, &creator, b"a", init_msg).unwrap()), expected);
+    }
+
+    assert!(address::instantiate2(&checksum, &creator, b"", None).is_err());
+    assert!(address::instantiate2(&checksum, &creator, &[0; 65], None).is_err());
+    assert!(address::instantiate2(&checksum[..31], &creator, b"a", None).is_err());
+}
+
+#[test]
+fn create2_addresses_match_eip1014() {
+    use sha3::{Digest, Keccak256};
+    // The examples of EIP-1014: sender, salt, init code, address
+    let cases = [
+        ("0000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000000", "00", "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38"),
+        ("deadbeef00000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000000", "00", "b928f69bb1d91cd65274e3c79d8986362984fda3"),
+        ("deadbeef00000000000000000000000000000000", "000000000000000000000000feed000000000000000000000000000000000000", "00", "d04116cdd17bebe565eb2422f2497e06cc1c9833"),
+        ("0000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000000", "deadbeef", "70f2b2914a2a4b783faefb75f459a580616fcb5e"),
+        ("00000000000000000000000000000000deadbeef", "00000000000000000000000000000000000000000000000000000000cafebabe", "deadbeef", "60f3f640a8508fc6a86d45df051962668e1e8ac7"),
+        (
+            "00000000000000000000000000000000deadbeef",
+            "00000000000000000000000000000000000000000000000000000000cafebabe",
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+            "1d8bfdc5d46dc4f61d6b6115972536ebe6a8854c",
+        ),
+        ("0000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000000", "", "e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0"),
+    ];
+    for (sender, salt, init_code, expected) in cases {
+        let sender: [u8; 20] = hex::decode(sender).unwrap().try_into().unwrap();
+        let init_code_hash: [u8; 32] = Keccak256::digest(hex::decode(init_code).unwrap()).into();
+        assert_eq!(hex::encode(address::create2(&sender, &hex32(salt), &init_code_hash)), expected);
+    }
+}
+
+#[test]
+fn module_addresses_match_the_cosmos_hub() {
+    // Module accounts of the Cosmos Hub, `authtypes.NewModuleAddress(name)`
+    let cases = [
+        ("fee_collector", "cosmos17xpfvakm2amg962yls6f84z3kell8c5lserqta"),
+        ("gov", "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"),
+        ("distribution", "cosmos1jv65s3grqf6v6jl3dp4t6c9t9rk99cd88lyufl"),
+        ("bonded_tokens_pool", "cosmos1fl48vsnmsdzcv85q5d2q4z5ajdha8yu34mf0eh"),
+        ("not_bonded_tokens_pool", "cosmos1tygms3xhhs3yv487phx3dw4a95jn7t7lpm470r"),
+    ];
+    for (name, expected) in cases {
+        assert_eq!(address::encode("cosmos", &address::module(name, &[])).unwrap(), expected);
+    }
+}
+
+#[test]
+fn legacy_multisig_addresses_hash_their_amino_encoding() {
+    // 2-of-3 over indices 0 to 2 of the test mnemonic. The amino bytes are spelled out from the
+    // `tendermint/PubKeyMultisigThreshold` and `tendermint/PubKeySecp256k1` definitions and the
+    // address computed apart from this crate
+    let pubkeys: Vec<[u8; 33]> = [
+        "024f4e2ad99c34d60b9ba6283c9431a8418af8673212961f97a77b6377fcd05b62",
+        "03a9a0776157f1dee1fe2d65628747059a8796de9a379f3015c4dcf483f64840a6",
+        "02e10ecea7c647934ed84a4c6f5ab326c669e95266260096e4d1a39ae133396445",
+    ]
+    .iter()
+    .map(|key| hex::decode(key).unwrap().try_into().unwrap())
+    .collect();
+    let amino = concat!(
+        "22c1f7e2", "0802",
+        "1226eb5ae98721", "024f4e2ad99c34d60b9ba6283c9431a8418af8673212961f97a77b6377fcd05b62",
+        "1226eb5ae98721", "03a9a0776157f1dee1fe2d65628747059a8796de9a379f3015c4dcf483f64840a6",
+        "1226eb5ae98721", "02e10ecea7c647934ed84a4c6f5ab326c669e95266260096e4d1a39ae133396445",
+    );
+    assert_eq!(hex::encode(address::legacy_multisig_bytes(2, &pubkeys).unwrap()), amino);
+    let multisig = address::legacy_multisig(2, &pubkeys).unwrap();
+    assert_eq!(hex::encode(multisig), "bbc41b88df544a9bc5cb1d19a06bf26423a61156");
+    assert_eq!(address::encode("cosmos", &multisig).unwrap(), "cosmos1h0zphzxl239fh3wtr5v6q6ljvs36vy2kxwlr2c");
+
+    // Key order and threshold are part of the address
+    let reordered = [pubkeys[1], pubkeys[0], pubkeys[2]];
+    assert_ne!(address::legacy_multisig(2, &reordered).unwrap(), multisig);
+    assert_ne!(address::legacy_multisig(3, &pubkeys).unwrap(), multisig);
+    assert!(address::legacy_multisig(4, &pubkeys).is_err());
+    assert!(address::legacy_multisig(0, &pubkeys).is_err());
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{generate, seed, try_generate_from};
+use secp256k1::PublicKey;
+use wallet_generator::address_encoder::{self, Addresses, AddressEncoder};
+use wallet_generator::generator::WalletEncoder;
+use wallet_generator::rederive::rederive;
+use wallet_generator::types::AddressEncoding;
+use wallet_generator::{derive_wallet, DerivationConfig, GeneratorError, KeyType};
+
+/// A chain addressing accounts by the Bech32m of the compressed key itself, with the hex key as
+/// its second address.
+struct RawKeyAddresses;
+
+impl AddressEncoder for RawKeyAddresses {
+    fn encode(&self, public_key: &PublicKey, fields: &mut WalletEncoder) -> Result<Addresses, GeneratorError> {
+        let hrp = fields.hrp()?;
+        let key = public_key.serialize();
+        Ok(Addresses { address: fields.address(hrp, &key)?, evm_address: None, secp256k1_address: Some(hex::encode(key)) })
+    }
+}
+
+#[test]
+fn built_in_encoders_address_as_before() {
+    let seed = seed();
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 2);
+    assert_eq!(wallets[0].address, "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+    let expected = derive_wallet(&seed, "m/44'/118'/0'/0/1", "cosmos", &KeyType::Secp256k1).unwrap();
+    assert_eq!(serde_json::to_value(&wallets[1]).unwrap(), serde_json::to_value(expected).unwrap());
+
+    let config = DerivationConfig { dual_address: true, ..DerivationConfig::new("evmos", KeyType::Ethsecp256k1) };
+    let wallet = generate(&config, 1).remove(0);
+    let expected = derive_wallet(&seed, "m/44'/60'/0'/0/0", "evmos", &KeyType::Ethsecp256k1).unwrap();
+    assert_eq!((&wallet.address, &wallet.evm_address), (&expected.address, &expected.evm_address));
+    let secp256k1 = derive_wallet(&seed, "m/44'/60'/0'/0/0", "evmos", &KeyType::Secp256k1).unwrap();
+    assert_eq!(wallet.secp256k1_address.as_deref(), Some(secp256k1.address.as_str()));
+}
+
+#[test]
+fn registered_encoders_address_generated_and_rederived_wallets() {
+    assert!(address_encoder::register("raw-key", Arc::new(RawKeyAddresses)).is_none());
+    let config = DerivationConfig {
+        encoding: AddressEncoding::Bech32m,
+        address_encoder: Some("raw-key".to_string()),
+        ..DerivationConfig::new("raw", KeyType::Secp256k1)
+    };
+
+    let wallets = generate(&config, 3);
+    for wallet in &wallets {
+        let (hrp, key) = bech32::decode(&wallet.address).unwrap();
+        assert_eq!((hrp.as_str(), hex::encode(key)), ("raw", wallet.pubkey_hex.clone().unwrap()));
+        assert_eq!(wallet.secp256k1_address, wallet.pubkey_hex);
+        assert_eq!(wallet.evm_address, None);
+    }
+    // Keys and paths are those of the key type
+    let standard = generate(&DerivationConfig::new("raw", KeyType::Secp256k1), 3);
+    assert_eq!(wallets[2].private_key, standard[2].private_key);
+    assert_eq!(wallets[2].derivation_path, standard[2].derivation_path);
+
+    let rederived = rederive(&standard[1], &config).unwrap();
+    assert_eq!(serde_json::to_value(rederived).unwrap(), serde_json::to_value(&wallets[1]).unwrap());
+}
+
+#[test]
+fn unregistered_encoders_are_an_error() {
+    let config = DerivationConfig { address_encoder: Some("missing".to_string()), ..DerivationConfig::new("cosmos", KeyType::Secp256k1) };
+    let error = try_generate_from(&config, 0, 1).unwrap_err();
+    assert_eq!(error.to_string(), "No address encoder is registered as 'missing'");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "age")]
+
+mod common;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use age::armor::{ArmoredWriter, Format};
+use age::secrecy::{ExposeSecret, SecretString};
+use age::{Encryptor, Recipient};
+use common::{seed, MNEMONIC};
+use wallet_generator::age_file::{self, RootSecret};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("wallet-generator-age-{}-{}", std::process::id(), name))
+}
+
+fn encrypt(recipient: &dyn Recipient, plaintext: &[u8], format: Format) -> Vec<u8> {
+    let encryptor = Encryptor::with_recipients(std::iter::once(recipient)).unwrap();
+    let mut output = Vec::new();
+    let mut writer = encryptor.wrap_output(ArmoredWriter::wrap_output(&mut output, format).unwrap()).unwrap();
+    writer.write_all(plaintext).unwrap();
+    writer.finish().unwrap().finish().unwrap();
+    output
+}
+
+fn no_passphrase() -> anyhow::Result<String> {
+    panic!("a file encrypted to recipients needs no passphrase")
+}
+
+#[test]
+fn decrypts_with_identity_file() {
+    let identity = age::x25519::Identity::generate();
+    let (file, identities) = (temp_path("identity.age"), temp_path("identity.txt"));
+    std::fs::write(&file, encrypt(&identity.to_public(), MNEMONIC.as_bytes(), Format::Binary)).unwrap();
+    std::fs::write(&identities, format!("# test key\n{}\n", identity.to_string().expose_secret())).unwrap();
+
+    let plaintext = age_file::decrypt(&file, Some(&identities), no_passphrase).unwrap();
+    assert_eq!(&plaintext[..], MNEMONIC.as_bytes());
+
+    // Without the identity, or with another one, the file stays sealed
+    assert!(age_file::decrypt(&file, None, no_passphrase).is_err());
+    std::fs::write(&identities, format!("{}\n", age::x25519::Identity::generate().to_string().expose_secret())).unwrap();
+    assert!(age_file::decrypt(&file, Some(&identities), no_passphrase).is_err());
+
+    std::fs::remove_file(&file).unwrap();
+    std::fs::remove_file(&identities).unwrap();
+}
+
+#[test]
+fn decrypts_armored_passphrase_file() {
+    let mut recipient = age::scrypt::Recipient::new(SecretString::from("correct horse".to_string()));
+    recipient.set_work_factor(2);
+    let file = temp_path("passphrase.age");
+    std::fs::write(&file, encrypt(&recipient, format!("  {}\n", MNEMONIC).as_bytes(), Format::AsciiArmor)).unwrap();
+
+    let plaintext = age_file::decrypt(&file, None, || Ok("correct horse".to_string())).unwrap();
+    match RootSecret::parse(&plaintext).unwrap() {
+        RootSecret::Mnemonic(phrase) => assert_eq!(phrase.as_str(), MNEMONIC),
+        RootSecret::Seed(_) => panic!("a mnemonic was encrypted"),
+    }
+    assert!(age_file::decrypt(&file, None, || Ok("wrong".to_string())).is_err());
+    std::fs::remove_file(&file).unwrap();
+}
+
+#[test]
+fn hex_seed_or_mnemonic() {
+    let seed = seed();
+    match RootSecret::parse(format!("{}\n", hex::encode(seed)).as_bytes()).unwrap() {
+        RootSecret::Seed(parsed) => assert_eq!(*parsed, seed),
+        RootSecret::Mnemonic(_) => panic!("a hex seed was given"),
+    }
+    match RootSecret::parse(b"abandon  abandon\nabout").unwrap() {
+        RootSecret::Mnemonic(phrase) => assert_eq!(phrase.as_str(), "abandon abandon about"),
+        RootSecret::Seed(_) => panic!("a phrase was given"),
+    }
+    assert!(RootSecret::parse(&[0xff, 0xfe]).is_err());
+    assert!(age_file::decrypt(temp_path("missing.age"), None, no_passphrase).is_err());
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::AtomicUsize;
+use std::time::Duration;
+
+use common::generate;
+use wallet_generator::lcd::{AccountInfo, LcdClient};
+use wallet_generator::scan::annotate_accounts;
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+/// Serve account lookups: the first address as a vesting account, the second as missing, and
+/// anything else as a server error.
+fn serve_accounts(existing: String, missing: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            BufReader::new(&stream).read_line(&mut request_line).unwrap();
+            let (status, body) = if request_line.contains(&existing) {
+                let account = format!(
+                    r#"{{"account":{{"@type":"/cosmos.vesting.v1beta1.DelayedVestingAccount","base_vesting_account":{{"base_account":{{"address":"{}","account_number":"42","sequence":"7"}}}}}}}}"#,
+                    existing
+                );
+                ("200 OK", account)
+            } else if request_line.contains(&missing) {
+                ("404 Not Found", r#"{"code":5,"message":"account not found"}"#.to_string())
+            } else {
+                ("400 Bad Request", r#"{"code":3,"message":"decoding bech32 failed"}"#.to_string())
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn records_carry_account_number_and_sequence() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 3);
+    let node = serve_accounts(wallets[0].address.clone(), wallets[1].address.clone());
+    let client = LcdClient::new(&node, Duration::from_secs(5), 0);
+
+    let progress = AtomicUsize::new(0);
+    let annotated = annotate_accounts(&client, wallets.clone(), 2, &progress).unwrap();
+    assert_eq!(progress.into_inner(), 3);
+
+    // Input order is kept
+    let addresses: Vec<_> = annotated.iter().map(|w| w.wallet.address.clone()).collect();
+    assert_eq!(addresses, wallets.iter().map(|w| w.address.clone()).collect::<Vec<_>>());
+
+    assert_eq!(annotated[0].account(), Some(AccountInfo { account_number: 42, sequence: 7 }));
+    let record = serde_json::to_value(&annotated[0]).unwrap();
+    assert_eq!((record["accountExists"].as_bool(), record["accountNumber"].as_u64(), record["sequence"].as_u64()), (Some(true), Some(42), Some(7)));
+
+    assert_eq!((annotated[1].account_exists, annotated[1].account()), (Some(false), None));
+    let record = serde_json::to_value(&annotated[1]).unwrap();
+    assert!(record.get("accountNumber").is_none() && record.get("error").is_none());
+
+    assert_eq!(annotated[2].account_exists, None);
+    assert!(annotated[2].error.as_deref().is_some_and(|error| error.contains("400")));
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+use wallet_generator::report::DigestWriter;
+use wallet_generator::secure_output::OutputPermissions;
+use wallet_generator::types::{KeyType, OutputFormat};
+use wallet_generator::wallet_file::{append_output, ExistingOutput, WalletWriter};
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn generate(count: usize) -> Vec<Wallet> {
+    common::generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), count)
+}
+
+fn encode(format: OutputFormat, wallets: &[Wallet]) -> Vec<u8> {
+    let mut writer = WalletWriter::new(Vec::new(), format, None).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    writer.finish().unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("wallet-generator-append-{}-{}", std::process::id(), name))
+}
+
+/// Append `wallets` to the file at `path`, returning the size and SHA-256 the run would report.
+fn append(path: &PathBuf, wallets: &[Wallet]) -> (u64, String) {
+    let existing = ExistingOutput::inspect(path).unwrap().unwrap();
+    let path = path.to_str().unwrap();
+    // The shared temp directory is world-writable
+    let permissions = OutputPermissions { insecure: true, ..OutputPermissions::default() };
+    let sink = append_output(path, &existing, 4096, &permissions).unwrap();
+    let output = DigestWriter::resume(sink, fs::File::open(path).unwrap()).unwrap();
+    let mut writer = WalletWriter::appending(output, existing.format, None, existing.records).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    let output = writer.finish().unwrap();
+    let digest = (output.bytes(), output.sha256());
+    output.into_inner().finish().unwrap();
+    digest
+}
+
+fn assert_appends_like_one_run(format: OutputFormat, name: &str) {
+    let path = temp_path(name);
+    let wallets = generate(9);
+    fs::write(&path, encode(format, &wallets[..4])).unwrap();
+
+    let existing = ExistingOutput::inspect(&path).unwrap().unwrap();
+    assert_eq!((existing.format, existing.records, existing.next_index()), (format, 4, 4));
+    assert_eq!(existing.highest, Some((3, wallets[3].address.clone())));
+
+    let (bytes, sha256) = append(&path, &wallets[4..]);
+    let written = fs::read(&path).unwrap();
+    assert_eq!(written, encode(format, &wallets));
+    assert_eq!(bytes, written.len() as u64);
+    assert_eq!(sha256, hex::encode(Sha256::digest(&written)));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn json_array_appends_like_one_run() {
+    assert_appends_like_one_run(OutputFormat::Json, "array.json");
+}
+
+#[test]
+fn ndjson_appends_like_one_run() {
+    assert_appends_like_one_run(OutputFormat::Ndjson, "lines.ndjson");
+}
+
+#[test]
+fn empty_array_and_missing_line_break() {
+    let path = temp_path("empty.json");
+    fs::write(&path, encode(OutputFormat::Json, &[])).unwrap();
+    assert_eq!(ExistingOutput::inspect(&path).unwrap().unwrap().next_index(), 0);
+    let wallets = generate(2);
+    append(&path, &wallets);
+    assert_eq!(fs::read(&path).unwrap(), encode(OutputFormat::Json, &wallets));
+    fs::remove_file(&path).unwrap();
+
+    let path = temp_path("unterminated.ndjson");
+    let wallets = generate(3);
+    let mut existing = encode(OutputFormat::Ndjson, &wallets[..1]);
+    existing.pop();
+    fs::write(&path, existing).unwrap();
+    append(&path, &wallets[1..]);
+    assert_eq!(fs::read(&path).unwrap(), encode(OutputFormat::Ndjson, &wallets));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn highest_index_wins_over_order() {
+    let path = temp_path("unordered.ndjson");
+    let mut wallets = generate(6);
+    wallets.swap(2, 5);
+    fs::write(&path, encode(OutputFormat::Ndjson, &wallets)).unwrap();
+    let existing = ExistingOutput::inspect(&path).unwrap().unwrap();
+    assert_eq!(existing.next_index(), 6);
+    assert_eq!(existing.highest.unwrap().1, wallets[2].address);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_files_it_cannot_continue() {
+    assert_eq!(ExistingOutput::inspect(temp_path("missing.json")).unwrap(), None);
+
+    let wallets = generate(2);
+    let path = temp_path("wallets.csv");
+    fs::write(&path, encode(OutputFormat::Csv, &wallets)).unwrap();
+    assert!(ExistingOutput::inspect(&path).is_err());
+
+    // A JSON array cut off mid-run has no closing bracket
+    let path = temp_path("truncated.json");
+    let mut truncated = encode(OutputFormat::Json, &wallets);
+    truncated.truncate(truncated.len() - 2);
+    fs::write(&path, truncated).unwrap();
+    assert!(ExistingOutput::inspect(&path).unwrap_err().to_string().contains("does not end with the ]"));
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(temp_path("wallets.csv")).unwrap();
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "arrow")]
+
+mod common;
+
+use std::io::Cursor;
+
+use arrow_array::{Array, StringArray, UInt64Array};
+use arrow_ipc::reader::FileReader;
+use common::{generate, seed};
+use wallet_generator::arrow::{generate_record_batches, record_batch, schema, ArrowIpcWriter, BATCH_ROWS};
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+fn strings<'a>(batch: &'a arrow_array::RecordBatch, column: &str) -> &'a StringArray {
+    batch.column_by_name(column).unwrap().as_any().downcast_ref::<StringArray>().unwrap()
+}
+
+#[test]
+fn batch_columns_match_the_records() {
+    let config = DerivationConfig::new("evmos", KeyType::Ethsecp256k1);
+    let wallets = generate(&config, 5);
+    let batch = record_batch(&wallets).unwrap();
+    assert_eq!(batch.schema(), schema());
+    assert_eq!(batch.num_rows(), 5);
+
+    for (row, wallet) in wallets.iter().enumerate() {
+        assert_eq!(strings(&batch, "address").value(row), wallet.address);
+        assert_eq!(strings(&batch, "evmAddress").value(row), wallet.evm_address.as_deref().unwrap());
+        assert_eq!(strings(&batch, "privateKey").value(row), wallet.private_key);
+    }
+    // Fields JSON omits are null
+    assert_eq!(strings(&batch, "wif").null_count(), 5);
+}
+
+#[test]
+fn generated_batches_are_in_index_order() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let batches = generate_record_batches(&seed(), 10, 25, &config, 10).unwrap();
+    assert_eq!(batches.iter().map(|batch| batch.num_rows()).collect::<Vec<_>>(), [10, 10, 5]);
+
+    let indices: Vec<u64> = batches
+        .iter()
+        .flat_map(|batch| {
+            let column = batch.column_by_name("index").unwrap().as_any().downcast_ref::<UInt64Array>().unwrap();
+            column.values().to_vec()
+        })
+        .collect();
+    assert_eq!(indices, (10..35).collect::<Vec<u64>>());
+}
+
+#[test]
+fn ipc_file_reads_back() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let wallets = generate(&config, BATCH_ROWS.min(3000) + 7);
+    let mut writer = ArrowIpcWriter::new(Vec::new()).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    assert_eq!(writer.records(), wallets.len());
+    let bytes = writer.finish().unwrap();
+
+    let reader = FileReader::try_new(Cursor::new(bytes), None).unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    let addresses: Vec<&str> = batches.iter().flat_map(|batch| strings(batch, "address").iter().flatten()).collect();
+    assert_eq!(addresses, wallets.iter().map(|wallet| wallet.address.as_str()).collect::<Vec<_>>());
+}
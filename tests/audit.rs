@@ -0,0 +1,88 @@
+mod common;
+
+use std::fs;
+
+use common::seed;
+use wallet_generator::derivation::master_fingerprint;
+use wallet_generator::report::{AuditArtifact, AuditEntry, RunParameters};
+use wallet_generator::types::{AddressEncoding, ChainFamily, DerivationScheme, KeyType, OutputFormat, PrivateKeyFormat, PubkeyFormat};
+
+fn log_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("wallet-generator-audit-{}-{}", std::process::id(), name))
+}
+
+fn parameters(start_index: u64) -> RunParameters {
+    RunParameters {
+        prefix: "cosmos".to_string(),
+        key_type: KeyType::Secp256k1,
+        coin_type: 118,
+        derivation_path: "m/44'/118'/0'/0/{index}".to_string(),
+        encoding: AddressEncoding::Bech32,
+        scheme: DerivationScheme::Standard,
+        family: ChainFamily::Cosmos,
+        pubkey_format: PubkeyFormat::Base64,
+        privkey_format: PrivateKeyFormat::Hex,
+        include_uncompressed: false,
+        dual_address: false,
+        encrypt_keys: false,
+        output_format: Some(OutputFormat::Json),
+        chain: None,
+        threads: 4,
+        chunk_size: None,
+        rate_limit: None,
+        nice: None,
+        start_index,
+        default_mnemonic: false,
+        check_duplicates: false,
+        max_memory_mb: None,
+        filter_address: None,
+        filter_evm: None,
+        match_file: None,
+        rotate_every: None,
+        rotate_size_bytes: None,
+        duration_secs: None,
+    }
+}
+
+#[test]
+fn entries_name_the_mnemonic_by_fingerprint_only() {
+    let seed = seed();
+    let mut entry = AuditEntry::new(master_fingerprint(&seed).unwrap(), parameters(100));
+    entry.last_index = Some(149);
+    entry.written = 50;
+    entry.artifacts.push(AuditArtifact { path: "wallets.json".to_string(), records: Some(150), bytes: Some(4096), sha256: Some("ab".repeat(32)) });
+
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["mnemonicFingerprint"], "73c5da0a");
+    assert_eq!((json["firstIndex"].as_u64(), json["lastIndex"].as_u64()), (Some(100), Some(149)));
+    assert_eq!(json["artifacts"][0]["sha256"], "ab".repeat(32));
+    assert!(json.get("error").is_none());
+    let line = json.to_string();
+    assert!(!line.contains("abandon"));
+    assert!(!line.contains(&hex::encode(seed)));
+}
+
+#[test]
+fn runs_append_one_line_each() {
+    let path = log_path("append.jsonl");
+    let _ = fs::remove_file(&path);
+    let seed = seed();
+
+    let first = AuditEntry { last_index: Some(9), written: 10, ..AuditEntry::new(master_fingerprint(&seed).unwrap(), parameters(0)) };
+    first.append(&path).unwrap();
+    let failed = AuditEntry { written: 3, error: Some("Wallet generation failed: disk full".to_string()), ..AuditEntry::new([1, 2, 3, 4], parameters(10)) };
+    failed.append(&path).unwrap();
+
+    let log = fs::read_to_string(&path).unwrap();
+    assert_eq!(log.lines().count(), 2);
+    let entries = AuditEntry::read_log(&path).unwrap();
+    assert_eq!(entries.iter().map(|entry| entry.mnemonic_fingerprint.as_str()).collect::<Vec<_>>(), ["73c5da0a", "01020304"]);
+    assert_eq!(entries[0].parameters, parameters(0));
+    assert_eq!((entries[1].first_index, entries[1].last_index), (10, None));
+    assert_eq!(entries[1].error.as_deref(), Some("Wallet generation failed: disk full"));
+
+    // Earlier lines are left as they were
+    AuditEntry::new([0; 4], parameters(20)).append(&path).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().starts_with(&log));
+    fs::remove_file(&path).unwrap();
+}
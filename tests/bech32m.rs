@@ -0,0 +1,68 @@
+mod common;
+
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32, Bech32m, Hrp};
+use wallet_generator::types::AddressEncoding;
+use wallet_generator::generator::encode_address;
+use wallet_generator::{address, DerivationConfig, KeyType};
+
+fn generate(key_type: KeyType, encoding: AddressEncoding) -> Vec<wallet_generator::Wallet> {
+    let mut config = DerivationConfig::new("cosmos", key_type);
+    config.encoding = encoding;
+    common::generate(&config, 5)
+}
+
+#[test]
+fn bech32m_round_trips_to_the_same_bytes() {
+    let hrp = Hrp::parse("osmo").unwrap();
+    for data in [vec![0u8; 20], (0u8..20).collect(), (0u8..32).collect()] {
+        let encoded = encode_address(hrp, &data, AddressEncoding::Bech32m).unwrap();
+        let checked = CheckedHrpstring::new::<Bech32m>(&encoded).unwrap();
+        assert_eq!(checked.hrp(), hrp);
+        assert_eq!(checked.byte_iter().collect::<Vec<_>>(), data);
+        assert_eq!(address::decode(&encoded).unwrap(), data);
+    }
+}
+
+#[test]
+fn checksums_are_not_interchangeable() {
+    let hrp = Hrp::parse("cosmos").unwrap();
+    let data = [7u8; 20];
+    let bech32 = encode_address(hrp, &data, AddressEncoding::Bech32).unwrap();
+    let bech32m = encode_address(hrp, &data, AddressEncoding::Bech32m).unwrap();
+
+    assert_ne!(bech32, bech32m);
+    assert!(CheckedHrpstring::new::<Bech32>(&bech32).is_ok());
+    assert!(CheckedHrpstring::new::<Bech32m>(&bech32).is_err());
+    assert!(CheckedHrpstring::new::<Bech32m>(&bech32m).is_ok());
+    assert!(CheckedHrpstring::new::<Bech32>(&bech32m).is_err());
+}
+
+#[test]
+fn generated_addresses_differ_only_in_checksum() {
+    for key_type in [KeyType::Secp256k1, KeyType::Ethsecp256k1] {
+        let classic = generate(key_type.clone(), AddressEncoding::Bech32);
+        let modern = generate(key_type, AddressEncoding::Bech32m);
+        for (a, b) in classic.iter().zip(&modern) {
+            assert_eq!(a.pubkey, b.pubkey);
+            assert_eq!(a.evm_address, b.evm_address);
+            assert_ne!(a.address, b.address);
+            // Same payload, only the six checksum characters change
+            assert_eq!(a.address[..a.address.len() - 6], b.address[..b.address.len() - 6]);
+            assert_eq!(address::decode(&a.address).unwrap(), address::decode(&b.address).unwrap());
+            assert!(CheckedHrpstring::new::<Bech32m>(&b.address).is_ok());
+        }
+    }
+}
+
+#[test]
+fn bip350_reference_vector() {
+    // Valid Bech32m string from BIP-350
+    let checked = CheckedHrpstring::new::<Bech32m>("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx").unwrap();
+    let data: Vec<u8> = checked.byte_iter().collect();
+    let hrp = Hrp::parse("abcdef").unwrap();
+    assert_eq!(
+        encode_address(hrp, &data, AddressEncoding::Bech32m).unwrap(),
+        "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx"
+    );
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::generate;
+use wallet_generator::binary::{self, BinaryReader, Header};
+use wallet_generator::bitcoin::Network;
+use wallet_generator::types::{BitcoinAddress, KeyType, PrivateKeyFormat, PubkeyFormat};
+use wallet_generator::wallet_file::{read_wallets, WalletWriter};
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn encode(config: &DerivationConfig, wallets: &[Wallet]) -> Vec<u8> {
+    let mut writer = WalletWriter::binary(Vec::new(), config).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    writer.finish().unwrap()
+}
+
+/// Write `wallets` as a binary file and read them back.
+fn round_trip(config: &DerivationConfig, wallets: &[Wallet]) -> Vec<Wallet> {
+    let path = std::env::temp_dir().join(format!("wallet-generator-binary-{}-{}.bin", config.prefix, std::process::id()));
+    std::fs::write(&path, encode(config, wallets)).unwrap();
+    assert!(binary::is_binary(&path).unwrap());
+    let read = BinaryReader::open(&path).unwrap().collect::<anyhow::Result<Vec<_>>>().unwrap();
+    assert_eq!(read_wallets(&path).unwrap().len(), read.len());
+    std::fs::remove_file(&path).unwrap();
+    read
+}
+
+fn assert_same(config: DerivationConfig) {
+    let wallets = generate(&config, 12);
+    let read = round_trip(&config, &wallets);
+    assert_eq!(serde_json::to_value(&read).unwrap(), serde_json::to_value(&wallets).unwrap());
+}
+
+#[test]
+fn cosmos_records_round_trip() {
+    assert_same(DerivationConfig::new("cosmos", KeyType::Secp256k1));
+    assert_same(DerivationConfig {
+        private_key_format: PrivateKeyFormat::Wif,
+        pubkey_format: PubkeyFormat::Hex,
+        include_uncompressed: true,
+        ..DerivationConfig::new("evmos", KeyType::Ethsecp256k1)
+    });
+}
+
+#[test]
+fn bitcoin_and_solana_records_round_trip() {
+    assert_same(DerivationConfig::bitcoin(Network::Mainnet, BitcoinAddress::P2pkh));
+    assert_same(DerivationConfig::bitcoin(Network::Testnet, BitcoinAddress::P2wpkh));
+    assert_same(DerivationConfig { pubkey_format: PubkeyFormat::Hex, ..DerivationConfig::solana() });
+}
+
+#[test]
+fn records_are_fixed_size() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let header = Header::new(&config);
+    assert_eq!(header.record_len(), 93);
+    let empty = encode(&config, &[]).len();
+    assert_eq!(encode(&config, &generate(&config, 10)).len(), empty + 10 * 93);
+    assert_eq!(Header::new(&DerivationConfig::solana()).record_len(), 72);
+}
+
+#[test]
+fn truncated_files_are_rejected() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let mut bytes = encode(&config, &generate(&config, 2));
+    bytes.pop();
+    let path = std::env::temp_dir().join(format!("wallet-generator-binary-truncated-{}.bin", std::process::id()));
+    std::fs::write(&path, bytes).unwrap();
+    let read = BinaryReader::open(&path).unwrap().collect::<anyhow::Result<Vec<_>>>();
+    std::fs::remove_file(&path).unwrap();
+    assert!(read.is_err());
+}
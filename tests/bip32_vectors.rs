@@ -0,0 +1,73 @@
+mod common;
+
+use common::{generate, seed};
+use wallet_generator::types::DerivationScheme;
+use wallet_generator::derivation::{derive_public_key, derive_xprv, extended_keys, parse_xpub, KeyDeriver};
+use wallet_generator::{DerivationConfig, KeyType};
+
+// BIP-32 test vector 1
+#[test]
+fn serializes_bip32_test_vector_1() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let vectors = [
+        (
+            "m",
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        ),
+        (
+            "m/0'",
+            "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+            "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+        ),
+        (
+            "m/0'/1/2'/2/1000000000",
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+            "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+        ),
+    ];
+    for (path, xpub, xprv) in vectors {
+        assert_eq!(extended_keys(&seed, path).unwrap(), (xpub.to_string(), xprv.to_string()), "{}", path);
+    }
+}
+
+// Outputs of the tiny-hderive implementation this crate used before
+#[test]
+fn matches_previous_derivation() {
+    let vectors = [
+        (KeyType::Secp256k1, DerivationScheme::Standard, 0, "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4", "c4a48e2fce1481cd3294b4490f6678090ea98d3d0e5cd984558ab0968741b104"),
+        (KeyType::Secp256k1, DerivationScheme::Standard, 2, "cosmos1kng7tv83qesgvv2ze7hxlw4urfrjk8vqqnpqdt", "42cb671a145903fcb5be9dd2a2446eec676bd000c63eb0a7efd181c9fa5a28ac"),
+        (KeyType::Ethsecp256k1, DerivationScheme::Standard, 0, "cosmos1npvwllfr9dqr8erajqqr6s0vxnk2ak55fsjtl6", "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"),
+        (KeyType::Ethsecp256k1, DerivationScheme::Standard, 1, "cosmos1d7ky6xxfzg6rh7r05uzfxexafepy4wwqtu4r9l", "9a983cb3d832fbde5ab49d692b7a8bf5b5d232479c99333d0fc8e1d21f1b55b6"),
+        (KeyType::Secp256k1, DerivationScheme::LedgerLive, 1, "cosmos1tehv5km5e9y706rc2gzk9yyun9dljjjnvyt3u0", "3992639e9c460fa71cde7fba107fb9d344ee312293a307fbf9d086e1d535742c"),
+    ];
+    for (key_type, scheme, index, address, private_key) in vectors {
+        let mut config = DerivationConfig::new("cosmos", key_type);
+        config.scheme = scheme;
+        let wallets = generate(&config, 3);
+        assert_eq!(wallets[index].address, address);
+        assert_eq!(wallets[index].private_key, private_key);
+    }
+}
+
+#[test]
+fn cached_derivation_matches_fresh_derivation() {
+    let seed = seed();
+    let mut deriver = KeyDeriver::new(&seed);
+    let paths = ["m/44'/118'/0'/0/0", "m/44'/118'/0'/0/7", "m/44'/118'/3'/0/0", "m/44'/60'/0'/0/1", "m/44'/118'/0'/1/7", "m/44'/118'/0'/0/7'"];
+    for path in paths {
+        assert_eq!(deriver.private_key(path).unwrap(), derive_xprv(&seed, path).unwrap().to_bytes(), "{}", path);
+    }
+}
+
+#[test]
+fn xpub_derives_the_same_public_keys() {
+    let seed = seed();
+    let (xpub, _) = extended_keys(&seed, "m/44'/118'/0'").unwrap();
+    let xpub = parse_xpub(&xpub).unwrap();
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 5);
+    for wallet in wallets {
+        let public_key = derive_public_key(&xpub, &format!("0/{}", wallet.index)).unwrap();
+        assert_eq!(Some(hex::encode(public_key)), wallet.pubkey_hex);
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use wallet_generator::bitcoin::{self, Network};
+use wallet_generator::types::BitcoinAddress;
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn generate(network: Network, address: BitcoinAddress, count: usize) -> Vec<Wallet> {
+    common::generate(&DerivationConfig::bitcoin(network, address), count)
+}
+
+// BIP-84 test vectors
+#[test]
+fn p2wpkh_matches_bip84() {
+    let wallets = generate(Network::Mainnet, BitcoinAddress::P2wpkh, 2);
+    assert_eq!(wallets[0].derivation_path, "m/84'/0'/0'/0/0");
+    assert_eq!(wallets[0].address, "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+    assert_eq!(wallets[0].pubkey_hex.as_deref(), Some("0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c"));
+    assert_eq!(wallets[0].wif.as_deref(), Some("KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d"));
+    assert_eq!(wallets[1].address, "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g");
+    assert_eq!(wallets[1].wif.as_deref(), Some("Kxpf5b8p3qX56DKEe5NqWbNUP9MnqoRFzZwHRtsFqhzuvUJsYZCy"));
+    assert!(wallets.iter().all(|wallet| wallet.pubkey_type.is_none() && wallet.evm_address.is_none()));
+}
+
+#[test]
+fn p2pkh_matches_bip44() {
+    let wallets = generate(Network::Mainnet, BitcoinAddress::P2pkh, 1);
+    assert_eq!(wallets[0].derivation_path, "m/44'/0'/0'/0/0");
+    assert_eq!(wallets[0].address, "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA");
+    assert_eq!(wallets[0].wif.as_deref(), Some("L4p2b9VAf8k5aUahF1JCJUzZkgNEAqLfq8DDdQiyAprQAKSbu8hf"));
+}
+
+#[test]
+fn testnet_uses_coin_type_1_and_test_prefixes() {
+    let segwit = generate(Network::Testnet, BitcoinAddress::P2wpkh, 1);
+    assert_eq!(segwit[0].derivation_path, "m/84'/1'/0'/0/0");
+    assert_eq!(segwit[0].address, "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl");
+    assert!(segwit[0].wif.as_deref().unwrap().starts_with('c'));
+
+    let legacy = generate(Network::Regtest, BitcoinAddress::P2pkh, 1);
+    assert!(legacy[0].address.starts_with(['m', 'n']));
+}
+
+#[test]
+fn p2pkh_and_p2wpkh_share_the_key_hash() {
+    let hash = bitcoin::hash160(&hex::decode("0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c").unwrap());
+    let segwit = bitcoin::p2wpkh(&hash, Network::Mainnet).unwrap();
+    let (_, version, program) = bech32::segwit::decode(&segwit).unwrap();
+    assert_eq!(version, bech32::Fe32::Q);
+    assert_eq!(program, hash);
+    let legacy = bs58::decode(bitcoin::p2pkh(&hash, Network::Mainnet)).with_check(None).into_vec().unwrap();
+    assert_eq!(legacy[1..], hash);
+}
+
+#[test]
+fn rejects_non_bitcoin_prefixes() {
+    assert!(Network::from_hrp("cosmos").is_err());
+    assert_eq!(Network::from_hrp("bcrt").unwrap(), Network::Regtest);
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::{generate, serve_http};
+use prost::Message;
+use serde_json::json;
+use wallet_generator::broadcast::{broadcast_all, SignedTx};
+use wallet_generator::coin::Coin;
+use wallet_generator::rpc::{BroadcastMode, RpcClient};
+use wallet_generator::tx::{proto, Fee, Msg, SignerData, Tx};
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, Wallet};
+
+/// A send from `wallet` to itself, signed at `sequence`.
+fn signed_tx(wallet: &Wallet, sequence: u64) -> SignedTx {
+    let msg = Msg::Send { from_address: wallet.address.clone(), to_address: wallet.address.clone(), amount: vec![Coin::new(1, "uatom")] };
+    let mut tx = Tx::new(vec![msg], Fee { amount: vec![Coin::new(500, "uatom")], gas_limit: 100_000, ..Default::default() }, String::new());
+    let key: [u8; 32] = hex::decode(&wallet.private_key).unwrap().try_into().unwrap();
+    tx.sign_direct(&key, &KeyType::Secp256k1, &SignerData { chain_id: "test-1".to_string(), account_number: 1, sequence }).unwrap();
+    SignedTx::from_raw(tx.to_raw_bytes().unwrap()).unwrap()
+}
+
+/// Signer sequence of a broadcast request's transaction.
+fn sequence_of(params: &serde_json::Value) -> (Vec<u8>, u64) {
+    let raw = general_purpose::STANDARD.decode(params["tx"].as_str().unwrap()).unwrap();
+    let tx = SignedTx::from_raw(raw).unwrap();
+    (tx.signer, tx.sequence)
+}
+
+#[test]
+fn signers_broadcast_in_sequence_order_and_stop_at_a_rejection() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 2);
+    let (a, b) = (&wallets[0], &wallets[1]);
+    let txs = vec![signed_tx(a, 2), signed_tx(b, 1), signed_tx(a, 0), signed_tx(b, 0), signed_tx(a, 1)];
+    let rejected = txs[4].signer.clone();
+
+    // The node rejects the first signer's sequence 1
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = received.clone();
+    let node = serve_http(move |request| {
+        let body = request.json();
+        assert_eq!(body["method"], "broadcast_tx_sync");
+        let (signer, sequence) = sequence_of(&body["params"]);
+        log.lock().unwrap().push((signer.clone(), sequence));
+        let code = if signer == rejected && sequence == 1 { 32 } else { 0 };
+        ("200 OK", json!({"jsonrpc": "2.0", "id": 1, "result": {"code": code, "log": "", "hash": format!("H{}", sequence)}}).to_string())
+    });
+
+    let client = RpcClient::new(&node, Duration::from_secs(5), 0);
+    let progress = AtomicUsize::new(0);
+    let outcomes = broadcast_all(&client, &txs, BroadcastMode::Sync, 2, &progress).unwrap();
+    assert_eq!(progress.into_inner(), 5);
+
+    assert_eq!(outcomes.iter().map(|o| o.index).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    assert_eq!(outcomes.iter().map(|o| o.is_accepted()).collect::<Vec<_>>(), [false, true, true, true, false]);
+    assert_eq!(outcomes[4].result.as_ref().map(|r| r.code), Some(32));
+    assert!(outcomes[0].result.is_none() && outcomes[0].error.as_deref().is_some_and(|e| e.starts_with("Skipped")));
+    assert_eq!(outcomes[1].result.as_ref().unwrap().hash, "H1");
+
+    // Each signer's transactions reach the node in sequence order; the skipped one never does
+    let received = received.lock().unwrap();
+    for signer in [&txs[2].signer, &txs[3].signer] {
+        let sequences: Vec<u64> = received.iter().filter(|(s, _)| s == signer).map(|(_, sequence)| *sequence).collect();
+        assert_eq!(sequences, [0, 1]);
+    }
+}
+
+#[test]
+fn commit_results_report_execution_and_height() {
+    let node = serve_http(|request| {
+        assert_eq!(request.json()["method"], "broadcast_tx_commit");
+        let result = json!({"check_tx": {"code": 0}, "tx_result": {"code": 5, "log": "insufficient funds"}, "hash": "AB", "height": "12"});
+        ("200 OK", json!({"jsonrpc": "2.0", "id": 1, "result": result}).to_string())
+    });
+    let client = RpcClient::new(&node, Duration::from_secs(5), 0);
+    let wallet = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    let result = client.broadcast_tx(&signed_tx(&wallet, 0).raw, BroadcastMode::Commit).unwrap();
+    assert_eq!((result.hash.as_str(), result.code, result.log.as_str(), result.height), ("AB", 5, "insufficient funds", Some(12)));
+
+    let node = serve_http(|_| {
+        let error = json!({"message": "Internal error", "data": "tx already exists in cache"});
+        ("200 OK", json!({"jsonrpc": "2.0", "id": 1, "error": error}).to_string())
+    });
+    let client = RpcClient::new(&node, Duration::from_secs(5), 0);
+    let error = client.broadcast_tx(&signed_tx(&wallet, 0).raw, BroadcastMode::Async).unwrap_err();
+    assert!(error.to_string().contains("tx already exists in cache"), "{}", error);
+}
+
+#[test]
+fn signed_txs_carry_their_signer_and_sequence() {
+    let wallet = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    let tx = signed_tx(&wallet, 7);
+    assert_eq!(tx.sequence, 7);
+    assert_eq!(general_purpose::STANDARD.encode(proto::PubKey::decode(tx.signer.as_slice()).unwrap().key), wallet.pubkey);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+#[test]
+fn coin_type_follows_the_key_type() {
+    let cosmos = common::generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    assert_eq!(cosmos.derivation_path, "m/44'/118'/0'/0/0");
+    assert_eq!(cosmos.address, "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+
+    // MetaMask's first account for the test mnemonic
+    let evmos = common::generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1).remove(0);
+    assert_eq!(evmos.derivation_path, "m/44'/60'/0'/0/0");
+    assert_eq!(evmos.evm_address.as_deref(), Some("0x9858effd232b4033e47d90003d41ec34ecaeda94"));
+}
+
+#[test]
+fn coin_type_can_be_overridden() {
+    let mut config = DerivationConfig::new("evmos", KeyType::Ethsecp256k1);
+    config.coin_type = 118;
+    let wallet = common::generate(&config, 1).remove(0);
+    assert_eq!(wallet.derivation_path, "m/44'/118'/0'/0/0");
+    // Same key as the cosmos wallet, hashed the Ethereum way
+    let cosmos = common::generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    assert_eq!(wallet.pubkey, cosmos.pubkey);
+    assert_eq!(wallet.evm_address.as_deref(), Some("0x7c1b02b575545d4f24243c229bc9a5ef1649b346"));
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn coin_type_flag_overrides_the_default() {
+    let dir = common::temp_dir("coin-type");
+    for (args, path) in [(&[][..], "m/44'/60'/0'/0/0"), (&["--coin-type", "118"][..], "m/44'/118'/0'/0/0")] {
+        let output = common::run_cli(&dir, &[&["-c", "1", "-o", "-", "-k", "ethsecp256k1", "-p", "evmos"][..], args].concat(), "");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let record: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(record["derivationPath"], path);
+    }
+}
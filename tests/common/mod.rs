@@ -0,0 +1,125 @@
+//! Fixture shared by the integration tests: the BIP-39 test mnemonic and its wallets, a mock HTTP
+//! node, and a runner for the binary.
+// Each test crate uses only part of it
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+use bip39::Mnemonic;
+use wallet_generator::{generate_wallets_batch, DerivationConfig, GeneratorError, Wallet};
+
+pub const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+pub fn seed() -> [u8; 64] {
+    Mnemonic::parse(MNEMONIC).unwrap().to_seed("")
+}
+
+/// The first `count` wallets of the test mnemonic.
+pub fn generate(config: &DerivationConfig, count: usize) -> Vec<Wallet> {
+    generate_from(config, 0, count)
+}
+
+/// `count` wallets of the test mnemonic from index `start`.
+pub fn generate_from(config: &DerivationConfig, start: usize, count: usize) -> Vec<Wallet> {
+    try_generate_from(config, start, count).unwrap()
+}
+
+/// As [`generate_from`], for configurations the generator may reject.
+pub fn try_generate_from(config: &DerivationConfig, start: usize, count: usize) -> Result<Vec<Wallet>, GeneratorError> {
+    generate_wallets_batch(&seed(), start, count, config, Arc::new(AtomicUsize::new(0)))
+}
+
+/// A request received by [`serve_http`].
+pub struct HttpRequest {
+    pub method: String,
+    /// Path and query string
+    pub target: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+/// Serve HTTP on a free local port, answering every request with the status line and JSON body
+/// `respond` returns. Returns the base URL.
+pub fn serve_http(respond: impl Fn(&HttpRequest) -> (&'static str, String) + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some(length) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut parts = request_line.split_whitespace();
+            let request = HttpRequest {
+                method: parts.next().unwrap_or_default().to_string(),
+                target: parts.next().unwrap_or_default().to_string(),
+                body,
+            };
+            let (status, body) = respond(&request);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    format!("http://{}", addr)
+}
+
+/// A fresh, empty temporary directory named after the calling test.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("wallet-generator-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    // Private, so the binary agrees to write private keys there
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+    }
+    dir
+}
+
+/// Run the `wallet-generator` binary in `dir` with `args`, feeding it `stdin`.
+#[cfg(feature = "cli")]
+pub fn run_cli(dir: &std::path::Path, args: &[&str], stdin: &str) -> std::process::Output {
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wallet-generator"))
+        .args(args)
+        .current_dir(dir)
+        .env_remove("WALLET_MNEMONIC")
+        .env_remove("RUST_LOG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
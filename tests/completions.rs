@@ -0,0 +1,35 @@
+#![cfg(feature = "cli")]
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use wallet_generator::Args;
+
+#[test]
+fn cli_definition_is_consistent() {
+    Args::command().debug_assert();
+}
+
+#[test]
+fn completions_cover_subcommands_and_flags() {
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::Elvish, Shell::PowerShell] {
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Args::command(), "wallet-generator", &mut script);
+        let script = String::from_utf8(script).unwrap();
+        for word in ["scan-balances", "rederive", "completions", "match-file", "chunk-size"] {
+            assert!(script.contains(word), "{:?} completions lack {}", shell, word);
+        }
+    }
+}
+
+#[test]
+fn man_pages_for_every_subcommand() {
+    let dir = std::env::temp_dir().join(format!("wallet-generator-man-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    clap_mangen::generate_to(Args::command(), &dir).unwrap();
+    for page in ["wallet-generator.1", "wallet-generator-rederive.1", "wallet-generator-address-module.1"] {
+        assert!(dir.join(page).is_file(), "{} is missing", page);
+    }
+    let main = std::fs::read_to_string(dir.join("wallet-generator.1")).unwrap();
+    assert!(main.contains("generate\\-man") && main.contains("mnemonic"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{run_cli, temp_dir};
+use serde_json::Value;
+
+fn addresses(stdout: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap()["address"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn config_file_sets_defaults_that_flags_override() {
+    let dir = temp_dir("config");
+    std::fs::write(
+        dir.join("wallet-factory.toml"),
+        "prefix = \"osmo\"\ncount = 2\noutput = \"-\"\n\n[chains.mychain]\nprefix = \"my\"\nkey-type = \"ethsecp256k1\"\n",
+    )
+    .unwrap();
+
+    // The configured count satisfies the required --count
+    let output = run_cli(&dir, &[], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(addresses(&output.stdout), ["osmo19rl4cm2hmr8afy4kldpxz3fka4jguq0a5m7df8", "osmo1jrkmdcwgq94uaamx6zax2luewlhf7u4k5r4pqs"]);
+
+    let output = run_cli(&dir, &["-p", "juno", "-c", "1"], "");
+    assert_eq!(addresses(&output.stdout), ["juno19rl4cm2hmr8afy4kldpxz3fka4jguq0a2jwxcf"]);
+
+    // Custom chain presets, with the key type's coin type
+    let output = run_cli(&dir, &["--chain", "mychain", "-c", "1"], "");
+    let record: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(record["address"].as_str().unwrap().starts_with("my1"));
+    assert_eq!(record["derivationPath"], "m/44'/60'/0'/0/0");
+}
+
+#[test]
+fn explicit_config_replaces_the_working_directory_file() {
+    let dir = temp_dir("config-explicit");
+    std::fs::write(dir.join("wallet-factory.toml"), "prefix = \"osmo\"\n").unwrap();
+    std::fs::write(dir.join("other.toml"), "prefix = \"juno\"\n").unwrap();
+    let output = run_cli(&dir, &["--config", "other.toml", "-c", "1", "-o", "-"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(addresses(&output.stdout), ["juno19rl4cm2hmr8afy4kldpxz3fka4jguq0a2jwxcf"]);
+}
+
+#[test]
+fn unknown_keys_are_errors() {
+    let dir = temp_dir("config-unknown");
+    std::fs::write(dir.join("wallet-factory.toml"), "prefx = \"osmo\"\n\n[nope]\nx = 1\n").unwrap();
+    let output = run_cli(&dir, &["-c", "1", "-o", "-"], "");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("prefx is not an option"), "{}", stderr);
+    assert!(stderr.contains("[nope] is not a subcommand"), "{}", stderr);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::seed;
+use serde_json::Value;
+use wallet_generator::coprocess::{Coprocess, Defaults};
+use wallet_generator::types::KeyType;
+use wallet_generator::derive_wallet;
+
+fn serve(requests: &str) -> Vec<Value> {
+    let seed = seed();
+    let mut coprocess = Coprocess::new(&seed, Defaults { prefix: "cosmos".to_string(), key_type: KeyType::Secp256k1 });
+    let mut output = Vec::new();
+    let answered = coprocess.serve(Cursor::new(requests), &mut output).unwrap();
+    let answers: Vec<Value> = output.split(|&byte| byte == b'\n').filter(|line| !line.is_empty()).map(|line| serde_json::from_slice(line).unwrap()).collect();
+    assert_eq!(answers.len(), answered);
+    answers
+}
+
+#[test]
+fn each_request_line_gets_one_answer_line() {
+    let seed = seed();
+    let answers = serve(concat!(
+        "{\"id\": 1, \"index\": 4}\n",
+        "\n",
+        "{\"id\": \"evm\", \"path\": \"m/44'/60'/0'/0/2\", \"prefix\": \"evmos\", \"key_type\": \"ethsecp256k1\", \"include_private_key\": true}\n",
+        "{\"index\": 7, \"coin_type\": 330, \"prefix\": \"terra\"}\n",
+    ));
+    assert_eq!(answers.len(), 3);
+
+    let expected = derive_wallet(&seed, "m/44'/118'/0'/0/4", "cosmos", &KeyType::Secp256k1).unwrap();
+    assert_eq!((answers[0]["id"].as_u64(), answers[0]["address"].as_str()), (Some(1), Some(expected.address.as_str())));
+    // Keys only when asked for
+    assert!(answers[0].get("privateKey").is_none());
+
+    let evm = derive_wallet(&seed, "m/44'/60'/0'/0/2", "evmos", &KeyType::Ethsecp256k1).unwrap();
+    assert_eq!(answers[1]["id"], "evm");
+    assert_eq!(answers[1]["evmAddress"].as_str(), evm.evm_address.as_deref());
+    assert_eq!(answers[1]["privateKey"].as_str(), Some(evm.private_key.as_str()));
+
+    assert_eq!(answers[2]["derivationPath"], "m/44'/330'/0'/0/7");
+    assert!(answers[2].get("id").is_none());
+    assert!(answers[2]["address"].as_str().unwrap().starts_with("terra1"));
+}
+
+#[test]
+fn bad_requests_do_not_end_the_session() {
+    let answers = serve(concat!(
+        "not json\n",
+        "{\"id\": 2}\n",
+        "{\"id\": 3, \"index\": 0, \"key_type\": \"ed448\"}\n",
+        "{\"id\": 4, \"path\": \"m/44'/118'/x\"}\n",
+        "{\"id\": 5, \"index\": 0}\n",
+    ));
+    assert!(answers[0]["error"].as_str().unwrap().starts_with("Invalid request"));
+    assert_eq!(answers[1]["error"], "A request needs a path or an index");
+    assert_eq!(answers[2]["error"], "Unknown key type: ed448");
+    assert_eq!(answers[3]["id"], 4);
+    assert!(answers[3]["error"].is_string());
+    assert_eq!(answers[4]["address"], "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+}
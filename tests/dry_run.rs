@@ -0,0 +1,64 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{run_cli, temp_dir};
+
+/// Run a dry run in `dir`, returning whether it passed and its stdout, or stderr on failure.
+fn dry_run(dir: &std::path::Path, args: &[&str]) -> (bool, String) {
+    let output = run_cli(dir, &[&["--dry-run"][..], args].concat(), "");
+    let text = if output.status.success() { output.stdout } else { output.stderr };
+    (output.status.success(), String::from_utf8_lossy(&text).into_owned())
+}
+
+#[test]
+fn dry_run_estimates_without_writing() {
+    let dir = temp_dir("dry-run");
+    let (ok, summary) = dry_run(&dir, &["-c", "5000", "-o", "out/wallets.json", "--report-json", "report.json"]);
+    assert!(ok, "{}", summary);
+    for line in [
+        "Wallets: 5,000",
+        "Derivation path: m/44'/118'/0'/0/{index} (index 0 to 4,999)",
+        "Output: out/wallets.json",
+        "Estimated runtime:",
+        "Estimated output size:",
+        "Estimated memory:",
+        "Nothing was written.",
+    ] {
+        assert!(summary.contains(line), "{} missing from\n{}", line, summary);
+    }
+    assert!(summary.lines().any(|line| line.starts_with("Calibration: ") && line.contains("wallets/sec")), "{}", summary);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+    // Quiet dry runs only validate
+    let (ok, summary) = dry_run(&dir, &["-c", "5000", "-q"]);
+    assert!(ok && summary.is_empty(), "{}", summary);
+}
+
+#[test]
+fn dry_run_validates_the_run() {
+    let dir = temp_dir("dry-run-invalid");
+    std::fs::write(dir.join("afile"), "").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(dir.join("afile"), std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+    let cases: [(&[&str], &str); 4] = [
+        (&["-m", "abandon abandon abandon"], "Invalid mnemonic"),
+        (&["-p", "Cosmos"], "invalid bech32 prefix"),
+        (&["--path", "m/44'/x/0"], "invalid derivation path"),
+        (&["-o", "afile/wallets.json"], "afile is not a directory"),
+    ];
+    for (args, error) in cases {
+        let (ok, stderr) = dry_run(&dir, &[&["-c", "10"][..], args].concat());
+        assert!(!ok, "{:?} passed", args);
+        assert!(stderr.contains(error), "{:?}: {}", args, stderr);
+    }
+
+    // A path spec sets the count
+    let (ok, summary) = dry_run(&dir, &["--path", "m/44'/118'/0-1'/0/0-4", "-o", "wallets.json"]);
+    assert!(ok, "{}", summary);
+    assert!(summary.contains("Wallets: 10"), "{}", summary);
+    assert!(!dir.join("wallets.json").exists());
+}
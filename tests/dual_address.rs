@@ -0,0 +1,52 @@
+mod common;
+
+use common::generate;
+use wallet_generator::binary::BinaryReader;
+use wallet_generator::rederive::rederive;
+use wallet_generator::types::KeyType;
+use wallet_generator::wallet_file::WalletWriter;
+use wallet_generator::DerivationConfig;
+
+fn dual(prefix: &str, key_type: KeyType) -> DerivationConfig {
+    DerivationConfig { dual_address: true, ..DerivationConfig::new(prefix, key_type) }
+}
+
+#[test]
+fn ethsecp256k1_keys_carry_both_addresses() {
+    let wallets = generate(&dual("evmos", KeyType::Ethsecp256k1), 3);
+    // The same keys hashed as plain secp256k1 keys
+    let secp256k1 = DerivationConfig::new("evmos", KeyType::Secp256k1);
+    for wallet in &wallets {
+        let expected = rederive(wallet, &secp256k1).unwrap().address;
+        assert_eq!(wallet.secp256k1_address.as_deref(), Some(expected.as_str()));
+        assert_ne!(wallet.secp256k1_address.as_ref(), Some(&wallet.address));
+    }
+
+    let record = serde_json::to_value(&wallets[0]).unwrap();
+    assert_eq!(record["secp256k1Address"].as_str(), wallets[0].secp256k1_address.as_deref());
+    assert_eq!(record["address"].as_str(), Some(wallets[0].address.as_str()));
+
+    // Off by default, and meaningless for secp256k1 keys
+    assert!(generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1)[0].secp256k1_address.is_none());
+    let record = serde_json::to_value(&generate(&dual("cosmos", KeyType::Secp256k1), 1)[0]).unwrap();
+    assert!(record.get("secp256k1Address").is_none());
+}
+
+#[test]
+fn rederived_and_binary_records_keep_both_addresses() {
+    let config = dual("evmos", KeyType::Ethsecp256k1);
+    let wallets = generate(&config, 4);
+
+    let plain = generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 4);
+    for (wallet, plain) in wallets.iter().zip(&plain) {
+        assert_eq!(rederive(plain, &config).unwrap().secp256k1_address, wallet.secp256k1_address);
+    }
+
+    let mut writer = WalletWriter::binary(Vec::new(), &config).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    let path = std::env::temp_dir().join(format!("wallet-generator-dual-address-{}.bin", std::process::id()));
+    std::fs::write(&path, writer.finish().unwrap()).unwrap();
+    let read = BinaryReader::open(&path).unwrap().collect::<anyhow::Result<Vec<_>>>().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(serde_json::to_value(&read).unwrap(), serde_json::to_value(&wallets).unwrap());
+}
@@ -0,0 +1,35 @@
+use std::time::{Duration, Instant};
+
+use wallet_generator::progress::{parse_duration, RunState};
+
+#[test]
+fn durations_parse_with_units() {
+    assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+    assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5_400));
+    assert_eq!(parse_duration("2.5h").unwrap(), Duration::from_secs(9_000));
+    assert_eq!(parse_duration("1d 500ms").unwrap(), Duration::from_millis(86_400_500));
+    assert!(parse_duration("").is_err());
+    assert!(parse_duration("0s").is_err());
+    assert!(parse_duration("10w").is_err());
+    assert!(parse_duration("m").is_err());
+}
+
+#[test]
+fn time_budgets_stop_new_work_once_spent() {
+    let state = RunState::new(1_000, 1).with_time_budget(Some(Duration::from_millis(100)));
+    assert!(state.within_deadline());
+    std::thread::sleep(Duration::from_millis(120));
+    assert!(!state.within_deadline());
+    // Not an abort: what was generated in time is kept
+    assert!(state.proceed());
+
+    // A rate-limited item waiting past the budget gives up rather than start late
+    let state = RunState::new(1_000, 1).with_rate_limit(Some(10)).with_time_budget(Some(Duration::from_millis(200)));
+    assert!(state.throttle(10));
+    let start = Instant::now();
+    assert!(!state.throttle(1));
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    assert!(RunState::new(10, 1).within_deadline());
+}
@@ -0,0 +1,70 @@
+use sha3::{Digest, Keccak256};
+use wallet_generator::eip712::{recover_typed_data, sign_typed_data, TypedData};
+
+/// The `Mail` example of the EIP-712 specification (assets/eip-712/Example.js).
+fn mail() -> TypedData {
+    serde_json::from_value(serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn mail_example_hashes_as_specified() {
+    let mail = mail();
+    assert_eq!(mail.encode_type("Mail").unwrap(), "Mail(Person from,Person to,string contents)Person(string name,address wallet)");
+    assert_eq!(hex::encode(mail.type_hash("Mail").unwrap()), "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2");
+    assert_eq!(hex::encode(mail.hash_struct("Mail", &mail.message).unwrap()), "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+    assert_eq!(hex::encode(mail.domain_separator().unwrap()), "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+    assert_eq!(hex::encode(mail.signing_hash().unwrap()), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+}
+
+#[test]
+fn mail_example_signs_as_specified() {
+    // The example signs with keccak256("cow"), the key of the sender's wallet
+    let private_key: [u8; 32] = Keccak256::digest(b"cow").into();
+    let signed = sign_typed_data(&private_key, &mail()).unwrap();
+    assert_eq!(signed.address, "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826");
+    assert_eq!(
+        signed.signature,
+        concat!(
+            "0x",
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+            "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+            "1c"
+        )
+    );
+    assert_eq!(hex::encode(recover_typed_data(&mail(), &signed.signature).unwrap()), "cd2a3d9f938e13cd947ec05abc7fe734df8dd826");
+
+    let mut tampered = mail();
+    tampered.message["contents"] = "Hello, Alice!".into();
+    assert_ne!(hex::encode(recover_typed_data(&tampered, &signed.signature).unwrap()), "cd2a3d9f938e13cd947ec05abc7fe734df8dd826");
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::{seed, try_generate_from};
+use wallet_generator::derivation::{derive_private_key, derive_public_key, parse_xpub};
+use wallet_generator::generator::generate_addresses;
+use wallet_generator::{derive_wallet, DerivationConfig, GeneratorError, KeyType};
+
+#[test]
+fn bad_prefixes_are_typed_errors() {
+    let error = try_generate_from(&DerivationConfig::new("Cosmos Hub", KeyType::Secp256k1), 0, 1).unwrap_err();
+    assert!(matches!(&error, GeneratorError::InvalidPrefix { prefix, .. } if prefix == "Cosmos Hub"), "{:?}", error);
+    assert!(matches!(derive_wallet(&seed(), "m/44'/118'/0'/0/0", "", &KeyType::Secp256k1), Err(GeneratorError::InvalidPrefix { .. })));
+}
+
+#[test]
+fn bad_paths_are_typed_errors() {
+    for path in ["m/44'/118'/x", "44/118", "m/4294967296"] {
+        let error = derive_private_key(&seed(), path).unwrap_err();
+        assert!(matches!(error, GeneratorError::InvalidPath { .. } | GeneratorError::Derivation { .. }), "{}: {:?}", path, error);
+        assert!(error.to_string().contains(path), "{}", error);
+    }
+    let xpub = parse_xpub(&wallet_generator::derivation::extended_keys(&seed(), "m/44'/118'/0'").unwrap().0).unwrap();
+    assert!(matches!(derive_public_key(&xpub, "0'/1"), Err(GeneratorError::Derivation { .. })));
+    assert!(matches!(parse_xpub("xpub"), Err(GeneratorError::Derivation { .. })));
+}
+
+#[test]
+fn out_of_range_keys_are_typed_errors() {
+    for key in [[0; 32], [0xff; 32]] {
+        assert!(matches!(generate_addresses(&key, "cosmos", &KeyType::Secp256k1), Err(GeneratorError::InvalidKey(_))));
+    }
+}
+
+#[test]
+fn indices_past_bip32_are_typed_errors() {
+    // The last non-hardened index derives; the next one cannot be a child number
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    assert_eq!(try_generate_from(&config, (1 << 31) - 1, 1).unwrap().len(), 1);
+    let error = try_generate_from(&config, (1 << 31) - 1, 2).unwrap_err();
+    assert!(matches!(&error, GeneratorError::Derivation { path, .. } if path.ends_with("/2147483648")), "{:?}", error);
+    assert!(try_generate_from(&config, usize::MAX, 2).is_err());
+}
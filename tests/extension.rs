@@ -0,0 +1,51 @@
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::{generate, seed};
+use wallet_generator::bitcoin::Network;
+use wallet_generator::derivation::master_fingerprint;
+use wallet_generator::extension::{bundle, parse_bip44_path, Bip44HdPath};
+use wallet_generator::types::{BitcoinAddress, DerivationScheme, KeyType};
+use wallet_generator::DerivationConfig;
+
+#[test]
+fn mnemonics_are_referenced_by_master_fingerprint() {
+    // The fingerprint hardware wallets show for the BIP-39 test mnemonic
+    assert_eq!(hex::encode(master_fingerprint(&seed()).unwrap()), "73c5da0a");
+}
+
+#[test]
+fn bundles_carry_what_the_extensions_import() {
+    let wallet = generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 3).remove(2);
+    let json = serde_json::to_value(bundle(&wallet, "qa-2".to_string(), "73c5da0a").unwrap()).unwrap();
+
+    assert_eq!(json["name"], "qa-2");
+    assert_eq!(json["algo"], "ethsecp256k1");
+    assert_eq!(json["bech32Address"].as_str(), Some(wallet.address.as_str()));
+    assert_eq!(json["ethereumHexAddress"].as_str(), wallet.evm_address.as_deref());
+    assert_eq!(json["pubKey"]["@type"], "/ethermint.crypto.v1.ethsecp256k1.PubKey");
+    let key = general_purpose::STANDARD.decode(json["pubKey"]["key"].as_str().unwrap()).unwrap();
+    assert_eq!(hex::encode(key), wallet.pubkey_hex.unwrap());
+    assert_eq!(json["coinType"], 60);
+    assert_eq!(json["bip44HDPath"], serde_json::json!({"account": 0, "change": 0, "addressIndex": 2}));
+    assert_eq!(json["mnemonicRef"], "73c5da0a");
+    // No key material
+    assert!(!json.to_string().contains(&wallet.private_key));
+
+    let cosmos = bundle(&generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1)[0], "qa-0".to_string(), "fleet-a").unwrap();
+    assert_eq!((cosmos.algo.as_str(), cosmos.coin_type), ("secp256k1", 118));
+    assert!(serde_json::to_value(&cosmos).unwrap().get("ethereumHexAddress").is_none());
+}
+
+#[test]
+fn ledger_live_accounts_and_other_chains() {
+    let config = DerivationConfig { scheme: DerivationScheme::LedgerLive, ..DerivationConfig::new("cosmos", KeyType::Secp256k1) };
+    let wallet = generate(&config, 4).remove(3);
+    assert_eq!(bundle(&wallet, String::new(), "").unwrap().bip44_hd_path, Bip44HdPath { account: 3, change: 0, address_index: 0 });
+
+    assert_eq!(parse_bip44_path("m/84'/0'/0'/0/1"), None);
+    assert_eq!(parse_bip44_path("m/44'/118'/0'/0"), None);
+    let bitcoin = generate(&DerivationConfig::bitcoin(Network::Mainnet, BitcoinAddress::P2pkh), 1).remove(0);
+    assert!(bundle(&bitcoin, String::new(), "").is_err());
+    assert!(bundle(&generate(&DerivationConfig::solana(), 1)[0], String::new(), "").is_err());
+}
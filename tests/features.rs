@@ -0,0 +1,55 @@
+mod common;
+
+use std::process::Command;
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+/// Names of the normal (non-dev, non-build) dependencies the crate pulls in with `features`.
+fn dependencies(features: &[&str]) -> Vec<String> {
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .args(["tree", "--offline", "--no-default-features", "--edges", "normal", "--prefix", "none", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    let output = command.output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).lines().filter_map(|line| line.split_whitespace().next()).map(str::to_string).collect()
+}
+
+#[test]
+fn core_leaves_out_terminal_and_thread_pool_dependencies() {
+    let core = dependencies(&[]);
+    assert!(core.iter().any(|name| name == "secp256k1"));
+    for heavy in ["clap", "indicatif", "rayon", "tracing-subscriber", "ratatui", "tokio"] {
+        assert!(!core.iter().any(|name| name == heavy), "{} is in the core build", heavy);
+    }
+
+    let parallel = dependencies(&["parallel"]);
+    assert!(parallel.iter().any(|name| name == "rayon"));
+    assert!(!parallel.iter().any(|name| name == "clap" || name == "indicatif"));
+
+    let cli = dependencies(&["cli"]);
+    assert!(["clap", "indicatif", "rayon"].iter().all(|heavy| cli.iter().any(|name| name == heavy)));
+}
+
+#[test]
+fn core_generates_without_optional_features() {
+    let wallets = common::generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 2);
+    assert_eq!(wallets[0].address, "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+    assert_eq!(wallets[1].address, "cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz");
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_generation_matches_the_core() {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    let config = DerivationConfig::new("evmos", KeyType::Ethsecp256k1);
+    let parallel = wallet_generator::generate_wallets_parallel(&common::seed(), 3, 250, &config, 16, Arc::new(AtomicUsize::new(0))).unwrap();
+    let batch = common::generate_from(&config, 3, 250);
+    assert!(parallel.iter().zip(&batch).all(|(a, b)| a.address == b.address && a.index == b.index));
+    assert_eq!(parallel.len(), 250);
+}
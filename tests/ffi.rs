@@ -0,0 +1,93 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+use common::{generate, seed, MNEMONIC};
+use wallet_generator::ffi::{wf_derive_address, wf_generate_batch, wf_mnemonic_to_seed, WfKeyType, WfStatus, WfWallet, WF_SEED_LEN};
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+fn text(buffer: &[c_char]) -> &str {
+    unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap()
+}
+
+fn empty_wallets(count: usize) -> Vec<WfWallet> {
+    (0..count).map(|_| unsafe { std::mem::zeroed() }).collect()
+}
+
+#[test]
+fn mnemonics_become_the_bip39_seed() {
+    let mnemonic = format!("{}\0", MNEMONIC);
+    let mut out = [0u8; WF_SEED_LEN];
+    assert_eq!(unsafe { wf_mnemonic_to_seed(mnemonic.as_ptr().cast(), out.as_mut_ptr()) }, WfStatus::Ok);
+    assert_eq!(out, seed());
+
+    assert_eq!(unsafe { wf_mnemonic_to_seed(ptr::null(), out.as_mut_ptr()) }, WfStatus::NullPointer);
+    assert_eq!(unsafe { wf_mnemonic_to_seed(mnemonic.as_ptr().cast(), ptr::null_mut()) }, WfStatus::NullPointer);
+    assert_eq!(unsafe { wf_mnemonic_to_seed(c"abandon \xff".as_ptr(), out.as_mut_ptr()) }, WfStatus::InvalidUtf8);
+    assert_eq!(unsafe { wf_mnemonic_to_seed(c"abandon abandon".as_ptr(), out.as_mut_ptr()) }, WfStatus::InvalidMnemonic);
+}
+
+#[test]
+fn batches_and_addresses_round_trip() {
+    let expected = generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 2);
+    let seed = seed();
+    let mut wallets = empty_wallets(2);
+    let status = unsafe {
+        wf_generate_batch(seed.as_ptr(), seed.len(), 0, 2, c"evmos".as_ptr(), WfKeyType::Ethsecp256k1 as u32, wallets.as_mut_ptr(), wallets.len())
+    };
+    assert_eq!(status, WfStatus::Ok);
+
+    for (wallet, expected) in wallets.iter().zip(&expected) {
+        assert_eq!(text(&wallet.address), expected.address);
+        assert_eq!(text(&wallet.evm_address), expected.evm_address.as_deref().unwrap());
+        assert_eq!(text(&wallet.derivation_path), expected.derivation_path);
+
+        // The private key written out derives the same addresses again
+        let key = hex::decode(text(&wallet.private_key)).unwrap();
+        let (mut address, mut evm) = ([0 as c_char; 128], [0 as c_char; 43]);
+        let status = unsafe {
+            wf_derive_address(key.as_ptr(), c"evmos".as_ptr(), WfKeyType::Ethsecp256k1 as u32, address.as_mut_ptr(), address.len(), evm.as_mut_ptr(), evm.len())
+        };
+        assert_eq!(status, WfStatus::Ok);
+        assert_eq!((text(&address), text(&evm)), (text(&wallet.address), text(&wallet.evm_address)));
+
+        // A buffer one byte short of the address and its NUL
+        let status = unsafe {
+            wf_derive_address(key.as_ptr(), c"evmos".as_ptr(), 1, address.as_mut_ptr(), expected.address.len(), ptr::null_mut(), 0)
+        };
+        assert_eq!(status, WfStatus::BufferTooSmall);
+    }
+}
+
+#[test]
+fn invalid_arguments_are_status_codes() {
+    let seed = seed();
+    let key = [1u8; 32];
+    let mut wallets = empty_wallets(1);
+    let mut address = [0 as c_char; 128];
+
+    // Key types C may pass that have no variant
+    for key_type in [2, u32::MAX] {
+        let status = unsafe { wf_generate_batch(seed.as_ptr(), seed.len(), 0, 1, c"cosmos".as_ptr(), key_type, wallets.as_mut_ptr(), 1) };
+        assert_eq!(status, WfStatus::InvalidKeyType);
+        let status = unsafe { wf_derive_address(key.as_ptr(), c"cosmos".as_ptr(), key_type, address.as_mut_ptr(), address.len(), ptr::null_mut(), 0) };
+        assert_eq!(status, WfStatus::InvalidKeyType);
+    }
+
+    let status = unsafe { wf_generate_batch(ptr::null(), 0, 0, 1, c"cosmos".as_ptr(), 0, wallets.as_mut_ptr(), 1) };
+    assert_eq!(status, WfStatus::NullPointer);
+    let status = unsafe { wf_generate_batch(seed.as_ptr(), seed.len(), 0, 2, c"cosmos".as_ptr(), 0, wallets.as_mut_ptr(), 1) };
+    assert_eq!(status, WfStatus::BufferTooSmall);
+    let status = unsafe { wf_generate_batch(seed.as_ptr(), seed.len(), 0, 1, c"cosmos\xff".as_ptr(), 0, wallets.as_mut_ptr(), 1) };
+    assert_eq!(status, WfStatus::InvalidUtf8);
+    let status = unsafe { wf_generate_batch(seed.as_ptr(), seed.len(), 0, 1, c"Cosmos Hub".as_ptr(), 0, wallets.as_mut_ptr(), 1) };
+    assert_eq!(status, WfStatus::InvalidPrefix);
+    let status = unsafe { wf_derive_address(ptr::null(), c"cosmos".as_ptr(), 0, address.as_mut_ptr(), address.len(), ptr::null_mut(), 0) };
+    assert_eq!(status, WfStatus::NullPointer);
+    let status = unsafe { wf_derive_address([0u8; 32].as_ptr(), c"cosmos".as_ptr(), 0, address.as_mut_ptr(), address.len(), ptr::null_mut(), 0) };
+    assert_eq!(status, WfStatus::InvalidKey);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use wallet_generator::filter::WalletFilter;
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn generate(prefix: &str, key_type: KeyType, count: usize) -> Vec<Wallet> {
+    common::generate(&DerivationConfig::new(prefix, key_type), count)
+}
+
+#[test]
+fn no_patterns_no_filter() {
+    assert!(WalletFilter::new(None, None, None).unwrap().is_none());
+    assert!(WalletFilter::new(Some("(unclosed"), None, None).is_err());
+}
+
+#[test]
+fn address_pattern_keeps_matching_wallets() {
+    let wallets = generate("cosmos", KeyType::Secp256k1, 200);
+    let filter = WalletFilter::new(Some("q$"), None, None).unwrap().unwrap();
+    let kept: Vec<&Wallet> = wallets.iter().filter(|wallet| filter.matches(wallet)).collect();
+    assert!(!kept.is_empty() && kept.len() < wallets.len());
+    assert!(kept.iter().all(|wallet| wallet.address.ends_with('q')));
+    assert_eq!(kept.len(), wallets.iter().filter(|wallet| wallet.address.ends_with('q')).count());
+}
+
+#[test]
+fn every_pattern_must_match() {
+    let wallets = generate("evmos", KeyType::Ethsecp256k1, 300);
+    let evm = WalletFilter::new(None, Some("^0x[0-7]"), None).unwrap().unwrap();
+    let both = WalletFilter::new(Some("[02468]$"), Some("^0x[0-7]"), None).unwrap().unwrap();
+    for wallet in &wallets {
+        let evm_matches = wallet.evm_address.as_deref().unwrap().as_bytes()[2] < b'8';
+        assert_eq!(evm.matches(wallet), evm_matches);
+        assert_eq!(both.matches(wallet), evm_matches && wallet.address.ends_with(['0', '2', '4', '6', '8']));
+    }
+
+    // Wallets without an EVM address never match an EVM pattern
+    let cosmos = generate("cosmos", KeyType::Secp256k1, 5);
+    let anything = WalletFilter::new(None, Some(""), None).unwrap().unwrap();
+    assert!(cosmos.iter().all(|wallet| !anything.matches(wallet)));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::sync::atomic::AtomicUsize;
+
+use common::{generate_from, seed};
+use wallet_generator::generator::find_index;
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, GeneratorError};
+
+fn account_bytes(address: &str) -> [u8; 20] {
+    bech32::decode(address).unwrap().1.try_into().unwrap()
+}
+
+#[test]
+fn indices_are_found_past_the_first_chunk() {
+    let wallet = generate_from(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1100, 1).remove(0);
+    let progress = AtomicUsize::new(0);
+    let found = find_index(&seed(), "m/44'/118'/0'/0/{index}", &account_bytes(&wallet.address), &KeyType::Secp256k1, 1500, &progress);
+    assert_eq!(found.unwrap(), Some(1100));
+
+    let target = account_bytes(&generate_from(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 0, 1)[0].address);
+    assert_eq!(find_index(&seed(), "m/44'/118'/0'/0/{index}", &target, &KeyType::Secp256k1, 0, &progress).unwrap(), Some(0));
+}
+
+#[test]
+fn evm_addresses_are_found_along_their_template() {
+    let wallet = generate_from(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 7, 1).remove(0);
+    let target: [u8; 20] = hex::decode(&wallet.evm_address.unwrap()[2..]).unwrap().try_into().unwrap();
+    let progress = AtomicUsize::new(0);
+    let found = find_index(&seed(), "m/44'/60'/0'/0/{index}", &target, &KeyType::Ethsecp256k1, 20, &progress).unwrap();
+    assert_eq!(found, Some(7));
+
+    // The same key under the other key type's address is not a match
+    assert_eq!(find_index(&seed(), "m/44'/60'/0'/0/{index}", &target, &KeyType::Secp256k1, 20, &progress).unwrap(), None);
+}
+
+#[test]
+fn exhausted_searches_and_bad_templates() {
+    let wallet = generate_from(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 50, 1).remove(0);
+    let progress = AtomicUsize::new(0);
+    let target = account_bytes(&wallet.address);
+    assert_eq!(find_index(&seed(), "m/44'/118'/0'/0/{index}", &target, &KeyType::Secp256k1, 49, &progress).unwrap(), None);
+    assert_eq!(progress.into_inner(), 50);
+
+    let error = find_index(&seed(), "m/44'/118'/0'/0/0", &target, &KeyType::Secp256k1, 10, &AtomicUsize::new(0)).unwrap_err();
+    assert!(matches!(error, GeneratorError::Derivation { .. }), "{:?}", error);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::{generate, run_cli, seed, temp_dir};
+use serde_json::Value;
+use wallet_generator::derivation::extended_keys;
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+fn ndjson(stdout: &[u8]) -> Vec<Value> {
+    String::from_utf8_lossy(stdout).lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[test]
+fn public_keys_in_base64_or_hex_address_like_their_wallets() {
+    let dir = temp_dir("from-pubkey");
+    let wallets = generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 3);
+    let public_key = |i: usize| secp256k1::PublicKey::from_slice(&general_purpose::STANDARD.decode(&wallets[i].pubkey).unwrap()).unwrap();
+    let input = format!(
+        "{}\n0x{}\n\n{}\n",
+        wallets[0].pubkey,
+        hex::encode(public_key(1).serialize()),
+        hex::encode(public_key(2).serialize_uncompressed())
+    );
+    std::fs::write(dir.join("keys.txt"), input).unwrap();
+
+    let output = run_cli(&dir, &["from-pubkey", "keys.txt", "--prefix", "evmos", "--key-type", "ethsecp256k1"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let records = ndjson(&output.stdout);
+    assert_eq!(records.len(), 3);
+    for (record, wallet) in records.iter().zip(&wallets) {
+        assert_eq!(record["address"], wallet.address.as_str());
+        assert_eq!(record["evmAddress"], wallet.evm_address.as_deref().unwrap());
+        assert_eq!(record["pubkey"], wallet.pubkey.as_str());
+    }
+
+    // Keys on stdin, addressed as cosmos secp256k1 by default
+    let wallet = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    let output = run_cli(&dir, &["from-pubkey"], &format!("{}\n", wallet.pubkey));
+    assert_eq!(ndjson(&output.stdout)[0]["address"], "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn account_xpubs_derive_the_receive_addresses() {
+    let dir = temp_dir("from-xpub");
+    let (xpub, _) = extended_keys(&seed(), "m/44'/118'/0'").unwrap();
+    let output = run_cli(&dir, &["from-pubkey", "--xpub", &xpub, "--start-index", "1", "--count", "3", "--output", "addresses.json"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let records: Vec<Value> = serde_json::from_slice(&std::fs::read(dir.join("addresses.json")).unwrap()).unwrap();
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 4);
+    assert_eq!(records.len(), 3);
+    for (record, wallet) in records.iter().zip(&wallets[1..]) {
+        assert_eq!(record["address"], wallet.address.as_str());
+        assert_eq!(record["index"], wallet.index);
+        assert_eq!(record["derivationPath"], format!("0/{}", wallet.index));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn invalid_keys_fail() {
+    let dir = temp_dir("from-pubkey-invalid");
+    let output = run_cli(&dir, &["from-pubkey"], "not a key\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("neither hex nor base64"));
+
+    let output = run_cli(&dir, &["from-pubkey", "--xpub", "xpub123"], "");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid --xpub"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::generate;
+use wallet_generator::coin::Coin;
+use wallet_generator::fund::{FundMode, FundPlan};
+use wallet_generator::tx::{AddressCoins, Msg};
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+fn plan(mode: FundMode) -> FundPlan {
+    let funder = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    FundPlan {
+        from: funder.address,
+        amount: vec![Coin::new(1000, "uatom"), Coin::new(5, "ustake")],
+        mode,
+        max_tx_bytes: 1 << 20,
+        max_recipients: 2,
+        gas_base: 80_000,
+        gas_per_recipient: 30_000,
+        gas_price: Some("0.025uatom".parse().unwrap()),
+        fee: vec![Coin::new(1, "uatom")],
+        memo: "funding".to_string(),
+    }
+}
+
+fn recipients() -> Vec<String> {
+    generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 6).into_iter().skip(1).map(|w| w.address).collect()
+}
+
+#[test]
+fn multisends_pay_every_recipient_from_one_input() {
+    let plan = plan(FundMode::MultiSend);
+    let recipients = recipients();
+    let addresses: Vec<&str> = recipients.iter().map(String::as_str).collect();
+    let txs = plan.build_txs(&addresses);
+
+    // At most two recipients per transaction
+    assert_eq!(txs.iter().map(|tx| tx.body.messages.len()).collect::<Vec<_>>(), [1, 1, 1]);
+    let mut paid = Vec::new();
+    for tx in &txs {
+        let Msg::MultiSend { inputs, outputs } = &tx.body.messages[0] else { panic!("not a multisend") };
+        let total = vec![Coin::new(1000 * outputs.len() as u128, "uatom"), Coin::new(5 * outputs.len() as u128, "ustake")];
+        assert_eq!(inputs, &[AddressCoins { address: plan.from.clone(), coins: total }]);
+        assert!(outputs.iter().all(|output| output.coins == plan.amount));
+        paid.extend(outputs.iter().map(|output| output.address.clone()));
+
+        // Gas grows with the recipients and the gas price sets the fee
+        let gas_limit = 80_000 + 30_000 * outputs.len() as u64;
+        assert_eq!(tx.auth_info.fee.gas_limit, gas_limit);
+        assert_eq!(tx.auth_info.fee.amount, [Coin::new((gas_limit as u128 * 25).div_ceil(1000), "uatom")]);
+        assert_eq!(tx.body.memo, "funding");
+        assert!(tx.signatures.is_empty());
+    }
+    assert_eq!(paid, recipients);
+}
+
+#[test]
+fn sends_batch_one_message_per_recipient() {
+    let plan = FundPlan { gas_price: None, ..plan(FundMode::Send) };
+    let recipients = recipients();
+    let addresses: Vec<&str> = recipients.iter().map(String::as_str).collect();
+    let txs = plan.build_txs(&addresses);
+
+    assert_eq!(txs.iter().map(|tx| tx.body.messages.len()).collect::<Vec<_>>(), [2, 2, 1]);
+    let messages: Vec<&Msg> = txs.iter().flat_map(|tx| &tx.body.messages).collect();
+    for (message, recipient) in messages.iter().zip(&recipients) {
+        let expected = Msg::Send { from_address: plan.from.clone(), to_address: recipient.clone(), amount: plan.amount.clone() };
+        assert_eq!(*message, &expected);
+    }
+    // Without a gas price every transaction pays the fixed fee
+    assert!(txs.iter().all(|tx| tx.auth_info.fee.amount == [Coin::new(1, "uatom")]));
+}
+
+#[test]
+fn transactions_stay_under_the_size_limit() {
+    let plan = FundPlan { max_recipients: 100, max_tx_bytes: 1000, ..plan(FundMode::MultiSend) };
+    let recipients: Vec<String> = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 40).into_iter().map(|w| w.address).collect();
+    let addresses: Vec<&str> = recipients.iter().map(String::as_str).collect();
+    let txs = plan.build_txs(&addresses);
+
+    assert!(txs.len() > 1);
+    for tx in &txs {
+        assert!(tx.body_bytes().len() + tx.auth_info_bytes().unwrap().len() < 1000, "{} body bytes", tx.body_bytes().len());
+    }
+    let paid: usize = txs
+        .iter()
+        .map(|tx| match &tx.body.messages[0] {
+            Msg::MultiSend { outputs, .. } => outputs.len(),
+            Msg::Send { .. } => 0,
+        })
+        .sum();
+    assert_eq!(paid, 40);
+    assert!(plan.build_txs(&[]).is_empty());
+}
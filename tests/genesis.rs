@@ -0,0 +1,78 @@
+mod common;
+
+use common::generate;
+use serde_json::{json, Value};
+use wallet_generator::coin::Coin;
+use wallet_generator::genesis::{add_accounts, GenesisAccountType};
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+/// A genesis with one vesting account (number 4) holding 100uatom.
+fn genesis() -> Value {
+    json!({
+        "chain_id": "test-1",
+        "app_state": {
+            "auth": {"accounts": [{
+                "@type": "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+                "base_vesting_account": {"base_account": {"address": "cosmos1existing", "pub_key": null, "account_number": "4", "sequence": "0"}},
+            }]},
+            "bank": {
+                "balances": [{"address": "cosmos1existing", "coins": [{"denom": "uatom", "amount": "100"}]}],
+                "supply": [{"denom": "uatom", "amount": "100"}],
+            },
+        },
+    })
+}
+
+fn addresses(config: &DerivationConfig, count: usize) -> Vec<String> {
+    generate(config, count).into_iter().map(|wallet| wallet.address).collect()
+}
+
+#[test]
+fn accounts_are_numbered_funded_and_counted_in_supply() {
+    let addresses = addresses(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 2);
+    let addresses: Vec<&str> = addresses.iter().map(String::as_str).collect();
+    let mut genesis = genesis();
+    add_accounts(&mut genesis, &addresses, &[Coin::new(7, "ustake"), Coin::new(10, "uatom")], GenesisAccountType::Base).unwrap();
+
+    let accounts = &genesis["app_state"]["auth"]["accounts"];
+    assert_eq!(
+        accounts[1],
+        json!({"@type": "/cosmos.auth.v1beta1.BaseAccount", "address": addresses[0], "pub_key": null, "account_number": "5", "sequence": "0"})
+    );
+    assert_eq!(accounts[2]["account_number"], "6");
+
+    let balances = &genesis["app_state"]["bank"]["balances"];
+    assert_eq!(balances[2], json!({"address": addresses[1], "coins": [{"denom": "uatom", "amount": "10"}, {"denom": "ustake", "amount": "7"}]}));
+    assert_eq!(genesis["app_state"]["bank"]["supply"], json!([{"denom": "uatom", "amount": "120"}, {"denom": "ustake", "amount": "14"}]));
+    assert_eq!(genesis["chain_id"], "test-1");
+}
+
+#[test]
+fn eth_accounts_wrap_a_base_account() {
+    let addresses = addresses(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1);
+    let mut genesis = genesis();
+    add_accounts(&mut genesis, &[&addresses[0]], &[Coin::new(1, "aevmos")], GenesisAccountType::Eth).unwrap();
+
+    let account = &genesis["app_state"]["auth"]["accounts"][1];
+    assert_eq!(account["@type"], "/ethermint.types.v1.EthAccount");
+    assert_eq!(account["base_account"]["address"], addresses[0].as_str());
+    assert_eq!(account["base_account"]["account_number"], "5");
+    assert_eq!(account["code_hash"], "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+}
+
+#[test]
+fn existing_and_repeated_accounts_leave_genesis_untouched() {
+    let addresses = addresses(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1);
+    for duplicate in [vec![addresses[0].as_str(), "cosmos1existing"], vec![addresses[0].as_str(), addresses[0].as_str()]] {
+        let mut genesis = genesis();
+        let error = add_accounts(&mut genesis, &duplicate, &[Coin::new(1, "uatom")], GenesisAccountType::Base).unwrap_err();
+        assert!(error.to_string().contains("already exists"), "{}", error);
+        assert_eq!(genesis, self::genesis());
+    }
+
+    let mut genesis = genesis();
+    let error = add_accounts(&mut genesis, &[&addresses[0]], &[Coin::new(u128::MAX, "uatom")], GenesisAccountType::Base).unwrap_err();
+    assert!(error.to_string().contains("overflows"), "{}", error);
+    assert_eq!(genesis, self::genesis());
+}
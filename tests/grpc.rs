@@ -0,0 +1,89 @@
+#![cfg(feature = "grpc")]
+
+mod common;
+
+use std::net::{SocketAddr, TcpListener};
+use std::time::Duration;
+
+use common::{generate_from, MNEMONIC};
+use tokio_stream::StreamExt;
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+use wallet_generator::grpc::proto::wallet_factory_client::WalletFactoryClient;
+use wallet_generator::grpc::proto::{DeriveRequest, GenerateRequest, KeyType as ProtoKeyType, WalletRecord};
+use wallet_generator::grpc::{serve, GrpcConfig};
+use wallet_generator::{DerivationConfig, KeyType};
+
+const TOKEN: &str = "s3cret";
+
+/// Serve the API on a free port, returning a client whose requests carry `TOKEN`.
+async fn start(max_stream_size: u64) -> WalletFactoryClient<Channel> {
+    let listen: SocketAddr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    tokio::spawn(serve(GrpcConfig { listen, auth_token: Some(TOKEN.to_string()), max_stream_size }));
+    for _ in 0..50 {
+        if let Ok(client) = WalletFactoryClient::connect(format!("http://{}", listen)).await {
+            return client;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("the server did not start");
+}
+
+fn authorized<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("authorization", format!("Bearer {}", TOKEN).parse().unwrap());
+    request
+}
+
+fn derive_request(index: u64) -> DeriveRequest {
+    DeriveRequest { mnemonic: MNEMONIC.to_string(), index, key_type: ProtoKeyType::Ethsecp256k1 as i32, prefix: "evmos".to_string(), ..Default::default() }
+}
+
+#[tokio::test]
+async fn derived_wallets_match_the_batch() {
+    let mut client = start(10).await;
+    let expected = generate_from(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 3, 1).remove(0);
+
+    let record = client.derive_wallet(authorized(derive_request(3))).await.unwrap().into_inner();
+    assert_eq!(record, WalletRecord { private_key: String::new(), ..WalletRecord::from(expected.clone()) });
+
+    let request = DeriveRequest { include_private_key: true, ..derive_request(3) };
+    let record = client.derive_wallet(authorized(request)).await.unwrap().into_inner();
+    assert_eq!(record, WalletRecord::from(expected));
+
+    // Empty prefixes are cosmos
+    let request = DeriveRequest { mnemonic: MNEMONIC.to_string(), ..Default::default() };
+    let record = client.derive_wallet(authorized(request)).await.unwrap().into_inner();
+    assert_eq!(record.address, "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+    assert_eq!(record.evm_address, "");
+}
+
+#[tokio::test]
+async fn generated_wallets_stream_in_index_order() {
+    let mut client = start(100).await;
+    let request = GenerateRequest { mnemonic: MNEMONIC.to_string(), start_index: 5, count: 30, ..Default::default() };
+    let stream = client.generate_wallets(authorized(request)).await.unwrap().into_inner();
+    let records: Vec<WalletRecord> = stream.collect::<Result<_, _>>().await.unwrap();
+
+    let expected = generate_from(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 5, 30);
+    assert_eq!(records, expected.into_iter().map(WalletRecord::from).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn bad_requests_are_rejected() {
+    let mut client = start(10).await;
+
+    let status = client.derive_wallet(derive_request(0)).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let request = DeriveRequest { mnemonic: "abandon about".to_string(), ..derive_request(0) };
+    assert_eq!(client.derive_wallet(authorized(request)).await.unwrap_err().code(), Code::InvalidArgument);
+    let request = DeriveRequest { key_type: 7, ..derive_request(0) };
+    assert_eq!(client.derive_wallet(authorized(request)).await.unwrap_err().code(), Code::InvalidArgument);
+
+    for count in [0, 11] {
+        let request = GenerateRequest { mnemonic: MNEMONIC.to_string(), count, ..Default::default() };
+        let status = client.generate_wallets(authorized(request)).await.unwrap_err();
+        assert_eq!((status.code(), status.message()), (Code::InvalidArgument, "count must be between 1 and 10"));
+    }
+}
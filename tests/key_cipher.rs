@@ -0,0 +1,56 @@
+mod common;
+
+use common::generate;
+use wallet_generator::bitcoin::Network;
+use wallet_generator::key_cipher::{is_encrypted, KeyCipher, KeyDecryptor};
+use wallet_generator::sign::wallet_private_key;
+use wallet_generator::types::{BitcoinAddress, KeyType};
+use wallet_generator::DerivationConfig;
+
+#[test]
+fn keys_round_trip_and_nothing_else_changes() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 3);
+    let cipher = KeyCipher::new("correct horse").unwrap();
+    let mut encrypted = wallets.clone();
+    encrypted.iter_mut().for_each(|wallet| cipher.encrypt_wallet(wallet));
+
+    for (plain, sealed) in wallets.iter().zip(&encrypted) {
+        assert!(is_encrypted(&sealed.private_key));
+        assert!(!sealed.private_key.contains(&plain.private_key));
+        assert_eq!((&sealed.address, &sealed.pubkey, sealed.index), (&plain.address, &plain.pubkey, plain.index));
+    }
+    // Fresh nonces, so equal keys do not encrypt alike
+    assert_ne!(cipher.encrypt("00", "a"), cipher.encrypt("00", "a"));
+    assert!(wallet_private_key(&encrypted[0]).unwrap_err().to_string().contains("encrypted"));
+
+    let mut decryptor = KeyDecryptor::new("correct horse");
+    for (plain, mut sealed) in wallets.into_iter().zip(encrypted) {
+        decryptor.decrypt_wallet(&mut sealed).unwrap();
+        assert_eq!(sealed.private_key, plain.private_key);
+    }
+}
+
+#[test]
+fn wif_keys_are_encrypted_too() {
+    let config = DerivationConfig::bitcoin(Network::Mainnet, BitcoinAddress::P2wpkh);
+    let wallet = generate(&config, 1).remove(0);
+    let mut sealed = wallet.clone();
+    KeyCipher::new("pass").unwrap().encrypt_wallet(&mut sealed);
+    assert!(sealed.wif.as_deref().is_some_and(is_encrypted));
+
+    KeyDecryptor::new("pass").decrypt_wallet(&mut sealed).unwrap();
+    assert_eq!(sealed.wif, wallet.wif);
+}
+
+#[test]
+fn wrong_passphrase_and_moved_keys_fail() {
+    let cipher = KeyCipher::new("right").unwrap();
+    let sealed = cipher.encrypt("deadbeef", "cosmos1first");
+
+    let error = KeyDecryptor::new("wrong").decrypt(&sealed, "cosmos1first").unwrap_err();
+    assert!(error.to_string().contains("wrong passphrase"), "{error}");
+    // The address is authenticated with the key
+    assert!(KeyDecryptor::new("right").decrypt(&sealed, "cosmos1second").is_err());
+    assert_eq!(KeyDecryptor::new("right").decrypt(&sealed, "cosmos1first").unwrap(), "deadbeef");
+    assert!(KeyCipher::new("").is_err());
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{run_cli, temp_dir};
+use serde_json::Value;
+
+/// Each stderr line as a JSON log event.
+fn events(stderr: &[u8]) -> Vec<Value> {
+    String::from_utf8_lossy(stderr).lines().map(|line| serde_json::from_str(line).unwrap_or_else(|_| panic!("not JSON: {}", line))).collect()
+}
+
+#[test]
+fn json_logs_carry_fields_and_phase_timings() {
+    let dir = temp_dir("logging");
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json", "--log-format", "json", "--log-level", "debug"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let events = events(&output.stderr);
+
+    let started = events.iter().find(|event| event["fields"]["message"] == "Generating wallets").unwrap();
+    assert_eq!(started["level"], "INFO");
+    assert_eq!(started["fields"]["count"], 2);
+
+    // Spans around derivation and writing report their time when they close
+    let closed = |name: &str| events.iter().find(|event| event["fields"]["message"] == "close" && event["span"]["name"] == name).cloned();
+    let derive = closed("derive_batch").unwrap();
+    assert_eq!(derive["level"], "DEBUG");
+    assert_eq!(derive["span"]["key_type"], "Secp256k1");
+    assert!(derive["fields"]["time.busy"].is_string());
+    let write = closed("write").unwrap();
+    assert_eq!(write["span"]["output"], "wallets.json");
+    assert_eq!(write["span"]["records"], 2);
+    assert!(closed("generate").is_some());
+}
+
+#[test]
+fn log_level_filters_events() {
+    let dir = temp_dir("log-level");
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json", "--log-format", "json"], "");
+    let levels: Vec<Value> = events(&output.stderr).into_iter().map(|event| event["level"].clone()).collect();
+    assert!(levels.contains(&"INFO".into()) && !levels.contains(&"DEBUG".into()));
+
+    // The default-mnemonic warning passes a warn filter; nothing below it does
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json", "--log-format", "json", "--log-level", "warn"], "");
+    let events = events(&output.stderr);
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| event["level"] == "WARN" || event["level"] == "ERROR"));
+
+    // An explicit level wins over --quiet
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json", "--quiet", "--log-level", "info"], "");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Generating wallets"));
+}
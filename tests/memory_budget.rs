@@ -0,0 +1,55 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{run_cli, temp_dir};
+use serde_json::Value;
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+#[test]
+fn max_memory_generates_and_writes_in_segments() {
+    let dir = temp_dir("max-memory");
+    let output = run_cli(&dir, &["-c", "3000", "--max-memory", "1", "-o", "wallets.json", "--log-format", "json"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let events: Vec<Value> = String::from_utf8_lossy(&output.stderr).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+    // Every segment in flight, plus the output buffer, fits in 1 MiB
+    let started = events.iter().find(|event| event["fields"]["message"] == "Generating wallets").unwrap();
+    let per_segment = started["fields"]["wallets_per_segment"].as_u64().unwrap();
+    let wallet_bytes = started["fields"]["wallet_bytes"].as_u64().unwrap();
+    assert!(per_segment < 3000);
+    assert!(per_segment * 4 * wallet_bytes + 256 * 1024 <= 1024 * 1024);
+
+    // Written a segment at a time, all in order
+    let writes: Vec<u64> = events
+        .iter()
+        .filter(|event| event["fields"]["message"] == "close" && event["span"]["name"] == "write")
+        .map(|event| event["span"]["records"].as_u64().unwrap())
+        .collect();
+    assert!(writes.len() > 1 && writes.iter().all(|records| *records <= per_segment));
+    assert_eq!(writes.iter().sum::<u64>(), 3000);
+
+    let records: Vec<Value> = serde_json::from_slice(&std::fs::read(dir.join("wallets.json")).unwrap()).unwrap();
+    assert_eq!(records.len(), 3000);
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    for index in [0, per_segment as usize - 1, per_segment as usize, 2999] {
+        assert_eq!(records[index]["address"], common::generate_from(&config, index, 1)[0].address.as_str());
+    }
+}
+
+#[test]
+fn max_memory_too_small_is_an_error() {
+    let dir = temp_dir("max-memory-small");
+    let output = run_cli(&dir, &["-c", "3", "--max-memory", "0", "-o", "wallets.json"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("leaves no room for wallets"));
+    assert!(!dir.join("wallets.json").exists());
+}
+
+#[test]
+fn memory_size_covers_the_record_strings() {
+    let wallet = common::generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1).remove(0);
+    let strings = wallet.address.len() + wallet.pubkey.len() + wallet.private_key.len() + wallet.derivation_path.len();
+    assert!(wallet.memory_size() > std::mem::size_of_val(&wallet) + strings);
+}
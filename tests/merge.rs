@@ -0,0 +1,130 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use wallet_generator::merge::{diff, merge, Coverage, DuplicateChecker, SortedMerge};
+use wallet_generator::types::{KeyType, OutputFormat};
+use wallet_generator::wallet_file::WalletWriter;
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn generate(count: usize) -> Vec<Wallet> {
+    common::generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), count)
+}
+
+/// Write `wallets` as NDJSON to a temporary file named `name`.
+fn shard(name: &str, wallets: &[Wallet]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("wallet-generator-merge-{}-{}.ndjson", std::process::id(), name));
+    let mut writer = WalletWriter::new(Vec::new(), OutputFormat::Ndjson, None).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    fs::write(&path, writer.finish().unwrap()).unwrap();
+    path
+}
+
+fn indices(wallets: &[Wallet]) -> Vec<u64> {
+    wallets.iter().map(|wallet| wallet.index).collect()
+}
+
+#[test]
+fn shards_interleave_into_index_order() {
+    let wallets = generate(30);
+    // Shards listed out of order, overlapping at 10..15
+    let inputs = [shard("late", &wallets[10..30]), shard("early", &wallets[..15])];
+
+    let mut sorted = SortedMerge::open(&inputs).unwrap();
+    let merged: Vec<Wallet> = sorted.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(indices(&merged), (0..30).collect::<Vec<_>>());
+    assert_eq!(sorted.duplicates(), 5);
+    assert_eq!(sorted.coverage(), Some(Coverage { first: 0, last: 29, missing: 0 }));
+
+    // The in-memory merge agrees
+    let unsorted = merge(&inputs).unwrap();
+    assert_eq!(indices(&unsorted.wallets), indices(&merged));
+    assert_eq!(unsorted.duplicates, 5);
+    assert_eq!(unsorted.coverage, sorted.coverage());
+    inputs.iter().for_each(|input| fs::remove_file(input).unwrap());
+}
+
+#[test]
+fn gaps_are_counted() {
+    let wallets = generate(20);
+    let inputs = [shard("first", &wallets[..5]), shard("second", &wallets[12..])];
+
+    let mut sorted = SortedMerge::open(&inputs).unwrap();
+    assert_eq!(sorted.by_ref().count(), 13);
+    let coverage = sorted.coverage().unwrap();
+    assert_eq!(coverage, Coverage { first: 0, last: 19, missing: 7 });
+    assert!(!coverage.is_contiguous());
+    inputs.iter().for_each(|input| fs::remove_file(input).unwrap());
+}
+
+#[test]
+fn unordered_input_and_conflicts_fail() {
+    let mut wallets = generate(6);
+    wallets.swap(2, 4);
+    let unordered = shard("unordered", &wallets);
+    let error = SortedMerge::open(&[&unordered]).unwrap().collect::<Result<Vec<_>, _>>().unwrap_err();
+    assert!(error.to_string().contains("not in index order"), "{error}");
+
+    let wallets = generate(3);
+    let mut changed = wallets.clone();
+    changed[1].private_key = "00".repeat(32);
+    let inputs = [shard("original", &wallets), shard("changed", &changed)];
+    let error = SortedMerge::open(&inputs).unwrap().collect::<Result<Vec<_>, _>>().unwrap_err();
+    assert!(error.to_string().contains("Conflicting records"), "{error}");
+    [unordered].iter().chain(&inputs).for_each(|input| fs::remove_file(input).unwrap());
+}
+
+#[test]
+fn diffs_report_missing_and_changed_records() {
+    let wallets = generate(8);
+    let mut changed = wallets[2..].to_vec();
+    changed[1].private_key = "00".repeat(32);
+    changed[1].pubkey = "AA==".to_string();
+    let inputs = [shard("left", &wallets[..6]), shard("right", &changed)];
+
+    let result = diff(&inputs[0], &inputs[1]).unwrap();
+    assert_eq!(result.only_left, [wallets[0].address.clone(), wallets[1].address.clone()]);
+    let mut only_right = vec![wallets[6].address.clone(), wallets[7].address.clone()];
+    only_right.sort();
+    assert_eq!(result.only_right, only_right);
+    assert_eq!(result.mismatched.len(), 1);
+    assert_eq!(result.mismatched[0].address, wallets[3].address);
+    assert_eq!(result.mismatched[0].fields, ["pubkey", "privateKey"]);
+    assert_eq!(result.matching, 3);
+    assert!(!result.is_empty());
+
+    assert!(diff(&inputs[0], &inputs[0]).unwrap().is_empty());
+    inputs.iter().for_each(|input| fs::remove_file(input).unwrap());
+}
+
+#[test]
+fn duplicates_list_every_occurrence() {
+    let wallets = generate(6);
+    let mut repeated = wallets[4..].to_vec();
+    repeated.push(wallets[1].clone());
+    let inputs = [shard("dupes-a", &wallets), shard("dupes-b", &repeated)];
+
+    let mut checker = DuplicateChecker::new();
+    inputs.iter().for_each(|input| checker.add_file(input).unwrap());
+    // A third occurrence of index 5 from memory
+    checker.add(&wallets[5], "extra", 0);
+    assert_eq!(checker.unique(), 6);
+
+    let mut duplicates = checker.finish();
+    duplicates.sort_by_key(|duplicate| duplicate.occurrences[0].record);
+    assert_eq!(duplicates.len(), 3);
+    let sources = |i: usize| -> Vec<(String, usize)> {
+        duplicates[i].occurrences.iter().map(|occurrence| (occurrence.source.clone(), occurrence.record)).collect()
+    };
+    let (a, b) = (inputs[0].display().to_string(), inputs[1].display().to_string());
+    assert_eq!(duplicates[0].address, wallets[1].address);
+    assert_eq!(sources(0), [(a.clone(), 1), (b.clone(), 2)]);
+    assert_eq!(duplicates[1].occurrences[1].derivation_path, wallets[4].derivation_path);
+    assert_eq!(sources(2), [(a, 5), (b, 1), ("extra".to_string(), 0)]);
+
+    let mut unique = DuplicateChecker::new();
+    unique.add_file(&inputs[0]).unwrap();
+    assert!(unique.finish().is_empty());
+    inputs.iter().for_each(|input| fs::remove_file(input).unwrap());
+}
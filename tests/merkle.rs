@@ -0,0 +1,48 @@
+use wallet_generator::merkle::{build_airdrop, AirdropEntry, LeafFormat, MerkleTree};
+
+const CLAIMS: [(&str, u128); 3] = [
+    ("cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4", 100),
+    ("cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz", 200),
+    ("cosmos1h0zphzxl239fh3wtr5v6q6ljvs36vy2kxwlr2c", 300),
+];
+
+fn tree() -> MerkleTree {
+    let leaves = CLAIMS.iter().enumerate().map(|(index, (address, amount))| LeafFormat::CosmWasm.leaf(index, address, *amount).unwrap());
+    MerkleTree::new(LeafFormat::CosmWasm, leaves.collect()).unwrap()
+}
+
+#[test]
+fn cosmwasm_roots_and_proofs_match_the_fixture() {
+    // Sorted leaves 0x530d.., 0x9444.., 0xd66a..: the first two pair up and the third is promoted
+    let entries: Vec<AirdropEntry> =
+        CLAIMS.iter().map(|(address, amount)| AirdropEntry { address: address.to_string(), evm_address: None, amount: Some(amount.to_string()) }).collect();
+    let airdrop = build_airdrop(&entries, LeafFormat::CosmWasm, None).unwrap();
+    assert_eq!(airdrop.root, "4e7f3123674c2c5011ec887a3198b386b4768dc3ecf996e7be780edb39923105");
+    assert_eq!(airdrop.total_amount, "600");
+    assert_eq!(
+        airdrop.claims[CLAIMS[0].0].proof,
+        ["94440b8486cd949b4e9112834222b316a500d0c98d751181c58b4fa6eedbd711", "d66a0e8fea4878f6ca2aa10184d2e966b196c4e844b201fcdabd812b7e74ddaf"]
+    );
+    assert_eq!(airdrop.claims[CLAIMS[2].0].proof, ["80c5b3619f315fffef1e141d40aa4803de9ac3b3048a59d34b9853e4edadf6ad"]);
+    assert_eq!(hex::encode(tree().root()), airdrop.root);
+}
+
+#[test]
+fn proofs_verify_only_their_own_claim() {
+    let tree = tree();
+    for (index, (address, amount)) in CLAIMS.iter().enumerate() {
+        let leaf = LeafFormat::CosmWasm.leaf(index, address, *amount).unwrap();
+        let proof = tree.proof(&leaf).unwrap();
+        assert!(tree.verify(&leaf, &proof));
+
+        let inflated = LeafFormat::CosmWasm.leaf(index, address, amount + 1).unwrap();
+        assert!(!tree.verify(&inflated, &proof));
+        assert!(tree.proof(&inflated).is_none());
+    }
+
+    let first = LeafFormat::CosmWasm.leaf(0, CLAIMS[0].0, CLAIMS[0].1).unwrap();
+    let mut proof = tree.proof(&first).unwrap();
+    proof[0][0] ^= 1;
+    assert!(!tree.verify(&first, &proof));
+    assert!(!tree.verify(&first, &[]));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::generate;
+use wallet_generator::message::{
+    adr36_sign_doc, eip191_hash, personal_sign, recover_personal_sign, sign_arbitrary, verify_arbitrary, verify_personal_sign, AminoPubKey, StdSignature,
+};
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+/// Index 0 of the test mnemonic.
+const ADDRESS: &str = "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4";
+
+fn key() -> [u8; 32] {
+    let wallet = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 1).remove(0);
+    hex::decode(wallet.private_key).unwrap().try_into().unwrap()
+}
+
+/// The ADR-36 signature of the message in Keplr's `signArbitrary` form, made with an RFC 6979
+/// secp256k1 implementation of its own.
+fn adr36_fixture() -> StdSignature {
+    StdSignature {
+        pub_key: AminoPubKey { type_name: "tendermint/PubKeySecp256k1".to_string(), value: "Ak9OKtmcNNYLm6YoPJQxqEGK+GcyEpYfl6d7Y3f80Fti".to_string() },
+        signature: "JPD88AVQMCppEx15yzg2j+JvEir8bfJunh3mELV7dhgSimNvMytyHMxKeYSQNVlqoOxYer9QjmyD++r+vG0oFA==".to_string(),
+    }
+}
+
+#[test]
+fn adr36_signatures_match_the_fixture() {
+    let sign_doc = concat!(
+        r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","#,
+        r#""msgs":[{"type":"sign/MsgSignData","value":{"data":"SGVsbG8sIHdhbGxldC1mYWN0b3J5IQ==","#,
+        r#""signer":"cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4"}}],"sequence":"0"}"#,
+    );
+    assert_eq!(String::from_utf8(adr36_sign_doc(ADDRESS, b"Hello, wallet-factory!")).unwrap(), sign_doc);
+    assert_eq!(sign_arbitrary(&key(), &KeyType::Secp256k1, ADDRESS, b"Hello, wallet-factory!").unwrap(), adr36_fixture());
+}
+
+#[test]
+fn adr36_signatures_verify_only_for_their_signer_and_data() {
+    assert!(verify_arbitrary(ADDRESS, b"Hello, wallet-factory!", &adr36_fixture()).unwrap());
+    assert!(!verify_arbitrary(ADDRESS, b"Hello, wallet-factory?", &adr36_fixture()).unwrap());
+    assert!(!verify_arbitrary("cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz", b"Hello, wallet-factory!", &adr36_fixture()).unwrap());
+
+    let signed = sign_arbitrary(&key(), &KeyType::Secp256k1, "cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz", b"data");
+    assert!(signed.is_err(), "a key signs only as its own address");
+}
+
+/// The key of the ethers.js `Wallet` documentation examples.
+const ETH_KEY: &str = "0123456789012345678901234567890123456789012345678901234567890123";
+const ETH_ADDRESS: &str = "0x14791697260e4c9a71f18484c9f997b308e59325";
+
+#[test]
+fn personal_sign_matches_the_fixture() {
+    // ethers.js `hashMessage("Hello World")`
+    assert_eq!(hex::encode(eip191_hash(b"Hello World")), "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2");
+
+    let key: [u8; 32] = hex::decode(ETH_KEY).unwrap().try_into().unwrap();
+    let signed = personal_sign(&key, b"Hello World").unwrap();
+    assert_eq!(signed.address, ETH_ADDRESS);
+    // RFC 6979 nonce, low s, v of 27 or 28, as ethers.js signs
+    assert_eq!(
+        signed.signature,
+        concat!(
+            "0x",
+            "e0ed34fbbe927a58267ce2e8067a611c69869e20e731bc99187a8bc97058664c",
+            "16de07f7660f06ce0985d1d8e063726783033fda59b307897f26a21392d62b3a",
+            "1c"
+        )
+    );
+}
+
+#[test]
+fn personal_signatures_recover_their_address() {
+    let key: [u8; 32] = hex::decode(ETH_KEY).unwrap().try_into().unwrap();
+    let signed = personal_sign(&key, b"Hello World").unwrap();
+    assert_eq!(format!("0x{}", hex::encode(recover_personal_sign(b"Hello World", &signed.signature).unwrap())), ETH_ADDRESS);
+    assert!(verify_personal_sign(ETH_ADDRESS, b"Hello World", &signed.signature).unwrap());
+    assert!(!verify_personal_sign(ETH_ADDRESS, b"Hello world", &signed.signature).unwrap());
+
+    // v as 0/1 recovers the same address
+    let v0 = format!("{}01", &signed.signature[..signed.signature.len() - 2]);
+    assert!(verify_personal_sign(ETH_ADDRESS, b"Hello World", &v0).unwrap());
+}
+
+#[test]
+fn generated_wallets_sign_as_their_evm_address() {
+    for wallet in generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 3) {
+        let key: [u8; 32] = hex::decode(&wallet.private_key).unwrap().try_into().unwrap();
+        let evm_address = wallet.evm_address.unwrap();
+        let signed = personal_sign(&key, b"wallet-factory").unwrap();
+        assert!(signed.address.eq_ignore_ascii_case(&evm_address), "{} signed as {}", evm_address, signed.address);
+        assert!(verify_personal_sign(&evm_address, b"wallet-factory", &signed.signature).unwrap());
+        assert!(!verify_personal_sign(ETH_ADDRESS, b"wallet-factory", &signed.signature).unwrap());
+    }
+}
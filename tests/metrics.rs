@@ -0,0 +1,79 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use wallet_generator::metrics::{self, run_metrics, Exposition, MetricType};
+use wallet_generator::progress::RunState;
+
+fn get(addr: SocketAddr, request: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn exposition_format() {
+    let body = Exposition::new()
+        .metric("jobs_total", MetricType::Counter, "Jobs run", 3)
+        .labelled("slots", MetricType::Gauge, "Slots by state", "state", [("free", 1), ("busy", 2)])
+        .finish();
+    assert_eq!(
+        body,
+        "# HELP jobs_total Jobs run\n# TYPE jobs_total counter\njobs_total 3\n\
+         # HELP slots Slots by state\n# TYPE slots gauge\nslots{state=\"free\"} 1\nslots{state=\"busy\"} 2\n"
+    );
+}
+
+#[test]
+fn run_metrics_report_state() {
+    let state = RunState::new(100, 2);
+    state.thread_counter(0).fetch_add(30, Ordering::Relaxed);
+    state.thread_counter(1).fetch_add(12, Ordering::Relaxed);
+    state.records_matched(40);
+    state.records_written(25);
+    state.set_bytes_written(4096);
+    state.segment_queued();
+    state.segment_queued();
+    state.segment_dequeued();
+
+    let body = run_metrics(&state, Instant::now());
+    for line in [
+        "wallet_generator_wallets_target 100",
+        "wallet_generator_wallets_generated_total 42",
+        "wallet_generator_thread_wallets_generated_total{thread=\"0\"} 30",
+        "wallet_generator_thread_wallets_generated_total{thread=\"1\"} 12",
+        "wallet_generator_wallets_matched_total 40",
+        "wallet_generator_wallets_written_total 25",
+        "wallet_generator_output_bytes 4096",
+        "wallet_generator_queue_depth 1",
+        "wallet_generator_paused 0",
+    ] {
+        assert!(body.lines().any(|l| l == line), "missing {:?} in\n{}", line, body);
+    }
+}
+
+#[test]
+fn endpoint_serves_metrics() {
+    let state = Arc::new(RunState::new(10, 1));
+    let started = Instant::now();
+    let render = {
+        let state = state.clone();
+        move || run_metrics(&state, started)
+    };
+    let addr = metrics::serve("127.0.0.1:0".parse().unwrap(), render).unwrap();
+
+    state.records_written(7);
+    let response = get(addr, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.contains(metrics::CONTENT_TYPE));
+    assert!(response.contains("\nwallet_generator_wallets_written_total 7\n"));
+
+    let response = get(addr, "GET / HTTP/1.1\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"), "{}", response);
+    let response = get(addr, "POST /metrics HTTP/1.1\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"), "{}", response);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use common::generate;
+use wallet_generator::mmap_output::{CsvLayout, MappedCsvWriter};
+use wallet_generator::secure_output::OutputPermissions;
+use wallet_generator::types::{KeyType, OutputFormat};
+use wallet_generator::wallet_file::WalletWriter;
+use wallet_generator::{DerivationConfig, Wallet};
+
+/// The shared temp directory is world-writable.
+const TEMP_DIR: OutputPermissions = OutputPermissions { umask: 0o077, insecure: true };
+
+fn streamed(wallets: &[Wallet]) -> Vec<u8> {
+    let mut writer = WalletWriter::new(Vec::new(), OutputFormat::Csv, None).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    writer.finish().unwrap()
+}
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("wallet-generator-{}-{}.csv", name, std::process::id()))
+}
+
+#[test]
+fn offsets_cross_digit_boundaries() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let wallets = generate(&config, 120);
+    let layout = CsvLayout::new(&wallets[0], 0).unwrap();
+    let expected = streamed(&wallets);
+    assert_eq!(layout.offset(120), expected.len() as u64);
+    assert_eq!(layout.offset(100), streamed(&wallets[..100]).len() as u64);
+
+    // A run starting mid-range counts only its own records
+    let layout = CsvLayout::new(&wallets[95], 95).unwrap();
+    assert_eq!(layout.offset(120), streamed(&wallets[95..]).len() as u64);
+}
+
+#[test]
+fn parallel_batches_match_the_streamed_file() {
+    let config = DerivationConfig::new("evmos", KeyType::Ethsecp256k1);
+    let wallets = generate(&config, 105);
+    let path = temp_path("mmap-match");
+
+    let mut writer = MappedCsvWriter::create(&path, CsvLayout::new(&wallets[0], 0).unwrap(), 105, &TEMP_DIR).unwrap();
+    writer.write(&[wallets[..7].to_vec(), wallets[7..60].to_vec()]).unwrap();
+    writer.write(&[wallets[60..].to_vec(), Vec::new()]).unwrap();
+    let (bytes, _) = writer.finish().unwrap();
+
+    let written = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(written, streamed(&wallets));
+    assert_eq!(bytes, written.len() as u64);
+}
+
+#[test]
+fn unfinished_run_is_trimmed() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let wallets = generate(&config, 30);
+    let path = temp_path("mmap-trim");
+
+    let mut writer = MappedCsvWriter::create(&path, CsvLayout::new(&wallets[0], 0).unwrap(), 1000, &TEMP_DIR).unwrap();
+    writer.write(std::slice::from_ref(&wallets)).unwrap();
+    assert_eq!(writer.records(), 30);
+    writer.finish().unwrap();
+
+    let written = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(written, streamed(&wallets));
+}
+
+#[test]
+fn variable_width_records_are_rejected() {
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let mut wallets = generate(&config, 20);
+    let path = temp_path("mmap-width");
+
+    // As a base58 address one character longer than its neighbours would be
+    wallets[3].address.push('1');
+    let mut writer = MappedCsvWriter::create(&path, CsvLayout::new(&wallets[0], 0).unwrap(), 20, &TEMP_DIR).unwrap();
+    let result = writer.write(&[wallets]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.unwrap_err().to_string().contains("fixed width"));
+}
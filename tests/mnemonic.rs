@@ -0,0 +1,83 @@
+mod common;
+
+use wallet_generator::mnemonic::{check, Correction, Problem};
+
+/// The test mnemonic with the word at 0-based `position` replaced.
+fn with_word(position: usize, word: &str) -> String {
+    let mut words: Vec<&str> = common::MNEMONIC.split_whitespace().collect();
+    words[position] = word;
+    words.join(" ")
+}
+
+#[test]
+fn valid_mnemonics_have_no_problems() {
+    assert!(check(common::MNEMONIC).is_empty());
+    assert!(check(&format!("  {}\n", common::MNEMONIC.replace(' ', "   "))).is_empty());
+}
+
+#[test]
+fn word_counts_outside_bip39_are_reported() {
+    let eleven: Vec<&str> = common::MNEMONIC.split_whitespace().skip(1).collect();
+    assert_eq!(check(&eleven.join(" ")), [Problem::WordCount(11)]);
+    assert_eq!(check(""), [Problem::WordCount(0)]);
+}
+
+#[test]
+fn typos_suggest_the_word_the_checksum_accepts() {
+    let problems = check(&with_word(0, "abandn"));
+    let [Problem::UnknownWord { position, word, suggestions }] = &problems[..] else {
+        panic!("{:?}", problems);
+    };
+    assert_eq!((*position, word.as_str()), (1, "abandn"));
+    assert_eq!(suggestions[0], "abandon");
+
+    // Close entries first, at most five per word
+    let problems = check(&with_word(11, "abuot"));
+    let [Problem::UnknownWord { position: 12, suggestions, .. }] = &problems[..] else {
+        panic!("{:?}", problems);
+    };
+    assert_eq!(suggestions[0], "about");
+    assert!(suggestions.len() <= 5);
+
+    // Several unknown words are each reported, and the count alongside them
+    let problems = check(&format!("abandon abandon abnadon zzzz{}", " abandon".repeat(7)));
+    assert_eq!(problems[0], Problem::WordCount(11));
+    assert!(problems.iter().any(|problem| matches!(problem, Problem::UnknownWord { position: 3, word, .. } if word == "abnadon")));
+    assert!(problems.iter().any(|problem| matches!(problem, Problem::UnknownWord { position: 4, suggestions, .. } if suggestions.contains(&"jazz"))));
+}
+
+#[test]
+fn checksum_failures_list_single_fixes() {
+    // A real word that breaks the checksum
+    let problems = check(&with_word(11, "above"));
+    let [Problem::Checksum { corrections }] = &problems[..] else {
+        panic!("{:?}", problems);
+    };
+    assert!(corrections.contains(&Correction::Replace { position: 12, word: "about" }), "{:?}", corrections);
+
+    // Neighbouring words swapped
+    let swapped = common::MNEMONIC.replace("abandon about", "about abandon");
+    assert_eq!(check(&swapped), [Problem::Checksum { corrections: vec![Correction::Swap { position: 11 }] }]);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn check_subcommand_diagnoses_phrases() {
+    let dir = common::temp_dir("mnemonic-check");
+    let output = common::run_cli(&dir, &["mnemonic", "check"], common::MNEMONIC);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Valid"));
+
+    let output = common::run_cli(&dir, &["mnemonic", "check", "-m", &with_word(10, "abandn")], "");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Word 11 \"abandn\" is not in the BIP-39 wordlist; did you mean: abandon"), "{}", stdout);
+
+    let output = common::run_cli(&dir, &["mnemonic", "check", "-m", &common::MNEMONIC.replace("abandon about", "about abandon")], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("swap words 11 and 12"));
+
+    // Generation points at the tool instead of failing opaquely
+    let output = common::run_cli(&dir, &["-c", "1", "-o", "-", "-m", &with_word(0, "abandn")], "");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`wallet-generator mnemonic check` suggests corrections"));
+}
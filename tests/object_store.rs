@@ -0,0 +1,53 @@
+use wallet_generator::object_store::{sign_v4, SignedRequest};
+
+/// SHA-256 of an empty body.
+const EMPTY_PAYLOAD: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+// Credentials, region and service of the AWS Signature Version 4 test suite
+const SUITE_SECRET: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+const SUITE_DATE: &str = "20150830T123600Z";
+
+fn suite_headers() -> Vec<(&'static str, String)> {
+    vec![("host", "example.amazonaws.com".to_string()), ("x-amz-date", SUITE_DATE.to_string())]
+}
+
+#[test]
+fn signatures_match_the_aws_test_suite() {
+    // get-vanilla
+    let headers = suite_headers();
+    let request = SignedRequest { method: "GET", path: "/", query: "", headers: &headers, payload_hash: EMPTY_PAYLOAD, amz_date: SUITE_DATE };
+    let signed = sign_v4(&request, "us-east-1", "service", SUITE_SECRET);
+    assert_eq!(
+        signed.canonical_request,
+        "GET\n/\n\nhost:example.amazonaws.com\nx-amz-date:20150830T123600Z\n\nhost;x-amz-date\ne3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        signed.string_to_sign,
+        "AWS4-HMAC-SHA256\n20150830T123600Z\n20150830/us-east-1/service/aws4_request\nbb579772317eb040ac9ed261061d46c1f17a8133879d6129b6e1c25292927e63"
+    );
+    assert_eq!(signed.signature, "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31");
+
+    // get-vanilla-query-order-key-case, with the query already in canonical order
+    let request = SignedRequest { query: "Param1=value1&Param2=value2", ..request };
+    assert_eq!(sign_v4(&request, "us-east-1", "service", SUITE_SECRET).signature, "b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500");
+
+    // post-vanilla
+    let request = SignedRequest { method: "POST", query: "", ..request };
+    assert_eq!(sign_v4(&request, "us-east-1", "service", SUITE_SECRET).signature, "5da7c1a2acd57cee7505fc6676e4e544621c30862966e37dddb68e92efbe5d6b");
+}
+
+#[test]
+fn s3_signatures_match_the_s3_documentation() {
+    // "GET Object" of the S3 Signature Version 4 header authentication examples
+    let headers = vec![
+        ("host", "examplebucket.s3.amazonaws.com".to_string()),
+        ("range", "bytes=0-9".to_string()),
+        ("x-amz-content-sha256", EMPTY_PAYLOAD.to_string()),
+        ("x-amz-date", "20130524T000000Z".to_string()),
+    ];
+    let request = SignedRequest { method: "GET", path: "/test.txt", query: "", headers: &headers, payload_hash: EMPTY_PAYLOAD, amz_date: "20130524T000000Z" };
+    let signed = sign_v4(&request, "us-east-1", "s3", "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY");
+    assert_eq!(signed.scope, "20130524/us-east-1/s3/aws4_request");
+    assert_eq!(signed.signed_headers, "host;range;x-amz-content-sha256;x-amz-date");
+    assert_eq!(signed.signature, "f0e8bdb87c964420e857bd35b5d6ed310bd44f0170aba48dd91039c6036bdb41");
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::{generate_from, seed};
+use wallet_generator::path_range::PathRange;
+use wallet_generator::types::KeyType;
+use wallet_generator::{derive_wallet, DerivationConfig};
+
+#[test]
+fn combinations_in_path_order() {
+    let range: PathRange = "m/44'/118'/0-4'/0/0-999".parse().unwrap();
+    assert_eq!(range.count(), Some(5000));
+    assert_eq!(range.path(0), "m/44'/118'/0'/0/0");
+    assert_eq!(range.path(999), "m/44'/118'/0'/0/999");
+    assert_eq!(range.path(1000), "m/44'/118'/1'/0/0");
+    assert_eq!(range.path(4999), "m/44'/118'/4'/0/999");
+
+    let offset: PathRange = "m/44h/60h/2-3h/0/10-11".parse().unwrap();
+    let paths: Vec<String> = (0..4).map(|index| offset.path(index)).collect();
+    assert_eq!(paths, ["m/44'/60'/2'/0/10", "m/44'/60'/2'/0/11", "m/44'/60'/3'/0/10", "m/44'/60'/3'/0/11"]);
+}
+
+#[test]
+fn wildcard_and_display() {
+    let range: PathRange = "m/44'/118'/*'/0/0-19".parse().unwrap();
+    assert_eq!(range.count(), Some((1 << 31) * 20));
+    assert_eq!(range.path(45), "m/44'/118'/2'/0/5");
+    assert_eq!(range.to_string(), "m/44'/118'/*'/0/0-19");
+    assert!(!range.is_hardened());
+    assert!("m/44'/501'/0-9'/0'".parse::<PathRange>().unwrap().is_hardened());
+
+    let json = serde_json::to_string(&range).unwrap();
+    assert_eq!(serde_json::from_str::<PathRange>(&json).unwrap(), range);
+}
+
+#[test]
+fn invalid_specs() {
+    for spec in ["44'/118'/0'/0/0", "m", "m/44'/118'/5-2'/0/0", "m/44'/x/0", "m/44'/118'/0'/0/2147483648", "m/44'//0"] {
+        assert!(spec.parse::<PathRange>().is_err(), "{}", spec);
+    }
+}
+
+#[test]
+fn batches_follow_the_range() {
+    let config = DerivationConfig {
+        path_range: Some("m/44'/118'/0-2'/0/0-3".parse().unwrap()),
+        ..DerivationConfig::new("cosmos", KeyType::Secp256k1)
+    };
+    // A batch starting mid-range continues the numbering
+    let wallets = generate_from(&config, 2, 8);
+    for (offset, wallet) in wallets.iter().enumerate() {
+        let index = 2 + offset as u64;
+        let path = format!("m/44'/118'/{}'/0/{}", index / 4, index % 4);
+        assert_eq!(wallet.index, index);
+        assert_eq!(wallet.derivation_path, path);
+        assert_eq!(wallet.address, derive_wallet(&seed(), &path, "cosmos", &KeyType::Secp256k1).unwrap().address);
+    }
+    assert_eq!(config.path_template(), "m/44'/118'/0-2'/0/0-3");
+}
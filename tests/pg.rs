@@ -0,0 +1,62 @@
+#![cfg(feature = "postgres")]
+
+use wallet_generator::pg::encode_copy;
+use wallet_generator::Wallet;
+
+fn wallet(index: u64, evm_address: Option<&str>) -> Wallet {
+    Wallet {
+        address: "cosmos1a".to_string(),
+        evm_address: evm_address.map(str::to_string),
+        pubkey: "Ak9O".to_string(),
+        private_key: "c4a4".to_string(),
+        derivation_path: "m/0".to_string(),
+        index,
+        pubkey_hex: None,
+        pubkey_type: Some("t".to_string()),
+        pubkey_uncompressed: None,
+        secp256k1_address: None,
+        wif: None,
+    }
+}
+
+#[test]
+fn copy_streams_encode_header_tuples_and_trailer() {
+    let wallets = [wallet(1, None), wallet(258, Some("0xab"))];
+    let expected = concat!(
+        // "PGCOPY\n\xff\r\n\0", no flags, no header extension
+        "5047434f50590aff0d0a00", "00000000", "00000000",
+        // index 1, address, NULL EVM address, pubkey, NULL pubkey hex, pubkey type, key, path
+        "0008",
+        "00000008", "0000000000000001",
+        "00000008", "636f736d6f733161",
+        "ffffffff",
+        "00000004", "416b394f",
+        "ffffffff",
+        "00000001", "74",
+        "00000004", "63346134",
+        "00000003", "6d2f30",
+        // index 258 with an EVM address
+        "0008",
+        "00000008", "0000000000000102",
+        "00000008", "636f736d6f733161",
+        "00000004", "30786162",
+        "00000004", "416b394f",
+        "ffffffff",
+        "00000001", "74",
+        "00000004", "63346134",
+        "00000003", "6d2f30",
+        // End of data
+        "ffff",
+    );
+    assert_eq!(hex::encode(encode_copy(&wallets).unwrap()), expected);
+}
+
+#[test]
+fn empty_copy_streams_are_header_and_trailer() {
+    assert_eq!(hex::encode(encode_copy(&[]).unwrap()), "5047434f50590aff0d0a000000000000000000ffff");
+}
+
+#[test]
+fn indices_beyond_int8_are_rejected() {
+    assert!(encode_copy(&[wallet(u64::MAX, None)]).is_err());
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::try_generate_from;
+use wallet_generator::bitcoin::Network;
+use wallet_generator::sign::wallet_private_key;
+use wallet_generator::types::{BitcoinAddress, KeyType, PrivateKeyFormat};
+use wallet_generator::{DerivationConfig, Wallet};
+
+const COSMOS_KEY: &str = "c4a48e2fce1481cd3294b4490f6678090ea98d3d0e5cd984558ab0968741b104";
+
+fn generate(mut config: DerivationConfig, format: PrivateKeyFormat) -> Vec<Wallet> {
+    config.private_key_format = format;
+    common::generate(&config, 2)
+}
+
+#[test]
+fn base64_encodes_the_same_key() {
+    let wallets = generate(DerivationConfig::new("cosmos", KeyType::Secp256k1), PrivateKeyFormat::Base64);
+    assert_eq!(general_purpose::STANDARD.decode(&wallets[0].private_key).unwrap(), hex::decode(COSMOS_KEY).unwrap());
+}
+
+#[test]
+fn wif_matches_bitcoin_wif_field() {
+    let wallets = generate(DerivationConfig::bitcoin(Network::Mainnet, BitcoinAddress::P2wpkh), PrivateKeyFormat::Wif);
+    assert_eq!(wallets[0].private_key, "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d");
+    assert!(wallets.iter().all(|wallet| wallet.wif.as_ref() == Some(&wallet.private_key)));
+
+    let testnet = generate(DerivationConfig::bitcoin(Network::Testnet, BitcoinAddress::P2wpkh), PrivateKeyFormat::Wif);
+    assert!(testnet[0].private_key.starts_with('c'));
+}
+
+#[test]
+fn cosmos_wif_uses_mainnet_version() {
+    let wallets = generate(DerivationConfig::new("cosmos", KeyType::Secp256k1), PrivateKeyFormat::Wif);
+    assert!(wallets[0].private_key.starts_with(['K', 'L']));
+}
+
+#[test]
+fn solana_has_no_wif() {
+    let mut config = DerivationConfig::solana();
+    config.private_key_format = PrivateKeyFormat::Wif;
+    assert!(try_generate_from(&config, 0, 1).is_err());
+}
+
+#[test]
+fn every_format_reads_back() {
+    for format in [PrivateKeyFormat::Hex, PrivateKeyFormat::Wif, PrivateKeyFormat::Base64] {
+        let wallets = generate(DerivationConfig::new("cosmos", KeyType::Secp256k1), format);
+        assert_eq!(hex::encode(wallet_private_key(&wallets[0]).unwrap()), COSMOS_KEY, "{:?}", format);
+    }
+}
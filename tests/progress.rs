@@ -0,0 +1,31 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::seed;
+use wallet_generator::bitcoin::Network;
+use wallet_generator::types::{BitcoinAddress, KeyType};
+use wallet_generator::{generate_wallets_batch, DerivationConfig};
+
+/// The progress counter a batch of `count` wallets leaves behind.
+fn counted(config: &DerivationConfig, count: usize) -> usize {
+    let progress = Arc::new(AtomicUsize::new(0));
+    generate_wallets_batch(&seed(), 0, count, config, progress.clone()).unwrap();
+    progress.load(Ordering::Relaxed)
+}
+
+#[test]
+fn batches_count_every_wallet_once() {
+    let cosmos = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    for count in [0, 1, 999, 1000, 1001, 2500] {
+        assert_eq!(counted(&cosmos, count), count);
+    }
+    for config in [
+        DerivationConfig::new("evmos", KeyType::Ethsecp256k1),
+        DerivationConfig::bitcoin(Network::Mainnet, BitcoinAddress::P2wpkh),
+        DerivationConfig::solana(),
+    ] {
+        assert_eq!(counted(&config, 1500), 1500);
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use secp256k1::PublicKey;
+use wallet_generator::types::{KeyType, PubkeyFormat};
+use wallet_generator::{DerivationConfig, GeneratorError, Wallet};
+
+const COMPRESSED: &str = "024f4e2ad99c34d60b9ba6283c9431a8418af8673212961f97a77b6377fcd05b62";
+
+fn generate(mut config: DerivationConfig, format: PubkeyFormat, include_uncompressed: bool) -> Result<Vec<Wallet>, GeneratorError> {
+    config.pubkey_format = format;
+    config.include_uncompressed = include_uncompressed;
+    common::try_generate_from(&config, 0, 2)
+}
+
+fn cosmos() -> DerivationConfig {
+    DerivationConfig::new("cosmos", KeyType::Secp256k1)
+}
+
+#[test]
+fn formats_encode_the_same_key() {
+    let base64 = generate(cosmos(), PubkeyFormat::Base64, false).unwrap();
+    assert_eq!(hex::encode(general_purpose::STANDARD.decode(&base64[0].pubkey).unwrap()), COMPRESSED);
+    assert!(base64[0].pubkey_uncompressed.is_none());
+
+    let hex = generate(cosmos(), PubkeyFormat::Hex, false).unwrap();
+    assert_eq!(hex[0].pubkey, COMPRESSED);
+
+    let uncompressed = generate(cosmos(), PubkeyFormat::UncompressedHex, false).unwrap();
+    let key = PublicKey::from_slice(&hex::decode(&uncompressed[0].pubkey).unwrap()).unwrap();
+    assert_eq!(uncompressed[0].pubkey.len(), 130);
+    assert_eq!(hex::encode(key.serialize()), COMPRESSED);
+    // pubkeyHex stays compressed whatever the pubkey format
+    assert_eq!(uncompressed[0].pubkey_hex.as_deref(), Some(COMPRESSED));
+}
+
+#[test]
+fn include_uncompressed_adds_both_forms() {
+    let wallets = generate(DerivationConfig::new("evmos", KeyType::Ethsecp256k1), PubkeyFormat::Base64, true).unwrap();
+    for wallet in &wallets {
+        let uncompressed = wallet.pubkey_uncompressed.as_deref().unwrap();
+        assert!(uncompressed.starts_with("04"));
+        let key = PublicKey::from_slice(&hex::decode(uncompressed).unwrap()).unwrap();
+        assert_eq!(general_purpose::STANDARD.encode(key.serialize()), wallet.pubkey);
+    }
+
+    let record = serde_json::to_value(&wallets[0]).unwrap();
+    assert_eq!(record["pubkeyUncompressed"].as_str(), wallets[0].pubkey_uncompressed.as_deref());
+}
+
+#[test]
+fn solana_has_no_uncompressed_form() {
+    assert!(generate(DerivationConfig::solana(), PubkeyFormat::UncompressedHex, false).is_err());
+    assert!(generate(DerivationConfig::solana(), PubkeyFormat::Base64, true).is_err());
+
+    let hex = generate(DerivationConfig::solana(), PubkeyFormat::Hex, false).unwrap();
+    assert_eq!(Some(&hex[0].pubkey), hex[0].pubkey_hex.as_ref());
+}
+
+#[test]
+fn sdk_json_wraps_the_key_with_its_type_url() {
+    let wallet = common::generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1).remove(0);
+    let record = wallet.to_sdk_json(KeyType::Secp256k1.pubkey_type_url());
+    assert_eq!(record["pubkey"]["@type"], "/ethermint.crypto.v1.ethsecp256k1.PubKey");
+    assert_eq!(record["pubkey"]["key"], wallet.pubkey.as_str());
+    assert_eq!(record["address"], wallet.address.as_str());
+
+    // The object form reads back as the bare key
+    let read: Wallet = serde_json::from_value(record).unwrap();
+    assert_eq!(read.pubkey, wallet.pubkey);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn sdk_json_output_from_the_binary() {
+    let dir = common::temp_dir("sdk-json");
+    let output = common::run_cli(&dir, &["-c", "2", "-o", "-", "--pubkey-format", "sdk-json"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let wallets = common::generate(&cosmos(), 2);
+    let records: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(records.len(), 2);
+    for (record, wallet) in records.iter().zip(&wallets) {
+        assert_eq!(record["pubkey"], serde_json::json!({ "@type": "/cosmos.crypto.secp256k1.PubKey", "key": wallet.pubkey }));
+    }
+
+    // CSV has no room for the object
+    let output = common::run_cli(&dir, &["-c", "2", "-o", "w.csv", "--output-format", "csv", "--pubkey-format", "sdk-json"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("needs a JSON output format"));
+}
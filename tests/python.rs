@@ -0,0 +1,84 @@
+#![cfg(all(feature = "python", unix))]
+
+mod common;
+
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{generate_from, MNEMONIC};
+use serde_json::Value;
+use wallet_generator::{DerivationConfig, KeyType};
+
+/// Run `script` in a Python interpreter that imports this build's extension module as `wf`,
+/// returning what it prints as JSON.
+fn run_python(script: &str) -> Value {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    // The cdylib is built next to the test binary; Python imports it under the module's name
+    let deps = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let library = deps.join(format!("{}wallet_generator{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX));
+    let dir = std::env::temp_dir().join(format!("wallet-factory-python-{}-{}", std::process::id(), CALLS.fetch_add(1, Ordering::Relaxed)));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(&library, dir.join("wallet_factory.so")).unwrap();
+
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(format!("import json\nimport wallet_factory as wf\nMNEMONIC = {:?}\n{}", MNEMONIC, script))
+        .env("PYTHONPATH", &dir)
+        .output()
+        .expect("python3 runs");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+
+#[test]
+fn wallets_match_the_cli_records() {
+    let wallets = run_python(
+        r#"print(json.dumps(wf.generate_wallets(MNEMONIC, 2, start_index=1, prefix="evmos", key_type="ethsecp256k1")))"#,
+    );
+    let expected = generate_from(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1, 2);
+    assert_eq!(wallets, serde_json::to_value(expected).unwrap());
+
+    let defaults = run_python("print(json.dumps(wf.generate_wallets(MNEMONIC, 1)))");
+    assert_eq!(defaults[0]["address"], "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+}
+
+#[test]
+fn addresses_of_a_private_key() {
+    let wallet = generate_from(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 0, 1).remove(0);
+    let derived = run_python(&format!(r#"print(json.dumps(wf.derive_addresses("0x{}", "evmos", "ethsecp256k1")))"#, wallet.private_key));
+    assert_eq!(derived["address"], wallet.address.as_str());
+    assert_eq!(derived["evmAddress"], wallet.evm_address.unwrap().as_str());
+    assert_eq!(derived["pubkey"], wallet.pubkey.as_str());
+    assert_eq!(derived["privateKey"], wallet.private_key.as_str());
+}
+
+#[test]
+fn bad_input_raises_value_error() {
+    let errors = run_python(
+        r#"
+calls = [
+    lambda: wf.generate_wallets("abandon about", 1),
+    lambda: wf.generate_wallets(MNEMONIC, 1, key_type="ed448"),
+    lambda: wf.generate_wallets(MNEMONIC, 1, prefix="Cosmos Hub"),
+    lambda: wf.derive_addresses("abcd"),
+    lambda: wf.derive_addresses("00" * 32),
+]
+errors = []
+for call in calls:
+    try:
+        call()
+        errors.append(None)
+    except ValueError as e:
+        errors.append(str(e))
+print(json.dumps(errors))
+"#,
+    );
+    let errors: Vec<String> = serde_json::from_value(errors).unwrap();
+    assert!(errors[0].starts_with("Invalid mnemonic"), "{}", errors[0]);
+    assert_eq!(errors[1], "Unknown key type: ed448");
+    assert!(errors[2].contains("Cosmos Hub"), "{}", errors[2]);
+    assert_eq!(errors[3], "Private key must be 32 bytes");
+    assert!(errors[4].contains("secret key"), "{}", errors[4]);
+}
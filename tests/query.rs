@@ -0,0 +1,71 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use common::generate;
+use wallet_generator::query::{build_index, find_records, index_path, RecordKey, WalletIndex};
+use wallet_generator::types::{KeyType, OutputFormat};
+use wallet_generator::wallet_file::WalletWriter;
+use wallet_generator::{DerivationConfig, Wallet};
+
+/// Write `wallets` in `format` to a temporary file named `name`.
+fn wallet_file(name: &str, wallets: &[Wallet], format: OutputFormat) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("wallet-generator-query-{}-{}", std::process::id(), name));
+    let mut writer = WalletWriter::new(Vec::new(), format, None).unwrap();
+    wallets.iter().for_each(|wallet| writer.write(wallet).unwrap());
+    fs::write(&path, writer.finish().unwrap()).unwrap();
+    let _ = fs::remove_file(index_path(&path));
+    path
+}
+
+fn addresses(wallets: &[Wallet]) -> Vec<&str> {
+    wallets.iter().map(|wallet| wallet.address.as_str()).collect()
+}
+
+#[test]
+fn records_are_found_by_every_key_with_and_without_an_index() {
+    let wallets = generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 40);
+    for format in [OutputFormat::Ndjson, OutputFormat::Json] {
+        let path = wallet_file(&format!("{:?}", format), &wallets, format);
+        for use_index in [false, true] {
+            if use_index {
+                assert_eq!(build_index(&path).unwrap(), 40);
+                assert!(WalletIndex::open(&path).unwrap().is_some());
+            }
+            let found = find_records(&path, &RecordKey::Address(wallets[17].address.clone()), use_index).unwrap();
+            assert_eq!(addresses(&found), [wallets[17].address.as_str()]);
+
+            // EVM addresses match in any case
+            let evm = wallets[3].evm_address.clone().unwrap().to_ascii_uppercase().replace("0X", "0x");
+            assert_eq!(addresses(&find_records(&path, &RecordKey::EvmAddress(evm), use_index).unwrap()), [wallets[3].address.as_str()]);
+
+            assert_eq!(addresses(&find_records(&path, &RecordKey::Index(39), use_index).unwrap()), [wallets[39].address.as_str()]);
+            assert!(find_records(&path, &RecordKey::Index(40), use_index).unwrap().is_empty());
+            assert!(find_records(&path, &RecordKey::Address("evmos1missing".to_string()), use_index).unwrap().is_empty());
+        }
+        fs::remove_file(index_path(&path)).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn indexes_of_changed_files_are_ignored() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 12);
+    let path = wallet_file("stale", &wallets[..10], OutputFormat::Ndjson);
+    build_index(&path).unwrap();
+
+    // Records appended after indexing are still found, by a scan
+    let mut writer = WalletWriter::new(Vec::new(), OutputFormat::Ndjson, None).unwrap();
+    wallets[10..].iter().for_each(|wallet| writer.write(wallet).unwrap());
+    let mut contents = fs::read(&path).unwrap();
+    contents.extend(writer.finish().unwrap());
+    fs::write(&path, contents).unwrap();
+
+    assert!(WalletIndex::open(&path).unwrap().is_none());
+    let found = find_records(&path, &RecordKey::Address(wallets[11].address.clone()), true).unwrap();
+    assert_eq!(addresses(&found), [wallets[11].address.as_str()]);
+
+    fs::remove_file(index_path(&path)).unwrap();
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicUsize;
+use std::time::Duration;
+
+use common::{seed, serve_http};
+use serde_json::json;
+use wallet_generator::coin::Coin;
+use wallet_generator::derive_wallet;
+use wallet_generator::lcd::LcdClient;
+use wallet_generator::recover::{recover, RecoverConfig};
+use wallet_generator::types::KeyType;
+
+const TEMPLATE: &str = "m/44'/118'/{account}'/0/{index}";
+
+fn address(account: u64, index: u64) -> String {
+    derive_wallet(&seed(), &format!("m/44'/118'/{}'/0/{}", account, index), "cosmos", &KeyType::Secp256k1).unwrap().address
+}
+
+/// Serve a chain where the given addresses have an auth account (`Some(number)`) and/or a
+/// balance; every other address is unused.
+fn serve_chain(used: HashMap<String, (Option<u64>, u128)>) -> String {
+    serve_http(move |request| {
+        let address = request.target.rsplit('/').next().unwrap().split('?').next().unwrap();
+        let (account, amount) = used.get(address).copied().unwrap_or((None, 0));
+        if request.target.starts_with("/cosmos/auth/v1beta1/accounts/") {
+            match account {
+                Some(number) => {
+                    let account = json!({"@type": "/cosmos.auth.v1beta1.BaseAccount", "address": address, "account_number": number.to_string()});
+                    ("200 OK", json!({ "account": account }).to_string())
+                }
+                None => ("404 Not Found", r#"{"code":5,"message":"not found"}"#.to_string()),
+            }
+        } else {
+            ("200 OK", json!({"balances": [{"denom": "uatom", "amount": amount.to_string()}], "pagination": {}}).to_string())
+        }
+    })
+}
+
+fn config(templates: &[String], gap_limit: u64) -> RecoverConfig<'_> {
+    RecoverConfig { templates, prefix: "cosmos", key_type: &KeyType::Secp256k1, gap_limit, max_accounts: 5, concurrency: 2 }
+}
+
+#[test]
+fn accounts_are_scanned_until_the_gap_limit() {
+    let used = HashMap::from([
+        (address(0, 0), (Some(3), 0)),
+        // Funded before its auth account exists
+        (address(0, 3), (None, 10)),
+        // Beyond three unused indices after 3
+        (address(0, 7), (Some(4), 0)),
+        (address(1, 2), (Some(5), 20)),
+        // Account 2 is unused, so account 3 is never reached
+        (address(3, 0), (Some(6), 0)),
+    ]);
+    let client = LcdClient::new(&serve_chain(used), Duration::from_secs(5), 0);
+    let templates = [TEMPLATE.to_string()];
+    let progress = AtomicUsize::new(0);
+    let recovered = recover(&client, &seed(), &config(&templates, 3), &progress).unwrap();
+
+    let paths: Vec<&str> = recovered.iter().map(|account| account.wallet.derivation_path.as_str()).collect();
+    assert_eq!(paths, ["m/44'/118'/0'/0/0", "m/44'/118'/0'/0/3", "m/44'/118'/1'/0/2"]);
+    assert_eq!((recovered[0].account_number, recovered[0].sequence), (Some(3), Some(0)));
+    assert_eq!((recovered[1].account_number, recovered[1].balances.clone()), (None, vec![Coin::new(10, "uatom")]));
+    assert_eq!(recovered[2].wallet.address, address(1, 2));
+    // Account 0 checks 0..7, account 1 checks 0..6 and account 2 checks 0..3
+    assert_eq!(progress.into_inner(), 16);
+
+    // A wider gap finds index 7 too
+    let recovered = recover(&client, &seed(), &config(&templates, 4), &AtomicUsize::new(0)).unwrap();
+    assert!(recovered.iter().any(|account| account.wallet.address == address(0, 7)));
+}
+
+#[test]
+fn bad_configurations_fail() {
+    let client = LcdClient::new(&serve_chain(HashMap::new()), Duration::from_secs(5), 0);
+    let templates = [TEMPLATE.to_string()];
+    assert!(recover(&client, &seed(), &config(&templates, 0), &AtomicUsize::new(0)).is_err());
+
+    let templates = ["m/44'/118'/0'/0/0".to_string()];
+    let error = recover(&client, &seed(), &config(&templates, 3), &AtomicUsize::new(0)).unwrap_err();
+    assert!(error.to_string().contains("has neither"), "{}", error);
+
+    // An unused mnemonic recovers nothing
+    let templates = [TEMPLATE.to_string()];
+    assert!(recover(&client, &seed(), &config(&templates, 2), &AtomicUsize::new(0)).unwrap().is_empty());
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::generate;
+use wallet_generator::bitcoin::Network;
+use wallet_generator::rederive::rederive;
+use wallet_generator::types::{AddressEncoding, BitcoinAddress, KeyType, PrivateKeyFormat};
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn rederive_all(wallets: &[Wallet], config: &DerivationConfig) -> Vec<Wallet> {
+    wallets.iter().map(|wallet| rederive(wallet, config).unwrap()).collect()
+}
+
+fn assert_same(left: &[Wallet], right: &[Wallet]) {
+    assert_eq!(serde_json::to_value(left).unwrap(), serde_json::to_value(right).unwrap());
+}
+
+#[test]
+fn same_keys_under_another_prefix_and_hashing() {
+    let cosmos = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 5);
+
+    let osmo = DerivationConfig::new("osmo", KeyType::Secp256k1);
+    assert_same(&rederive_all(&cosmos, &osmo), &generate(&osmo, 5));
+
+    // Keccak addresses of the coin type 118 keys, not of the keys Ethermint wallets derive at 60
+    let evmos = DerivationConfig { coin_type: 118, ..DerivationConfig::new("evmos", KeyType::Ethsecp256k1) };
+    assert_same(&rederive_all(&cosmos, &evmos), &generate(&evmos, 5));
+
+    let bech32m = DerivationConfig { encoding: AddressEncoding::Bech32m, ..osmo };
+    assert_same(&rederive_all(&cosmos, &bech32m), &generate(&bech32m, 5));
+}
+
+#[test]
+fn reads_wif_and_base64_keys() {
+    let mut bitcoin = DerivationConfig::bitcoin(Network::Mainnet, BitcoinAddress::P2wpkh);
+    bitcoin.private_key_format = PrivateKeyFormat::Wif;
+    let wallets = generate(&bitcoin, 3);
+    assert_same(&rederive_all(&wallets, &bitcoin), &wallets);
+
+    let base64 = DerivationConfig { private_key_format: PrivateKeyFormat::Base64, ..DerivationConfig::new("cosmos", KeyType::Secp256k1) };
+    let cosmos = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 3);
+    let encoded = rederive_all(&cosmos, &base64);
+    assert_same(&encoded, &generate(&base64, 3));
+    assert_same(&rederive_all(&encoded, &DerivationConfig::new("cosmos", KeyType::Secp256k1)), &cosmos);
+}
+
+#[test]
+fn rejects_mismatched_and_ed25519_keys() {
+    let cosmos = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    let mut wallets = generate(&cosmos, 2);
+    wallets[0].pubkey_hex = wallets[1].pubkey_hex.clone();
+    assert!(rederive(&wallets[0], &cosmos).is_err());
+    assert!(rederive(&wallets[1], &DerivationConfig::solana()).is_err());
+
+    let solana = generate(&DerivationConfig::solana(), 1);
+    assert!(rederive(&solana[0], &cosmos).is_err());
+}
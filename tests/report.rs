@@ -0,0 +1,56 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{run_cli, temp_dir};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+#[test]
+fn report_json_describes_the_run_and_its_output() {
+    let dir = temp_dir("report");
+    let output = run_cli(&dir, &["-c", "3", "-o", "out/wallets.json", "--report-json", "out/report.json", "-p", "osmo", "-q"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let written = std::fs::read(dir.join("out/wallets.json")).unwrap();
+    let wallets: Vec<Value> = serde_json::from_slice(&written).unwrap();
+    let expected = common::generate(&DerivationConfig::new("osmo", KeyType::Secp256k1), 3);
+    assert!(wallets.iter().zip(&expected).all(|(record, wallet)| record["address"] == wallet.address.as_str()));
+
+    let report: Value = serde_json::from_slice(&std::fs::read(dir.join("out/report.json")).unwrap()).unwrap();
+    assert_eq!(report["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(report["wallets"], 3);
+    assert_eq!(report["output"], "out/wallets.json");
+    assert_eq!(report["outputBytes"], written.len());
+    assert_eq!(report["outputSha256"], hex::encode(Sha256::digest(&written)));
+    assert!(report["walletsPerSec"].as_f64().unwrap() > 0.0);
+    assert!(report["totalSecs"].as_f64().unwrap() >= report["generationSecs"].as_f64().unwrap());
+    assert!(report.get("matched").is_none());
+
+    let parameters = &report["parameters"];
+    assert_eq!(parameters["prefix"], "osmo");
+    assert_eq!(parameters["keyType"], "secp256k1");
+    assert_eq!(parameters["derivationPath"], "m/44'/118'/0'/0/{index}");
+    assert_eq!(parameters["defaultMnemonic"], true);
+}
+
+#[test]
+fn quiet_silences_logs_and_the_summary() {
+    let dir = temp_dir("quiet");
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json"], "");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Summary:") && stdout.contains("Wallets generated: 2"), "{}", stdout);
+    assert!(!output.stderr.is_empty());
+
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json", "--quiet"], "");
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stdout.is_empty());
+
+    // Errors still get through
+    let output = run_cli(&dir, &["-c", "2", "-o", "wallets.json", "--quiet", "-p", "Not A Prefix"], "");
+    assert!(!output.status.success());
+    assert!(!output.stderr.is_empty());
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use std::path::Path;
+
+use common::generate;
+use sha2::{Digest, Sha256};
+use wallet_generator::rotate::{manifest_path, parse_size, part_path, Manifest, ManifestPart, PartCheck, Rotation};
+use wallet_generator::types::{KeyType, OutputFormat};
+use wallet_generator::wallet_file::write_json_array;
+use wallet_generator::DerivationConfig;
+
+#[test]
+fn sizes_parse_with_decimal_and_binary_units() {
+    assert_eq!(parse_size("1GB").unwrap(), 1_000_000_000);
+    assert_eq!(parse_size("1 gb").unwrap(), 1_000_000_000);
+    assert_eq!(parse_size("512MiB").unwrap(), 512 << 20);
+    assert_eq!(parse_size("1.5KiB").unwrap(), 1536);
+    assert_eq!(parse_size("4096").unwrap(), 4096);
+    assert!(parse_size("1XB").is_err());
+    assert!(parse_size("GB").is_err());
+    assert!(parse_size("0MB").is_err());
+}
+
+#[test]
+fn parts_are_numbered_beside_the_output() {
+    assert_eq!(part_path("data/wallets.json", 1), Path::new("data/wallets.00001.json"));
+    assert_eq!(part_path("wallets.tar.csv", 12), Path::new("wallets.tar.00012.csv"));
+    assert_eq!(part_path("wallets", 3), Path::new("wallets.00003"));
+    assert_eq!(manifest_path("data/wallets.json"), Path::new("data/wallets.manifest.json"));
+}
+
+#[test]
+fn files_fill_up_to_the_limits() {
+    let every = Rotation { records: Some(10), bytes: None };
+    assert!(!every.is_full(9, 1 << 30, 300));
+    assert!(every.is_full(10, 0, 300));
+
+    // The next record, as wide as the widest so far, must fit with a margin to spare
+    let size = Rotation { records: None, bytes: Some(1000) };
+    assert!(!size.is_full(2, 600, 300));
+    assert!(size.is_full(3, 900, 300));
+    // A file always takes its first record, however large
+    assert!(!size.is_full(0, 5000, 5000));
+    assert!(!Rotation::default().is_full(u64::MAX, u64::MAX / 2, 300));
+}
+
+#[test]
+fn manifest_round_trips_and_hashes_what_it_wrote() {
+    let mut manifest = Manifest::new(OutputFormat::Ndjson);
+    for (part, first_index) in [(1, 0), (2, 100)] {
+        manifest.push(ManifestPart {
+            path: part_path("wallets.ndjson", part).display().to_string(),
+            records: 100,
+            first_index,
+            bytes: 40_000,
+            sha256: "00".repeat(32),
+        });
+    }
+    assert_eq!((manifest.wallets, manifest.bytes()), (200, 80_000));
+
+    let path = std::env::temp_dir().join(format!("wallet-generator-rotate-{}.manifest.json", std::process::id()));
+    let sha256 = manifest.write(&path).unwrap();
+    assert_eq!(sha256, hex::encode(Sha256::digest(std::fs::read(&path).unwrap())));
+    assert_eq!(Manifest::read(&path).unwrap(), manifest);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn check_finds_changed_and_missing_files() {
+    let dir = std::env::temp_dir().join(format!("wallet-generator-verify-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 25);
+    write_json_array(dir.join("wallets.json"), &wallets, None).unwrap();
+    let contents = std::fs::read(dir.join("wallets.json")).unwrap();
+    let part = ManifestPart {
+        path: "wallets.json".to_string(),
+        records: 25,
+        first_index: 0,
+        bytes: contents.len() as u64,
+        sha256: hex::encode(Sha256::digest(&contents)),
+    };
+
+    let found = part.check(&dir, OutputFormat::Json).unwrap().unwrap();
+    assert_eq!(found, PartCheck { bytes: part.bytes, sha256: part.sha256.clone(), records: Some(25) });
+    assert!(part.differences(&found).is_empty());
+
+    // A manifest edited to claim other records is caught by reading the file back
+    let edited = ManifestPart { records: 26, ..part.clone() };
+    assert_eq!(edited.differences(&found), ["25 records, expected 26"]);
+
+    let mut corrupted = contents.clone();
+    corrupted[100] ^= 1;
+    std::fs::write(dir.join("wallets.json"), &corrupted).unwrap();
+    let found = part.check(&dir, OutputFormat::Json).unwrap().unwrap();
+    assert_eq!(found.records, None);
+    assert_eq!(part.differences(&found), ["SHA-256 differs"]);
+
+    std::fs::remove_file(dir.join("wallets.json")).unwrap();
+    assert_eq!(part.check(&dir, OutputFormat::Json).unwrap(), None);
+    std::fs::remove_dir(&dir).unwrap();
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::sync::atomic::AtomicUsize;
+use std::time::Duration;
+
+use common::{generate, serve_http};
+use wallet_generator::coin::Coin;
+use wallet_generator::lcd::LcdClient;
+use wallet_generator::scan::scan_balances;
+use wallet_generator::types::KeyType;
+use wallet_generator::DerivationConfig;
+
+/// Serve balances: the first address over two pages, the second as unknown to the bank module,
+/// and anything else as a server error.
+fn serve_balances(funded: String, unknown: String) -> String {
+    serve_http(move |request| {
+        if request.target.contains(&funded) && request.target.contains("pagination.key=page2") {
+            ("200 OK", r#"{"balances":[{"denom":"ustake","amount":"0"}],"pagination":{"next_key":null}}"#.to_string())
+        } else if request.target.contains(&funded) {
+            ("200 OK", r#"{"balances":[{"denom":"uatom","amount":"5"}],"pagination":{"next_key":"page2"}}"#.to_string())
+        } else if request.target.contains(&unknown) {
+            ("404 Not Found", r#"{"code":5,"message":"not found"}"#.to_string())
+        } else {
+            ("500 Internal Server Error", r#"{"code":13,"message":"internal"}"#.to_string())
+        }
+    })
+}
+
+#[test]
+fn balances_follow_pagination_and_failures_stay_per_record() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 3);
+    let node = serve_balances(wallets[0].address.clone(), wallets[1].address.clone());
+    let client = LcdClient::new(&node, Duration::from_secs(5), 0);
+
+    let progress = AtomicUsize::new(0);
+    let scanned = scan_balances(&client, wallets.clone(), 2, &progress).unwrap();
+    assert_eq!(progress.into_inner(), 3);
+    let addresses: Vec<_> = scanned.iter().map(|w| w.wallet.address.clone()).collect();
+    assert_eq!(addresses, wallets.iter().map(|w| w.address.clone()).collect::<Vec<_>>());
+
+    assert_eq!(scanned[0].balances, Some(vec![Coin::new(5, "uatom"), Coin::new(0, "ustake")]));
+    assert!(scanned[0].is_funded(None) && scanned[0].is_funded(Some("uatom")));
+    assert!(!scanned[0].is_funded(Some("ustake")));
+
+    assert_eq!(scanned[1].balances, Some(vec![]));
+    assert!(!scanned[1].is_funded(None) && scanned[1].error.is_none());
+
+    assert_eq!(scanned[2].balances, None);
+    assert!(!scanned[2].is_funded(None));
+    assert!(scanned[2].error.as_deref().is_some_and(|error| error.contains("500")));
+
+    // Records are the wallet with its balances, or its error
+    let record = serde_json::to_value(&scanned[0]).unwrap();
+    assert_eq!(record["address"], wallets[0].address.as_str());
+    assert_eq!(record["balances"][0], serde_json::json!({"denom": "uatom", "amount": "5"}));
+    let record = serde_json::to_value(&scanned[2]).unwrap();
+    assert!(record.get("balances").is_none() && record["privateKey"] == wallets[2].private_key.as_str());
+}
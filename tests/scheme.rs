@@ -0,0 +1,50 @@
+mod common;
+
+use wallet_generator::derive_wallet;
+use wallet_generator::types::{DerivationScheme, KeyType};
+use wallet_generator::DerivationConfig;
+
+fn ledger_live(key_type: KeyType) -> DerivationConfig {
+    DerivationConfig { scheme: DerivationScheme::LedgerLive, ..DerivationConfig::new("cosmos", key_type) }
+}
+
+#[test]
+fn ledger_live_increments_the_account() {
+    for key_type in [KeyType::Secp256k1, KeyType::Ethsecp256k1] {
+        let coin_type = key_type.coin_type();
+        let wallets = common::generate(&ledger_live(key_type.clone()), 3);
+        let standard = common::generate(&DerivationConfig::new("cosmos", key_type.clone()), 2);
+        for (wallet, account) in wallets.iter().zip(0..) {
+            let path = format!("m/44'/{coin_type}'/{account}'/0/0");
+            assert_eq!(wallet.derivation_path, path);
+            assert_eq!(wallet.index, account);
+            assert_eq!(wallet.address, derive_wallet(&common::seed(), &path, "cosmos", &key_type).unwrap().address);
+        }
+        // Both schemes share the first wallet only
+        assert_eq!(wallets[0].address, standard[0].address);
+        assert_ne!(wallets[1].address, standard[1].address);
+    }
+}
+
+#[test]
+fn ledger_live_accounts_stay_below_the_hardened_range() {
+    assert!(common::try_generate_from(&ledger_live(KeyType::Secp256k1), (1 << 31) - 1, 1).is_ok());
+    assert!(common::try_generate_from(&ledger_live(KeyType::Secp256k1), 1 << 31, 1).is_err());
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn scheme_flag_selects_ledger_live() {
+    let dir = common::temp_dir("scheme");
+    let output = common::run_cli(&dir, &["-c", "2", "-o", "-", "--scheme", "ledger-live"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let paths: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["derivationPath"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(paths, ["m/44'/118'/0'/0/0", "m/44'/118'/1'/0/0"]);
+
+    // An explicit path already says which level increments
+    let output = common::run_cli(&dir, &["-c", "1", "-o", "-", "--scheme", "ledger-live", "--path", "m/44'/118'/0'/0/0"], "");
+    assert!(!output.status.success());
+}
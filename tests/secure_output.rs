@@ -0,0 +1,93 @@
+#[cfg(unix)]
+use std::fs;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use wallet_generator::secure_output::{parse_umask, OutputPermissions};
+#[cfg(unix)]
+use wallet_generator::wallet_file::write_json_array;
+
+#[cfg(unix)]
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("wallet-generator-secure-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn umasks_parse_as_octal() {
+    assert_eq!(parse_umask("077").unwrap(), 0o077);
+    assert_eq!(parse_umask("0027").unwrap(), 0o027);
+    assert_eq!(parse_umask("0o22").unwrap(), 0o022);
+    assert!(parse_umask("089").is_err());
+    assert!(parse_umask("1777").is_err());
+    assert!(parse_umask("").is_err());
+
+    let permissions = OutputPermissions { umask: 0o027, insecure: false };
+    assert_eq!((permissions.file_mode(), permissions.dir_mode()), (0o640, 0o750));
+    assert!(!permissions.owner_only());
+    assert!(OutputPermissions::default().owner_only());
+}
+
+#[cfg(unix)]
+#[test]
+fn key_files_are_owner_only_whatever_they_replace() {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = |path: &PathBuf| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+    let root = temp_path("files");
+    let path = root.join("nested/wallets.json");
+    let permissions = OutputPermissions::default();
+    permissions.create(&path).unwrap();
+    assert_eq!(mode(&path), 0o600);
+    assert_eq!(mode(&root.join("nested")), 0o700);
+
+    // A file that already existed loses its looser mode
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    permissions.create(&path).unwrap();
+    assert_eq!(mode(&path), 0o600);
+
+    OutputPermissions { umask: 0o027, insecure: false }.create(&path).unwrap();
+    assert_eq!(mode(&path), 0o640);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn directories_others_can_read_are_refused() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = temp_path("dir");
+    fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("wallets.json");
+    let permissions = OutputPermissions::default();
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+    let error = permissions.check_directory(&output).unwrap_err().to_string();
+    assert!(error.contains("--insecure-output"), "{error}");
+    assert!(OutputPermissions { insecure: true, ..permissions }.check_directory(&output).is_ok());
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o750)).unwrap();
+    assert!(permissions.check_directory(&output).is_ok());
+    // Missing directories are created private
+    assert!(permissions.check_directory(&dir.join("later/wallets.json")).is_ok());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn key_bearing_json_arrays_are_owner_only() {
+    use std::os::unix::fs::PermissionsExt;
+    // As scan-balances, annotate and recover write their records, private keys included
+    let dir = temp_path("records");
+    fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("scanned.json");
+    let records = [serde_json::json!({"address": "cosmos1...", "privateKey": "00".repeat(32)})];
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+    let error = write_json_array(&output, &records, Some(&OutputPermissions::default())).unwrap_err().to_string();
+    assert!(error.contains("--insecure-output"), "{error}");
+    assert!(!output.exists());
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).unwrap();
+    write_json_array(&output, &records, Some(&OutputPermissions::default())).unwrap();
+    assert_eq!(fs::metadata(&output).unwrap().permissions().mode() & 0o777, 0o600);
+    fs::remove_dir_all(&dir).unwrap();
+}
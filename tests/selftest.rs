@@ -0,0 +1,18 @@
+use wallet_generator::selftest::{self, VECTORS};
+
+#[test]
+fn derivations_match_published_wallets() {
+    let outcomes = selftest::run();
+    assert_eq!(outcomes.len(), VECTORS.len());
+    for outcome in outcomes {
+        let vector = outcome.vector;
+        assert!(outcome.passed(), "{} index {}: {:?} {:?}", vector.chain, vector.index, outcome.error, outcome.mismatches);
+    }
+}
+
+#[test]
+fn covers_cosmos_hub_osmosis_and_evmos() {
+    for chain in ["cosmoshub", "osmosis", "evmos"] {
+        assert!(VECTORS.iter().any(|vector| vector.chain == chain), "{}", chain);
+    }
+}
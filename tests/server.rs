@@ -0,0 +1,87 @@
+#![cfg(feature = "server")]
+
+mod common;
+
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use common::{seed, MNEMONIC};
+use serde_json::{json, Value};
+use wallet_generator::derivation::extended_keys;
+use wallet_generator::server::{self, ServerConfig};
+
+/// Serve on a free loopback port and return its base URL.
+fn start(max_jobs: usize, max_pending_jobs: usize) -> String {
+    let listen: SocketAddr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let config = ServerConfig { listen, auth_token: None, max_job_size: 1_000_000, max_jobs, max_pending_jobs, job_ttl: Duration::from_secs(60) };
+    std::thread::spawn(move || tokio::runtime::Runtime::new().unwrap().block_on(server::serve(config)).unwrap());
+    while TcpStream::connect(listen).is_err() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    format!("http://{}", listen)
+}
+
+/// Status and JSON body of a POST, error statuses included.
+fn post(url: &str, body: Value) -> (u16, Value) {
+    match ureq::post(url).send_json(body) {
+        Ok(response) => (response.status(), response.into_json().unwrap()),
+        Err(ureq::Error::Status(status, response)) => (status, response.into_json().unwrap()),
+        Err(e) => panic!("{}", e),
+    }
+}
+
+#[test]
+fn derive_answers_from_a_mnemonic_or_an_xpub() {
+    let base = start(1, 4);
+    let (status, wallet) = post(&format!("{}/derive", base), json!({ "mnemonic": MNEMONIC, "index": 1 }));
+    assert_eq!(status, 200);
+    assert_eq!(wallet["address"], "cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz");
+    assert_eq!(wallet["derivationPath"], "m/44'/118'/0'/0/1");
+    assert!(wallet.get("privateKey").is_none());
+
+    let (xpub, _) = extended_keys(&seed(), "m/44'/118'/0'").unwrap();
+    let (status, watch_only) = post(&format!("{}/derive", base), json!({ "xpub": xpub, "index": 1 }));
+    assert_eq!(status, 200);
+    assert_eq!((&watch_only["address"], &watch_only["pubkey"]), (&wallet["address"], &wallet["pubkey"]));
+    assert_eq!(watch_only["derivationPath"], "0/1");
+
+    for body in [
+        json!({ "mnemonic": MNEMONIC, "xpub": xpub }),
+        json!({ "index": 1 }),
+        json!({ "xpub": xpub, "include_private_key": true }),
+        json!({ "xpub": xpub, "path": "0'/1" }),
+        json!({ "mnemonic": "abandon abandon abandon" }),
+    ] {
+        let (status, error) = post(&format!("{}/derive", base), body.clone());
+        assert_eq!(status, 400, "{}", body);
+        assert!(error["error"].is_string());
+    }
+}
+
+#[test]
+fn generate_refuses_jobs_beyond_the_pending_cap() {
+    let base = start(1, 1);
+    let (status, job) = post(&format!("{}/generate", base), json!({ "mnemonic": MNEMONIC, "count": 200_000 }));
+    assert_eq!(status, 202);
+    let (status, error) = post(&format!("{}/generate", base), json!({ "mnemonic": MNEMONIC, "count": 1 }));
+    assert_eq!(status, 429);
+    assert!(error["error"].as_str().unwrap().contains("already queued or running"));
+
+    // Discarding the job frees its place
+    ureq::delete(&format!("{}/jobs/{}", base, job["id"].as_str().unwrap())).call().unwrap();
+    let (status, _) = post(&format!("{}/generate", base), json!({ "mnemonic": MNEMONIC, "count": 1 }));
+    assert_eq!(status, 202);
+}
+
+#[test]
+fn finished_jobs_return_their_wallets() {
+    let base = start(2, 4);
+    let (_, job) = post(&format!("{}/generate", base), json!({ "mnemonic": MNEMONIC, "count": 3, "start_index": 1 }));
+    let status_url = format!("{}/jobs/{}", base, job["id"].as_str().unwrap());
+    while ureq::get(&status_url).call().unwrap().into_json::<Value>().unwrap()["state"] != "completed" {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let wallets: Value = ureq::get(&format!("{}/wallets", status_url)).call().unwrap().into_json().unwrap();
+    assert_eq!(wallets.as_array().unwrap().len(), 3);
+    assert_eq!(wallets[0]["address"], "cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz");
+}
@@ -0,0 +1,123 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use base64::{engine::general_purpose, Engine as _};
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message, PublicKey, Secp256k1};
+use wallet_generator::coin::Coin;
+use wallet_generator::lcd::AccountInfo;
+use wallet_generator::sign::{SendSpec, WalletSigner};
+use wallet_generator::signer::{Signer, SoftwareSigner};
+use wallet_generator::tx::{payload_digest, SignerData, Tx};
+use wallet_generator::types::KeyType;
+use wallet_generator::generate_addresses;
+
+const FUNDER_KEY: [u8; 32] = [7; 32];
+
+/// A backend that keeps its key out of reach, as an HSM would, and counts what it signs.
+struct CountingSigner {
+    inner: SoftwareSigner,
+    signed: Rc<Cell<usize>>,
+}
+
+impl Signer for CountingSigner {
+    fn key_type(&self) -> &KeyType {
+        self.inner.key_type()
+    }
+
+    fn public_key(&self) -> PublicKey {
+        self.inner.public_key()
+    }
+
+    fn sign(&self, payload: &[u8]) -> anyhow::Result<[u8; 64]> {
+        self.signed.set(self.signed.get() + 1);
+        self.inner.sign(payload)
+    }
+}
+
+fn send_spec() -> SendSpec {
+    SendSpec {
+        to_address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+        amount: vec![Coin::new(1, "uatom")],
+        gas_limit: 100_000,
+        fee: Vec::new(),
+        memo: String::new(),
+    }
+}
+
+fn assert_verifies(tx: &Tx, signer: &SignerData, public_key: &PublicKey, key_type: &KeyType) {
+    let digest = payload_digest(&tx.sign_doc_bytes(signer).unwrap(), key_type);
+    let signature = general_purpose::STANDARD.decode(&tx.signatures[0]).unwrap();
+    Secp256k1::verification_only()
+        .verify_ecdsa(&Message::from_digest(digest), &Signature::from_compact(&signature).unwrap(), public_key)
+        .unwrap();
+}
+
+#[test]
+fn address_matches_generated_wallets() {
+    for key_type in [KeyType::Secp256k1, KeyType::Ethsecp256k1] {
+        let signer = SoftwareSigner::new(&FUNDER_KEY, key_type.clone()).unwrap();
+        let (address, _, _, _) = generate_addresses(&FUNDER_KEY, "cosmos", &key_type).unwrap();
+        assert_eq!(signer.address("cosmos").unwrap(), address);
+    }
+}
+
+#[test]
+fn sign_with_matches_sign_direct() {
+    let signer_data = SignerData { chain_id: "test-1".to_string(), account_number: 3, sequence: 9 };
+    for key_type in [KeyType::Secp256k1, KeyType::Ethsecp256k1] {
+        let signer = SoftwareSigner::new(&FUNDER_KEY, key_type.clone()).unwrap();
+        let from = signer.address("cosmos").unwrap();
+
+        let mut direct = send_spec().build_tx(&from);
+        direct.sign_direct(&FUNDER_KEY, &key_type, &signer_data).unwrap();
+        let mut with = send_spec().build_tx(&from);
+        with.sign_with(&signer, &signer_data).unwrap();
+
+        assert_eq!(direct, with);
+        assert_verifies(&with, &signer_data, &signer.public_key(), &key_type);
+    }
+}
+
+#[test]
+fn external_signer_signs_its_own_transactions() {
+    let signed = Rc::new(Cell::new(0));
+    let funder = CountingSigner { inner: SoftwareSigner::new(&FUNDER_KEY, KeyType::Secp256k1).unwrap(), signed: signed.clone() };
+    let public_key = funder.public_key();
+    let from = funder.address("cosmos").unwrap();
+    let accounts = HashMap::from([(from.clone(), AccountInfo { account_number: 5, sequence: 2 })]);
+
+    // The key is not in the wallet file; only the registered backend can sign for it
+    let mut signer = WalletSigner::new(&[], accounts.clone(), "test-1".to_string(), None);
+    assert!(signer.sign(&mut send_spec().build_tx(&from)).is_err());
+
+    let mut signer = WalletSigner::new(&[], accounts, "test-1".to_string(), None);
+    signer.add_signer(from.clone(), Box::new(funder));
+    let mut txs = [send_spec().build_tx(&from), send_spec().build_tx(&from)];
+    for tx in txs.iter_mut() {
+        signer.sign(tx).unwrap();
+    }
+    assert_eq!(signed.get(), 2);
+
+    for (tx, sequence) in txs.iter().zip(2..) {
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, sequence);
+        let signer_data = SignerData { chain_id: "test-1".to_string(), account_number: 5, sequence };
+        assert_verifies(tx, &signer_data, &public_key, &KeyType::Secp256k1);
+    }
+}
+
+#[cfg(feature = "pkcs11")]
+#[test]
+fn pkcs11_module_must_load() {
+    use wallet_generator::pkcs11::{Pkcs11Config, Pkcs11Signer};
+
+    let config = Pkcs11Config {
+        module: std::path::Path::new("/nonexistent/libpkcs11.so"),
+        slot: None,
+        label: "funder",
+        pin: None,
+    };
+    let error = Pkcs11Signer::open(&config, KeyType::Secp256k1).err().unwrap();
+    assert!(error.to_string().contains("Failed to load PKCS#11 module"), "{}", error);
+}
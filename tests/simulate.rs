@@ -0,0 +1,129 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::AtomicUsize;
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::generate;
+use prost::Message;
+use wallet_generator::coin::{Coin, GasPrice};
+use wallet_generator::lcd::LcdClient;
+use wallet_generator::sign::wallet_public_key;
+use wallet_generator::simulate::{simulate_all, SimulateOptions};
+use wallet_generator::tx::{proto, Fee, Msg, SignMode, SignerData, Tx};
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, Wallet};
+
+/// Sequence of every account the mock node knows.
+const SEQUENCE: u64 = 4;
+
+/// Serve accounts for `known` addresses, and simulations charging 10 gas per byte plus 1000 per
+/// signer with a public key, failing transactions whose signer is not at [`SEQUENCE`].
+fn serve_node(known: Vec<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some(length) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, body) = if request_line.starts_with("POST /cosmos/tx/v1beta1/simulate") {
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                let tx_bytes = general_purpose::STANDARD.decode(request["tx_bytes"].as_str().unwrap()).unwrap();
+                let raw = proto::TxRaw::decode(tx_bytes.as_slice()).unwrap();
+                let auth_info = proto::AuthInfo::decode(raw.auth_info_bytes.as_slice()).unwrap();
+                let signer = &auth_info.signer_infos[0];
+                match signer.sequence == SEQUENCE && raw.signatures == [vec![0; 64]] {
+                    true => {
+                        let gas = tx_bytes.len() as u64 * 10 + if signer.public_key.is_some() { 1000 } else { 0 };
+                        ("200 OK", format!(r#"{{"gas_info":{{"gas_wanted":"0","gas_used":"{}"}},"result":{{}}}}"#, gas))
+                    }
+                    false => ("400 Bad Request", r#"{"code":32,"message":"account sequence mismatch"}"#.to_string()),
+                }
+            } else if let Some(address) = known.iter().find(|address| request_line.contains(address.as_str())) {
+                let account = format!(
+                    r#"{{"account":{{"@type":"/cosmos.auth.v1beta1.BaseAccount","address":"{}","account_number":"9","sequence":"{}"}}}}"#,
+                    address, SEQUENCE
+                );
+                ("200 OK", account)
+            } else {
+                ("404 Not Found", r#"{"code":5,"message":"account not found"}"#.to_string())
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn send_from(wallet: &Wallet) -> Tx {
+    let send = Msg::Send { from_address: wallet.address.clone(), to_address: wallet.address.clone(), amount: vec![Coin::new(1, "uatom")] };
+    Tx::new(vec![send], Fee { amount: Vec::new(), gas_limit: 200_000, payer: String::new(), granter: String::new() }, String::new())
+}
+
+#[test]
+fn estimates_gas_and_fee_per_transaction() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 3);
+    let client = LcdClient::new(&serve_node(vec![wallets[0].address.clone(), wallets[1].address.clone()]), Duration::from_secs(5), 0);
+
+    // Two unsigned transactions from the first wallet, a signed one from the second, whose
+    // sequence is ahead of the chain, and one from an address with no account
+    let mut signed = send_from(&wallets[1]);
+    let key: [u8; 32] = hex::decode(&wallets[1].private_key).unwrap().try_into().unwrap();
+    signed.sign_direct(&key, &KeyType::Secp256k1, &SignerData { chain_id: "test-1".to_string(), account_number: 9, sequence: SEQUENCE + 3 }).unwrap();
+    let txs = vec![send_from(&wallets[0]), send_from(&wallets[0]), signed, send_from(&wallets[2])];
+
+    let options = SimulateOptions { sign_mode: SignMode::LegacyAminoJson, gas_adjustment: 1.5, gas_price: Some("0.025uatom".parse::<GasPrice>().unwrap()) };
+    let progress = AtomicUsize::new(0);
+    let simulations = simulate_all(&client, &txs, &wallets, &options, 4, &progress).unwrap();
+    assert_eq!(progress.into_inner(), 4);
+
+    let first = &simulations[0];
+    assert_eq!((first.index, first.signer.as_str(), first.sign_mode.as_str()), (0, wallets[0].address.as_str(), "SIGN_MODE_LEGACY_AMINO_JSON"));
+    let gas_used = first.gas_used.unwrap();
+    // The wallet file supplied the public key
+    let expected_bytes = txs[0].simulation_bytes(wallet_public_key(&wallets[0]).as_ref(), SignMode::LegacyAminoJson, SEQUENCE).unwrap();
+    assert_eq!(gas_used, expected_bytes.len() as u64 * 10 + 1000);
+    assert_eq!(first.gas_limit, Some((gas_used as f64 * 1.5).ceil() as u64));
+    assert_eq!(first.fee, Some(vec![Coin::new((first.gas_limit.unwrap() as f64 * 0.025).ceil() as u128, "uatom")]));
+    assert_eq!(simulations[1].gas_used, Some(gas_used));
+
+    // Signed transactions keep their sign mode and are simulated at the chain's sequence
+    assert_eq!(simulations[2].sign_mode, "SIGN_MODE_DIRECT");
+    assert!(simulations[2].error.is_none(), "{:?}", simulations[2].error);
+
+    assert!(simulations[3].gas_used.is_none());
+    assert!(simulations[3].error.as_deref().is_some_and(|error| error.contains("does not exist")));
+}
+
+#[test]
+fn public_keys_come_from_wallet_records() {
+    let wallet = generate(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1).remove(0);
+    let public_key = wallet_public_key(&wallet).unwrap();
+    assert_eq!(public_key.type_url, "/ethermint.crypto.v1.ethsecp256k1.PubKey");
+    assert_eq!(general_purpose::STANDARD.decode(&public_key.key).unwrap(), hex::decode(wallet.pubkey_hex.as_ref().unwrap()).unwrap());
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::generate;
+use wallet_generator::derivation::Ed25519Deriver;
+use wallet_generator::solana;
+use wallet_generator::types::DerivationScheme;
+use wallet_generator::DerivationConfig;
+
+// SLIP-0010 test vector 1 for ed25519
+#[test]
+fn matches_slip10_test_vector_1() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let vectors = [
+        ("m", "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7", "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"),
+        ("m/0'", "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3", "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"),
+        ("m/0'/1'/2'/2'/1000000000'", "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793", "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a"),
+    ];
+    // One deriver for every path, as in a batch
+    let mut deriver = Ed25519Deriver::new(&seed);
+    for (path, private_key, public_key) in vectors {
+        let key = deriver.private_key(path).unwrap();
+        assert_eq!(hex::encode(key), private_key, "{}", path);
+        assert_eq!(hex::encode(solana::public_key(&key)), public_key, "{}", path);
+    }
+}
+
+#[test]
+fn rejects_unhardened_children() {
+    let seed = [0u8; 64];
+    assert!(Ed25519Deriver::new(&seed).private_key("m/44'/501'/0'/0").is_err());
+}
+
+#[test]
+fn generates_phantom_accounts() {
+    let wallets = generate(&DerivationConfig::solana(), 2);
+    assert_eq!(wallets[0].derivation_path, "m/44'/501'/0'/0'");
+    assert_eq!(wallets[1].derivation_path, "m/44'/501'/1'/0'");
+    assert_eq!(wallets[0].address, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+    for wallet in &wallets {
+        let public_key: [u8; 32] = bs58::decode(&wallet.address).into_vec().unwrap().try_into().unwrap();
+        assert_eq!(wallet.pubkey_hex.as_deref(), Some(hex::encode(public_key).as_str()));
+        assert!(wallet.evm_address.is_none() && wallet.pubkey_type.is_none());
+    }
+}
+
+#[test]
+fn ledger_live_scheme_drops_the_change_level() {
+    let mut config = DerivationConfig::solana();
+    config.scheme = DerivationScheme::LedgerLive;
+    assert_eq!(config.path(3), "m/44'/501'/3'");
+    assert_eq!(config.path_template(), "m/44'/501'/{index}'");
+}
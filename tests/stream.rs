@@ -0,0 +1,40 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{generate_from, seed};
+use tokio_stream::StreamExt;
+use wallet_generator::stream::{generate_wallets, wallet_stream};
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, GeneratorError, Wallet};
+
+fn json(wallets: &[Wallet]) -> serde_json::Value {
+    serde_json::to_value(wallets).unwrap()
+}
+
+#[tokio::test]
+async fn streamed_wallets_match_the_batch() {
+    let expected = generate_from(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 3, 25);
+
+    let generated = generate_wallets(seed().to_vec(), 3, 25, "evmos".to_string(), KeyType::Ethsecp256k1).await.unwrap();
+    assert_eq!(json(&generated), json(&expected));
+
+    // Chunks that do not divide the count
+    let stream = wallet_stream(seed().to_vec(), 3, 25, "evmos".to_string(), KeyType::Ethsecp256k1, 7).unwrap();
+    let streamed: Vec<Wallet> = stream.collect::<Result<_, _>>().await.unwrap();
+    assert_eq!(json(&streamed), json(&expected));
+}
+
+#[tokio::test]
+async fn overflowing_ranges_are_an_error() {
+    let error = wallet_stream(seed().to_vec(), usize::MAX, 2, "cosmos".to_string(), KeyType::Secp256k1, 10).unwrap_err();
+    assert!(matches!(error, GeneratorError::IndexOverflow { start: usize::MAX, count: 2 }));
+    let error = generate_wallets(seed().to_vec(), usize::MAX, 2, "cosmos".to_string(), KeyType::Secp256k1).await.unwrap_err();
+    assert!(matches!(error, GeneratorError::IndexOverflow { .. }));
+
+    // Errors of the generator itself end the stream
+    let stream = wallet_stream(seed().to_vec(), 0, 5, "Not A Prefix".to_string(), KeyType::Secp256k1, 2).unwrap();
+    let results: Vec<Result<Wallet, GeneratorError>> = stream.collect().await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
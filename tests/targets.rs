@@ -0,0 +1,57 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use wallet_generator::filter::WalletFilter;
+use wallet_generator::targets::TargetSet;
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, Wallet};
+
+fn generate(prefix: &str, key_type: KeyType, start: usize, count: usize) -> Vec<Wallet> {
+    common::generate_from(&DerivationConfig::new(prefix, key_type), start, count)
+}
+
+#[test]
+fn every_listed_address_is_found() {
+    let wallets = generate("cosmos", KeyType::Secp256k1, 0, 500);
+    let targets = TargetSet::new(wallets.iter().map(|wallet| &wallet.address));
+    assert_eq!(targets.len(), 500);
+    assert!(wallets.iter().all(|wallet| targets.matches(wallet)));
+
+    let others = generate("cosmos", KeyType::Secp256k1, 500, 500);
+    assert!(others.iter().all(|wallet| !targets.matches(wallet)));
+}
+
+#[test]
+fn bech32_matches_under_any_prefix() {
+    let osmo = generate("osmo", KeyType::Secp256k1, 0, 3);
+    let cosmos = generate("cosmos", KeyType::Secp256k1, 0, 3);
+    let targets = TargetSet::new([&osmo[1].address]);
+    assert!(targets.matches(&cosmos[1]));
+    assert!(!targets.matches(&cosmos[0]) && !targets.matches(&cosmos[2]));
+}
+
+#[test]
+fn evm_address_matches_ethsecp256k1_wallet() {
+    let wallets = generate("evmos", KeyType::Ethsecp256k1, 0, 3);
+    let evm = wallets[2].evm_address.clone().unwrap();
+    let targets = TargetSet::new([evm.to_uppercase().replacen("0X", "0x", 1)]);
+    assert!(targets.matches(&wallets[2]));
+    assert!(!targets.matches(&wallets[0]));
+}
+
+#[test]
+fn load_skips_comments_and_blank_lines() {
+    let wallets = generate("cosmos", KeyType::Secp256k1, 0, 2);
+    let path = std::env::temp_dir().join(format!("wallet-generator-targets-{}.txt", std::process::id()));
+    let list = format!("# addresses to recover\n\n  {}  \n{}\n{}\n", wallets[0].address, wallets[1].address, wallets[0].address);
+    std::fs::write(&path, list).unwrap();
+    let targets = TargetSet::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(targets.len(), 2);
+
+    let filter = WalletFilter::new(None, None, Some(targets)).unwrap().unwrap();
+    assert!(filter.has_targets());
+    assert!(wallets.iter().all(|wallet| filter.matches(wallet)));
+    assert!(TargetSet::load("/nonexistent/targets.txt").is_err());
+}
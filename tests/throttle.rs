@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+use wallet_generator::progress::RunState;
+use wallet_generator::throttle::RateLimiter;
+
+#[test]
+fn reservations_follow_each_other_at_the_rate() {
+    let limiter = RateLimiter::new(1_000);
+    assert!(limiter.reserve(100) < Duration::from_millis(5));
+    // The next item waits for the 100 ms the first one was given, and so on
+    let second = limiter.reserve(50);
+    assert!(second > Duration::from_millis(90) && second <= Duration::from_millis(100), "{second:?}");
+    let third = limiter.reserve(1);
+    assert!(third > Duration::from_millis(140) && third <= Duration::from_millis(150), "{third:?}");
+}
+
+#[test]
+fn throttled_runs_stay_under_the_cap_and_can_abort() {
+    let state = RunState::new(300, 1).with_rate_limit(Some(1_000));
+    let start = Instant::now();
+    assert!((0..3).all(|_| state.throttle(100)));
+    // Three items of 100 wallets at 1,000/sec: the third starts 200 ms in
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    state.abort();
+    let start = Instant::now();
+    assert!(!state.throttle(100_000));
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // Without a limit nothing waits
+    assert!(RunState::new(10, 1).throttle(1_000_000));
+}
+
+#[cfg(unix)]
+#[test]
+fn lowering_priority_needs_no_privileges() {
+    // Niceness 19 is the lowest priority; on Linux only the calling thread takes it
+    std::thread::spawn(|| wallet_generator::throttle::lower_priority(19).unwrap()).join().unwrap();
+}
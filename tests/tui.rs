@@ -0,0 +1,83 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::Terminal;
+use wallet_generator::progress::RunState;
+use wallet_generator::tui::{handle_key, Dashboard};
+use wallet_generator::types::KeyType;
+use wallet_generator::{generate_wallets_batch, DerivationConfig};
+
+/// The dashboard as text, one line per terminal row.
+fn render(dashboard: &mut Dashboard, state: &RunState) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(80, 16)).unwrap();
+    terminal.draw(|frame| dashboard.draw(frame, state)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let width = buffer.area.width as usize;
+    buffer.content.chunks(width).map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>()).collect::<Vec<_>>().join("\n")
+}
+
+fn press(state: &RunState, code: KeyCode) {
+    handle_key(state, KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+#[test]
+fn dashboard_shows_generation_and_write_progress() {
+    let state = RunState::new(10, 2);
+    let config = DerivationConfig::new("cosmos", KeyType::Secp256k1);
+    generate_wallets_batch(&common::seed(), 0, 3, &config, state.thread_counter(0)).unwrap();
+    generate_wallets_batch(&common::seed(), 3, 1, &config, state.thread_counter(1)).unwrap();
+    let mut dashboard = Dashboard::new(&state, "cosmos wallets");
+
+    let screen = render(&mut dashboard, &state);
+    assert!(screen.contains("cosmos wallets"), "{}", screen);
+    assert!(screen.contains("Status: Generating"), "{}", screen);
+    assert!(screen.contains("4/10"), "{}", screen);
+    assert!(screen.contains("0/10"), "{}", screen);
+    assert!(screen.contains("p pause/resume"), "{}", screen);
+    let rows: Vec<Vec<&str>> = screen.lines().map(|line| line.split(|c: char| c == '│' || c.is_whitespace()).filter(|s| !s.is_empty()).collect()).collect();
+    assert!(rows.contains(&vec!["0", "3", "0"]) && rows.contains(&vec!["1", "1", "0"]), "{}", screen);
+
+    state.start_writing();
+    state.records_written(4);
+    let screen = render(&mut dashboard, &state);
+    assert!(screen.contains("Status: Writing") && screen.contains("finishing write..."), "{}", screen);
+    assert!(!screen.contains("0/10"), "{}", screen);
+}
+
+#[test]
+fn keys_pause_resume_and_abort() {
+    let state = RunState::new(10, 1);
+    let mut dashboard = Dashboard::new(&state, "run");
+    press(&state, KeyCode::Char('p'));
+    assert!(state.is_paused());
+    assert!(render(&mut dashboard, &state).contains("Status: Paused"));
+    press(&state, KeyCode::Char(' '));
+    assert!(!state.is_paused());
+
+    // Releases and other keys do nothing
+    handle_key(&state, KeyEvent { kind: KeyEventKind::Release, ..KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE) });
+    press(&state, KeyCode::Char('x'));
+    press(&state, KeyCode::Char('c'));
+    assert!(!state.is_aborted());
+
+    handle_key(&state, KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    assert!(state.is_aborted());
+    assert!(render(&mut dashboard, &state).contains("Status: Aborting"));
+    for code in [KeyCode::Char('q'), KeyCode::Esc] {
+        let state = RunState::new(10, 1);
+        press(&state, code);
+        assert!(state.is_aborted());
+    }
+}
+
+#[test]
+fn keys_are_ignored_while_writing() {
+    let state = RunState::new(10, 1);
+    state.start_writing();
+    press(&state, KeyCode::Char('p'));
+    press(&state, KeyCode::Char('q'));
+    assert!(!state.is_paused() && !state.is_aborted());
+}
@@ -0,0 +1,61 @@
+use base64::{engine::general_purpose, Engine as _};
+use wallet_generator::coin::Coin;
+use wallet_generator::tx::{Fee, Msg, SignerData, Tx};
+use wallet_generator::types::KeyType;
+
+/// Key of index 0 of the test mnemonic, cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4.
+const KEY: &str = "c4a48e2fce1481cd3294b4490f6678090ea98d3d0e5cd984558ab0968741b104";
+
+// Encoded field by field from the cosmos.tx.v1beta1 and cosmos.bank.v1beta1 proto definitions,
+// and signed with an RFC 6979 secp256k1 implementation of its own
+const BODY: &str = concat!(
+    "0a8d010a1c2f636f736d6f732e62616e6b2e763162657461312e4d736753656e64126d0a2d636f736d6f733139726c",
+    "34636d32686d7238616679346b6c6470787a33666b61346a6775713061757164616c34122d636f736d6f73316a726b",
+    "6d646377677139347561616d78367a6178326c7565776c68663775346b756378336b7a1a0d0a057561746f6d120431",
+    "303030120e77616c6c65742d666163746f7279",
+);
+const AUTH_INFO: &str = concat!(
+    "0a500a460a1f2f636f736d6f732e63727970746f2e736563703235366b312e5075624b657912230a21024f4e2ad99c",
+    "34d60b9ba6283c9431a8418af8673212961f97a77b6377fcd05b6212040a020801180712120a0c0a057561746f6d12",
+    "0335303010c09a0c",
+);
+const SIGNATURE: &str = concat!(
+    "1c34406968b6c8e78694d827e2727fd72172662b3f4cdbdca4ba92f2651e7f22",
+    "6e62bbf8cb8978c7829c7cb03bdf0879e6ab82a46ef001e93a1cff8c4e5da910",
+);
+
+fn signed_send() -> Tx {
+    let send = Msg::Send {
+        from_address: "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4".to_string(),
+        to_address: "cosmos1jrkmdcwgq94uaamx6zax2luewlhf7u4kucx3kz".to_string(),
+        amount: vec![Coin::new(1000, "uatom")],
+    };
+    let fee = Fee { amount: vec![Coin::new(500, "uatom")], gas_limit: 200_000, payer: String::new(), granter: String::new() };
+    let mut tx = Tx::new(vec![send], fee, "wallet-factory".to_string());
+    let key: [u8; 32] = hex::decode(KEY).unwrap().try_into().unwrap();
+    tx.sign_direct(&key, &KeyType::Secp256k1, &signer_data()).unwrap();
+    tx
+}
+
+fn signer_data() -> SignerData {
+    SignerData { chain_id: "cosmoshub-4".to_string(), account_number: 42, sequence: 7 }
+}
+
+#[test]
+fn direct_sign_doc_encodes_field_by_field() {
+    let tx = signed_send();
+    assert_eq!(hex::encode(tx.body_bytes()), BODY);
+    assert_eq!(hex::encode(tx.auth_info_bytes().unwrap()), AUTH_INFO);
+    // SignDoc: body, auth info, chain ID and account number
+    let sign_doc = format!("0aa001{}1266{}1a0b{}202a", BODY, AUTH_INFO, hex::encode("cosmoshub-4"));
+    assert_eq!(hex::encode(tx.sign_doc_bytes(&signer_data()).unwrap()), sign_doc);
+}
+
+#[test]
+fn direct_signatures_and_raw_bytes_match_the_fixture() {
+    let tx = signed_send();
+    assert_eq!(hex::encode(general_purpose::STANDARD.decode(&tx.signatures[0]).unwrap()), SIGNATURE);
+    // TxRaw: body, auth info and the one signature
+    let raw = format!("0aa001{}1266{}1a40{}", BODY, AUTH_INFO, SIGNATURE);
+    assert_eq!(hex::encode(tx.to_raw_bytes().unwrap()), raw);
+}
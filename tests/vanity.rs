@@ -0,0 +1,70 @@
+mod common;
+
+use common::generate;
+use wallet_generator::types::KeyType;
+use wallet_generator::vanity::{Alphabet, Pattern, Position};
+use wallet_generator::DerivationConfig;
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual / expected - 1.0).abs() < 1e-9
+}
+
+#[test]
+fn odds_follow_the_alphabet() {
+    let bech32 = Pattern::new("QQQ", Alphabet::Bech32, Position::Prefix, false).unwrap();
+    assert_eq!(bech32.text(), "qqq");
+    assert!(close(bech32.expected_attempts(), 32768.0));
+
+    let hex = Pattern::new("dead", Alphabet::Hex, Position::Suffix, false).unwrap();
+    assert!(close(hex.expected_attempts(), 65536.0));
+
+    // Each letter's EIP-55 case halves the odds again; digits have no case
+    let checksummed = Pattern::new("De0d", Alphabet::Hex, Position::Prefix, true).unwrap();
+    assert!(close(checksummed.expected_attempts(), 65536.0 * 8.0));
+
+    // A 1-character pattern can start at any of the 38 characters of a bech32 address
+    let anywhere = Pattern::new("q", Alphabet::Bech32, Position::Contains, false).unwrap();
+    assert!(close(anywhere.probability(), 1.0));
+    let anywhere = Pattern::new("qqqq", Alphabet::Bech32, Position::Contains, false).unwrap();
+    assert!(close(anywhere.probability(), 35.0 / 1_048_576.0));
+}
+
+#[test]
+fn attempts_for_a_confidence() {
+    let pattern = Pattern::new("qqqq", Alphabet::Bech32, Position::Prefix, false).unwrap();
+    // Half of all searches finish within ln(2) of the mean
+    let median = pattern.attempts_for(0.5);
+    assert!((median / pattern.expected_attempts() - std::f64::consts::LN_2).abs() < 1e-3);
+    assert!(pattern.attempts_for(0.99) > pattern.attempts_for(0.9));
+    assert_eq!(Pattern::new("q", Alphabet::Bech32, Position::Contains, false).unwrap().attempts_for(0.99), 1.0);
+}
+
+#[test]
+fn rejects_characters_addresses_cannot_hold() {
+    for (text, alphabet, case_sensitive) in [
+        ("abc", Alphabet::Bech32, false),
+        ("q1", Alphabet::Bech32, false),
+        ("qq", Alphabet::Bech32, true),
+        ("dead-", Alphabet::Hex, false),
+        ("", Alphabet::Hex, false),
+    ] {
+        assert!(Pattern::new(text, alphabet, Position::Prefix, case_sensitive).is_err(), "{:?}", text);
+    }
+    assert!(Pattern::new(&"q".repeat(39), Alphabet::Bech32, Position::Prefix, false).is_err());
+    assert!(Pattern::new(&"0".repeat(40), Alphabet::Hex, Position::Prefix, false).is_ok());
+}
+
+#[test]
+fn estimate_matches_generated_addresses() {
+    let wallets = generate(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 8192);
+
+    // One fixed character: 256 matches expected, within five standard deviations
+    let pattern = Pattern::new("z", Alphabet::Bech32, Position::Prefix, false).unwrap();
+    let matched = wallets.iter().filter(|wallet| pattern.matches(&wallet.address["cosmos1".len()..])).count();
+    let expected = wallets.len() as f64 * pattern.probability();
+    assert!((matched as f64 - expected).abs() < 5.0 * expected.sqrt(), "{} matches, {} expected", matched, expected);
+
+    let pattern = Pattern::new("9", Alphabet::Bech32, Position::Suffix, false).unwrap();
+    let matched = wallets.iter().filter(|wallet| pattern.matches(&wallet.address["cosmos1".len()..])).count();
+    assert!((matched as f64 - expected).abs() < 5.0 * expected.sqrt(), "{} matches, {} expected", matched, expected);
+}
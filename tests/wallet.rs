@@ -0,0 +1,45 @@
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use wallet_generator::types::KeyType;
+use wallet_generator::{DerivationConfig, Wallet};
+
+#[test]
+fn records_carry_index_pubkey_hex_and_type_url() {
+    for (key_type, type_url) in [
+        (KeyType::Secp256k1, "/cosmos.crypto.secp256k1.PubKey"),
+        (KeyType::Ethsecp256k1, "/ethermint.crypto.v1.ethsecp256k1.PubKey"),
+    ] {
+        let wallets = common::generate_from(&DerivationConfig::new("cosmos", key_type), 5, 2);
+        for (wallet, index) in wallets.iter().zip(5..) {
+            assert_eq!(wallet.index, index);
+            assert!(wallet.derivation_path.ends_with(&format!("/{index}")));
+            let pubkey = general_purpose::STANDARD.decode(&wallet.pubkey).unwrap();
+            assert_eq!(wallet.pubkey_hex.as_deref(), Some(hex::encode(pubkey).as_str()));
+            assert_eq!(wallet.pubkey_type.as_deref(), Some(type_url));
+        }
+
+        let record = serde_json::to_value(&wallets[0]).unwrap();
+        assert_eq!(record["index"], 5);
+        assert_eq!(record["pubkeyHex"].as_str(), wallets[0].pubkey_hex.as_deref());
+        assert_eq!(record["pubkeyType"], type_url);
+    }
+}
+
+#[test]
+fn records_without_the_new_fields_still_load() {
+    let wallet: Wallet = serde_json::from_value(json!({
+        "address": "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4",
+        "pubkey": "Ak9OKtmcNNYLm6YoPJQxqEGK+GcyEpYfl6d7Y3f80Fti",
+        "privateKey": "00",
+        "derivationPath": "m/44'/118'/0'/0/0",
+    }))
+    .unwrap();
+    assert_eq!(wallet.index, 0);
+    assert!(wallet.pubkey_hex.is_none() && wallet.pubkey_type.is_none());
+
+    // Absent fields stay absent when written back
+    let record = serde_json::to_value(&wallet).unwrap();
+    assert!(record.get("pubkeyHex").is_none() && record.get("pubkeyType").is_none());
+}
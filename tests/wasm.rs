@@ -0,0 +1,39 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+mod common;
+
+use common::{generate_from, MNEMONIC};
+use serde_json::Value;
+use wallet_generator::wasm::{generate_addresses_js, generate_wallets_js, MAX_WASM_BATCH};
+use wallet_generator::{DerivationConfig, KeyType};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn to_json(value: wasm_bindgen::JsValue) -> Value {
+    serde_wasm_bindgen::from_value(value).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn wallets_serialize_as_the_cli_records() {
+    let wallets = to_json(generate_wallets_js(MNEMONIC, 1, 2, "evmos", "ethsecp256k1").unwrap());
+    let expected = generate_from(&DerivationConfig::new("evmos", KeyType::Ethsecp256k1), 1, 2);
+    assert_eq!(wallets, serde_json::to_value(expected).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn addresses_of_a_private_key() {
+    let wallet = generate_from(&DerivationConfig::new("cosmos", KeyType::Secp256k1), 0, 1).remove(0);
+    let addresses = to_json(generate_addresses_js(&format!("0x{}", wallet.private_key), "cosmos", "secp256k1").unwrap());
+    assert_eq!(addresses["address"], "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+    assert_eq!(addresses["pubkey"], wallet.pubkey.as_str());
+    assert_eq!(addresses["privateKey"], wallet.private_key.as_str());
+    assert!(addresses.get("evmAddress").is_none());
+}
+
+#[wasm_bindgen_test]
+fn bad_input_is_rejected() {
+    assert!(generate_wallets_js(MNEMONIC, 0, MAX_WASM_BATCH + 1, "cosmos", "secp256k1").is_err());
+    assert!(generate_wallets_js("abandon about", 0, 1, "cosmos", "secp256k1").is_err());
+    assert!(generate_wallets_js(MNEMONIC, 0, 1, "cosmos", "ed448").is_err());
+    assert!(generate_addresses_js("abcd", "cosmos", "secp256k1").is_err());
+    assert!(generate_addresses_js(&"00".repeat(32), "cosmos", "secp256k1").is_err());
+}